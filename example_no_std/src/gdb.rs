@@ -18,11 +18,11 @@ impl Target for DummyTarget {
     type Arch = arch::arm::Armv4t;
     type Error = &'static str;
 
-    fn base_ops(&mut self) -> target::ext::base::BaseOps<Self::Arch, Self::Error> {
+    fn base_ops(&mut self) -> target::ext::base::BaseOps<'_, Self::Arch, Self::Error> {
         target::ext::base::BaseOps::MultiThread(self)
     }
 
-    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<Self>> {
+    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<'_, Self>> {
         Some(self)
     }
 }
@@ -99,12 +99,20 @@ impl MultiThreadOps for DummyTarget {
 
 impl target::ext::breakpoints::SwBreakpoint for DummyTarget {
     #[inline(never)]
-    fn add_sw_breakpoint(&mut self, _addr: u32) -> TargetResult<bool, Self> {
+    fn add_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         Ok(true)
     }
 
     #[inline(never)]
-    fn remove_sw_breakpoint(&mut self, _addr: u32) -> TargetResult<bool, Self> {
+    fn remove_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         Ok(true)
     }
 }
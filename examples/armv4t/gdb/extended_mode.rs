@@ -1,5 +1,6 @@
 use gdbstub::common::Pid;
 use gdbstub::target;
+use gdbstub::target::ext::base::multithread::ThreadStopReason;
 use gdbstub::target::ext::extended_mode::{Args, ShouldTerminate};
 use gdbstub::target::TargetResult;
 
@@ -30,7 +31,7 @@ impl target::ext::extended_mode::ExtendedMode for Emu {
         Ok(())
     }
 
-    fn attach(&mut self, pid: Pid) -> TargetResult<(), Self> {
+    fn attach(&mut self, pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
         eprintln!("GDB tried to attach to a process with PID {}", pid);
         Err(().into()) // non-specific failure
     }
@@ -61,23 +62,23 @@ impl target::ext::extended_mode::ExtendedMode for Emu {
         Ok(Pid::new(1337).unwrap())
     }
 
-    fn configure_aslr(&mut self) -> Option<target::ext::extended_mode::ConfigureASLROps<Self>> {
+    fn configure_aslr(&mut self) -> Option<target::ext::extended_mode::ConfigureASLROps<'_, Self>> {
         Some(self)
     }
 
-    fn configure_env(&mut self) -> Option<target::ext::extended_mode::ConfigureEnvOps<Self>> {
+    fn configure_env(&mut self) -> Option<target::ext::extended_mode::ConfigureEnvOps<'_, Self>> {
         Some(self)
     }
 
     fn configure_startup_shell(
         &mut self,
-    ) -> Option<target::ext::extended_mode::ConfigureStartupShellOps<Self>> {
+    ) -> Option<target::ext::extended_mode::ConfigureStartupShellOps<'_, Self>> {
         Some(self)
     }
 
     fn configure_working_dir(
         &mut self,
-    ) -> Option<target::ext::extended_mode::ConfigureWorkingDirOps<Self>> {
+    ) -> Option<target::ext::extended_mode::ConfigureWorkingDirOps<'_, Self>> {
         Some(self)
     }
 }
@@ -32,27 +32,29 @@ impl Target for Emu {
     type Arch = arch::arm::Armv4t;
     type Error = &'static str;
 
-    fn base_ops(&mut self) -> target::ext::base::BaseOps<Self::Arch, Self::Error> {
+    fn base_ops(&mut self) -> target::ext::base::BaseOps<'_, Self::Arch, Self::Error> {
         target::ext::base::BaseOps::SingleThread(self)
     }
 
-    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<Self>> {
+    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<'_, Self>> {
         Some(self)
     }
 
-    fn hw_watchpoint(&mut self) -> Option<target::ext::breakpoints::HwWatchpointOps<Self>> {
+    fn hw_watchpoint(&mut self) -> Option<target::ext::breakpoints::HwWatchpointOps<'_, Self>> {
         Some(self)
     }
 
-    fn extended_mode(&mut self) -> Option<target::ext::extended_mode::ExtendedModeOps<Self>> {
+    fn extended_mode(&mut self) -> Option<target::ext::extended_mode::ExtendedModeOps<'_, Self>> {
         Some(self)
     }
 
-    fn monitor_cmd(&mut self) -> Option<target::ext::monitor_cmd::MonitorCmdOps<Self>> {
+    fn monitor_cmd(&mut self) -> Option<target::ext::monitor_cmd::MonitorCmdOps<'_, Self>> {
         Some(self)
     }
 
-    fn section_offsets(&mut self) -> Option<target::ext::section_offsets::SectionOffsetsOps<Self>> {
+    fn section_offsets(
+        &mut self,
+    ) -> Option<target::ext::section_offsets::SectionOffsetsOps<'_, Self>> {
         Some(self)
     }
 }
@@ -64,11 +66,11 @@ impl SingleThreadOps for Emu {
         check_gdb_interrupt: &mut dyn FnMut() -> bool,
     ) -> Result<StopReason<u32>, Self::Error> {
         let event = match action {
-            ResumeAction::Step => match self.step() {
+            ResumeAction::Step(_) => match self.step() {
                 Some(e) => e,
                 None => return Ok(StopReason::DoneStep),
             },
-            ResumeAction::Continue => {
+            ResumeAction::Continue(_) => {
                 let mut cycles = 0;
                 loop {
                     if let Some(event) = self.step() {
@@ -86,7 +88,7 @@ impl SingleThreadOps for Emu {
 
         Ok(match event {
             Event::Halted => StopReason::Halted,
-            Event::Break => StopReason::HwBreak,
+            Event::Break => StopReason::SwBreak,
             Event::WatchWrite(addr) => StopReason::Watch {
                 kind: WatchKind::Write,
                 addr,
@@ -130,11 +132,11 @@ impl SingleThreadOps for Emu {
         &mut self,
         reg_id: arch::arm::reg::id::ArmCoreRegId,
         dst: &mut [u8],
-    ) -> TargetResult<(), Self> {
+    ) -> TargetResult<usize, Self> {
         if let Some(i) = cpu_reg_id(reg_id) {
             let w = self.cpu.reg_get(self.cpu.mode(), i);
             dst.copy_from_slice(&w.to_le_bytes());
-            Ok(())
+            Ok(dst.len())
         } else {
             Err(().into())
         }
@@ -173,12 +175,20 @@ impl SingleThreadOps for Emu {
 }
 
 impl target::ext::breakpoints::SwBreakpoint for Emu {
-    fn add_sw_breakpoint(&mut self, addr: u32) -> TargetResult<bool, Self> {
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         self.breakpoints.push(addr);
         Ok(true)
     }
 
-    fn remove_sw_breakpoint(&mut self, addr: u32) -> TargetResult<bool, Self> {
+    fn remove_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         match self.breakpoints.iter().position(|x| *x == addr) {
             None => return Ok(false),
             Some(pos) => self.breakpoints.remove(pos),
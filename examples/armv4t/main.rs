@@ -72,6 +72,10 @@ fn main() -> DynResult<()> {
             while emu.step() != Some(emu::Event::Halted) {}
         }
         DisconnectReason::TargetHalted => println!("Target halted!"),
+        DisconnectReason::TargetExited(code) => println!("Target exited with code {}!", code),
+        DisconnectReason::TargetTerminated(sig) => {
+            println!("Target terminated with signal {}!", sig)
+        }
         DisconnectReason::Kill => {
             println!("GDB sent a kill command!");
             return Ok(());
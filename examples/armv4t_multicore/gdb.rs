@@ -48,15 +48,15 @@ impl Target for Emu {
     type Arch = arch::arm::Armv4t;
     type Error = &'static str;
 
-    fn base_ops(&mut self) -> target::ext::base::BaseOps<Self::Arch, Self::Error> {
+    fn base_ops(&mut self) -> target::ext::base::BaseOps<'_, Self::Arch, Self::Error> {
         target::ext::base::BaseOps::MultiThread(self)
     }
 
-    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<Self>> {
+    fn sw_breakpoint(&mut self) -> Option<target::ext::breakpoints::SwBreakpointOps<'_, Self>> {
         Some(self)
     }
 
-    fn hw_watchpoint(&mut self) -> Option<target::ext::breakpoints::HwWatchpointOps<Self>> {
+    fn hw_watchpoint(&mut self) -> Option<target::ext::breakpoints::HwWatchpointOps<'_, Self>> {
         Some(self)
     }
 }
@@ -67,38 +67,40 @@ impl MultiThreadOps for Emu {
         actions: Actions,
         check_gdb_interrupt: &mut dyn FnMut() -> bool,
     ) -> Result<ThreadStopReason<u32>, Self::Error> {
-        // in this emulator, each core runs in lock-step, so we can ignore the
-        // TidSelector associated with each action, and only care if GDB
-        // requests execution to start / stop.
-        //
-        // In general, the behavior of multi-threaded systems during debugging is
-        // determined by the system scheduler. On certain systems, this behavior can be
-        // configured using the GDB command `set scheduler-locking _mode_`, but at the
-        // moment, `gdbstub` doesn't plumb-through that configuration command.
-
-        // FIXME: properly handle multiple actions...
-        let actions = actions.collect::<Vec<_>>();
-        let (_, action) = actions[0];
-
-        match action {
-            ResumeAction::Step => match self.step() {
-                Some((event, id)) => Ok(event_to_stopreason(event, id)),
-                None => Ok(ThreadStopReason::DoneStep),
-            },
-            ResumeAction::Continue => {
-                let mut cycles: usize = 0;
-                loop {
-                    // check for GDB interrupt every 1024 instructions
-                    if cycles % 1024 == 0 && check_gdb_interrupt() {
-                        return Ok(ThreadStopReason::GdbInterrupt);
-                    }
-                    cycles += 1;
-
-                    if let Some((event, id)) = self.step() {
-                        return Ok(event_to_stopreason(event, id));
-                    };
+        let mut cores = [
+            (cpuid_to_tid(CpuId::Cpu), None),
+            (cpuid_to_tid(CpuId::Cop), None),
+        ];
+        actions.resolve(&mut cores);
+
+        // cores without an associated action remain stopped, while the remaining
+        // cores run in lock-step until some event occurs, or until a stepping core
+        // completes its step.
+        let stepping = cores
+            .iter()
+            .any(|(_, action)| matches!(action, Some(ResumeAction::Step(_))));
+
+        let mut cycles: usize = 0;
+        loop {
+            // check for GDB interrupt every 1024 instructions
+            if cycles % 1024 == 0 && check_gdb_interrupt() {
+                return Ok(ThreadStopReason::GdbInterrupt);
+            }
+            cycles += 1;
+
+            let mut evt = None;
+            for (tid, _) in cores.iter().filter(|(_, action)| action.is_some()) {
+                let id = tid_to_cpuid(*tid)?;
+                if let Some(event) = self.step_core(id) {
+                    evt = evt.or(Some((event, id)));
                 }
             }
+
+            match evt {
+                Some((event, id)) => return Ok(event_to_stopreason(event, id)),
+                None if stepping => return Ok(ThreadStopReason::DoneStep),
+                None => {}
+            }
         }
     }
 
@@ -183,12 +185,20 @@ impl MultiThreadOps for Emu {
 }
 
 impl target::ext::breakpoints::SwBreakpoint for Emu {
-    fn add_sw_breakpoint(&mut self, addr: u32) -> TargetResult<bool, Self> {
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         self.breakpoints.push(addr);
         Ok(true)
     }
 
-    fn remove_sw_breakpoint(&mut self, addr: u32) -> TargetResult<bool, Self> {
+    fn remove_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: arch::arm::ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
         match self.breakpoints.iter().position(|x| *x == addr) {
             None => return Ok(false),
             Some(pos) => self.breakpoints.remove(pos),
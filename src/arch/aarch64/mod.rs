@@ -0,0 +1,137 @@
+//! Implementations for the AArch64 (64-bit ARM) architecture.
+//!
+//! Two flavors of AArch64 are provided:
+//!
+//! - [`AArch64`], which uses the core + FP/SIMD register set.
+//! - [`AArch64Sve`], which uses the core + SVE (Scalable Vector Extension)
+//!   register set, whose vector registers scale with the current vector
+//!   length.
+//!
+//! Targets without SVE hardware should use `AArch64`, which avoids having to
+//! serialize the (rather large) SVE register file.
+
+use core::fmt::{self, Write};
+
+use crate::arch::Arch;
+
+pub mod reg;
+
+/// Implements `Arch` for AArch64 (core + FP/SIMD registers).
+pub enum AArch64 {}
+
+impl Arch for AArch64 {
+    type Usize = u64;
+    type Registers = reg::AArch64CoreRegs;
+    type RegId = reg::id::AArch64RegId;
+    type BreakpointKind = ();
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(r#"<target version="1.0"><architecture>aarch64</architecture></target>"#)
+    }
+}
+
+/// Implements `Arch` for AArch64 with the Scalable Vector Extension (SVE).
+///
+/// The size of the SVE registers depends on the vector length currently in
+/// use, which is tracked by the `vg` register (i.e: the number of 64-bit
+/// "granules" in each vector register). Registers are serialized according to
+/// the current value of
+/// [`AArch64SveRegs::vg`](reg::AArch64SveRegs::vg).
+///
+/// _Note:_ As the target description depends on the current vector length,
+/// this `Arch` does not provide a static target description XML. Instead,
+/// implement the
+/// [`TargetDescriptionXmlOverride`](crate::target::ext::target_description_xml_override::TargetDescriptionXmlOverride)
+/// extension, and use [`sve_target_description_xml`] to generate it.
+pub enum AArch64Sve {}
+
+impl Arch for AArch64Sve {
+    type Usize = u64;
+    type Registers = reg::AArch64SveRegs;
+    type RegId = reg::id::AArch64SveRegId;
+    type BreakpointKind = ();
+}
+
+/// Generate the target description XML for an [`AArch64Sve`] target using
+/// the vector length `vg` (in units of 64-bit granules).
+///
+/// This method follows the same paging semantics as
+/// [`TargetDescriptionXmlOverride::target_description_xml`](crate::target::ext::target_description_xml_override::TargetDescriptionXmlOverride::target_description_xml):
+/// The XML is copied into `buf` starting at `offset`, and the number of bytes
+/// written is returned (returning `0` once the end of the XML is reached).
+pub fn sve_target_description_xml(vg: u64, offset: u64, buf: &mut [u8]) -> usize {
+    crate::arch::write_paged(offset, buf, |w| write_sve_target_description_xml(w, vg))
+}
+
+fn write_sve_target_description_xml(w: &mut dyn Write, vg: u64) -> fmt::Result {
+    // each 128-bit quadword in a vector register
+    let vq = vg / 2;
+
+    w.write_str(r#"<?xml version="1.0"?><!DOCTYPE target SYSTEM "gdb-target.dtd">"#)?;
+    w.write_str(r#"<target version="1.0"><architecture>aarch64</architecture>"#)?;
+
+    w.write_str(r#"<feature name="org.gnu.gdb.aarch64.core">"#)?;
+    for i in 0..31 {
+        write!(
+            w,
+            r#"<reg name="x{}" bitsize="64" type="int" regnum="{}"/>"#,
+            i, i
+        )?;
+    }
+    w.write_str(r#"<reg name="sp" bitsize="64" type="data_ptr"/>"#)?;
+    w.write_str(r#"<reg name="pc" bitsize="64" type="code_ptr"/>"#)?;
+    w.write_str(r#"<reg name="cpsr" bitsize="32" type="int"/>"#)?;
+    w.write_str("</feature>")?;
+
+    w.write_str(r#"<feature name="org.gnu.gdb.aarch64.sve">"#)?;
+    for (id, ty, count) in &[
+        ("svevqu", "uint128", vq),
+        ("svevdu", "uint64", vq * 2),
+        ("svevsu", "uint32", vq * 4),
+        ("svevhu", "uint16", vq * 8),
+        ("svevbu", "uint8", vq * 16),
+        ("svep", "uint8", vq * 2),
+    ] {
+        write!(
+            w,
+            r#"<vector id="{}" type="{}" count="{}"/>"#,
+            id, ty, count
+        )?;
+    }
+    w.write_str(r#"<union id="svev">"#)?;
+    for (name, ty) in &[
+        ("q", "svevqu"),
+        ("d", "svevdu"),
+        ("s", "svevsu"),
+        ("h", "svevhu"),
+        ("b", "svevbu"),
+    ] {
+        write!(w, r#"<field name="{}" type="{}"/>"#, name, ty)?;
+    }
+    w.write_str("</union>")?;
+
+    for i in 0..32 {
+        write!(
+            w,
+            r#"<reg name="z{}" bitsize="{}" type="svev" regnum="{}"/>"#,
+            i,
+            vg * 64,
+            34 + i
+        )?;
+    }
+    w.write_str(r#"<reg name="fpsr" bitsize="32" type="int"/>"#)?;
+    w.write_str(r#"<reg name="fpcr" bitsize="32" type="int"/>"#)?;
+    for i in 0..16 {
+        write!(
+            w,
+            r#"<reg name="p{}" bitsize="{}" type="svep"/>"#,
+            i,
+            vg * 8
+        )?;
+    }
+    write!(w, r#"<reg name="ffr" bitsize="{}" type="svep"/>"#, vg * 8)?;
+    w.write_str(r#"<reg name="vg" bitsize="64" type="int"/>"#)?;
+    w.write_str("</feature>")?;
+
+    w.write_str("</target>")
+}
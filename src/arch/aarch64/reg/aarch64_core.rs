@@ -0,0 +1,84 @@
+use crate::arch::{Endian, Registers};
+
+/// AArch64 core registers (+ FP/SIMD registers).
+///
+/// Source: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-core.xml
+/// Additionally: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-fpu.xml
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AArch64CoreRegs {
+    /// General purpose registers (X0-X30)
+    pub x: [u64; 31],
+    /// Stack pointer
+    pub sp: u64,
+    /// Program counter
+    pub pc: u64,
+    /// Process State (AArch32: CPSR)
+    pub cpsr: u32,
+    /// FP/SIMD registers (V0-V31)
+    pub v: [u128; 32],
+    /// Floating-point Status Register
+    pub fpsr: u32,
+    /// Floating-point Control Register
+    pub fpcr: u32,
+}
+
+impl Registers for AArch64CoreRegs {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_bytes {
+            ($bytes:expr) => {
+                for b in $bytes {
+                    write_byte(Some(*b))
+                }
+            };
+        }
+
+        macro_rules! to_bytes {
+            ($val:expr) => {
+                match endian {
+                    Endian::Little => $val.to_le_bytes(),
+                    Endian::Big => $val.to_be_bytes(),
+                }
+            };
+        }
+
+        for reg in self.x.iter() {
+            write_bytes!(&to_bytes!(reg));
+        }
+        write_bytes!(&to_bytes!(self.sp));
+        write_bytes!(&to_bytes!(self.pc));
+        write_bytes!(&to_bytes!(self.cpsr));
+
+        for reg in self.v.iter() {
+            write_bytes!(&to_bytes!(reg));
+        }
+        write_bytes!(&to_bytes!(self.fpsr));
+        write_bytes!(&to_bytes!(self.fpcr));
+    }
+
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        if bytes.len() != 31 * 8 + 8 + 8 + 4 + 32 * 16 + 4 + 4 {
+            return Err(());
+        }
+
+        let (x, bytes) = bytes.split_at(31 * 8);
+        for (reg, b) in self.x.iter_mut().zip(x.chunks_exact(8)) {
+            *reg = endian.read(b).unwrap();
+        }
+        let (sp, bytes) = bytes.split_at(8);
+        self.sp = endian.read(sp).unwrap();
+        let (pc, bytes) = bytes.split_at(8);
+        self.pc = endian.read(pc).unwrap();
+        let (cpsr, bytes) = bytes.split_at(4);
+        self.cpsr = endian.read(cpsr).unwrap();
+
+        let (v, bytes) = bytes.split_at(32 * 16);
+        for (reg, b) in self.v.iter_mut().zip(v.chunks_exact(16)) {
+            *reg = endian.read(b).unwrap();
+        }
+        let (fpsr, fpcr) = bytes.split_at(4);
+        self.fpsr = endian.read(fpsr).unwrap();
+        self.fpcr = endian.read(fpcr).unwrap();
+
+        Ok(())
+    }
+}
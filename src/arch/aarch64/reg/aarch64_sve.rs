@@ -0,0 +1,151 @@
+use crate::arch::{Endian, Registers};
+
+/// The maximum supported SVE vector length, in units of 64-bit granules
+/// (i.e: 2048-bit vectors).
+pub const SVE_MAX_VG: usize = 32;
+
+// size of the core registers (x0-x30, sp, pc, cpsr)
+const CORE_LEN: usize = 31 * 8 + 8 + 8 + 4;
+
+/// AArch64 core registers + SVE registers.
+///
+/// The SVE registers (`z`, `p`, and `ffr`) are stored at their maximum size,
+/// but are only serialized up to the vector length specified by `vg`. e.g:
+/// with `vg = 4` (i.e: 256-bit vectors), only `z[n][..32]`, `p[n][..4]`, and
+/// `ffr[..4]` are sent to GDB.
+///
+/// Source: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-core.xml
+/// Additionally: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-sve.c
+#[derive(Debug, Clone, PartialEq)]
+pub struct AArch64SveRegs {
+    /// General purpose registers (X0-X30)
+    pub x: [u64; 31],
+    /// Stack pointer
+    pub sp: u64,
+    /// Program counter
+    pub pc: u64,
+    /// Process State (AArch32: CPSR)
+    pub cpsr: u32,
+    /// Scalable vector registers (Z0-Z31), in the target's byte order
+    pub z: [[u8; SVE_MAX_VG * 8]; 32],
+    /// Floating-point Status Register
+    pub fpsr: u32,
+    /// Floating-point Control Register
+    pub fpcr: u32,
+    /// Scalable predicate registers (P0-P15)
+    pub p: [[u8; SVE_MAX_VG]; 16],
+    /// First Fault Register
+    pub ffr: [u8; SVE_MAX_VG],
+    /// Vector Granule: the current vector length, in units of 64-bit granules
+    ///
+    /// Must be a multiple of 2, and no larger than [`SVE_MAX_VG`].
+    pub vg: u64,
+}
+
+impl Default for AArch64SveRegs {
+    fn default() -> AArch64SveRegs {
+        AArch64SveRegs {
+            x: [0; 31],
+            sp: 0,
+            pc: 0,
+            cpsr: 0,
+            z: [[0; SVE_MAX_VG * 8]; 32],
+            fpsr: 0,
+            fpcr: 0,
+            p: [[0; SVE_MAX_VG]; 16],
+            ffr: [0; SVE_MAX_VG],
+            // 128-bit vectors
+            vg: 2,
+        }
+    }
+}
+
+impl Registers for AArch64SveRegs {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_bytes {
+            ($bytes:expr) => {
+                for b in $bytes {
+                    write_byte(Some(*b))
+                }
+            };
+        }
+
+        macro_rules! to_bytes {
+            ($val:expr) => {
+                match endian {
+                    Endian::Little => $val.to_le_bytes(),
+                    Endian::Big => $val.to_be_bytes(),
+                }
+            };
+        }
+
+        let vg = (self.vg as usize).min(SVE_MAX_VG);
+
+        for reg in self.x.iter() {
+            write_bytes!(&to_bytes!(reg));
+        }
+        write_bytes!(&to_bytes!(self.sp));
+        write_bytes!(&to_bytes!(self.pc));
+        write_bytes!(&to_bytes!(self.cpsr));
+
+        // the vector registers are opaque byte arrays, and are sent as-is
+        for reg in self.z.iter() {
+            write_bytes!(&reg[..vg * 8]);
+        }
+        write_bytes!(&to_bytes!(self.fpsr));
+        write_bytes!(&to_bytes!(self.fpcr));
+        for reg in self.p.iter() {
+            write_bytes!(&reg[..vg]);
+        }
+        write_bytes!(&self.ffr[..vg]);
+        write_bytes!(&to_bytes!(self.vg));
+    }
+
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        // the vector length isn't known until the trailing `vg` register is
+        // parsed, so it's derived from the overall length instead.
+        let sve_len = bytes.len().checked_sub(CORE_LEN + 4 + 4 + 8).ok_or(())?;
+        // 32 z regs (vg * 8 bytes) + 16 p regs (vg bytes) + ffr (vg bytes)
+        if sve_len % (32 * 8 + 16 + 1) != 0 {
+            return Err(());
+        }
+        let vg = sve_len / (32 * 8 + 16 + 1);
+        if vg == 0 || vg > SVE_MAX_VG {
+            return Err(());
+        }
+
+        let (x, bytes) = bytes.split_at(31 * 8);
+        for (reg, b) in self.x.iter_mut().zip(x.chunks_exact(8)) {
+            *reg = endian.read(b).unwrap();
+        }
+        let (sp, bytes) = bytes.split_at(8);
+        self.sp = endian.read(sp).unwrap();
+        let (pc, bytes) = bytes.split_at(8);
+        self.pc = endian.read(pc).unwrap();
+        let (cpsr, bytes) = bytes.split_at(4);
+        self.cpsr = endian.read(cpsr).unwrap();
+
+        let (z, bytes) = bytes.split_at(32 * vg * 8);
+        for (reg, b) in self.z.iter_mut().zip(z.chunks_exact(vg * 8)) {
+            reg[..vg * 8].copy_from_slice(b);
+        }
+        let (fpsr, bytes) = bytes.split_at(4);
+        self.fpsr = endian.read(fpsr).unwrap();
+        let (fpcr, bytes) = bytes.split_at(4);
+        self.fpcr = endian.read(fpcr).unwrap();
+        let (p, bytes) = bytes.split_at(16 * vg);
+        for (reg, b) in self.p.iter_mut().zip(p.chunks_exact(vg)) {
+            reg[..vg].copy_from_slice(b);
+        }
+        let (ffr, bytes) = bytes.split_at(vg);
+        self.ffr[..vg].copy_from_slice(ffr);
+
+        let new_vg: u64 = endian.read(bytes).unwrap();
+        if new_vg as usize != vg {
+            return Err(());
+        }
+        self.vg = new_vg;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use crate::arch::{name_in_table, raw_id_in_table, RegId, RegIdSet};
+
+use super::SVE_MAX_VG;
+
+/// AArch64 core + FP/SIMD register identifier.
+///
+/// Source: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-core.xml
+/// Additionally: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-fpu.xml
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AArch64RegId {
+    /// General purpose registers (X0-X30)
+    X(u8),
+    /// Stack pointer
+    Sp,
+    /// Program counter
+    Pc,
+    /// Process State (AArch32: CPSR)
+    Cpsr,
+    /// FP/SIMD registers (V0-V31)
+    V(u8),
+    /// Floating-point Status Register
+    Fpsr,
+    /// Floating-point Control Register
+    Fpcr,
+}
+
+/// Register names, indexed by raw GDB register number.
+const NAMES: [&str; 68] = [
+    "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14",
+    "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27",
+    "x28", "x29", "x30", "sp", "pc", "cpsr", "v0", "v1", "v2", "v3", "v4", "v5", "v6", "v7", "v8",
+    "v9", "v10", "v11", "v12", "v13", "v14", "v15", "v16", "v17", "v18", "v19", "v20", "v21",
+    "v22", "v23", "v24", "v25", "v26", "v27", "v28", "v29", "v30", "v31", "fpsr", "fpcr",
+];
+
+impl RegId for AArch64RegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        use self::AArch64RegId::*;
+
+        let r = match id {
+            0..=30 => (X(id as u8), 8),
+            31 => (Sp, 8),
+            32 => (Pc, 8),
+            33 => (Cpsr, 4),
+            34..=65 => (V((id - 34) as u8), 16),
+            66 => (Fpsr, 4),
+            67 => (Fpcr, 4),
+            _ => return None,
+        };
+        Some(r)
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&NAMES, name)
+    }
+}
+
+impl RegIdSet for AArch64RegId {
+    const NUM_REGS: usize = 68;
+}
+
+/// AArch64 core + SVE register identifier.
+///
+/// The `Z`, `P`, and `Ffr` registers are variable-sized, and report their
+/// _maximum_ size (i.e: the size at a vector length of
+/// [`SVE_MAX_VG`](super::SVE_MAX_VG)). When reading these registers, only as
+/// many bytes as the current vector length requires should be written (see
+/// `Target::read_register`).
+///
+/// Source: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-core.xml
+/// Additionally: https://github.com/bminor/binutils-gdb/blob/master/gdb/features/aarch64-sve.c
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AArch64SveRegId {
+    /// General purpose registers (X0-X30)
+    X(u8),
+    /// Stack pointer
+    Sp,
+    /// Program counter
+    Pc,
+    /// Process State (AArch32: CPSR)
+    Cpsr,
+    /// Scalable vector registers (Z0-Z31)
+    Z(u8),
+    /// Floating-point Status Register
+    Fpsr,
+    /// Floating-point Control Register
+    Fpcr,
+    /// Scalable predicate registers (P0-P15)
+    P(u8),
+    /// First Fault Register
+    Ffr,
+    /// Vector Granule
+    Vg,
+}
+
+/// Register names, indexed by raw GDB register number.
+const SVE_NAMES: [&str; 86] = [
+    "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14",
+    "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27",
+    "x28", "x29", "x30", "sp", "pc", "cpsr", "z0", "z1", "z2", "z3", "z4", "z5", "z6", "z7", "z8",
+    "z9", "z10", "z11", "z12", "z13", "z14", "z15", "z16", "z17", "z18", "z19", "z20", "z21",
+    "z22", "z23", "z24", "z25", "z26", "z27", "z28", "z29", "z30", "z31", "fpsr", "fpcr", "p0",
+    "p1", "p2", "p3", "p4", "p5", "p6", "p7", "p8", "p9", "p10", "p11", "p12", "p13", "p14", "p15",
+    "ffr", "vg",
+];
+
+impl RegId for AArch64SveRegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        use self::AArch64SveRegId::*;
+
+        let r = match id {
+            0..=30 => (X(id as u8), 8),
+            31 => (Sp, 8),
+            32 => (Pc, 8),
+            33 => (Cpsr, 4),
+            34..=65 => (Z((id - 34) as u8), SVE_MAX_VG * 8),
+            66 => (Fpsr, 4),
+            67 => (Fpcr, 4),
+            68..=83 => (P((id - 68) as u8), SVE_MAX_VG),
+            84 => (Ffr, SVE_MAX_VG),
+            85 => (Vg, 8),
+            _ => return None,
+        };
+        Some(r)
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&SVE_NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&SVE_NAMES, name)
+    }
+}
+
+impl RegIdSet for AArch64SveRegId {
+    const NUM_REGS: usize = 86;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arch::aarch64::reg::{AArch64CoreRegs, AArch64SveRegs};
+    use crate::arch::{Endian, Registers};
+
+    fn roundtrip<R: Registers>(regs: &R) -> R {
+        roundtrip_endian(regs, Endian::Little)
+    }
+
+    fn roundtrip_endian<R: Registers>(regs: &R, endian: Endian) -> R {
+        let mut data = Vec::new();
+        regs.gdb_serialize(endian, |b| data.push(b.unwrap()));
+
+        let mut new_regs = R::default();
+        new_regs.gdb_deserialize(endian, &data).unwrap();
+        new_regs
+    }
+
+    #[test]
+    fn core_regs_roundtrip() {
+        let mut regs = AArch64CoreRegs::default();
+        regs.x[30] = 0xdead_beef;
+        regs.pc = 0x4000_0000;
+        regs.v[31] = u128::MAX - 1;
+        regs.fpcr = 0x1234;
+
+        assert_eq!(roundtrip(&regs), regs);
+        assert_eq!(roundtrip_endian(&regs, Endian::Big), regs);
+    }
+
+    #[test]
+    fn core_regs_big_endian() {
+        let regs = AArch64CoreRegs {
+            pc: 0x0102_0304_0506_0708,
+            ..Default::default()
+        };
+
+        let mut data = Vec::new();
+        regs.gdb_serialize(Endian::Big, |b| data.push(b.unwrap()));
+        assert_eq!(data[32 * 8..33 * 8], [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    fn sve_regs(vg: u64) -> AArch64SveRegs {
+        let vl = vg as usize * 8;
+        let mut regs = AArch64SveRegs {
+            vg,
+            pc: 0x4000_0000,
+            fpsr: 0x10,
+            ..Default::default()
+        };
+        for (i, z) in regs.z.iter_mut().enumerate() {
+            for (j, b) in z[..vl].iter_mut().enumerate() {
+                *b = (i + j) as u8;
+            }
+        }
+        regs.p[15][..vg as usize].iter_mut().for_each(|b| *b = 0xff);
+        regs.ffr[0] = 0x5a;
+        regs
+    }
+
+    #[test]
+    fn sve_regs_roundtrip_vg2() {
+        let regs = sve_regs(2);
+
+        let mut len = 0;
+        regs.gdb_serialize(Endian::Little, |_| len += 1);
+        // core + 32 * 16-byte z + fpsr/fpcr + 16 * 2-byte p + 2-byte ffr + vg
+        assert_eq!(len, 268 + 32 * 16 + 8 + 16 * 2 + 2 + 8);
+
+        assert_eq!(roundtrip(&regs), regs);
+    }
+
+    #[test]
+    fn sve_regs_roundtrip_vg4() {
+        // 256-bit vectors
+        let regs = sve_regs(4);
+
+        let mut len = 0;
+        regs.gdb_serialize(Endian::Little, |_| len += 1);
+        assert_eq!(len, 268 + 32 * 32 + 8 + 16 * 4 + 4 + 8);
+
+        assert_eq!(roundtrip(&regs), regs);
+        assert_eq!(roundtrip_endian(&regs, Endian::Big), regs);
+    }
+
+    #[test]
+    fn sve_deserialize_rejects_mismatched_vg() {
+        let mut data = Vec::new();
+        sve_regs(4).gdb_serialize(Endian::Little, |b| data.push(b.unwrap()));
+        // claim a different vector length than the data contains
+        let vg_offset = data.len() - 8;
+        data[vg_offset] = 2;
+
+        assert!(AArch64SveRegs::default()
+            .gdb_deserialize(Endian::Little, &data)
+            .is_err());
+    }
+
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::aarch64::AArch64>(AArch64RegId::NUM_REGS + 1);
+        crate::arch::assert_reg_names::<crate::arch::aarch64::AArch64Sve>(
+            AArch64SveRegId::NUM_REGS + 1,
+        );
+    }
+
+    #[test]
+    fn sve_regid() {
+        assert!(matches!(
+            AArch64SveRegId::from_raw_id(34),
+            Some((AArch64SveRegId::Z(0), 256))
+        ));
+        assert!(matches!(
+            AArch64SveRegId::from_raw_id(83),
+            Some((AArch64SveRegId::P(15), 32))
+        ));
+        assert!(matches!(
+            AArch64SveRegId::from_raw_id(85),
+            Some((AArch64SveRegId::Vg, 8))
+        ));
+        assert!(AArch64SveRegId::from_raw_id(86).is_none());
+    }
+}
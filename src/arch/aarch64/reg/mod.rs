@@ -0,0 +1,10 @@
+//! `Register` structs for the AArch64 architecture.
+
+/// `RegId` definitions for the AArch64 architecture.
+pub mod id;
+
+mod aarch64_core;
+mod aarch64_sve;
+
+pub use aarch64_core::AArch64CoreRegs;
+pub use aarch64_sve::{AArch64SveRegs, SVE_MAX_VG};
@@ -1,4 +1,4 @@
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 
 /// 32-bit ARM core registers.
 ///
@@ -18,7 +18,7 @@ pub struct ArmCoreRegs {
 }
 
 impl Registers for ArmCoreRegs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_bytes {
             ($bytes:expr) => {
                 for b in $bytes {
@@ -27,31 +27,39 @@ impl Registers for ArmCoreRegs {
             };
         }
 
+        macro_rules! to_bytes {
+            ($val:expr) => {
+                match endian {
+                    Endian::Little => $val.to_le_bytes(),
+                    Endian::Big => $val.to_be_bytes(),
+                }
+            };
+        }
+
         for reg in self.r.iter() {
-            write_bytes!(&reg.to_le_bytes());
+            write_bytes!(&to_bytes!(reg));
         }
-        write_bytes!(&self.sp.to_le_bytes());
-        write_bytes!(&self.lr.to_le_bytes());
-        write_bytes!(&self.pc.to_le_bytes());
+        write_bytes!(&to_bytes!(self.sp));
+        write_bytes!(&to_bytes!(self.lr));
+        write_bytes!(&to_bytes!(self.pc));
 
         // Floating point registers (unused)
         for _ in 0..25 {
             (0..4).for_each(|_| write_byte(None))
         }
 
-        write_bytes!(&self.cpsr.to_le_bytes());
+        write_bytes!(&to_bytes!(self.cpsr));
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         // ensure bytes.chunks_exact(4) won't panic
         if bytes.len() % 4 != 0 {
             return Err(());
         }
 
-        use core::convert::TryInto;
         let mut regs = bytes
             .chunks_exact(4)
-            .map(|c| u32::from_le_bytes(c.try_into().unwrap()));
+            .map(|c| endian.read::<u32>(c).unwrap());
 
         for reg in self.r.iter_mut() {
             *reg = regs.next().ok_or(())?
@@ -74,3 +82,51 @@ impl Registers for ArmCoreRegs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serialize(regs: &ArmCoreRegs, endian: Endian) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        regs.gdb_serialize(endian, |b| bytes.push(b.unwrap_or(0)));
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut regs = ArmCoreRegs::default();
+        regs.r[0] = 0x1122_3344;
+        regs.sp = 0x8000;
+        regs.pc = 0xdead_beef;
+        regs.cpsr = 0x6000_0010;
+
+        for &endian in &[Endian::Little, Endian::Big] {
+            let bytes = serialize(&regs, endian);
+            assert_eq!(bytes.len(), 42 * 4);
+
+            let mut deserialized = ArmCoreRegs::default();
+            deserialized.gdb_deserialize(endian, &bytes).unwrap();
+            assert_eq!(deserialized, regs);
+        }
+    }
+
+    #[test]
+    fn byte_order() {
+        let mut regs = ArmCoreRegs::default();
+        regs.r[0] = 0x1122_3344;
+
+        assert_eq!(
+            serialize(&regs, Endian::Little)[..4],
+            [0x44, 0x33, 0x22, 0x11]
+        );
+        assert_eq!(serialize(&regs, Endian::Big)[..4], [0x11, 0x22, 0x33, 0x44]);
+
+        // bytes serialized in one byte order aren't valid in the other
+        let mut deserialized = ArmCoreRegs::default();
+        deserialized
+            .gdb_deserialize(Endian::Big, &serialize(&regs, Endian::Little))
+            .unwrap();
+        assert_eq!(deserialized.r[0], 0x4433_2211);
+    }
+}
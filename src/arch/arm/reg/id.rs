@@ -1,4 +1,4 @@
-use crate::arch::RegId;
+use crate::arch::{name_in_table, raw_id_in_table, RegId, RegIdSet};
 
 /// 32-bit ARM core register identifier.
 #[derive(Debug, Clone, Copy)]
@@ -20,6 +20,14 @@ pub enum ArmCoreRegId {
     Cpsr,
 }
 
+/// Register names, indexed by raw GDB register number.
+///
+/// Register 24 (`fps`) isn't supported.
+const NAMES: [&str; 26] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "sp", "lr",
+    "pc", "f0", "f1", "f2", "f3", "f4", "f5", "f6", "f7", "", "cpsr",
+];
+
 impl RegId for ArmCoreRegId {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
         let reg = match id {
@@ -33,4 +41,24 @@ impl RegId for ArmCoreRegId {
         };
         Some((reg, 4))
     }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&NAMES, name)
+    }
+}
+
+impl RegIdSet for ArmCoreRegId {
+    const NUM_REGS: usize = 26;
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::arm::Armv4t>(32)
+    }
 }
@@ -0,0 +1,46 @@
+//! Implementations for the Atmel / Microchip AVR family of 8-bit MCUs.
+//!
+//! # Address spaces
+//!
+//! AVR is a Harvard architecture, with separate program (flash), data (SRAM +
+//! memory mapped I/O), and EEPROM address spaces. GDB has no notion of
+//! multiple address spaces, and instead maps all three into a single linear
+//! address space, using the high bits of each address to select the
+//! underlying address space:
+//!
+//! | GDB address range         | Address space            |
+//! |---------------------------|--------------------------|
+//! | `0x000000` - `0x7fffff`   | Program memory (flash)   |
+//! | `0x800000` - `0x80ffff`   | Data memory (SRAM + I/O) |
+//! | `0x810000` - `0x81ffff`   | EEPROM                   |
+//!
+//! As such, implementations of `read_addrs` / `write_addrs` (and breakpoint /
+//! watchpoint addresses) must decode the high bits of each address to
+//! determine which memory to access, and strip them before indexing into
+//! it. e.g: a read from `0x800100` corresponds to SRAM address `0x0100`.
+//!
+//! Since these linear addresses don't fit in 16 bits, `Avr` uses a 32-bit
+//! `Usize`, even though the MCU's native program / data addresses are 16 bits
+//! wide. For the same reason, GDB transfers the PC as a 32-bit _byte_ address
+//! into program memory (i.e: twice the MCU's word-addressed program counter).
+
+use crate::arch::Arch;
+
+pub mod reg;
+
+/// Implements `Arch` for 8-bit AVR MCUs.
+///
+/// See the [module level docs](index.html#address-spaces) for details on how
+/// GDB lays out the AVR's address spaces.
+pub enum Avr {}
+
+impl Arch for Avr {
+    type Usize = u32;
+    type Registers = reg::AvrCoreRegs;
+    type RegId = reg::id::AvrCoreRegId;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(include_str!("avr.xml"))
+    }
+}
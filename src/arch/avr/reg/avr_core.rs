@@ -0,0 +1,86 @@
+use crate::arch::{Endian, Registers};
+
+/// 8-bit AVR core registers.
+///
+/// Source: https://github.com/bminor/binutils-gdb/blob/master/gdb/avr-tdep.c
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct AvrCoreRegs {
+    /// General purpose registers (R0-R31)
+    pub r: [u8; 32],
+    /// Status Register
+    pub sreg: u8,
+    /// Stack Pointer
+    pub sp: u16,
+    /// Program Counter
+    ///
+    /// _Note:_ GDB expects the PC as a _byte_ address into program memory,
+    /// which is twice the value of the MCU's (word-addressed) program counter.
+    pub pc: u32,
+}
+
+impl Registers for AvrCoreRegs {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_bytes {
+            ($bytes:expr) => {
+                for b in $bytes {
+                    write_byte(Some(*b))
+                }
+            };
+        }
+
+        write_bytes!(&self.r);
+        write_byte(Some(self.sreg));
+        write_bytes!(&self.sp.to_le_bytes());
+        write_bytes!(&self.pc.to_le_bytes());
+    }
+
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        // r0-r31 (32 bytes) + SREG (1 byte) + SP (2 bytes) + PC (4 bytes)
+        if bytes.len() != 39 {
+            return Err(());
+        }
+
+        use core::convert::TryInto;
+        self.r.copy_from_slice(&bytes[0..32]);
+        self.sreg = bytes[32];
+        self.sp = u16::from_le_bytes(bytes[33..35].try_into().unwrap());
+        self.pc = u32::from_le_bytes(bytes[35..39].try_into().unwrap());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut regs = AvrCoreRegs::default();
+        for (i, r) in regs.r.iter_mut().enumerate() {
+            *r = i as u8 * 3;
+        }
+        regs.sreg = 0x82;
+        regs.sp = 0x08ff;
+        regs.pc = 0x0001_2344;
+
+        let mut bytes = [0; 39];
+        let mut len = 0;
+        regs.gdb_serialize(Endian::Little, |b| {
+            bytes[len] = b.unwrap();
+            len += 1;
+        });
+        assert_eq!(len, bytes.len());
+        assert_eq!(&bytes[32..], &[0x82, 0xff, 0x08, 0x44, 0x23, 0x01, 0x00]);
+
+        let mut deserialized = AvrCoreRegs::default();
+        deserialized
+            .gdb_deserialize(Endian::Little, &bytes)
+            .unwrap();
+        assert_eq!(deserialized, regs);
+
+        assert!(deserialized
+            .gdb_deserialize(Endian::Little, &bytes[..38])
+            .is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::arch::{RegId, RegIdSet};
+
+/// 8-bit AVR core register identifier.
+///
+/// GDB does not provide a XML file for the AVR.
+/// The best file to reference is [avr-tdep.c](https://github.com/bminor/binutils-gdb/blob/master/gdb/avr-tdep.c).
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum AvrCoreRegId {
+    /// General purpose registers (R0-R31)
+    Gpr(u8),
+    /// Status Register
+    Sreg,
+    /// Stack Pointer
+    Sp,
+    /// Program Counter
+    Pc,
+}
+
+impl RegId for AvrCoreRegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        let reg = match id {
+            0..=31 => (Self::Gpr(id as u8), 1),
+            32 => (Self::Sreg, 1),
+            33 => (Self::Sp, 2),
+            34 => (Self::Pc, 4),
+            _ => return None,
+        };
+        Some(reg)
+    }
+}
+
+impl RegIdSet for AvrCoreRegId {
+    const NUM_REGS: usize = 35;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arch::traits::RegId;
+    use crate::arch::traits::{Endian, Registers};
+
+    fn test<Rs: Registers, RId: RegId>() {
+        // Obtain the data length written by `gdb_serialize` by passing a custom
+        // closure.
+        let mut serialized_data_len = 0;
+        let counter = |b: Option<u8>| {
+            if b.is_some() {
+                serialized_data_len += 1;
+            }
+        };
+        Rs::default().gdb_serialize(Endian::Little, counter);
+
+        // Accumulate register sizes returned by `from_raw_id`.
+        let mut i = 0;
+        let mut sum_reg_sizes = 0;
+        while let Some((_, size)) = RId::from_raw_id(i) {
+            sum_reg_sizes += size;
+            i += 1;
+        }
+
+        assert_eq!(serialized_data_len, sum_reg_sizes);
+    }
+
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::avr::Avr>(36)
+    }
+
+    #[test]
+    fn test_avr() {
+        test::<crate::arch::avr::reg::AvrCoreRegs, crate::arch::avr::reg::id::AvrCoreRegId>()
+    }
+}
@@ -33,6 +33,7 @@ impl<RegIdImpl: RegId> Arch for Mips<RegIdImpl> {
     type Usize = u32;
     type Registers = reg::MipsCoreRegs<u32>;
     type RegId = RegIdImpl;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(r#"<target version="1.0"><architecture>mips</architecture></target>"#)
@@ -43,6 +44,7 @@ impl<RegIdImpl: RegId> Arch for Mips64<RegIdImpl> {
     type Usize = u64;
     type Registers = reg::MipsCoreRegs<u64>;
     type RegId = RegIdImpl;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(r#"<target version="1.0"><architecture>mips64</architecture></target>"#)
@@ -53,6 +55,7 @@ impl Arch for MipsWithDsp {
     type Usize = u32;
     type Registers = reg::MipsCoreRegsWithDsp<u32>;
     type RegId = reg::id::MipsRegId<u32>;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(
@@ -65,6 +68,7 @@ impl Arch for Mips64WithDsp {
     type Usize = u64;
     type Registers = reg::MipsCoreRegsWithDsp<u64>;
     type RegId = reg::id::MipsRegId<u64>;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(
@@ -1,4 +1,4 @@
-use crate::arch::RegId;
+use crate::arch::{name_in_table, raw_id_in_table, RegId};
 
 /// MIPS register identifier.
 #[derive(Debug, Clone, Copy)]
@@ -44,6 +44,16 @@ pub enum MipsRegId<U> {
     _Size(U),
 }
 
+/// Register names, indexed by raw GDB register number.
+const NAMES: [&str; 80] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "r13", "r14",
+    "r15", "r16", "r17", "r18", "r19", "r20", "r21", "r22", "r23", "r24", "r25", "r26", "r27",
+    "r28", "r29", "r30", "r31", "status", "lo", "hi", "badvaddr", "cause", "pc", "f0", "f1", "f2",
+    "f3", "f4", "f5", "f6", "f7", "f8", "f9", "f10", "f11", "f12", "f13", "f14", "f15", "f16",
+    "f17", "f18", "f19", "f20", "f21", "f22", "f23", "f24", "f25", "f26", "f27", "f28", "f29",
+    "f30", "f31", "fcsr", "fir", "hi1", "lo1", "hi2", "lo2", "hi3", "lo3", "dspctl", "restart",
+];
+
 fn from_raw_id<U>(id: usize) -> Option<(MipsRegId<U>, usize)> {
     let reg = match id {
         0..=31 => MipsRegId::Gpr(id as u8),
@@ -76,18 +86,34 @@ impl RegId for MipsRegId<u32> {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
         from_raw_id::<u32>(id)
     }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&NAMES, name)
+    }
 }
 
 impl RegId for MipsRegId<u64> {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
         from_raw_id::<u64>(id)
     }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&NAMES, name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::arch::traits::RegId;
-    use crate::arch::traits::Registers;
+    use crate::arch::traits::{Endian, Registers};
 
     fn test<Rs: Registers, RId: RegId>() {
         // Obtain the data length written by `gdb_serialize` by passing a custom
@@ -98,7 +124,7 @@ mod tests {
                 serialized_data_len += 1;
             }
         };
-        Rs::default().gdb_serialize(counter);
+        Rs::default().gdb_serialize(Endian::Little, counter);
 
         // Accumulate register sizes returned by `from_raw_id`.
         let mut i = 0;
@@ -111,6 +137,12 @@ mod tests {
         assert_eq!(serialized_data_len, sum_reg_sizes);
     }
 
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::mips::MipsWithDsp>(81);
+        crate::arch::assert_reg_names::<crate::arch::mips::Mips64WithDsp>(81);
+    }
+
     #[test]
     fn test_mips32() {
         test::<
@@ -2,7 +2,7 @@ use core::convert::TryInto;
 
 use num_traits::PrimInt;
 
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 use crate::internal::LeBytes;
 
 /// MIPS registers.
@@ -92,7 +92,7 @@ impl<U> Registers for MipsCoreRegs<U>
 where
     U: PrimInt + LeBytes + Default + core::fmt::Debug,
 {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_le_bytes {
             ($value:expr) => {
                 let mut buf = [0; 16];
@@ -134,7 +134,7 @@ where
         write_le_bytes!(&self.fpu.fir);
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         let ptrsize = core::mem::size_of::<U>();
 
         // Ensure bytes contains enough data for all 72 registers
@@ -183,7 +183,7 @@ impl<U> Registers for MipsCoreRegsWithDsp<U>
 where
     U: PrimInt + LeBytes + Default + core::fmt::Debug,
 {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_le_bytes {
             ($value:expr) => {
                 let mut buf = [0; 16];
@@ -197,7 +197,7 @@ where
         }
 
         // Serialize the core registers first
-        self.core.gdb_serialize(&mut write_byte);
+        self.core.gdb_serialize(endian, &mut write_byte);
 
         // Write the DSP registers
         write_le_bytes!(&self.dsp.hi1);
@@ -214,9 +214,9 @@ where
         write_le_bytes!(&self.dsp.restart);
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         // Deserialize the core registers first
-        self.core.gdb_deserialize(bytes)?;
+        self.core.gdb_deserialize(endian, bytes)?;
 
         // Ensure bytes contains enough data for all 79 registers of target-width
         // and the dspctl register which is always 4 bytes
@@ -49,12 +49,107 @@
 //! have a default implementation, only a single breaking API change will be
 //! required to remove `RegIdImpl` entirely (along with this documentation).
 
+pub mod aarch64;
 pub mod arm;
+pub mod avr;
 pub mod mips;
 pub mod msp430;
 pub mod ppc;
 pub mod riscv;
+pub mod s390x;
+pub mod wasm32;
 pub mod x86;
 
 mod traits;
 pub use traits::*;
+
+use core::fmt::{self, Write};
+
+/// Writes the chunk of a formatted string starting at `offset` into `buf`.
+struct PagedWriter<'a> {
+    offset: usize,
+    buf: &'a mut [u8],
+    written: usize,
+}
+
+impl Write for PagedWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut s = s.as_bytes();
+
+        let skip = self.offset.min(s.len());
+        self.offset -= skip;
+        s = &s[skip..];
+
+        let n = s.len().min(self.buf.len() - self.written);
+        self.buf[self.written..self.written + n].copy_from_slice(&s[..n]);
+        self.written += n;
+        Ok(())
+    }
+}
+
+/// Run `write` against a writer which copies the chunk of its output starting
+/// at `offset` into `buf`, returning the number of bytes copied.
+///
+/// Used to implement helpers which generate target description XML at runtime
+/// (e.g: [`sve_target_description_xml`](aarch64::sve_target_description_xml)).
+fn write_paged(
+    offset: u64,
+    buf: &mut [u8],
+    write: impl FnOnce(&mut dyn Write) -> fmt::Result,
+) -> usize {
+    let mut w = PagedWriter {
+        offset: offset as usize,
+        buf,
+        written: 0,
+    };
+    // PagedWriter is infallible
+    let _ = write(&mut w);
+    w.written
+}
+
+/// Iterate over the `(regnum, name)` of each `<reg>` element in a target
+/// description XML string.
+///
+/// As in GDB, registers without an explicit `regnum` attribute are numbered
+/// sequentially, following the previous register.
+fn xml_regs(xml: &'static str) -> impl Iterator<Item = (usize, &'static str)> {
+    let mut next_regnum = 0;
+    xml.split("<reg ").skip(1).filter_map(move |elem| {
+        let elem = &elem[..elem.find('>').unwrap_or(elem.len())];
+        let name = xml_attr(elem, "name")?;
+        let regnum = match xml_attr(elem, "regnum") {
+            Some(regnum) => regnum.parse().ok()?,
+            None => next_regnum,
+        };
+        next_regnum = regnum + 1;
+        Some((regnum, name))
+    })
+}
+
+/// Extract the value of attribute `attr` from the body of an XML element.
+fn xml_attr(elem: &'static str, attr: &str) -> Option<&'static str> {
+    let mut rest = elem;
+    loop {
+        let idx = rest.find(attr)?;
+        let at_word_start = idx == 0 || rest.as_bytes()[idx - 1].is_ascii_whitespace();
+        rest = &rest[idx + attr.len()..];
+        if at_word_start && rest.starts_with("=\"") {
+            let val = &rest[2..];
+            return Some(&val[..val.find('"')?]);
+        }
+    }
+}
+
+/// Look up register `id` in a table of register names (indexed by raw GDB
+/// register number). Empty names mark register numbers which aren't used.
+fn name_in_table(names: &[&'static str], id: usize) -> Option<&'static str> {
+    names.get(id).copied().filter(|name| !name.is_empty())
+}
+
+/// The inverse of [`name_in_table`].
+fn raw_id_in_table(names: &[&str], name: &str) -> Option<usize> {
+    if name.is_empty() {
+        return None;
+    }
+    names.iter().position(|n| *n == name)
+}
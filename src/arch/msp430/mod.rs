@@ -9,17 +9,41 @@ pub mod reg;
 ///
 /// Check out the [module level docs](../index.html#whats-with-regidimpl) for
 /// more info about the `RegIdImpl` type parameter.
-pub enum Msp430<RegIdImpl: RegId = reg::id::Msp430RegId> {
+pub enum Msp430<RegIdImpl: RegId = reg::id::Msp430RegId<u16>> {
     #[doc(hidden)]
     _Marker(core::marker::PhantomData<RegIdImpl>),
 }
 
 impl<RegIdImpl: RegId> Arch for Msp430<RegIdImpl> {
     type Usize = u32;
-    type Registers = reg::Msp430Regs;
+    type Registers = reg::Msp430Regs<u16>;
     type RegId = RegIdImpl;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
-        Some(r#"<target version="1.0"><architecture>msp430</architecture></target>"#)
+        Some(include_str!("msp430.xml"))
+    }
+}
+
+/// Implements `Arch` for 20-bit TI-MSP430X MCUs.
+///
+/// MSP430X registers are 20 bits wide, and are transferred to / from GDB as
+/// 32-bit values (with the upper 12 bits cleared).
+///
+/// Check out the [module level docs](../index.html#whats-with-regidimpl) for
+/// more info about the `RegIdImpl` type parameter.
+pub enum Msp430X<RegIdImpl: RegId = reg::id::Msp430RegId<u32>> {
+    #[doc(hidden)]
+    _Marker(core::marker::PhantomData<RegIdImpl>),
+}
+
+impl<RegIdImpl: RegId> Arch for Msp430X<RegIdImpl> {
+    type Usize = u32;
+    type Registers = reg::Msp430Regs<u32>;
+    type RegId = RegIdImpl;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(include_str!("msp430x.xml"))
     }
 }
@@ -2,11 +2,14 @@ use crate::arch::RegId;
 
 /// TI-MSP430 register identifier.
 ///
+/// The register width is set based on the `<U>` type. For 16-bit MSP430 MCUs
+/// use `u16`, and for 20-bit MSP430X MCUs use `u32`.
+///
 /// GDB does not provide a XML file for the MSP430.
 /// The best file to reference is [msp430-tdep.c](https://github.com/bminor/binutils-gdb/blob/master/gdb/msp430-tdep.c).
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
-pub enum Msp430RegId {
+pub enum Msp430RegId<U> {
     /// Program Counter (R0)
     Pc,
     /// Stack Pointer (R1)
@@ -17,28 +20,42 @@ pub enum Msp430RegId {
     Cg,
     /// General Purpose Registers (R4-R15)
     Gpr(u8),
+    #[doc(hidden)]
+    _Size(U),
+}
+
+fn from_raw_id<U>(id: usize) -> Option<(Msp430RegId<U>, usize)> {
+    let reg = match id {
+        0 => Msp430RegId::Pc,
+        1 => Msp430RegId::Sp,
+        2 => Msp430RegId::Sr,
+        3 => Msp430RegId::Cg,
+        4..=15 => Msp430RegId::Gpr((id as u8) - 4),
+        _ => return None,
+    };
+
+    let ptrsize = core::mem::size_of::<U>();
+    Some((reg, ptrsize))
 }
 
-impl RegId for Msp430RegId {
+impl RegId for Msp430RegId<u16> {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
-        let reg = match id {
-            0 => Self::Pc,
-            1 => Self::Sp,
-            2 => Self::Sr,
-            3 => Self::Cg,
-            4..=15 => Self::Gpr((id as u8) - 4),
-            _ => return None,
-        };
-        Some((reg, 2))
+        from_raw_id::<u16>(id)
+    }
+}
+
+impl RegId for Msp430RegId<u32> {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        from_raw_id::<u32>(id)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::arch::traits::RegId;
-    use crate::arch::traits::Registers;
+    use crate::arch::traits::{Endian, Registers};
 
-    fn test<Rs: Registers, RId: RegId>() {
+    fn test<Rs: Registers, RId: RegId>(cg_size: usize) {
         // Obtain the data length written by `gdb_serialize` by passing a custom
         // closure.
         let mut serialized_data_len = 0;
@@ -47,11 +64,11 @@ mod tests {
                 serialized_data_len += 1;
             }
         };
-        Rs::default().gdb_serialize(counter);
+        Rs::default().gdb_serialize(Endian::Little, counter);
 
         // The `Msp430Regs` implementation does not increment the size for
         // the CG register since it will always be the constant zero.
-        serialized_data_len += 4;
+        serialized_data_len += cg_size;
 
         // Accumulate register sizes returned by `from_raw_id`.
         let mut i = 0;
@@ -64,8 +81,28 @@ mod tests {
         assert_eq!(serialized_data_len, sum_reg_sizes);
     }
 
+    #[test]
+    fn reg_names() {
+        use crate::arch::msp430::reg::id::Msp430RegId;
+        use crate::arch::msp430::{Msp430, Msp430X};
+
+        crate::arch::assert_reg_names::<Msp430<Msp430RegId<u16>>>(17);
+        crate::arch::assert_reg_names::<Msp430X<Msp430RegId<u32>>>(17);
+    }
+
     #[test]
     fn test_msp430() {
-        test::<crate::arch::msp430::reg::Msp430Regs, crate::arch::msp430::reg::id::Msp430RegId>()
+        test::<
+            crate::arch::msp430::reg::Msp430Regs<u16>,
+            crate::arch::msp430::reg::id::Msp430RegId<u16>,
+        >(2)
+    }
+
+    #[test]
+    fn test_msp430x() {
+        test::<
+            crate::arch::msp430::reg::Msp430Regs<u32>,
+            crate::arch::msp430::reg::id::Msp430RegId<u32>,
+        >(4)
     }
 }
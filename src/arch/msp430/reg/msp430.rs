@@ -1,54 +1,78 @@
-use crate::arch::Registers;
+use num_traits::{NumCast, PrimInt};
 
-/// 16-bit TI-MSP430 registers.
+use crate::arch::{Endian, Registers};
+use crate::internal::LeBytes;
+
+/// TI-MSP430 registers.
+///
+/// The register width is set based on the `<U>` type. For 16-bit MSP430 MCUs
+/// use `u16`, and for 20-bit MSP430X MCUs use `u32`.
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
-pub struct Msp430Regs {
+pub struct Msp430Regs<U> {
     /// Program Counter (R0)
-    pub pc: u16,
+    pub pc: U,
     /// Stack Pointer (R1)
-    pub sp: u16,
+    pub sp: U,
     /// Status Register (R2)
-    pub sr: u16,
+    pub sr: U,
     /// General Purpose Registers (R4-R15)
-    pub r: [u16; 11],
+    pub r: [U; 12],
 }
 
-impl Registers for Msp430Regs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
-        macro_rules! write_bytes {
-            ($bytes:expr) => {
-                for b in $bytes {
-                    write_byte(Some(*b))
+/// Mask `val` to the register's architectural width (i.e: 20 bits on
+/// MSP430X, where registers are transferred as 32-bit values).
+fn mask<U: PrimInt>(val: U) -> U {
+    match core::mem::size_of::<U>() {
+        2 => val,
+        _ => val & NumCast::from(0xf_ffffu32).unwrap(),
+    }
+}
+
+impl<U> Registers for Msp430Regs<U>
+where
+    U: PrimInt + LeBytes + Default + core::fmt::Debug,
+{
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_le_bytes {
+            ($value:expr) => {
+                let mut buf = [0; 16];
+                // infallible (unless digit is a >128 bit number)
+                let len = mask(*$value).to_le_bytes(&mut buf).unwrap();
+                let buf = &buf[..len];
+                for b in buf {
+                    write_byte(Some(*b));
                 }
             };
         }
 
-        write_bytes!(&self.pc.to_le_bytes());
-        write_bytes!(&self.sp.to_le_bytes());
-        write_bytes!(&self.sr.to_le_bytes());
-        (0..4).for_each(|_| write_byte(None)); // Constant Generator (CG/R3)
+        write_le_bytes!(&self.pc);
+        write_le_bytes!(&self.sp);
+        write_le_bytes!(&self.sr);
+        // Constant Generator (CG/R3)
+        (0..core::mem::size_of::<U>()).for_each(|_| write_byte(None));
         for reg in self.r.iter() {
-            write_bytes!(&reg.to_le_bytes());
+            write_le_bytes!(reg);
         }
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
-        // ensure bytes.chunks_exact(2) won't panic
-        if bytes.len() % 2 != 0 {
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        let ptrsize = core::mem::size_of::<U>();
+
+        // ensure bytes.chunks_exact(ptrsize) won't panic
+        if bytes.len() % ptrsize != 0 {
             return Err(());
         }
 
-        use core::convert::TryInto;
         let mut regs = bytes
-            .chunks_exact(2)
-            .map(|c| u16::from_le_bytes(c.try_into().unwrap()));
+            .chunks_exact(ptrsize)
+            .map(|c| mask(U::from_le_bytes(c).unwrap()));
 
         self.pc = regs.next().ok_or(())?;
         self.sp = regs.next().ok_or(())?;
         self.sr = regs.next().ok_or(())?;
 
         // Constant Generator (CG/R3) should always be 0
-        if regs.next().ok_or(())? != 0 {
+        if regs.next().ok_or(())? != U::zero() {
             return Err(());
         }
 
@@ -63,3 +87,72 @@ impl Registers for Msp430Regs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<U>(regs: Msp430Regs<U>) -> std::vec::Vec<u8>
+    where
+        U: PrimInt + LeBytes + Default + core::fmt::Debug,
+    {
+        let mut bytes = std::vec::Vec::new();
+        regs.gdb_serialize(Endian::Little, |b| bytes.push(b.unwrap_or(0)));
+
+        let mut deserialized = Msp430Regs::default();
+        deserialized
+            .gdb_deserialize(Endian::Little, &bytes)
+            .unwrap();
+        assert_eq!(deserialized, regs);
+
+        bytes
+    }
+
+    #[test]
+    fn round_trip_msp430() {
+        let mut regs = Msp430Regs::<u16> {
+            pc: 0x4400,
+            sp: 0x43fe,
+            sr: 0x0003,
+            ..Default::default()
+        };
+        for (i, r) in regs.r.iter_mut().enumerate() {
+            *r = 0x1000 + i as u16;
+        }
+
+        let bytes = round_trip(regs);
+        assert_eq!(bytes.len(), 16 * 2);
+        assert_eq!(
+            &bytes[..8],
+            &[0x00, 0x44, 0xfe, 0x43, 0x03, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(&bytes[30..], &[0x0b, 0x10]);
+    }
+
+    #[test]
+    fn round_trip_msp430x() {
+        let mut regs = Msp430Regs::<u32> {
+            pc: 0x1_4400,
+            sp: 0x43fe,
+            sr: 0x0003,
+            ..Default::default()
+        };
+        for (i, r) in regs.r.iter_mut().enumerate() {
+            *r = 0xf_0000 + i as u32;
+        }
+
+        let bytes = round_trip(regs.clone());
+        assert_eq!(bytes.len(), 16 * 4);
+        assert_eq!(&bytes[..4], &[0x00, 0x44, 0x01, 0x00]);
+        assert_eq!(&bytes[60..], &[0x0b, 0x00, 0x0f, 0x00]);
+
+        // registers are only 20 bits wide
+        let mut bytes = bytes;
+        bytes[3] = 0xff;
+        let mut deserialized = Msp430Regs::<u32>::default();
+        deserialized
+            .gdb_deserialize(Endian::Little, &bytes)
+            .unwrap();
+        assert_eq!(deserialized.pc, 0x1_4400);
+    }
+}
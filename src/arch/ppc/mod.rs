@@ -1,10 +1,32 @@
 //! Implementations for various PowerPC architectures.
 
 use crate::arch::Arch;
+use crate::arch::Endian;
 use crate::arch::RegId;
 
 pub mod reg;
 
+/// Implements `Arch` for 32-bit PowerPC (core + FPU registers).
+///
+/// _Note:_ Unlike most other architectures, PowerPC registers are transferred
+/// to / from GDB in big-endian byte order.
+pub enum PowerPc {}
+
+impl Arch for PowerPc {
+    type Usize = u32;
+    type Registers = reg::PowerPcCoreRegs;
+    type RegId = reg::id::PowerPcCoreRegId;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(include_str!("powerpc32.xml"))
+    }
+
+    fn endianness() -> Endian {
+        Endian::Big
+    }
+}
+
 /// Implements `Arch` for 32-bit PowerPC + AltiVec SIMD.
 ///
 /// Check out the [module level docs](../index.html#whats-with-regidimpl) for
@@ -18,10 +40,15 @@ impl<RegIdImpl: RegId> Arch for PowerPcAltivec32<RegIdImpl> {
     type Usize = u32;
     type Registers = reg::PowerPcCommonRegs;
     type RegId = RegIdImpl;
+    type BreakpointKind = ();
 
     fn target_description_xml() -> Option<&'static str> {
         Some(
             r#"<target version="1.0"><architecture>powerpc:common</architecture><feature name="org.gnu.gdb.power.core"></feature><feature name="org.gnu.gdb.power.fpu"></feature><feature name="org.gnu.gdb.power.altivec"></feature></target>"#,
         )
     }
+
+    fn endianness() -> Endian {
+        Endian::Big
+    }
 }
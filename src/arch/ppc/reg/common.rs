@@ -1,5 +1,5 @@
 use crate::arch::ppc::reg::PpcVector;
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 
 use core::convert::TryInto;
 
@@ -39,7 +39,7 @@ pub struct PowerPcCommonRegs {
 }
 
 impl Registers for PowerPcCommonRegs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_bytes {
             ($bytes:expr) => {
                 for b in $bytes {
@@ -74,7 +74,7 @@ impl Registers for PowerPcCommonRegs {
         write_regs!(vscr, vrsave);
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         if bytes.len() < 0x3a4 {
             return Err(());
         }
@@ -145,14 +145,14 @@ mod tests {
 
         let mut data = vec![];
 
-        regs_before.gdb_serialize(|x| {
+        regs_before.gdb_serialize(Endian::Little, |x| {
             data.push(x.unwrap_or(b'x'));
         });
 
         assert_eq!(data.len(), 0x3a4);
 
         let mut regs_after = PowerPcCommonRegs::default();
-        regs_after.gdb_deserialize(&data).unwrap();
+        regs_after.gdb_deserialize(Endian::Little, &data).unwrap();
 
         assert_eq!(regs_before, regs_after);
     }
@@ -1,2 +1,89 @@
+use crate::arch::RegId;
+
+/// 32-bit PowerPC core + FPU register identifier.
+///
+/// Sources:
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/rs6000/power-core.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/rs6000/power-fpu.xml
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum PowerPcCoreRegId {
+    /// General purpose registers (R0-R31)
+    Gpr(u8),
+    /// Floating point registers (F0-F31)
+    Fpr(u8),
+    /// Program counter (a.k.a: `nip`)
+    Pc,
+    /// Machine state
+    Msr,
+    /// Condition register
+    Cr,
+    /// Link register
+    Lr,
+    /// Count register
+    Ctr,
+    /// Integer exception register
+    Xer,
+    /// Floating-point status and control register
+    Fpscr,
+}
+
+impl RegId for PowerPcCoreRegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        let reg = match id {
+            0..=31 => (Self::Gpr(id as u8), 4),
+            32..=63 => (Self::Fpr((id - 32) as u8), 8),
+            64 => (Self::Pc, 4),
+            65 => (Self::Msr, 4),
+            66 => (Self::Cr, 4),
+            67 => (Self::Lr, 4),
+            68 => (Self::Ctr, 4),
+            69 => (Self::Xer, 4),
+            70 => (Self::Fpscr, 4),
+            _ => return None,
+        };
+        Some(reg)
+    }
+}
+
 // TODO: Add proper `RegId` implementation. See [issue #29](https://github.com/daniel5151/gdbstub/issues/29)
 // pub enum PowerPc32RegId {}
+
+#[cfg(test)]
+mod tests {
+    use crate::arch::traits::RegId;
+    use crate::arch::traits::{Endian, Registers};
+
+    fn test<Rs: Registers, RId: RegId>() {
+        // Obtain the data length written by `gdb_serialize` by passing a custom
+        // closure.
+        let mut serialized_data_len = 0;
+        let counter = |b: Option<u8>| {
+            if b.is_some() {
+                serialized_data_len += 1;
+            }
+        };
+        Rs::default().gdb_serialize(Endian::Little, counter);
+
+        // Accumulate register sizes returned by `from_raw_id`.
+        let mut i = 0;
+        let mut sum_reg_sizes = 0;
+        while let Some((_, size)) = RId::from_raw_id(i) {
+            sum_reg_sizes += size;
+            i += 1;
+        }
+
+        assert_eq!(serialized_data_len, sum_reg_sizes);
+    }
+
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::ppc::PowerPc>(72)
+    }
+
+    #[test]
+    fn test_powerpc() {
+        test::<crate::arch::ppc::reg::PowerPcCoreRegs, crate::arch::ppc::reg::id::PowerPcCoreRegId>(
+        )
+    }
+}
@@ -4,6 +4,8 @@
 pub mod id;
 
 mod common;
+mod power_core;
 
 pub use common::PowerPcCommonRegs;
+pub use power_core::PowerPcCoreRegs;
 type PpcVector = u128;
@@ -0,0 +1,136 @@
+use crate::arch::{Endian, Registers};
+
+use core::convert::TryInto;
+
+/// 32-bit PowerPC core registers + FPU registers.
+///
+/// All registers are transferred in big-endian byte order.
+///
+/// Sources:
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/rs6000/power-core.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/rs6000/power-fpu.xml
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PowerPcCoreRegs {
+    /// General purpose registers
+    pub r: [u32; 32],
+    /// Floating Point registers
+    pub f: [f64; 32],
+    /// Program counter (a.k.a: `nip`)
+    pub pc: u32,
+    /// Machine state
+    pub msr: u32,
+    /// Condition register
+    pub cr: u32,
+    /// Link register
+    pub lr: u32,
+    /// Count register
+    pub ctr: u32,
+    /// Integer exception register
+    pub xer: u32,
+    /// Floating-point status and control register
+    pub fpscr: u32,
+}
+
+impl Registers for PowerPcCoreRegs {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_be_bytes {
+            ($value:expr) => {
+                for b in &$value.to_be_bytes() {
+                    write_byte(Some(*b))
+                }
+            };
+        }
+
+        for reg in &self.r {
+            write_be_bytes!(reg);
+        }
+
+        for reg in &self.f {
+            write_be_bytes!(reg);
+        }
+
+        write_be_bytes!(self.pc);
+        write_be_bytes!(self.msr);
+        write_be_bytes!(self.cr);
+        write_be_bytes!(self.lr);
+        write_be_bytes!(self.ctr);
+        write_be_bytes!(self.xer);
+        write_be_bytes!(self.fpscr);
+    }
+
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        // 32 GPRs + 32 FPRs + 7 special purpose registers
+        if bytes.len() != 0x80 + 0x100 + 0x1c {
+            return Err(());
+        }
+
+        let (gprs, rest) = bytes.split_at(0x80);
+        let (fprs, sprs) = rest.split_at(0x100);
+
+        for (reg, x) in self.r.iter_mut().zip(gprs.chunks_exact(4)) {
+            *reg = u32::from_be_bytes(x.try_into().unwrap());
+        }
+
+        for (reg, x) in self.f.iter_mut().zip(fprs.chunks_exact(8)) {
+            *reg = f64::from_be_bytes(x.try_into().unwrap());
+        }
+
+        let mut sprs = sprs
+            .chunks_exact(4)
+            .map(|x| u32::from_be_bytes(x.try_into().unwrap()));
+
+        self.pc = sprs.next().ok_or(())?;
+        self.msr = sprs.next().ok_or(())?;
+        self.cr = sprs.next().ok_or(())?;
+        self.lr = sprs.next().ok_or(())?;
+        self.ctr = sprs.next().ok_or(())?;
+        self.xer = sprs.next().ok_or(())?;
+        self.fpscr = sprs.next().ok_or(())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ppc_core_fpu_round_trip() {
+        let mut regs_before = PowerPcCoreRegs {
+            pc: 0x0100_0200,
+            msr: 0x0000_3032,
+            cr: 3,
+            lr: 4,
+            ctr: 5,
+            xer: 6,
+            fpscr: 7,
+            ..Default::default()
+        };
+        for (i, r) in regs_before.r.iter_mut().enumerate() {
+            *r = 0x1122_3300 + i as u32;
+        }
+        for (i, f) in regs_before.f.iter_mut().enumerate() {
+            *f = i as f64 + 0.5;
+        }
+
+        let mut data = vec![];
+        regs_before.gdb_serialize(Endian::Little, |x| data.push(x.unwrap()));
+        assert_eq!(data.len(), 0x19c);
+
+        // registers are big-endian
+        assert_eq!(&data[0x00..0x04], &[0x11, 0x22, 0x33, 0x00]);
+        assert_eq!(&data[0x7c..0x80], &[0x11, 0x22, 0x33, 0x1f]);
+        assert_eq!(&data[0x80..0x88], &0.5f64.to_be_bytes());
+        assert_eq!(&data[0x180..0x184], &[0x01, 0x00, 0x02, 0x00]);
+        assert_eq!(&data[0x198..0x19c], &[0x00, 0x00, 0x00, 0x07]);
+
+        let mut regs_after = PowerPcCoreRegs::default();
+        regs_after.gdb_deserialize(Endian::Little, &data).unwrap();
+        assert_eq!(regs_before, regs_after);
+
+        assert!(regs_after
+            .gdb_deserialize(Endian::Little, &data[..0x198])
+            .is_err());
+    }
+}
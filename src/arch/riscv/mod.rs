@@ -12,10 +12,14 @@ pub enum Riscv32 {}
 /// Implements `Arch` for 64-bit RISC-V.
 pub enum Riscv64 {}
 
+/// Implements `Arch` for 128-bit RISC-V.
+pub enum Riscv128 {}
+
 impl Arch for Riscv32 {
     type Usize = u32;
     type Registers = reg::RiscvCoreRegs<u32>;
-    type RegId = reg::id::RiscvRegId;
+    type RegId = reg::id::RiscvRegId<u32>;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(r#"<target version="1.0"><architecture>riscv</architecture></target>"#)
@@ -25,9 +29,65 @@ impl Arch for Riscv32 {
 impl Arch for Riscv64 {
     type Usize = u64;
     type Registers = reg::RiscvCoreRegs<u64>;
-    type RegId = reg::id::RiscvRegId;
+    type RegId = reg::id::RiscvRegId<u64>;
+    type BreakpointKind = usize;
 
     fn target_description_xml() -> Option<&'static str> {
         Some(r#"<target version="1.0"><architecture>riscv64</architecture></target>"#)
     }
 }
+
+impl Arch for Riscv128 {
+    type Usize = u128;
+    type Registers = reg::RiscvCoreRegs<u128>;
+    type RegId = reg::id::RiscvRegId<u128>;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        // GDB doesn't have a built-in description for RV128, so the 128-bit wide
+        // registers must be spelled out explicitly.
+        Some(
+            r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <architecture>riscv</architecture>
+  <feature name="org.gnu.gdb.riscv.cpu">
+    <reg name="zero" bitsize="128" type="int" regnum="0"/>
+    <reg name="ra" bitsize="128" type="int" regnum="1"/>
+    <reg name="sp" bitsize="128" type="int" regnum="2"/>
+    <reg name="gp" bitsize="128" type="int" regnum="3"/>
+    <reg name="tp" bitsize="128" type="int" regnum="4"/>
+    <reg name="t0" bitsize="128" type="int" regnum="5"/>
+    <reg name="t1" bitsize="128" type="int" regnum="6"/>
+    <reg name="t2" bitsize="128" type="int" regnum="7"/>
+    <reg name="fp" bitsize="128" type="int" regnum="8"/>
+    <reg name="s1" bitsize="128" type="int" regnum="9"/>
+    <reg name="a0" bitsize="128" type="int" regnum="10"/>
+    <reg name="a1" bitsize="128" type="int" regnum="11"/>
+    <reg name="a2" bitsize="128" type="int" regnum="12"/>
+    <reg name="a3" bitsize="128" type="int" regnum="13"/>
+    <reg name="a4" bitsize="128" type="int" regnum="14"/>
+    <reg name="a5" bitsize="128" type="int" regnum="15"/>
+    <reg name="a6" bitsize="128" type="int" regnum="16"/>
+    <reg name="a7" bitsize="128" type="int" regnum="17"/>
+    <reg name="s2" bitsize="128" type="int" regnum="18"/>
+    <reg name="s3" bitsize="128" type="int" regnum="19"/>
+    <reg name="s4" bitsize="128" type="int" regnum="20"/>
+    <reg name="s5" bitsize="128" type="int" regnum="21"/>
+    <reg name="s6" bitsize="128" type="int" regnum="22"/>
+    <reg name="s7" bitsize="128" type="int" regnum="23"/>
+    <reg name="s8" bitsize="128" type="int" regnum="24"/>
+    <reg name="s9" bitsize="128" type="int" regnum="25"/>
+    <reg name="s10" bitsize="128" type="int" regnum="26"/>
+    <reg name="s11" bitsize="128" type="int" regnum="27"/>
+    <reg name="t3" bitsize="128" type="int" regnum="28"/>
+    <reg name="t4" bitsize="128" type="int" regnum="29"/>
+    <reg name="t5" bitsize="128" type="int" regnum="30"/>
+    <reg name="t6" bitsize="128" type="int" regnum="31"/>
+    <reg name="pc" bitsize="128" type="int" regnum="32"/>
+  </feature>
+</target>
+"#,
+        )
+    }
+}
@@ -1,9 +1,12 @@
-use crate::arch::RegId;
+use crate::arch::{name_in_table, raw_id_in_table, RegId};
 
 /// RISC-V Register identifier.
+///
+/// The size of the GPRs, PC, and CSRs is set to `u32`, `u64`, or `u128`
+/// based on the `<U>` type.
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
-pub enum RiscvRegId {
+pub enum RiscvRegId<U = u32> {
     /// General Purpose Register (x0-x31).
     Gpr(u8),
     /// Floating Point Register (f0-f31).
@@ -14,18 +17,149 @@ pub enum RiscvRegId {
     Csr(u16),
     /// Privilege level.
     Priv,
+    #[doc(hidden)]
+    _Size(U),
 }
 
-impl RegId for RiscvRegId {
+fn from_raw_id<U>(id: usize) -> Option<(RiscvRegId<U>, usize)> {
+    let ptrsize = core::mem::size_of::<U>();
+
+    let reg_size = match id {
+        0..=31 => (RiscvRegId::Gpr(id as u8), ptrsize),
+        32 => (RiscvRegId::Pc, ptrsize),
+        33..=64 => (RiscvRegId::Fpr((id - 33) as u8), 4),
+        65..=4160 => (RiscvRegId::Csr((id - 65) as u16), ptrsize),
+        PRIV_ID => (RiscvRegId::Priv, 1),
+        _ => return None,
+    };
+    Some(reg_size)
+}
+
+/// GPR, PC, and FPR names, indexed by raw GDB register number.
+const NAMES: [&str; 65] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "fp", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6", "pc", "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0",
+    "fa1", "fa2", "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7",
+    "fs8", "fs9", "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+];
+
+/// Raw GDB register number of the privilege level (`priv`) register.
+const PRIV_ID: usize = 4161;
+
+// CSRs aren't named, as GDB only knows the names of a subset of them.
+fn name_of_raw_id(id: usize) -> Option<&'static str> {
+    match id {
+        PRIV_ID => Some("priv"),
+        _ => name_in_table(&NAMES, id),
+    }
+}
+
+fn raw_id_of_name(name: &str) -> Option<usize> {
+    match name {
+        "priv" => Some(PRIV_ID),
+        _ => raw_id_in_table(&NAMES, name),
+    }
+}
+
+impl RegId for RiscvRegId<u32> {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
-        let reg_size = match id {
-            0..=31 => (Self::Gpr(id as u8), 4),
-            32 => (Self::Pc, 4),
-            33..=64 => (Self::Fpr((id - 33) as u8), 4),
-            65..=4160 => (Self::Csr((id - 65) as u16), 4),
-            4161 => (Self::Priv, 1),
-            _ => return None,
-        };
-        Some(reg_size)
+        from_raw_id::<u32>(id)
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_of_raw_id(id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_of_name(name)
+    }
+}
+
+impl RegId for RiscvRegId<u64> {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        from_raw_id::<u64>(id)
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_of_raw_id(id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_of_name(name)
+    }
+}
+
+impl RegId for RiscvRegId<u128> {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        from_raw_id::<u128>(id)
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_of_raw_id(id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_of_name(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arch::riscv::reg::RiscvCoreRegs;
+    use crate::arch::{Endian, Registers};
+
+    #[test]
+    fn reg_names() {
+        use crate::arch::riscv::{Riscv128, Riscv32, Riscv64};
+        use crate::arch::Arch;
+
+        // everything but the (unnamed) CSRs
+        crate::arch::assert_reg_names::<Riscv32>(65);
+        crate::arch::assert_reg_names::<Riscv64>(65);
+        crate::arch::assert_reg_names::<Riscv128>(65);
+
+        assert_eq!(Riscv32::reg_name(PRIV_ID), Some("priv"));
+        assert_eq!(Riscv32::reg_id_by_name("priv"), Some(PRIV_ID));
+        assert_eq!(Riscv32::reg_name(65), None);
+        // names in the RV128 target description match the built-in names
+        assert_eq!(Riscv128::reg_name(8), Some("fp"));
+        assert_eq!(Riscv128::reg_id_by_name("pc"), Some(32));
+    }
+
+    #[test]
+    fn rv128_regid() {
+        assert!(matches!(
+            RiscvRegId::<u128>::from_raw_id(32),
+            Some((RiscvRegId::Pc, 16))
+        ));
+        assert!(matches!(
+            RiscvRegId::<u128>::from_raw_id(5),
+            Some((RiscvRegId::Gpr(5), 16))
+        ));
+    }
+
+    #[test]
+    fn rv128_regs_roundtrip() {
+        let mut regs = RiscvCoreRegs::<u128>::default();
+        for (i, reg) in regs.x.iter_mut().enumerate() {
+            *reg = (i as u128) << 100 | 0x0123_4567_89ab_cdef;
+        }
+        regs.pc = u128::MAX - 1;
+
+        let mut bytes = [0; 33 * 16];
+        let mut len = 0;
+        regs.gdb_serialize(Endian::Little, |b| {
+            bytes[len] = b.unwrap();
+            len += 1;
+        });
+        assert_eq!(len, bytes.len());
+        // registers are little-endian
+        assert_eq!(bytes[32 * 16..], (u128::MAX - 1).to_le_bytes());
+
+        let mut new_regs = RiscvCoreRegs::<u128>::default();
+        new_regs.gdb_deserialize(Endian::Little, &bytes).unwrap();
+        assert_eq!(regs, new_regs);
     }
 }
@@ -1,11 +1,12 @@
 use num_traits::PrimInt;
 
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 use crate::internal::LeBytes;
 
 /// RISC-V Integer registers.
 ///
-/// The register width is set to `u32` or `u64` based on the `<U>` type.
+/// The register width is set to `u32`, `u64`, or `u128` based on the `<U>`
+/// type.
 ///
 /// Useful links:
 /// * [GNU binutils-gdb XML descriptions](https://github.com/bminor/binutils-gdb/blob/master/gdb/features/riscv)
@@ -22,7 +23,7 @@ impl<U> Registers for RiscvCoreRegs<U>
 where
     U: PrimInt + LeBytes + Default + core::fmt::Debug,
 {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_le_bytes {
             ($value:expr) => {
                 let mut buf = [0; 16];
@@ -44,7 +45,7 @@ where
         write_le_bytes!(&self.pc);
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         let ptrsize = core::mem::size_of::<U>();
 
         // ensure bytes.chunks_exact(ptrsize) won't panic
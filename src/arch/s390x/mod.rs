@@ -0,0 +1,31 @@
+//! Implementations for the IBM z/Architecture (s390x).
+
+use crate::arch::{Arch, Endian};
+
+pub mod reg;
+
+/// Implements `Arch` for 64-bit s390x (z/Architecture).
+///
+/// All registers are transferred in big-endian byte order.
+///
+/// There is no dedicated program counter register on s390x. Instead, the
+/// address of the next instruction lives in the second half of the Program
+/// Status Word (PSW), which GDB exposes as the `pswa` register (the first half,
+/// `pswm`, holds the PSW's mask / condition bits). See
+/// [`S390xRegId::Pswa`](reg::id::S390xRegId::Pswa) for details.
+pub enum S390x {}
+
+impl Arch for S390x {
+    type Usize = u64;
+    type Registers = reg::S390xCoreRegs;
+    type RegId = reg::id::S390xRegId;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(include_str!("s390x.xml"))
+    }
+
+    fn endianness() -> Endian {
+        Endian::Big
+    }
+}
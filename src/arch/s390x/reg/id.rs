@@ -0,0 +1,84 @@
+use crate::arch::RegId;
+
+/// s390x register identifier.
+///
+/// Sources:
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390x-core64.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390-acr.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390-fpr.xml
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum S390xRegId {
+    /// Program Status Word - mask
+    Pswm,
+    /// Program Status Word - instruction address
+    ///
+    /// s390x has no dedicated PC register: GDB treats `pswa` as the program
+    /// counter, reading it to determine where the target stopped, and writing
+    /// it to redirect execution (e.g: when stepping over a breakpoint, or
+    /// jumping to a different address). Writes to `pswa` should therefore
+    /// update the address of the next instruction to execute, leaving the
+    /// PSW mask (`pswm`) untouched.
+    Pswa,
+    /// General purpose registers (R0-R15)
+    Gpr(u8),
+    /// Access registers (A0-A15)
+    Acr(u8),
+    /// Floating point control register
+    Fpc,
+    /// Floating point registers (F0-F15)
+    Fpr(u8),
+}
+
+impl RegId for S390xRegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        let reg = match id {
+            0 => (Self::Pswm, 8),
+            1 => (Self::Pswa, 8),
+            2..=17 => (Self::Gpr((id - 2) as u8), 8),
+            18..=33 => (Self::Acr((id - 18) as u8), 4),
+            34 => (Self::Fpc, 4),
+            35..=50 => (Self::Fpr((id - 35) as u8), 8),
+            _ => return None,
+        };
+        Some(reg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arch::traits::RegId;
+    use crate::arch::traits::{Endian, Registers};
+
+    fn test<Rs: Registers, RId: RegId>() {
+        // Obtain the data length written by `gdb_serialize` by passing a custom
+        // closure.
+        let mut serialized_data_len = 0;
+        let counter = |b: Option<u8>| {
+            if b.is_some() {
+                serialized_data_len += 1;
+            }
+        };
+        Rs::default().gdb_serialize(Endian::Little, counter);
+
+        // Accumulate register sizes returned by `from_raw_id`.
+        let mut i = 0;
+        let mut sum_reg_sizes = 0;
+        while let Some((_, size)) = RId::from_raw_id(i) {
+            sum_reg_sizes += size;
+            i += 1;
+        }
+
+        assert_eq!(serialized_data_len, sum_reg_sizes);
+    }
+
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::s390x::S390x>(52)
+    }
+
+    #[test]
+    fn test_s390x() {
+        test::<crate::arch::s390x::reg::S390xCoreRegs, crate::arch::s390x::reg::id::S390xRegId>()
+    }
+}
@@ -0,0 +1,8 @@
+//! `Register` structs for the s390x architecture.
+
+/// `RegId` definitions for the s390x architecture.
+pub mod id;
+
+mod s390x_core;
+
+pub use s390x_core::S390xCoreRegs;
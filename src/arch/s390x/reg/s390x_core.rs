@@ -0,0 +1,133 @@
+use core::convert::TryInto;
+
+use crate::arch::{Endian, Registers};
+
+/// s390x core, access, and floating point registers.
+///
+/// All registers are transferred in big-endian byte order.
+///
+/// Sources:
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390x-core64.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390-acr.xml
+/// * https://github.com/bminor/binutils-gdb/blob/master/gdb/features/s390-fpr.xml
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct S390xCoreRegs {
+    /// Program Status Word - mask
+    pub pswm: u64,
+    /// Program Status Word - instruction address (i.e: the PC)
+    pub pswa: u64,
+    /// General purpose registers (R0-R15)
+    pub r: [u64; 16],
+    /// Access registers (A0-A15)
+    pub a: [u32; 16],
+    /// Floating point control register
+    pub fpc: u32,
+    /// Floating point registers (F0-F15)
+    pub f: [f64; 16],
+}
+
+impl Registers for S390xCoreRegs {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        macro_rules! write_be_bytes {
+            ($value:expr) => {
+                for b in &$value.to_be_bytes() {
+                    write_byte(Some(*b))
+                }
+            };
+        }
+
+        write_be_bytes!(self.pswm);
+        write_be_bytes!(self.pswa);
+        for reg in &self.r {
+            write_be_bytes!(reg);
+        }
+        for reg in &self.a {
+            write_be_bytes!(reg);
+        }
+        write_be_bytes!(self.fpc);
+        for reg in &self.f {
+            write_be_bytes!(reg);
+        }
+    }
+
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        // PSW (16 bytes) + GPRs (128 bytes) + ACRs (64 bytes) + FPC (4 bytes) +
+        // FPRs (128 bytes)
+        if bytes.len() != 16 + 128 + 64 + 4 + 128 {
+            return Err(());
+        }
+
+        let (psw, rest) = bytes.split_at(16);
+        let (gprs, rest) = rest.split_at(128);
+        let (acrs, rest) = rest.split_at(64);
+        let (fpc, fprs) = rest.split_at(4);
+
+        self.pswm = u64::from_be_bytes(psw[..8].try_into().unwrap());
+        self.pswa = u64::from_be_bytes(psw[8..].try_into().unwrap());
+        for (reg, x) in self.r.iter_mut().zip(gprs.chunks_exact(8)) {
+            *reg = u64::from_be_bytes(x.try_into().unwrap());
+        }
+        for (reg, x) in self.a.iter_mut().zip(acrs.chunks_exact(4)) {
+            *reg = u32::from_be_bytes(x.try_into().unwrap());
+        }
+        self.fpc = u32::from_be_bytes(fpc.try_into().unwrap());
+        for (reg, x) in self.f.iter_mut().zip(fprs.chunks_exact(8)) {
+            *reg = f64::from_be_bytes(x.try_into().unwrap());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn s390x_round_trip() {
+        let mut regs_before = S390xCoreRegs {
+            pswm: 0x0705_0001_8000_0000,
+            pswa: 0x0000_0000_0100_0a2e,
+            fpc: 0x0008_0000,
+            ..Default::default()
+        };
+        for (i, r) in regs_before.r.iter_mut().enumerate() {
+            *r = 0x1122_3344_5566_7700 + i as u64;
+        }
+        for (i, a) in regs_before.a.iter_mut().enumerate() {
+            *a = 0xaabb_cc00 + i as u32;
+        }
+        for (i, f) in regs_before.f.iter_mut().enumerate() {
+            *f = i as f64 * 1.5;
+        }
+
+        let mut data = vec![];
+        regs_before.gdb_serialize(Endian::Little, |x| data.push(x.unwrap()));
+        assert_eq!(data.len(), 340);
+
+        // registers are big-endian
+        assert_eq!(
+            &data[..8],
+            &[0x07, 0x05, 0x00, 0x01, 0x80, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            &data[8..16],
+            &[0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x2e]
+        );
+        assert_eq!(
+            &data[16..24],
+            &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x00]
+        );
+        assert_eq!(&data[144..148], &[0xaa, 0xbb, 0xcc, 0x00]);
+        assert_eq!(&data[208..212], &[0x00, 0x08, 0x00, 0x00]);
+        assert_eq!(&data[220..228], &1.5f64.to_be_bytes());
+
+        let mut regs_after = S390xCoreRegs::default();
+        regs_after.gdb_deserialize(Endian::Little, &data).unwrap();
+        assert_eq!(regs_before, regs_after);
+
+        assert!(regs_after
+            .gdb_deserialize(Endian::Little, &data[..339])
+            .is_err());
+    }
+}
@@ -12,6 +12,24 @@ pub trait RegId: Sized + Debug {
     ///
     /// Returns `None` if the register is not available.
     fn from_raw_id(id: usize) -> Option<(Self, usize)>;
+
+    /// (optional) Return the name of the register with raw GDB register
+    /// number `id`, as it appears in GDB's target description for the
+    /// architecture (e.g: `"pc"`).
+    ///
+    /// Register numbers which aren't decoded by `from_raw_id` should not have
+    /// a name.
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        let _ = id;
+        None
+    }
+
+    /// (optional) Return the raw GDB register number of the register called
+    /// `name`. The inverse of [`RegId::name_of_raw_id`].
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        let _ = name;
+        None
+    }
 }
 
 /// Stub implementation -- Returns `None` for all raw IDs.
@@ -21,6 +39,98 @@ impl RegId for () {
     }
 }
 
+/// A [`RegId`] which describes a self-contained register set, spanning the
+/// contiguous range of raw GDB register numbers `0..NUM_REGS`.
+///
+/// Register sets can be composed into a single `RegId` via [`ComposedRegId`].
+pub trait RegIdSet: RegId {
+    /// The number of raw GDB register numbers spanned by this register set
+    /// (including any numbers which `from_raw_id` doesn't map to a register).
+    const NUM_REGS: usize;
+}
+
+/// A [`RegId`] composed of a primary register set, followed by a secondary
+/// register set whose raw GDB register numbers start immediately after the
+/// primary set's.
+///
+/// This makes it possible to compose register sets (e.g: a core register file
+/// and a separate accelerator / coprocessor register bank described by a
+/// second target description feature) without hand-computing any offsets.
+/// The secondary set's `from_raw_id` is always passed ids relative to the
+/// start of the set.
+///
+/// Typically, `Arch::Registers` would only cover the primary set (which is
+/// transferred in bulk via the `g`/`G` packets), while registers from the
+/// secondary set are only ever accessed individually via
+/// `Target::read/write_register` (i.e: the `p`/`P` packets).
+///
+/// More than two register sets can be composed by nesting `ComposedRegId`s
+/// (e.g: `ComposedRegId<CoreRegId, ComposedRegId<FpuRegId, DspRegId>>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposedRegId<P, S> {
+    /// A register from the primary register set.
+    Primary(P),
+    /// A register from the secondary register set.
+    Secondary(S),
+}
+
+impl<P: RegIdSet, S: RegId> RegId for ComposedRegId<P, S> {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        if id < P::NUM_REGS {
+            let (reg, size) = P::from_raw_id(id)?;
+            Some((ComposedRegId::Primary(reg), size))
+        } else {
+            let (reg, size) = S::from_raw_id(id - P::NUM_REGS)?;
+            Some((ComposedRegId::Secondary(reg), size))
+        }
+    }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        if id < P::NUM_REGS {
+            P::name_of_raw_id(id)
+        } else {
+            S::name_of_raw_id(id - P::NUM_REGS)
+        }
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        P::raw_id_of_name(name).or_else(|| Some(S::raw_id_of_name(name)? + P::NUM_REGS))
+    }
+}
+
+impl<P: RegIdSet, S: RegIdSet> RegIdSet for ComposedRegId<P, S> {
+    const NUM_REGS: usize = P::NUM_REGS + S::NUM_REGS;
+}
+
+/// The byte order used to de/serialize a target's registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// Little-endian
+    Little,
+    /// Big-endian
+    Big,
+}
+
+impl Endian {
+    /// Write `val` into the start of `buf` using this byte order, returning
+    /// the number of bytes written (or `None` if `buf` is too small).
+    pub fn write<I: LeBytes + BeBytes>(self, val: I, buf: &mut [u8]) -> Option<usize> {
+        match self {
+            Endian::Little => val.to_le_bytes(buf),
+            Endian::Big => val.to_be_bytes(buf),
+        }
+    }
+
+    /// Parse a value from `buf` using this byte order. Returns `None` upon
+    /// overflow.
+    pub fn read<I: LeBytes + BeBytes>(self, buf: &[u8]) -> Option<I> {
+        match self {
+            Endian::Little => I::from_le_bytes(buf),
+            Endian::Big => I::from_be_bytes(buf),
+        }
+    }
+}
+
 /// Methods to read/write architecture-specific registers.
 ///
 /// Registers must be de/serialized in the order specified by the architecture's
@@ -30,14 +140,51 @@ impl RegId for () {
 /// github.com/bminor/binutils-gdb/blob/master/gdb/features/arm/arm-core.xml
 // TODO: add way to de/serialize arbitrary "missing"/"uncollected" registers.
 pub trait Registers: Default + Debug + Clone + PartialEq {
-    /// Serialize `self` into a GDB register bytestream.
+    /// Serialize `self` into a GDB register bytestream, using the byte order
+    /// `endian`.
     ///
     /// Missing registers are serialized by passing `None` to write_byte.
-    fn gdb_serialize(&self, write_byte: impl FnMut(Option<u8>));
+    ///
+    /// `gdbstub` passes the target's current byte order (see
+    /// [`Arch::endianness`]). Register sets which only exist in a single byte
+    /// order may ignore `endian`.
+    fn gdb_serialize(&self, endian: Endian, write_byte: impl FnMut(Option<u8>));
 
-    /// Deserialize a GDB register bytestream into `self`.
+    /// Deserialize a GDB register bytestream into `self`, using the byte order
+    /// `endian`.
     #[allow(clippy::clippy::result_unit_err)]
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()>;
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()>;
+}
+
+/// Breakpoint kind for specific architectures.
+///
+/// This trait corresponds to the _kind_ field of the `Z0` / `Z1` breakpoint
+/// packets, as documented [here](https://sourceware.org/gdb/current/onlinedocs/gdb/Packets.html#insert-breakpoint-or-watchpoint-packet).
+///
+/// A breakpoint's "kind" is architecture-specific, and typically indicates the
+/// size of the breakpoint instruction which should be inserted (e.g: ARM uses
+/// it to distinguish between 16-bit Thumb, 32-bit Thumb-2, and 32-bit ARM
+/// breakpoints).
+///
+/// Architectures with a single breakpoint size can use `()`, while
+/// architectures which don't require any special interpretation of the kind
+/// can use `usize` (i.e: the raw kind value).
+pub trait BreakpointKind: Sized + Debug {
+    /// Parse `Self` from the raw kind value sent by GDB, returning `None` if
+    /// the value is invalid.
+    fn from_usize(kind: usize) -> Option<Self>;
+}
+
+impl BreakpointKind for () {
+    fn from_usize(_kind: usize) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl BreakpointKind for usize {
+    fn from_usize(kind: usize) -> Option<Self> {
+        Some(kind)
+    }
 }
 
 /// Encodes architecture-specific information, such as pointer size, register
@@ -60,9 +207,16 @@ pub trait Arch {
     ///
     /// NOTE: The `RegId` type is not required to have a 1:1 correspondence with
     /// the `Registers` type, and may include register identifiers which are
-    /// separate from the main `Registers` structure.
+    /// separate from the main `Registers` structure. Architectures with
+    /// additional register sets (e.g: coprocessor registers exposed via a
+    /// separate target description feature) can use [`ComposedRegId`] to
+    /// append them after the main register set.
     type RegId: RegId;
 
+    /// Breakpoint kind, used to decode the `kind` field of software and
+    /// hardware breakpoint packets (see [`BreakpointKind`]).
+    type BreakpointKind: BreakpointKind;
+
     /// (optional) Return the platform's `features.xml` file.
     ///
     /// Implementing this method enables `gdb` to automatically detect the
@@ -82,4 +236,142 @@ pub trait Arch {
     fn target_description_xml() -> Option<&'static str> {
         None
     }
+
+    /// (optional) Return the byte order used to de/serialize the
+    /// architecture's registers (via the `g` / `G` packets).
+    ///
+    /// Defaults to little-endian. Bi-endian targets whose byte order can
+    /// change at runtime should implement the
+    /// [`EndiannessOverride`](crate::target::ext::endianness_override::EndiannessOverride)
+    /// extension instead.
+    ///
+    /// _Note:_ GDB's target description format has no way to declare a byte
+    /// order, so GDB must still be told about big-endian targets via the
+    /// `set endian big` command.
+    fn endianness() -> Endian {
+        Endian::Little
+    }
+
+    /// (optional) Return the raw GDB register numbers of registers which
+    /// should be "expedited" in stop replies (e.g: the PC and SP).
+    ///
+    /// GDB fetches these registers after almost every stop, so including their
+    /// values directly in the stop reply eliminates a `p` packet round-trip
+    /// per register, which noticeably speeds up single-stepping.
+    ///
+    /// Expedited registers are read via `Target::read_register`, and must be
+    /// no larger than 64 bytes. Registers which are unavailable (i.e: which
+    /// return a non-fatal error) are omitted from the stop reply.
+    fn expedited_registers() -> &'static [usize] {
+        &[]
+    }
+
+    /// (optional) Return the name of the register with raw GDB register
+    /// number `reg_id` (e.g: `"pc"`).
+    ///
+    /// This is useful when mapping GDB's register numbers onto a target's
+    /// internal register storage (e.g: when implementing
+    /// `Target::read/write_register`).
+    ///
+    /// By default, the name is taken from the `<reg>` elements of
+    /// [`Arch::target_description_xml`] (if any), falling back to
+    /// [`RegId::name_of_raw_id`].
+    fn reg_name(reg_id: usize) -> Option<&'static str> {
+        Self::target_description_xml()
+            .and_then(|xml| super::xml_regs(xml).find(|(id, _)| *id == reg_id))
+            .map(|(_, name)| name)
+            .or_else(|| Self::RegId::name_of_raw_id(reg_id))
+    }
+
+    /// (optional) Return the raw GDB register number of the register called
+    /// `name`. The inverse of [`Arch::reg_name`].
+    fn reg_id_by_name(name: &str) -> Option<usize> {
+        Self::target_description_xml()
+            .and_then(|xml| super::xml_regs(xml).find(|(_, n)| *n == name))
+            .map(|(id, _)| id)
+            .or_else(|| Self::RegId::raw_id_of_name(name))
+    }
+}
+
+/// Check that every register decoded by `A::RegId` (with a raw id below
+/// `num_regs`) has a unique name, which maps back to the same raw id.
+#[cfg(test)]
+pub(crate) fn assert_reg_names<A: Arch>(num_regs: usize) {
+    for id in 0..num_regs {
+        match (A::RegId::from_raw_id(id), A::reg_name(id)) {
+            (Some(_), Some(name)) => assert_eq!(A::reg_id_by_name(name), Some(id), "{}", name),
+            (Some(reg), None) => panic!("{:?} (raw id {}) has no name", reg, id),
+            (None, Some(name)) => panic!("raw id {} isn't decoded, but is named {}", id, name),
+            (None, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Core(usize);
+
+    impl RegId for Core {
+        fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+            // register 2 is a "hole" in the core register set
+            match id {
+                0 | 1 | 3 => Some((Core(id), 4)),
+                _ => None,
+            }
+        }
+    }
+
+    impl RegIdSet for Core {
+        const NUM_REGS: usize = 4;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Acc(usize);
+
+    impl RegId for Acc {
+        fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+            match id {
+                0..=1 => Some((Acc(id), 8)),
+                _ => None,
+            }
+        }
+    }
+
+    impl RegIdSet for Acc {
+        const NUM_REGS: usize = 2;
+    }
+
+    type Dsp = ComposedRegId<Core, Acc>;
+
+    #[test]
+    fn composed_reg_id() {
+        use ComposedRegId::*;
+
+        assert_eq!(Dsp::from_raw_id(0), Some((Primary(Core(0)), 4)));
+        assert_eq!(Dsp::from_raw_id(2), None);
+        assert_eq!(Dsp::from_raw_id(3), Some((Primary(Core(3)), 4)));
+        assert_eq!(Dsp::from_raw_id(4), Some((Secondary(Acc(0)), 8)));
+        assert_eq!(Dsp::from_raw_id(5), Some((Secondary(Acc(1)), 8)));
+        assert_eq!(Dsp::from_raw_id(6), None);
+        assert_eq!(Dsp::NUM_REGS, 6);
+    }
+
+    #[test]
+    fn nested_composed_reg_id() {
+        use ComposedRegId::*;
+
+        type Nested = ComposedRegId<Core, ComposedRegId<Acc, Acc>>;
+        assert_eq!(
+            Nested::from_raw_id(5),
+            Some((Secondary(Primary(Acc(1))), 8))
+        );
+        assert_eq!(
+            Nested::from_raw_id(6),
+            Some((Secondary(Secondary(Acc(0))), 8))
+        );
+        assert_eq!(Nested::NUM_REGS, 8);
+    }
 }
@@ -0,0 +1,91 @@
+//! Implementations for WebAssembly (wasm32) interpreters.
+//!
+//! WebAssembly doesn't have any conventional registers. Instead, this `Arch`
+//! exposes the current code offset as a `pc` register, alongside a
+//! configurable number of "synthetic" 64-bit registers, which interpreters can
+//! use to expose whatever state they see fit (e.g: the current function's
+//! locals, or the module's globals).
+//!
+//! The number of synthetic registers is chosen at runtime (and may change
+//! between debugging sessions), and is tracked by
+//! [`Wasm32Regs::num_regs`](reg::Wasm32Regs::num_regs).
+//!
+//! _Note:_ Mainline GDB does not include support for WebAssembly, so this
+//! `Arch` requires a debugger which is able to work with an
+//! `<architecture>wasm32</architecture>` target description.
+
+use core::fmt::{self, Write};
+
+use crate::arch::Arch;
+
+pub mod reg;
+
+/// Implements `Arch` for 32-bit WebAssembly.
+///
+/// _Note:_ As the target description depends on the number of synthetic
+/// registers in use, this `Arch` does not provide a static target description
+/// XML. Instead, implement the
+/// [`TargetDescriptionXmlOverride`](crate::target::ext::target_description_xml_override::TargetDescriptionXmlOverride)
+/// extension, and use [`wasm32_target_description_xml`] to generate it.
+pub enum Wasm32 {}
+
+impl Arch for Wasm32 {
+    type Usize = u32;
+    type Registers = reg::Wasm32Regs;
+    type RegId = reg::id::Wasm32RegId;
+    type BreakpointKind = usize;
+}
+
+/// Generate the target description XML for a [`Wasm32`] target exposing
+/// `num_regs` synthetic registers (clamped to
+/// [`WASM32_MAX_REGS`](reg::WASM32_MAX_REGS)).
+///
+/// This method follows the same paging semantics as
+/// [`TargetDescriptionXmlOverride::target_description_xml`](crate::target::ext::target_description_xml_override::TargetDescriptionXmlOverride::target_description_xml):
+/// The XML is copied into `buf` starting at `offset`, and the number of bytes
+/// written is returned (returning `0` once the end of the XML is reached).
+pub fn wasm32_target_description_xml(num_regs: usize, offset: u64, buf: &mut [u8]) -> usize {
+    crate::arch::write_paged(offset, buf, |w| {
+        write_wasm32_target_description_xml(w, num_regs.min(reg::WASM32_MAX_REGS))
+    })
+}
+
+fn write_wasm32_target_description_xml(w: &mut dyn Write, num_regs: usize) -> fmt::Result {
+    w.write_str(r#"<?xml version="1.0"?><!DOCTYPE target SYSTEM "gdb-target.dtd">"#)?;
+    w.write_str(r#"<target version="1.0"><architecture>wasm32</architecture>"#)?;
+    w.write_str(r#"<feature name="org.gnu.gdb.wasm32.core">"#)?;
+    w.write_str(r#"<reg name="pc" bitsize="32" type="code_ptr" regnum="0"/>"#)?;
+    for i in 0..num_regs {
+        write!(w, r#"<reg name="s{}" bitsize="64" type="int"/>"#, i)?;
+    }
+    w.write_str("</feature>")?;
+    w.write_str("</target>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_description_xml() {
+        let mut buf = [0; 1024];
+        let len = wasm32_target_description_xml(2, 0, &mut buf);
+        let xml = core::str::from_utf8(&buf[..len]).unwrap();
+        assert!(xml.contains(r#"<reg name="pc" bitsize="32" type="code_ptr" regnum="0"/>"#));
+        assert!(xml.contains(r#"<reg name="s1" bitsize="64" type="int"/>"#));
+        assert!(!xml.contains(r#"name="s2""#));
+        assert!(xml.ends_with("</target>"));
+
+        // paged reads reassemble to the same XML
+        let mut chunk = [0; 7];
+        let mut paged = std::vec::Vec::new();
+        loop {
+            let n = wasm32_target_description_xml(2, paged.len() as u64, &mut chunk);
+            if n == 0 {
+                break;
+            }
+            paged.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(paged, xml.as_bytes());
+    }
+}
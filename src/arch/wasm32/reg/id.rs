@@ -0,0 +1,33 @@
+use crate::arch::{RegId, RegIdSet};
+
+use super::WASM32_MAX_REGS;
+
+/// WebAssembly register identifier.
+///
+/// `from_raw_id` maps register numbers for all [`WASM32_MAX_REGS`] synthetic
+/// registers, regardless of how many are currently in use. Targets should
+/// return a non-fatal error when accessing a synthetic register beyond
+/// [`Wasm32Regs::num_regs`](super::Wasm32Regs::num_regs).
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Wasm32RegId {
+    /// Program counter (i.e: the current code offset)
+    Pc,
+    /// Synthetic registers
+    Synthetic(u8),
+}
+
+impl RegId for Wasm32RegId {
+    fn from_raw_id(id: usize) -> Option<(Self, usize)> {
+        let reg = match id {
+            0 => (Self::Pc, 4),
+            _ if id <= WASM32_MAX_REGS => (Self::Synthetic((id - 1) as u8), 8),
+            _ => return None,
+        };
+        Some(reg)
+    }
+}
+
+impl RegIdSet for Wasm32RegId {
+    const NUM_REGS: usize = 1 + WASM32_MAX_REGS;
+}
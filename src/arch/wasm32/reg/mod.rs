@@ -0,0 +1,8 @@
+//! `Register` structs for WebAssembly interpreters.
+
+/// `RegId` definitions for WebAssembly interpreters.
+pub mod id;
+
+mod wasm32;
+
+pub use wasm32::{Wasm32Regs, WASM32_MAX_REGS};
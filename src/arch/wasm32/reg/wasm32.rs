@@ -0,0 +1,106 @@
+use core::convert::TryInto;
+
+use crate::arch::{Endian, Registers};
+
+/// The maximum number of synthetic registers a [`Wasm32Regs`] can hold.
+pub const WASM32_MAX_REGS: usize = 64;
+
+/// WebAssembly "registers": the current code offset, followed by a variable
+/// number of synthetic 64-bit registers.
+///
+/// The synthetic registers are stored at their maximum count, but only the
+/// first [`num_regs`](Wasm32Regs::num_regs) are sent to GDB (which must match
+/// the number of registers described in the target description XML).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wasm32Regs {
+    /// Program counter (i.e: the current code offset)
+    pub pc: u32,
+    /// Synthetic registers (e.g: locals / globals)
+    pub regs: [u64; WASM32_MAX_REGS],
+    /// The number of synthetic registers currently in use
+    ///
+    /// Must be no larger than [`WASM32_MAX_REGS`].
+    pub num_regs: usize,
+}
+
+impl Default for Wasm32Regs {
+    fn default() -> Wasm32Regs {
+        Wasm32Regs {
+            pc: 0,
+            regs: [0; WASM32_MAX_REGS],
+            num_regs: 0,
+        }
+    }
+}
+
+impl Registers for Wasm32Regs {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
+        for b in &self.pc.to_le_bytes() {
+            write_byte(Some(*b));
+        }
+        for reg in &self.regs[..self.num_regs.min(WASM32_MAX_REGS)] {
+            for b in &reg.to_le_bytes() {
+                write_byte(Some(*b));
+            }
+        }
+    }
+
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
+        if bytes.len() < 4 {
+            return Err(());
+        }
+
+        let (pc, regs) = bytes.split_at(4);
+        let num_regs = regs.len() / 8;
+        if !regs.chunks_exact(8).remainder().is_empty() || num_regs > WASM32_MAX_REGS {
+            return Err(());
+        }
+
+        self.pc = u32::from_le_bytes(pc.try_into().unwrap());
+        for (reg, x) in self.regs.iter_mut().zip(regs.chunks_exact(8)) {
+            *reg = u64::from_le_bytes(x.try_into().unwrap());
+        }
+        self.num_regs = num_regs;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wasm32_round_trip() {
+        let mut regs_before = Wasm32Regs {
+            pc: 0x0000_1a2b,
+            num_regs: 3,
+            ..Default::default()
+        };
+        regs_before.regs[0] = 1;
+        regs_before.regs[1] = u64::MAX;
+        regs_before.regs[2] = 0x0102_0304_0506_0708;
+
+        let mut data = vec![];
+        regs_before.gdb_serialize(Endian::Little, |x| data.push(x.unwrap()));
+        assert_eq!(data.len(), 4 + 3 * 8);
+        assert_eq!(&data[..4], &[0x2b, 0x1a, 0x00, 0x00]);
+        assert_eq!(
+            &data[20..],
+            &[0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]
+        );
+
+        let mut regs_after = Wasm32Regs::default();
+        regs_after.gdb_deserialize(Endian::Little, &data).unwrap();
+        assert_eq!(regs_before, regs_after);
+
+        // the number of synthetic registers is inferred from the packet length
+        regs_after
+            .gdb_deserialize(Endian::Little, &data[..12])
+            .unwrap();
+        assert_eq!(regs_after.num_regs, 1);
+        assert!(regs_after
+            .gdb_deserialize(Endian::Little, &data[..11])
+            .is_err());
+    }
+}
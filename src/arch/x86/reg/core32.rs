@@ -1,7 +1,7 @@
 use core::convert::TryInto;
 
 use crate::arch::x86::reg::{X87FpuInternalRegs, F80};
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 
 /// 32-bit x86 core registers (+ SSE extensions).
 ///
@@ -42,7 +42,7 @@ pub struct X86CoreRegs {
 }
 
 impl Registers for X86CoreRegs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_bytes {
             ($bytes:expr) => {
                 for b in $bytes {
@@ -71,7 +71,7 @@ impl Registers for X86CoreRegs {
             write_bytes!(st_reg);
         }
 
-        self.fpu.gdb_serialize(&mut write_byte);
+        self.fpu.gdb_serialize(endian, &mut write_byte);
 
         // xmm0 to xmm15
         for xmm_reg in &self.xmm {
@@ -85,7 +85,7 @@ impl Registers for X86CoreRegs {
         (0..4).for_each(|_| write_byte(None))
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         if bytes.len() < 0x138 {
             return Err(());
         }
@@ -117,7 +117,7 @@ impl Registers for X86CoreRegs {
             *reg = regs.next().ok_or(())?.map_err(|_| ())?;
         }
 
-        self.fpu.gdb_deserialize(&bytes[0x90..0xb0])?;
+        self.fpu.gdb_deserialize(endian, &bytes[0x90..0xb0])?;
 
         let mut regs = bytes[0xb0..0x130]
             .chunks_exact(0x10)
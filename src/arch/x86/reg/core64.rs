@@ -1,7 +1,7 @@
 use core::convert::TryInto;
 
 use crate::arch::x86::reg::{X87FpuInternalRegs, F80};
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 
 /// 64-bit x86 core registers (+ SSE extensions).
 ///
@@ -28,7 +28,7 @@ pub struct X86_64CoreRegs {
 }
 
 impl Registers for X86_64CoreRegs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_bytes {
             ($bytes:expr) => {
                 for b in $bytes {
@@ -58,7 +58,7 @@ impl Registers for X86_64CoreRegs {
             write_bytes!(st_reg);
         }
 
-        self.fpu.gdb_serialize(&mut write_byte);
+        self.fpu.gdb_serialize(endian, &mut write_byte);
 
         // xmm0 to xmm15
         for xmm_reg in &self.xmm {
@@ -67,14 +67,9 @@ impl Registers for X86_64CoreRegs {
 
         // mxcsr
         write_bytes!(&self.mxcsr.to_le_bytes());
-
-        // padding?
-        // XXX: Couldn't figure out what these do and GDB doesn't actually display any
-        // registers that use these values.
-        (0..0x18).for_each(|_| write_byte(None))
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         if bytes.len() < 0x218 {
             return Err(());
         }
@@ -104,7 +99,7 @@ impl Registers for X86_64CoreRegs {
             *reg = regs.next().ok_or(())?.map_err(|_| ())?;
         }
 
-        self.fpu.gdb_deserialize(&bytes[0xF4..0x114])?;
+        self.fpu.gdb_deserialize(endian, &bytes[0xF4..0x114])?;
 
         let mut regs = bytes[0x114..0x214]
             .chunks_exact(0x10)
@@ -119,3 +114,44 @@ impl Registers for X86_64CoreRegs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn x87_round_trip() {
+        let mut regs = X86_64CoreRegs {
+            rip: 0x1122_3344_5566_7788,
+            eflags: 0x246,
+            mxcsr: 0x1f80,
+            ..Default::default()
+        };
+        // 1.0, as an 80-bit extended precision float
+        regs.st[0] = [0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f];
+        regs.st[7] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        regs.fpu.fctrl = 0x037f;
+        regs.fpu.fop = 0x07ff;
+        regs.xmm[0] = 0xaa;
+
+        let mut bytes = Vec::new();
+        regs.gdb_serialize(Endian::Little, |b| bytes.push(b.unwrap()));
+
+        assert_eq!(bytes.len(), 0x218);
+        assert_eq!(bytes[0x80..0x88], 0x1122_3344_5566_7788u64.to_le_bytes());
+        assert_eq!(bytes[0x88..0x8C], 0x246u32.to_le_bytes());
+        // st0..st7 are exactly 10 bytes each, little-endian
+        assert_eq!(bytes[0xA4..0xAE], regs.st[0]);
+        assert_eq!(bytes[0xEA..0xF4], regs.st[7]);
+        assert_eq!(bytes[0xF4..0xF8], 0x037fu32.to_le_bytes());
+        assert_eq!(bytes[0x110..0x114], 0x07ffu32.to_le_bytes());
+        assert_eq!(bytes[0x114], 0xaa);
+        assert_eq!(bytes[0x214..0x218], 0x1f80u32.to_le_bytes());
+
+        let mut deserialized = X86_64CoreRegs::default();
+        deserialized
+            .gdb_deserialize(Endian::Little, &bytes)
+            .unwrap();
+        assert_eq!(deserialized, regs);
+    }
+}
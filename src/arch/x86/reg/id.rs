@@ -1,4 +1,4 @@
-use crate::arch::RegId;
+use crate::arch::{name_in_table, raw_id_in_table, RegId};
 
 /// FPU register identifier.
 #[derive(Debug, Clone, Copy)]
@@ -79,6 +79,14 @@ pub enum X86CoreRegId {
     Mxcsr,
 }
 
+/// Register names, indexed by raw GDB register number.
+const X86_NAMES: [&str; 41] = [
+    "eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "eip", "eflags", "cs", "ss", "ds",
+    "es", "fs", "gs", "st0", "st1", "st2", "st3", "st4", "st5", "st6", "st7", "fctrl", "fstat",
+    "ftag", "fiseg", "fioff", "foseg", "fooff", "fop", "xmm0", "xmm1", "xmm2", "xmm3", "xmm4",
+    "xmm5", "xmm6", "xmm7", "mxcsr",
+];
+
 impl RegId for X86CoreRegId {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
         use self::X86CoreRegId::*;
@@ -106,6 +114,14 @@ impl RegId for X86CoreRegId {
         };
         Some(r)
     }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&X86_NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&X86_NAMES, name)
+    }
 }
 
 /// 64-bit x86 core + SSE register identifier.
@@ -134,14 +150,23 @@ pub enum X86_64CoreRegId {
     Mxcsr,
 }
 
+/// Register names, indexed by raw GDB register number.
+const X86_64_NAMES: [&str; 57] = [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15", "rip", "eflags", "cs", "ss", "ds", "es", "fs", "gs", "st0", "st1", "st2", "st3",
+    "st4", "st5", "st6", "st7", "fctrl", "fstat", "ftag", "fiseg", "fioff", "foseg", "fooff",
+    "fop", "xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7", "xmm8", "xmm9", "xmm10",
+    "xmm11", "xmm12", "xmm13", "xmm14", "xmm15", "mxcsr",
+];
+
 impl RegId for X86_64CoreRegId {
     fn from_raw_id(id: usize) -> Option<(Self, usize)> {
         use self::X86_64CoreRegId::*;
 
         let r = match id {
             0..=15 => (Gpr(id as u8), 8),
-            16 => (Rip, 4),
-            17 => (Eflags, 8),
+            16 => (Rip, 8),
+            17 => (Eflags, 4),
             18..=23 => (Segment(id as u8 - 18), 4),
             24..=31 => (St(id as u8 - 24), 10),
             32..=39 => match X87FpuInternalRegId::from_u8(id as u8 - 32) {
@@ -154,12 +179,20 @@ impl RegId for X86_64CoreRegId {
         };
         Some(r)
     }
+
+    fn name_of_raw_id(id: usize) -> Option<&'static str> {
+        name_in_table(&X86_64_NAMES, id)
+    }
+
+    fn raw_id_of_name(name: &str) -> Option<usize> {
+        raw_id_in_table(&X86_64_NAMES, name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::arch::traits::RegId;
-    use crate::arch::traits::Registers;
+    use crate::arch::traits::{Endian, Registers};
 
     /// Compare the following two values which are expected to be the same:
     /// * length of data written by `Registers::gdb_serialize()` in byte
@@ -173,7 +206,7 @@ mod tests {
                 serialized_data_len += 1;
             }
         };
-        Rs::default().gdb_serialize(counter);
+        Rs::default().gdb_serialize(Endian::Little, counter);
 
         // Accumulate register sizes returned by `from_raw_id`.
         let mut i = 0;
@@ -186,6 +219,25 @@ mod tests {
         assert_eq!(serialized_data_len, sum_reg_sizes);
     }
 
+    #[test]
+    fn reg_names() {
+        crate::arch::assert_reg_names::<crate::arch::x86::X86_SSE>(42);
+        crate::arch::assert_reg_names::<crate::arch::x86::X86_64_SSE>(58);
+    }
+
+    #[test]
+    fn reg_names_match_target_xml() {
+        use crate::arch::x86::X86_64_SSE;
+        use crate::arch::Arch;
+
+        for id in 0..57 {
+            assert_eq!(
+                super::X86_64CoreRegId::name_of_raw_id(id),
+                X86_64_SSE::<super::X86_64CoreRegId>::reg_name(id)
+            );
+        }
+    }
+
     #[test]
     fn test_x86() {
         test::<crate::arch::x86::reg::X86CoreRegs, crate::arch::x86::reg::id::X86CoreRegId>()
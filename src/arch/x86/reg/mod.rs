@@ -2,7 +2,7 @@
 
 use core::convert::TryInto;
 
-use crate::arch::Registers;
+use crate::arch::{Endian, Registers};
 
 /// `RegId` definitions for x86 architectures.
 pub mod id;
@@ -38,7 +38,7 @@ pub struct X87FpuInternalRegs {
 }
 
 impl Registers for X87FpuInternalRegs {
-    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+    fn gdb_serialize(&self, _endian: Endian, mut write_byte: impl FnMut(Option<u8>)) {
         macro_rules! write_bytes {
             ($bytes:expr) => {
                 for b in $bytes {
@@ -59,7 +59,7 @@ impl Registers for X87FpuInternalRegs {
         write_bytes!(&self.fop.to_le_bytes());
     }
 
-    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+    fn gdb_deserialize(&mut self, _endian: Endian, bytes: &[u8]) -> Result<(), ()> {
         if bytes.len() != 0x20 {
             return Err(());
         }
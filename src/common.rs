@@ -5,3 +5,490 @@ pub type Tid = core::num::NonZeroUsize;
 
 /// Process ID
 pub type Pid = core::num::NonZeroUsize;
+
+/// A signal, using GDB's target-independent signal numbering.
+///
+/// GDB uses its own signal numbers in the remote protocol (which don't
+/// necessarily match the target's native signal numbers, e.g: `SIGUSR1` is
+/// `10` on Linux, but `30` in GDB). See the `include/gdb/signals.def` file in
+/// the GDB source tree for the full list.
+///
+/// Targets which use a different signal numbering should implement the
+/// [`SignalMapping`](crate::target::ext::signal_mapping::SignalMapping) extension.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum Signal {
+    /// Signal 0 (i.e: no signal)
+    SIG0 = 0,
+    /// Hangup
+    SIGHUP = 1,
+    /// Interrupt
+    SIGINT = 2,
+    /// Quit
+    SIGQUIT = 3,
+    /// Illegal instruction
+    SIGILL = 4,
+    /// Trace/breakpoint trap
+    SIGTRAP = 5,
+    /// Aborted
+    SIGABRT = 6,
+    /// Emulation trap
+    SIGEMT = 7,
+    /// Arithmetic exception
+    SIGFPE = 8,
+    /// Killed
+    SIGKILL = 9,
+    /// Bus error
+    SIGBUS = 10,
+    /// Segmentation fault
+    SIGSEGV = 11,
+    /// Bad system call
+    SIGSYS = 12,
+    /// Broken pipe
+    SIGPIPE = 13,
+    /// Alarm clock
+    SIGALRM = 14,
+    /// Terminated
+    SIGTERM = 15,
+    /// Urgent I/O condition
+    SIGURG = 16,
+    /// Stopped (signal)
+    SIGSTOP = 17,
+    /// Stopped (user)
+    SIGTSTP = 18,
+    /// Continued
+    SIGCONT = 19,
+    /// Child status changed
+    SIGCHLD = 20,
+    /// Stopped (tty input)
+    SIGTTIN = 21,
+    /// Stopped (tty output)
+    SIGTTOU = 22,
+    /// I/O possible
+    SIGIO = 23,
+    /// CPU time limit exceeded
+    SIGXCPU = 24,
+    /// File size limit exceeded
+    SIGXFSZ = 25,
+    /// Virtual timer expired
+    SIGVTALRM = 26,
+    /// Profiling timer expired
+    SIGPROF = 27,
+    /// Window size changed
+    SIGWINCH = 28,
+    /// Resource lost
+    SIGLOST = 29,
+    /// User defined signal 1
+    SIGUSR1 = 30,
+    /// User defined signal 2
+    SIGUSR2 = 31,
+    /// Power fail/restart
+    SIGPWR = 32,
+    /// Pollable event occurred
+    SIGPOLL = 33,
+    /// Window system event
+    SIGWIND = 34,
+    /// Phone line status change
+    SIGPHONE = 35,
+    /// Process's LWPs are blocked
+    SIGWAITING = 36,
+    /// Signal LWP
+    SIGLWP = 37,
+    /// Swap space dangerously low
+    SIGDANGER = 38,
+    /// Monitor mode granted
+    SIGGRANT = 39,
+    /// Need to relinquish monitor mode
+    SIGRETRACT = 40,
+    /// Monitor mode data available
+    SIGMSG = 41,
+    /// Sound completed
+    SIGSOUND = 42,
+    /// Secure attention
+    SIGSAK = 43,
+    /// Process priority changed
+    SIGPRIO = 44,
+    /// Real-time event 33
+    SIG33 = 45,
+    /// Real-time event 34
+    SIG34 = 46,
+    /// Real-time event 35
+    SIG35 = 47,
+    /// Real-time event 36
+    SIG36 = 48,
+    /// Real-time event 37
+    SIG37 = 49,
+    /// Real-time event 38
+    SIG38 = 50,
+    /// Real-time event 39
+    SIG39 = 51,
+    /// Real-time event 40
+    SIG40 = 52,
+    /// Real-time event 41
+    SIG41 = 53,
+    /// Real-time event 42
+    SIG42 = 54,
+    /// Real-time event 43
+    SIG43 = 55,
+    /// Real-time event 44
+    SIG44 = 56,
+    /// Real-time event 45
+    SIG45 = 57,
+    /// Real-time event 46
+    SIG46 = 58,
+    /// Real-time event 47
+    SIG47 = 59,
+    /// Real-time event 48
+    SIG48 = 60,
+    /// Real-time event 49
+    SIG49 = 61,
+    /// Real-time event 50
+    SIG50 = 62,
+    /// Real-time event 51
+    SIG51 = 63,
+    /// Real-time event 52
+    SIG52 = 64,
+    /// Real-time event 53
+    SIG53 = 65,
+    /// Real-time event 54
+    SIG54 = 66,
+    /// Real-time event 55
+    SIG55 = 67,
+    /// Real-time event 56
+    SIG56 = 68,
+    /// Real-time event 57
+    SIG57 = 69,
+    /// Real-time event 58
+    SIG58 = 70,
+    /// Real-time event 59
+    SIG59 = 71,
+    /// Real-time event 60
+    SIG60 = 72,
+    /// Real-time event 61
+    SIG61 = 73,
+    /// Real-time event 62
+    SIG62 = 74,
+    /// Real-time event 63
+    SIG63 = 75,
+    /// LWP internal signal
+    SIGCANCEL = 76,
+    /// Real-time event 32
+    SIG32 = 77,
+    /// Real-time event 64
+    SIG64 = 78,
+    /// Real-time event 65
+    SIG65 = 79,
+    /// Real-time event 66
+    SIG66 = 80,
+    /// Real-time event 67
+    SIG67 = 81,
+    /// Real-time event 68
+    SIG68 = 82,
+    /// Real-time event 69
+    SIG69 = 83,
+    /// Real-time event 70
+    SIG70 = 84,
+    /// Real-time event 71
+    SIG71 = 85,
+    /// Real-time event 72
+    SIG72 = 86,
+    /// Real-time event 73
+    SIG73 = 87,
+    /// Real-time event 74
+    SIG74 = 88,
+    /// Real-time event 75
+    SIG75 = 89,
+    /// Real-time event 76
+    SIG76 = 90,
+    /// Real-time event 77
+    SIG77 = 91,
+    /// Real-time event 78
+    SIG78 = 92,
+    /// Real-time event 79
+    SIG79 = 93,
+    /// Real-time event 80
+    SIG80 = 94,
+    /// Real-time event 81
+    SIG81 = 95,
+    /// Real-time event 82
+    SIG82 = 96,
+    /// Real-time event 83
+    SIG83 = 97,
+    /// Real-time event 84
+    SIG84 = 98,
+    /// Real-time event 85
+    SIG85 = 99,
+    /// Real-time event 86
+    SIG86 = 100,
+    /// Real-time event 87
+    SIG87 = 101,
+    /// Real-time event 88
+    SIG88 = 102,
+    /// Real-time event 89
+    SIG89 = 103,
+    /// Real-time event 90
+    SIG90 = 104,
+    /// Real-time event 91
+    SIG91 = 105,
+    /// Real-time event 92
+    SIG92 = 106,
+    /// Real-time event 93
+    SIG93 = 107,
+    /// Real-time event 94
+    SIG94 = 108,
+    /// Real-time event 95
+    SIG95 = 109,
+    /// Real-time event 96
+    SIG96 = 110,
+    /// Real-time event 97
+    SIG97 = 111,
+    /// Real-time event 98
+    SIG98 = 112,
+    /// Real-time event 99
+    SIG99 = 113,
+    /// Real-time event 100
+    SIG100 = 114,
+    /// Real-time event 101
+    SIG101 = 115,
+    /// Real-time event 102
+    SIG102 = 116,
+    /// Real-time event 103
+    SIG103 = 117,
+    /// Real-time event 104
+    SIG104 = 118,
+    /// Real-time event 105
+    SIG105 = 119,
+    /// Real-time event 106
+    SIG106 = 120,
+    /// Real-time event 107
+    SIG107 = 121,
+    /// Real-time event 108
+    SIG108 = 122,
+    /// Real-time event 109
+    SIG109 = 123,
+    /// Real-time event 110
+    SIG110 = 124,
+    /// Real-time event 111
+    SIG111 = 125,
+    /// Real-time event 112
+    SIG112 = 126,
+    /// Real-time event 113
+    SIG113 = 127,
+    /// Real-time event 114
+    SIG114 = 128,
+    /// Real-time event 115
+    SIG115 = 129,
+    /// Real-time event 116
+    SIG116 = 130,
+    /// Real-time event 117
+    SIG117 = 131,
+    /// Real-time event 118
+    SIG118 = 132,
+    /// Real-time event 119
+    SIG119 = 133,
+    /// Real-time event 120
+    SIG120 = 134,
+    /// Real-time event 121
+    SIG121 = 135,
+    /// Real-time event 122
+    SIG122 = 136,
+    /// Real-time event 123
+    SIG123 = 137,
+    /// Real-time event 124
+    SIG124 = 138,
+    /// Real-time event 125
+    SIG125 = 139,
+    /// Real-time event 126
+    SIG126 = 140,
+    /// Real-time event 127
+    SIG127 = 141,
+    /// Information request
+    SIGINFO = 142,
+    /// Unknown signal
+    SIGUNKNOWN = 143,
+    /// Could not access memory
+    EXC_BAD_ACCESS = 145,
+    /// Illegal instruction/operand
+    EXC_BAD_INSTRUCTION = 146,
+    /// Arithmetic exception
+    EXC_ARITHMETIC = 147,
+    /// Emulation instruction
+    EXC_EMULATION = 148,
+    /// Software generated exception
+    EXC_SOFTWARE = 149,
+    /// Breakpoint
+    EXC_BREAKPOINT = 150,
+    /// librt internal signal
+    SIGLIBRT = 151,
+}
+
+impl Signal {
+    /// Convert a raw GDB signal number into a `Signal`, returning `None` if
+    /// the number doesn't correspond to a known signal.
+    pub fn from_raw(raw: u8) -> Option<Signal> {
+        use self::Signal::*;
+
+        let sig = match raw {
+            0 => SIG0,
+            1 => SIGHUP,
+            2 => SIGINT,
+            3 => SIGQUIT,
+            4 => SIGILL,
+            5 => SIGTRAP,
+            6 => SIGABRT,
+            7 => SIGEMT,
+            8 => SIGFPE,
+            9 => SIGKILL,
+            10 => SIGBUS,
+            11 => SIGSEGV,
+            12 => SIGSYS,
+            13 => SIGPIPE,
+            14 => SIGALRM,
+            15 => SIGTERM,
+            16 => SIGURG,
+            17 => SIGSTOP,
+            18 => SIGTSTP,
+            19 => SIGCONT,
+            20 => SIGCHLD,
+            21 => SIGTTIN,
+            22 => SIGTTOU,
+            23 => SIGIO,
+            24 => SIGXCPU,
+            25 => SIGXFSZ,
+            26 => SIGVTALRM,
+            27 => SIGPROF,
+            28 => SIGWINCH,
+            29 => SIGLOST,
+            30 => SIGUSR1,
+            31 => SIGUSR2,
+            32 => SIGPWR,
+            33 => SIGPOLL,
+            34 => SIGWIND,
+            35 => SIGPHONE,
+            36 => SIGWAITING,
+            37 => SIGLWP,
+            38 => SIGDANGER,
+            39 => SIGGRANT,
+            40 => SIGRETRACT,
+            41 => SIGMSG,
+            42 => SIGSOUND,
+            43 => SIGSAK,
+            44 => SIGPRIO,
+            45 => SIG33,
+            46 => SIG34,
+            47 => SIG35,
+            48 => SIG36,
+            49 => SIG37,
+            50 => SIG38,
+            51 => SIG39,
+            52 => SIG40,
+            53 => SIG41,
+            54 => SIG42,
+            55 => SIG43,
+            56 => SIG44,
+            57 => SIG45,
+            58 => SIG46,
+            59 => SIG47,
+            60 => SIG48,
+            61 => SIG49,
+            62 => SIG50,
+            63 => SIG51,
+            64 => SIG52,
+            65 => SIG53,
+            66 => SIG54,
+            67 => SIG55,
+            68 => SIG56,
+            69 => SIG57,
+            70 => SIG58,
+            71 => SIG59,
+            72 => SIG60,
+            73 => SIG61,
+            74 => SIG62,
+            75 => SIG63,
+            76 => SIGCANCEL,
+            77 => SIG32,
+            78 => SIG64,
+            79 => SIG65,
+            80 => SIG66,
+            81 => SIG67,
+            82 => SIG68,
+            83 => SIG69,
+            84 => SIG70,
+            85 => SIG71,
+            86 => SIG72,
+            87 => SIG73,
+            88 => SIG74,
+            89 => SIG75,
+            90 => SIG76,
+            91 => SIG77,
+            92 => SIG78,
+            93 => SIG79,
+            94 => SIG80,
+            95 => SIG81,
+            96 => SIG82,
+            97 => SIG83,
+            98 => SIG84,
+            99 => SIG85,
+            100 => SIG86,
+            101 => SIG87,
+            102 => SIG88,
+            103 => SIG89,
+            104 => SIG90,
+            105 => SIG91,
+            106 => SIG92,
+            107 => SIG93,
+            108 => SIG94,
+            109 => SIG95,
+            110 => SIG96,
+            111 => SIG97,
+            112 => SIG98,
+            113 => SIG99,
+            114 => SIG100,
+            115 => SIG101,
+            116 => SIG102,
+            117 => SIG103,
+            118 => SIG104,
+            119 => SIG105,
+            120 => SIG106,
+            121 => SIG107,
+            122 => SIG108,
+            123 => SIG109,
+            124 => SIG110,
+            125 => SIG111,
+            126 => SIG112,
+            127 => SIG113,
+            128 => SIG114,
+            129 => SIG115,
+            130 => SIG116,
+            131 => SIG117,
+            132 => SIG118,
+            133 => SIG119,
+            134 => SIG120,
+            135 => SIG121,
+            136 => SIG122,
+            137 => SIG123,
+            138 => SIG124,
+            139 => SIG125,
+            140 => SIG126,
+            141 => SIG127,
+            142 => SIGINFO,
+            143 => SIGUNKNOWN,
+            145 => EXC_BAD_ACCESS,
+            146 => EXC_BAD_INSTRUCTION,
+            147 => EXC_ARITHMETIC,
+            148 => EXC_EMULATION,
+            149 => EXC_SOFTWARE,
+            150 => EXC_BREAKPOINT,
+            151 => SIGLIBRT,
+            _ => return None,
+        };
+        Some(sig)
+    }
+
+    /// Return the signal's raw GDB signal number.
+    pub fn as_raw(self) -> u8 {
+        self as u8
+    }
+}
@@ -0,0 +1,57 @@
+/// An `async` version of [`Connection`](super::Connection), for use with
+/// [`AsyncGdbStub`](crate::AsyncGdbStub).
+///
+/// Aside from `read`, `write`, `write_all`, `flush`, and `on_session_start`
+/// being `async`, the semantics of each method match their `Connection`
+/// counterparts. `read_peeked` has no `Connection` counterpart.
+#[allow(async_fn_in_trait)]
+pub trait AsyncConnection {
+    /// Transport-specific error type.
+    type Error;
+
+    /// Read a single byte.
+    async fn read(&mut self) -> Result<u8, Self::Error>;
+
+    /// Write a single byte.
+    async fn write(&mut self, byte: u8) -> Result<(), Self::Error>;
+
+    /// Write the entire buffer.
+    ///
+    /// This method's default implementation calls `self.write()` on each byte
+    /// in the buffer. This can be quite inefficient, so if a more efficient
+    /// implementation exists (such as calling `write_all()` on an underlying
+    /// `AsyncWrite` object), this method should be overwritten.
+    async fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        for b in buf {
+            self.write(*b).await?;
+        }
+        Ok(())
+    }
+
+    /// Peek a single byte. This MUST be a **non-blocking** operation, returning
+    /// `None` if no byte is available.
+    ///
+    /// This method is used to check for incoming interrupt packets while the
+    /// target is running, and as such, is not `async`.
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error>;
+
+    /// Consume (and return) the byte returned by the preceding call to `peek`.
+    /// This MUST be a **non-blocking** operation.
+    ///
+    /// This method is used to consume interrupt packets detected via `peek`
+    /// while the target is running, and as such, is not `async`. It is only
+    /// ever called after `peek` has returned `Some`.
+    fn read_peeked(&mut self) -> Result<u8, Self::Error>;
+
+    /// Flush this Connection, ensuring that all intermediately buffered
+    /// contents reach their destination.
+    async fn flush(&mut self) -> Result<(), Self::Error>;
+
+    /// Called at the start of a debugging session _before_ any GDB packets have
+    /// been sent/received.
+    ///
+    /// This method's default implementation is a no-op.
+    async fn on_session_start(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use crate::Connection;
 
 use alloc::boxed::Box;
@@ -13,6 +15,10 @@ impl<E> Connection for Box<dyn Connection<Error = E>> {
         (**self).read_exact(buf)
     }
 
+    fn read_timeout(&mut self, timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        (**self).read_timeout(timeout)
+    }
+
     fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
         (**self).write(byte)
     }
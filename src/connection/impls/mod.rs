@@ -10,6 +10,8 @@ mod tcpstream;
 #[cfg(all(feature = "std", unix))]
 mod unixstream;
 
+use core::time::Duration;
+
 use super::Connection;
 
 impl<E> Connection for &mut dyn Connection<Error = E> {
@@ -23,6 +25,10 @@ impl<E> Connection for &mut dyn Connection<Error = E> {
         (**self).read_exact(buf)
     }
 
+    fn read_timeout(&mut self, timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        (**self).read_timeout(timeout)
+    }
+
     fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
         (**self).write(byte)
     }
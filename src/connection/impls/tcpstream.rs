@@ -1,4 +1,5 @@
 use std::net::TcpStream;
+use std::time::Duration;
 
 use crate::Connection;
 
@@ -25,6 +26,33 @@ impl Connection for TcpStream {
         Read::read_exact(self, buf)
     }
 
+    fn read_timeout(&mut self, timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        use std::io::{ErrorKind, Read};
+
+        // `set_read_timeout` rejects zero-length timeouts
+        if timeout.as_nanos() == 0 {
+            return match Connection::peek(self)? {
+                Some(_) => Connection::read(self).map(Some),
+                None => Ok(None),
+            };
+        }
+
+        self.set_nonblocking(false)?;
+        self.set_read_timeout(Some(timeout))?;
+        let mut buf = [0u8];
+        let res = Read::read(self, &mut buf);
+        self.set_read_timeout(None)?;
+
+        match res {
+            Ok(0) => Err(ErrorKind::UnexpectedEof.into()),
+            Ok(_) => Ok(Some(buf[0])),
+            Err(ref e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
         self.set_nonblocking(true)?;
 
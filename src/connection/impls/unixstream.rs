@@ -2,6 +2,7 @@ use core::ffi::c_void;
 use std::io;
 use std::os::unix::io::AsRawFd;
 use std::os::unix::net::UnixStream;
+use std::time::Duration;
 
 use crate::Connection;
 
@@ -69,6 +70,33 @@ impl Connection for UnixStream {
         Read::read_exact(self, buf)
     }
 
+    fn read_timeout(&mut self, timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        use std::io::{ErrorKind, Read};
+
+        // `set_read_timeout` rejects zero-length timeouts
+        if timeout.as_nanos() == 0 {
+            return match Connection::peek(self)? {
+                Some(_) => Connection::read(self).map(Some),
+                None => Ok(None),
+            };
+        }
+
+        self.set_nonblocking(false)?;
+        self.set_read_timeout(Some(timeout))?;
+        let mut buf = [0u8];
+        let res = Read::read(self, &mut buf);
+        self.set_read_timeout(None)?;
+
+        match res {
+            Ok(0) => Err(ErrorKind::UnexpectedEof.into()),
+            Ok(_) => Ok(Some(buf[0])),
+            Err(ref e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
         self.set_nonblocking(true)?;
 
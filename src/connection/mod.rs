@@ -1,5 +1,13 @@
+use core::time::Duration;
+
 mod impls;
 
+#[cfg(feature = "async")]
+mod async_connection;
+
+#[cfg(feature = "async")]
+pub use async_connection::AsyncConnection;
+
 /// A trait to perform in-order, serial, byte-wise I/O.
 ///
 /// When the `std` feature is enabled, this trait is automatically implemented
@@ -25,6 +33,20 @@ pub trait Connection {
         Ok(())
     }
 
+    /// Read a single byte, giving up after `timeout` has elapsed without any
+    /// data arriving. Returns `Ok(None)` on timeout.
+    ///
+    /// A `timeout` of zero polls the connection, returning `Ok(None)` if no
+    /// byte is immediately available.
+    ///
+    /// This method is only used when the target implements the
+    /// [`Idle`](crate::target::ext::idle::Idle) extension. Its default
+    /// implementation never times out, and simply calls `self.read()`.
+    fn read_timeout(&mut self, timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        let _ = timeout;
+        self.read().map(Some)
+    }
+
     /// Write a single byte.
     fn write(&mut self, byte: u8) -> Result<(), Self::Error>;
 
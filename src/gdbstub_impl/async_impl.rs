@@ -0,0 +1,250 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use managed::ManagedSlice;
+
+#[cfg(feature = "stats")]
+use super::SessionStats;
+use super::{DisconnectReason, Error, GdbStubImpl, PacketDirection, PacketTraceFn, StatsCounter};
+use crate::connection::{AsyncConnection, Connection};
+use crate::protocol::{Packet, PacketParseError, ResponseWriterError};
+use crate::target::Target;
+use crate::util::managed_vec::ManagedVec;
+
+/// Debug a [`Target`] using the GDB Remote Serial Protocol over a given
+/// [`AsyncConnection`].
+///
+/// `AsyncGdbStub` shares its protocol implementation with
+/// [`GdbStub`](super::GdbStub), and only differs in how it interacts with the
+/// underlying connection: incoming packets are `await`ed, and responses are
+/// written out asynchronously once each packet has been handled.
+///
+/// _Note:_ Only the connection I/O is `async`. `Target` methods are still
+/// invoked synchronously, and will block the current task until they return
+/// (e.g: while the target is running after a `resume`). As such, any console
+/// output sent while the target is running (i.e: `O` packets) is buffered,
+/// and is only written out once the target stops.
+pub struct AsyncGdbStub<'a, T: Target, C: AsyncConnection> {
+    conn: SyncAdapter<C>,
+    packet_buffer: ManagedSlice<'a, u8>,
+    state: GdbStubImpl<T, SyncAdapter<C>>,
+}
+
+impl<T: Target, C: AsyncConnection> AsyncGdbStub<'static, T, C> {
+    /// Create a new `AsyncGdbStub` using the provided connection, and a
+    /// heap-allocated packet buffer.
+    pub fn new(conn: C) -> AsyncGdbStub<'static, T, C> {
+        // need to pick some arbitrary value to report to GDB
+        // 4096 seems reasonable?
+        let len = 4096;
+        AsyncGdbStub {
+            conn: SyncAdapter::new(conn),
+            packet_buffer: ManagedSlice::Owned(vec![0; len]),
+            state: GdbStubImpl::new(len),
+        }
+    }
+}
+
+impl<'a, T: Target, C: AsyncConnection> AsyncGdbStub<'a, T, C> {
+    /// Create a new `AsyncGdbStub` using the provided connection and packet
+    /// buffer.
+    pub fn with_packet_buffer(conn: C, packet_buffer: &'a mut [u8]) -> AsyncGdbStub<'a, T, C> {
+        let len = packet_buffer.len();
+        AsyncGdbStub {
+            conn: SyncAdapter::new(conn),
+            packet_buffer: ManagedSlice::Borrowed(packet_buffer),
+            state: GdbStubImpl::new(len),
+        }
+    }
+
+    /// Invoke `callback` with the raw bytes of every packet received from and
+    /// sent to GDB (including acknowledgements).
+    ///
+    /// See [`PacketTraceFn`] for details on how packets are reported.
+    pub fn set_packet_trace(&mut self, callback: PacketTraceFn) {
+        self.state.packet_trace = Some(callback);
+    }
+
+    /// Return a snapshot of the traffic exchanged with GDB so far.
+    ///
+    /// _Note:_ `stats` is only available when the `stats` feature is enabled.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> SessionStats {
+        self.state.stats.snapshot()
+    }
+
+    /// Starts a GDB remote debugging session.
+    ///
+    /// Returns once the GDB client closes the debugging session, or if the
+    /// target halts.
+    pub async fn run(
+        &mut self,
+        target: &mut T,
+    ) -> Result<DisconnectReason, Error<T::Error, C::Error>> {
+        let conn = &mut self.conn;
+        let state = &mut self.state;
+
+        conn.inner
+            .on_session_start()
+            .await
+            .map_err(Error::ConnectionRead)?;
+
+        state.init(target)?;
+
+        loop {
+            let res = state.report_stop_events(target, conn);
+            conn.drain().await?;
+            if let Some(disconnect_reason) = res? {
+                return Ok(disconnect_reason);
+            }
+
+            let packet = match recv_packet(
+                &mut conn.inner,
+                target,
+                &mut self.packet_buffer,
+                state.packet_trace,
+                &state.stats,
+            )
+            .await
+            {
+                // ask GDB to re-send corrupted packets
+                Err(Error::PacketParse(PacketParseError::ChecksumMismatched { .. }))
+                    if !state.no_ack_mode =>
+                {
+                    state.send_nack(conn)?;
+                    conn.drain().await?;
+                    continue;
+                }
+                packet => packet?,
+            };
+            let res = match packet {
+                Packet::Command(command) => {
+                    // Acknowledge the command _before_ handling it, as the handler may
+                    // block for quite some time (e.g: while the target is running).
+                    if !state.no_ack_mode {
+                        conn.output.push(b'+');
+                        state.stats.ack_sent();
+                        if let Some(trace) = state.packet_trace {
+                            trace(PacketDirection::Outgoing, b"+");
+                        }
+                        conn.drain().await?;
+                    }
+
+                    state.handle_command_packet(target, conn, command)
+                }
+                packet => state.handle_packet(target, conn, packet),
+            };
+            // even if an error occurred, there may be a final response to send (e.g: the
+            // stop reason reported after a fatal target error)
+            conn.drain().await?;
+            if let Some(disconnect_reason) = res? {
+                return Ok(disconnect_reason);
+            }
+        }
+    }
+}
+
+/// Asynchronously read a packet into the packet buffer.
+///
+/// This mirrors `GdbStubImpl::recv_packet`.
+async fn recv_packet<'a, T: Target, C: AsyncConnection>(
+    conn: &mut C,
+    target: &mut T,
+    pkt_buf: &'a mut ManagedSlice<'_, u8>,
+    trace: Option<PacketTraceFn>,
+    stats: &StatsCounter,
+) -> Result<Packet<'a>, Error<T::Error, C::Error>> {
+    let header_byte = conn.read().await.map_err(Error::ConnectionRead)?;
+
+    // Wrap the buf in a `ManagedVec` to keep the code readable.
+    let mut buf = ManagedVec::new(pkt_buf);
+
+    buf.clear();
+    buf.push(header_byte)?;
+    if header_byte == b'$' {
+        // read the packet body
+        loop {
+            let c = conn.read().await.map_err(Error::ConnectionRead)?;
+            buf.push(c)?;
+            if c == b'#' {
+                break;
+            }
+        }
+        // read the checksum as well
+        buf.push(conn.read().await.map_err(Error::ConnectionRead)?)?;
+        buf.push(conn.read().await.map_err(Error::ConnectionRead)?)?;
+    }
+
+    if let Some(trace) = trace {
+        trace(PacketDirection::Incoming, buf.as_slice());
+    }
+    stats.received(buf.as_slice());
+
+    match Packet::from_buf(target, pkt_buf.as_mut()) {
+        Ok(packet) => Ok(packet),
+        Err(e) => Err(Error::PacketParse(e)),
+    }
+}
+
+/// Exposes an [`AsyncConnection`] as a synchronous [`Connection`], allowing it
+/// to be used with the existing `GdbStubImpl` packet handlers.
+///
+/// Outgoing data is buffered, and must be written out via `drain`.
+struct SyncAdapter<C> {
+    inner: C,
+    output: Vec<u8>,
+}
+
+impl<C: AsyncConnection> SyncAdapter<C> {
+    fn new(inner: C) -> SyncAdapter<C> {
+        SyncAdapter {
+            inner,
+            output: Vec::new(),
+        }
+    }
+
+    /// Write out + flush any buffered output.
+    async fn drain<T>(&mut self) -> Result<(), Error<T, C::Error>> {
+        if self.output.is_empty() {
+            return Ok(());
+        }
+
+        let res = self.inner.write_all(&self.output).await;
+        self.output.clear();
+        res.map_err(|e| Error::ConnectionWrite(ResponseWriterError(e)))?;
+        self.inner
+            .flush()
+            .await
+            .map_err(|e| Error::ConnectionWrite(ResponseWriterError(e)))
+    }
+}
+
+impl<C: AsyncConnection> Connection for SyncAdapter<C> {
+    type Error = C::Error;
+
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        // Aside from `recv_packet` (which `AsyncGdbStub` replaces with its own async
+        // implementation), `GdbStubImpl` only reads from the connection to consume an
+        // interrupt byte it has just peeked at while the target is running.
+        self.inner.read_peeked()
+    }
+
+    fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.output.push(byte);
+        Ok(())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.output.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        self.inner.peek()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // output is flushed once the packet has been handled (via `drain`)
+        Ok(())
+    }
+}
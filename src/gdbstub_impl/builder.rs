@@ -3,7 +3,7 @@ use core::marker::PhantomData;
 
 use managed::ManagedSlice;
 
-use super::{Connection, GdbStub, GdbStubImpl, Target};
+use super::{Connection, GdbStub, GdbStubImpl, PacketBuffer, Target};
 
 /// An error which may occur when building a [`GdbStub`].
 #[derive(Debug)]
@@ -33,13 +33,104 @@ impl Display for GdbStubBuilderError {
 #[cfg(feature = "std")]
 impl std::error::Error for GdbStubBuilderError {}
 
+/// The direction in which a traced packet was sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketDirection {
+    /// Received from the GDB client.
+    Incoming,
+    /// Sent to the GDB client.
+    Outgoing,
+}
+
+/// A packet trace callback, as registered via
+/// [`GdbStubBuilder::trace_packets`].
+///
+/// The callback is invoked with the raw bytes of each packet as they appear
+/// on the wire (i.e: including the leading `$` / `%` and trailing checksum),
+/// including any `+` / `-` acknowledgements.
+///
+/// Incoming packets are always reported in full, whereas outgoing packets
+/// (which are written to the connection as they are being generated) may be
+/// reported in several consecutive chunks. Neither direction allocates, so
+/// packet tracing is available in `#![no_std]` environments.
+pub type PacketTraceFn = fn(PacketDirection, &[u8]);
+
 /// Helper to construct and customize [`GdbStub`].
-pub struct GdbStubBuilder<'a, T: Target, C: Connection> {
+///
+/// # Example
+///
+/// ```
+/// use gdbstub::target::Target;
+/// use gdbstub::{Connection, GdbStub, GdbStubBuilderError, PacketDirection};
+///
+/// fn trace(direction: PacketDirection, packet: &[u8]) {
+///     let arrow = match direction {
+///         PacketDirection::Incoming => "<--",
+///         PacketDirection::Outgoing => "-->",
+///     };
+///     eprintln!("{} {}", arrow, String::from_utf8_lossy(packet));
+/// }
+///
+/// fn make_stub<'a, T: Target, C: Connection>(
+///     conn: C,
+///     packet_buffer: &'a mut [u8],
+/// ) -> Result<GdbStub<'a, T, C>, GdbStubBuilderError> {
+///     GdbStub::builder(conn)
+///         // use a statically allocated packet buffer...
+///         .with_packet_buffer(packet_buffer)
+///         // ...but only advertise the first 1024 bytes of it to GDB
+///         .packet_buffer_size(1024)
+///         // log every packet sent / received
+///         .trace_packets(trace)
+///         .build()
+/// }
+/// ```
+pub struct GdbStubBuilder<'a, T: Target, C: Connection, B = &'a mut [u8]> {
     conn: C,
-    packet_buffer: Option<&'a mut [u8]>,
+    packet_buffer: Option<B>,
     packet_buffer_size: Option<usize>,
+    no_ack_mode: bool,
+    packet_trace: Option<PacketTraceFn>,
 
     _target: PhantomData<T>,
+    _lifetime: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a, T: Target, C: Connection, B> GdbStubBuilder<'a, T, C, B> {
+    /// Specify a custom size for the packet buffer. Defaults to 4096 bytes.
+    ///
+    /// When used alongside `with_packet_buffer`, the provided `size` must be
+    /// less than or equal to the length of the packet buffer, and only the
+    /// first `size` bytes of the buffer are used.
+    ///
+    /// The packet buffer size is advertised to GDB (via `qSupported`'s
+    /// `PacketSize`), which limits the size of the packets GDB sends (e.g:
+    /// splitting large memory writes across several packets).
+    pub fn packet_buffer_size(mut self, size: usize) -> Self {
+        self.packet_buffer_size = Some(size);
+        self
+    }
+
+    /// Start the session in "no-ack" mode, where packets are not acknowledged
+    /// with `+` / `-`. Defaults to `false`.
+    ///
+    /// GDB normally requests no-ack mode at the start of each session (via
+    /// `QStartNoAckMode`), so this is only useful when the client is known to
+    /// _already_ be operating without acknowledgements (e.g: when resuming a
+    /// session over a reliable transport, after the stub was restarted).
+    pub fn no_ack_mode(mut self, enabled: bool) -> Self {
+        self.no_ack_mode = enabled;
+        self
+    }
+
+    /// Invoke `callback` with the raw bytes of every packet received from and
+    /// sent to GDB. Useful for debugging protocol-level issues.
+    ///
+    /// See [`PacketTraceFn`] for details on how packets are reported.
+    pub fn trace_packets(mut self, callback: PacketTraceFn) -> Self {
+        self.packet_trace = Some(callback);
+        self
+    }
 }
 
 impl<'a, T: Target, C: Connection> GdbStubBuilder<'a, T, C> {
@@ -49,26 +140,41 @@ impl<'a, T: Target, C: Connection> GdbStubBuilder<'a, T, C> {
             conn,
             packet_buffer: None,
             packet_buffer_size: None,
+            no_ack_mode: false,
+            packet_trace: None,
 
             _target: PhantomData,
+            _lifetime: PhantomData,
         }
     }
 
     /// Use a pre-allocated packet buffer (instead of heap-allocating).
     ///
-    /// _Note:_ This method is _required_ when the `alloc` feature is disabled!
+    /// _Note:_ Either this method or
+    /// [`with_owned_packet_buffer`](Self::with_owned_packet_buffer) is
+    /// _required_ when the `alloc` feature is disabled!
     pub fn with_packet_buffer(mut self, packet_buffer: &'a mut [u8]) -> Self {
         self.packet_buffer = Some(packet_buffer);
         self
     }
 
-    /// Specify a custom size for the packet buffer. Defaults to 4096 bytes.
-    ///
-    /// When used alongside `with_packet_buffer`, the provided `size` must be
-    /// less than or equal to the length of the packet buffer.
-    pub fn packet_buffer_size(mut self, size: usize) -> Self {
-        self.packet_buffer_size = Some(size);
-        self
+    /// Use a fixed-capacity [`PacketBuffer`], which is moved into the
+    /// resulting `GdbStub` (instead of heap-allocating, or borrowing a
+    /// buffer).
+    pub fn with_owned_packet_buffer<const N: usize>(
+        self,
+        packet_buffer: PacketBuffer<N>,
+    ) -> GdbStubBuilder<'a, T, C, PacketBuffer<N>> {
+        GdbStubBuilder {
+            conn: self.conn,
+            packet_buffer: Some(packet_buffer),
+            packet_buffer_size: self.packet_buffer_size,
+            no_ack_mode: self.no_ack_mode,
+            packet_trace: self.packet_trace,
+
+            _target: PhantomData,
+            _lifetime: PhantomData,
+        }
     }
 
     /// Build the GdbStub, returning an error if something went wrong.
@@ -85,16 +191,17 @@ impl<'a, T: Target, C: Connection> GdbStubBuilder<'a, T, C> {
                     }
                     None => buf.len(),
                 };
-                (ManagedSlice::Borrowed(buf), len)
+                // only use as much of the buffer as will be advertised to GDB
+                (ManagedSlice::Borrowed(&mut buf[..len]), len)
             }
             None => {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "alloc")] {
-                        use alloc::vec::Vec;
+                        use alloc::vec;
                         // need to pick some arbitrary value to report to GDB
                         // 4096 seems reasonable?
                         let len = self.packet_buffer_size.unwrap_or(4096);
-                        (ManagedSlice::Owned(Vec::with_capacity(len)), len)
+                        (ManagedSlice::Owned(vec![0; len]), len)
                     } else {
                         return Err(GdbStubBuilderError::MissingPacketBuffer);
                     }
@@ -102,10 +209,42 @@ impl<'a, T: Target, C: Connection> GdbStubBuilder<'a, T, C> {
             }
         };
 
+        let mut state = GdbStubImpl::new(packet_buffer_len);
+        state.no_ack_mode = self.no_ack_mode;
+        state.packet_trace = self.packet_trace;
+
+        Ok(GdbStub {
+            conn: self.conn,
+            packet_buffer,
+            state,
+            _lifetime: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: Target, C: Connection, const N: usize> GdbStubBuilder<'a, T, C, PacketBuffer<N>> {
+    /// Build the GdbStub, returning an error if something went wrong.
+    pub fn build(self) -> Result<GdbStub<'a, T, C, PacketBuffer<N>>, GdbStubBuilderError> {
+        let packet_buffer = self
+            .packet_buffer
+            .ok_or(GdbStubBuilderError::MissingPacketBuffer)?;
+        let packet_buffer_len = match self.packet_buffer_size {
+            Some(custom_len) if custom_len > N => {
+                return Err(GdbStubBuilderError::PacketBufSizeMismatch)
+            }
+            Some(custom_len) => custom_len,
+            None => N,
+        };
+
+        let mut state = GdbStubImpl::new(packet_buffer_len);
+        state.no_ack_mode = self.no_ack_mode;
+        state.packet_trace = self.packet_trace;
+
         Ok(GdbStub {
             conn: self.conn,
             packet_buffer,
-            state: GdbStubImpl::new(packet_buffer_len),
+            state,
+            _lifetime: PhantomData,
         })
     }
 }
@@ -11,8 +11,9 @@ pub enum GdbStubError<T, C> {
     ConnectionRead(C),
     /// Connection Error while writing response.
     ConnectionWrite(ResponseWriterError<C>),
-    /// Client nack'd the last packet, but `gdbstub` doesn't implement
-    /// re-transmission.
+    /// Client nack'd the last packet, but `gdbstub` can't re-transmit it
+    /// (re-transmission requires the `alloc` feature, and is only supported
+    /// for responses which fit in the packet buffer).
     ClientSentNack,
     /// GdbStub was not provided with a packet buffer in `no_std` mode
     /// (missing call to `with_packet_buffer`)
@@ -20,8 +21,11 @@ pub enum GdbStubError<T, C> {
     /// Packet cannot fit in the provided packet buffer.
     PacketBufferOverlow,
     /// Could not parse the packet into a valid command.
+    ///
+    /// Malformed commands include a snippet of the offending packet.
     PacketParse(PacketParseError),
-    /// GDB client sent an unexpected packet.
+    /// GDB client sent an unexpected packet (e.g: a packet which is only valid
+    /// when the target supports a feature it doesn't implement).
     PacketUnexpected,
     /// GDB client sent a packet with too much data for the given target.
     TargetMismatch,
@@ -29,8 +33,6 @@ pub enum GdbStubError<T, C> {
     TargetError(T),
     /// Target didn't report any active threads.
     NoActiveThreads,
-    /// Resuming with a signal is not implemented yet. Consider opening a PR?
-    ResumeWithSignalUnimplemented,
     /// Internal - A non-fatal error occurred (with errno-style error code)
     #[doc(hidden)]
     NonFatalError(u8),
@@ -58,15 +60,14 @@ where
         match self {
             ConnectionRead(e) => write!(f, "Connection Error while reading request: {:?}", e),
             ConnectionWrite(e) => write!(f, "Connection Error while writing response: {:?}", e),
-            ClientSentNack => write!(f, "Client nack'd the last packet, but `gdbstub` doesn't implement re-transmission."),
+            ClientSentNack => write!(f, "Client nack'd the last packet, but `gdbstub` can't re-transmit it (re-transmission requires the `alloc` feature, and is only supported for responses which fit in the packet buffer)."),
             MissingPacketBuffer => write!(f, "GdbStub was not provided with a packet buffer in `no_std` mode (missing call to `with_packet_buffer`)"),
             PacketBufferOverlow => write!(f, "Packet too big for provided buffer!"),
-            PacketParse(e) => write!(f, "Could not parse the packet into a valid command: {:?}", e),
+            PacketParse(e) => write!(f, "Could not parse the packet into a valid command: {}", e),
             PacketUnexpected => write!(f, "Client sent an unexpected packet."),
             TargetMismatch => write!(f, "GDB client sent a packet with too much data for the given target."),
             TargetError(e) => write!(f, "Target threw a fatal error: {:?}", e),
             NoActiveThreads => write!(f, "Target didn't report any active threads."),
-            ResumeWithSignalUnimplemented => write!(f, "Resuming with a signal is not implemented yet. Consider opening a PR?"),
             NonFatalError(_) => write!(f, "Internal - A non-fatal error occurred (with errno-style error code)"),
         }
     }
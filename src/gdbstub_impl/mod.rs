@@ -1,13 +1,14 @@
 use core::marker::PhantomData;
+use core::ops::DerefMut;
 
 #[cfg(feature = "alloc")]
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, vec::Vec};
 
 use managed::ManagedSlice;
 
 use crate::common::*;
 use crate::{
-    arch::{Arch, RegId, Registers},
+    arch::{Arch, BreakpointKind, RegId, Registers},
     connection::Connection,
     internal::*,
     protocol::{
@@ -15,19 +16,34 @@ use crate::{
         ConsoleOutput, IdKind, Packet, ResponseWriter, ThreadId,
     },
     target::ext::base::multithread::{Actions, ResumeAction, ThreadStopReason, TidSelector},
-    target::ext::base::BaseOps,
-    target::Target,
+    target::ext::base::{BaseOps, ResumeCapabilities},
+    target::ext::host_syscall::HostSyscallRequest,
+    target::{Target, TargetResult},
     util::managed_vec::ManagedVec,
     FAKE_PID, SINGLE_THREAD_TID,
 };
 
+#[cfg(feature = "async")]
+mod async_impl;
 mod builder;
 mod error;
+mod packet_buffer;
+mod stats;
 mod target_result_ext;
 
-pub use builder::{GdbStubBuilder, GdbStubBuilderError};
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+pub use crate::protocol::{PacketParseError, PacketSnippet};
+#[cfg(feature = "async")]
+pub use async_impl::AsyncGdbStub;
+pub use builder::{GdbStubBuilder, GdbStubBuilderError, PacketDirection, PacketTraceFn};
 pub use error::GdbStubError;
+pub use packet_buffer::PacketBuffer;
+#[cfg(feature = "stats")]
+pub use stats::SessionStats;
 
+use stats::StatsCounter;
 use target_result_ext::TargetResultExt;
 
 use GdbStubError as Error;
@@ -37,18 +53,29 @@ use GdbStubError as Error;
 pub enum DisconnectReason {
     /// Target Halted
     TargetHalted,
-    /// GDB issued a disconnect command
+    /// GDB issued a disconnect command (i.e: `detach`).
+    ///
+    /// Unlike `Kill`, the target should be left running.
     Disconnect,
     /// GDB issued a kill command
     Kill,
+    /// The target exited with the given exit code
+    TargetExited(u8),
+    /// The target was terminated by the given (native) signal
+    TargetTerminated(u8),
 }
 
 /// Debug a [`Target`] using the GDB Remote Serial Protocol over a given
 /// [`Connection`].
-pub struct GdbStub<'a, T: Target, C: Connection> {
+///
+/// By default, the packet buffer is either heap-allocated or borrowed (see
+/// [`GdbStubBuilder::with_packet_buffer`]). Stubs which own a fixed-capacity
+/// [`PacketBuffer`] instead use it as their `B` type parameter.
+pub struct GdbStub<'a, T: Target, C: Connection, B = ManagedSlice<'a, u8>> {
     conn: C,
-    packet_buffer: ManagedSlice<'a, u8>,
+    packet_buffer: B,
     state: GdbStubImpl<T, C>,
+    _lifetime: PhantomData<&'a mut [u8]>,
 }
 
 impl<'a, T: Target, C: Connection> GdbStub<'a, T, C> {
@@ -67,14 +94,40 @@ impl<'a, T: Target, C: Connection> GdbStub<'a, T, C> {
     pub fn new(conn: C) -> GdbStub<'a, T, C> {
         GdbStubBuilder::new(conn).build().unwrap()
     }
+}
+
+impl<'a, T: Target, C: Connection, B: DerefMut<Target = [u8]>> GdbStub<'a, T, C, B> {
+    /// Return the size of the packet buffer, which is advertised to GDB as the
+    /// largest packet it may send (including the packet's `$`, `#`, and
+    /// checksum bytes).
+    pub fn packet_buffer_size(&self) -> usize {
+        self.state.packet_buffer_len
+    }
+
+    /// Invoke `callback` with the raw bytes of every packet received from and
+    /// sent to GDB (including acknowledgements).
+    ///
+    /// See [`PacketTraceFn`] for details on how packets are reported.
+    pub fn set_packet_trace(&mut self, callback: PacketTraceFn) {
+        self.state.packet_trace = Some(callback);
+    }
+
+    /// Return a snapshot of the traffic exchanged with GDB so far.
+    ///
+    /// _Note:_ `stats` is only available when the `stats` feature is enabled.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> SessionStats {
+        self.state.stats.snapshot()
+    }
 
     /// Starts a GDB remote debugging session.
     ///
     /// Returns once the GDB client closes the debugging session, or if the
     /// target halts.
     pub fn run(&mut self, target: &mut T) -> Result<DisconnectReason, Error<T::Error, C::Error>> {
-        self.state
-            .run(target, &mut self.conn, &mut self.packet_buffer)
+        let len = self.state.packet_buffer_len;
+        let mut packet_buffer = ManagedSlice::Borrowed(&mut self.packet_buffer[..len]);
+        self.state.run(target, &mut self.conn, &mut packet_buffer)
     }
 }
 
@@ -86,6 +139,41 @@ struct GdbStubImpl<T: Target, C: Connection> {
     current_mem_tid: Tid,
     current_resume_tid: TidSelector,
     no_ack_mode: bool,
+    non_stop: bool,
+    // In non-stop mode, only a single `%Stop` notification may be in-flight at
+    // any given time. Subsequent stop events are reported in response to
+    // `vStopped` packets, until the target has no more stop events to report.
+    stop_notification_pending: bool,
+    // In non-stop mode, `?` reports each stopped thread in turn (the first in
+    // response to `?`, and the rest in response to `vStopped`). Tracks the
+    // index of the next stopped thread to report.
+    stopped_thread_offset: Option<usize>,
+    // A stop event which couldn't be reported as the reply to a packet (e.g:
+    // the stop of a process attached to in non-stop mode), and is instead
+    // reported via a `%Stop` notification.
+    deferred_stop: Option<ThreadStopReason<<T::Arch as Arch>::Usize>>,
+    // Whether GDB + the target negotiated reporting fork / vfork events.
+    fork_events: bool,
+    vfork_events: bool,
+    // Whether GDB enabled reporting thread create / exit events via `QThreadEvents`.
+    thread_events: bool,
+    // Whether the target requested a host syscall (via an `F` stop reply), and is waiting for
+    // GDB's `F` reply.
+    host_syscall_pending: bool,
+    // Whether responses are sent using run-length encoding.
+    rle_enabled: bool,
+    // Whether GDB + the target negotiated the multiprocess extensions (i.e: thread-ids include
+    // a pid).
+    multiprocess: bool,
+    // How many threads have already been reported via `qfThreadInfo` / `qsThreadInfo`.
+    thread_list_offset: usize,
+    // Whether GDB + the target negotiated `swbreak` / `hwbreak` stop reasons.
+    swbreak: bool,
+    hwbreak: bool,
+    // Callback invoked with the raw bytes of each incoming / outgoing packet.
+    packet_trace: Option<PacketTraceFn>,
+    // Packet / byte counters (a no-op unless the `stats` feature is enabled).
+    stats: StatsCounter,
 
     // Used to track which Pids were attached to / spawned when running in extended mode.
     //
@@ -94,11 +182,27 @@ struct GdbStubImpl<T: Target, C: Connection> {
     // state structure whether or not extended mode is actually being used.
     #[cfg(feature = "alloc")]
     attached_pids: BTreeMap<Pid, bool>,
+    // The number of times each sw / hw breakpoint (keyed by type, address, and raw kind) has
+    // been inserted, so that the target only sees the first insertion, and the last removal.
+    #[cfg(feature = "alloc")]
+    breakpoint_refs: BreakpointRefs<T>,
+    // The filename passed to the most recent successful `vRun`, which is re-used when GDB
+    // sends a `vRun` without a filename (i.e: "re-run the last program").
+    #[cfg(feature = "alloc")]
+    last_run_filename: Option<Vec<u8>>,
+    // The raw bytes of the last response, which are re-transmitted if GDB nacks it.
+    #[cfg(feature = "alloc")]
+    last_response: Vec<u8>,
 }
 
+#[cfg(feature = "alloc")]
+type BreakpointRefs<T> = BTreeMap<(u8, <<T as Target>::Arch as Arch>::Usize, usize), usize>;
+
 enum HandlerStatus {
     Handled,
     NeedsOK,
+    // The packet doesn't expect any response (not even an empty one)
+    NoResponse,
     Disconnect(DisconnectReason),
 }
 
@@ -116,9 +220,30 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
             current_mem_tid: SINGLE_THREAD_TID,
             current_resume_tid: TidSelector::All,
             no_ack_mode: false,
+            non_stop: false,
+            stop_notification_pending: false,
+            stopped_thread_offset: None,
+            deferred_stop: None,
+            fork_events: false,
+            vfork_events: false,
+            thread_events: false,
+            host_syscall_pending: false,
+            rle_enabled: false,
+            multiprocess: false,
+            thread_list_offset: 0,
+            swbreak: false,
+            hwbreak: false,
+            packet_trace: None,
+            stats: StatsCounter::new(),
 
             #[cfg(feature = "alloc")]
             attached_pids: BTreeMap::new(),
+            #[cfg(feature = "alloc")]
+            breakpoint_refs: BTreeMap::new(),
+            #[cfg(feature = "alloc")]
+            last_run_filename: None,
+            #[cfg(feature = "alloc")]
+            last_response: Vec::new(),
         }
     }
 
@@ -130,6 +255,36 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
     ) -> Result<DisconnectReason, Error<T::Error, C::Error>> {
         conn.on_session_start().map_err(Error::ConnectionRead)?;
 
+        self.init(target)?;
+
+        loop {
+            if let Some(disconnect_reason) = self.report_stop_events(target, conn)? {
+                return Ok(disconnect_reason);
+            }
+
+            let packet = match Self::recv_packet(
+                conn,
+                target,
+                packet_buffer,
+                self.packet_trace,
+                &self.stats,
+            ) {
+                // ask GDB to re-send corrupted packets
+                Err(Error::PacketParse(PacketParseError::ChecksumMismatched { .. }))
+                    if !self.no_ack_mode =>
+                {
+                    self.send_nack(conn)?;
+                    continue;
+                }
+                packet => packet?,
+            };
+            if let Some(disconnect_reason) = self.handle_packet(target, conn, packet)? {
+                return Ok(disconnect_reason);
+            }
+        }
+    }
+
+    fn init(&mut self, target: &mut T) -> Result<(), Error<T::Error, C::Error>> {
         // before even accepting packets, we query the target to get a sane value for
         // `self.current_mem_tid`.
         // NOTE: this will break if extended mode is ever implemented...
@@ -148,69 +303,285 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
             }
         };
 
-        loop {
-            match Self::recv_packet(conn, target, packet_buffer)? {
-                Packet::Ack => {}
-                Packet::Nack => return Err(Error::ClientSentNack),
-                Packet::Interrupt => {
-                    debug!("<-- interrupt packet");
-                    let mut res = ResponseWriter::new(conn);
-                    res.write_str("S05")?;
-                    res.flush()?;
-                }
-                Packet::Command(command) => {
-                    // Acknowledge the command
-                    if !self.no_ack_mode {
-                        conn.write(b'+').map_err(Error::ConnectionRead)?;
-                    }
-
-                    let mut res = ResponseWriter::new(conn);
-                    let disconnect = match self.handle_command(&mut res, target, command) {
-                        Ok(HandlerStatus::Handled) => None,
-                        Ok(HandlerStatus::NeedsOK) => {
-                            res.write_str("OK")?;
-                            None
-                        }
-                        Ok(HandlerStatus::Disconnect(reason)) => Some(reason),
-                        // HACK: handling this "dummy" error is required as part of the
-                        // `TargetResultExt::handle_error()` machinery.
-                        Err(Error::NonFatalError(code)) => {
-                            res.write_str("E")?;
-                            res.write_num(code)?;
-                            None
-                        }
-                        Err(Error::TargetError(e)) => {
-                            // unlike all other errors which are "unrecoverable" in the sense that
-                            // the GDB session cannot continue, there's still a chance that a target
-                            // might want to keep the debugging session alive to do a "post-mortem"
-                            // analysis. As such, we simply report a standard TRAP stop reason.
-                            let mut res = ResponseWriter::new(conn);
-                            res.write_str("S05")?;
-                            res.flush()?;
-                            return Err(Error::TargetError(e));
-                        }
-                        Err(e) => return Err(e),
-                    };
+        Ok(())
+    }
 
-                    // HACK: this could be more elegant...
-                    if disconnect != Some(DisconnectReason::Kill) {
-                        res.flush()?;
+    fn handle_packet(
+        &mut self,
+        target: &mut T,
+        conn: &mut C,
+        packet: Packet<'_>,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        match packet {
+            Packet::Ack => Ok(None),
+            // acks are meaningless once no-ack mode has been entered
+            Packet::Nack if self.no_ack_mode => Ok(None),
+            Packet::Nack => {
+                self.retransmit(conn)?;
+                Ok(None)
+            }
+            Packet::Interrupt => self.handle_interrupt(target, conn),
+            Packet::Command(command) => {
+                // Acknowledge the command
+                if !self.no_ack_mode {
+                    conn.write(b'+').map_err(Error::ConnectionRead)?;
+                    self.stats.ack_sent();
+                    if let Some(trace) = self.packet_trace {
+                        trace(PacketDirection::Outgoing, b"+");
                     }
+                }
 
-                    if let Some(disconnect_reason) = disconnect {
-                        return Ok(disconnect_reason);
-                    }
+                self.handle_command_packet(target, conn, command)
+            }
+        }
+    }
+
+    /// Check if the target wants `qSupported` to advertise the feature `name`.
+    fn advertise_feature(target: &mut T, name: &str) -> bool {
+        match target.qsupported() {
+            Some(ops) => ops.advertise_feature(name.as_bytes()),
+            None => true,
+        }
+    }
+
+    /// Reply to a `qXfer:<object>:read` request with the chunk of the object
+    /// starting at `offset`.
+    ///
+    /// `read` is called with `offset`, the length of the chunk (i.e: `len`,
+    /// clamped to the size of `buf`), and the buffer to read the chunk into, and
+    /// returns the number of bytes read.
+    fn write_xfer_chunk(
+        res: &mut ResponseWriter<C>,
+        offset: u64,
+        len: usize,
+        buf: &mut [u8],
+        read: impl FnOnce(u64, usize, &mut [u8]) -> Result<usize, Error<T::Error, C::Error>>,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        let len = len.min(buf.len());
+        let buf = &mut buf[..len];
+        let ret = read(offset, len, buf)?;
+        let data = buf.get(..ret).ok_or(Error::PacketBufferOverlow)?;
+
+        // a short read indicates that there's no more data to send
+        res.write_str(if ret < len { "l" } else { "m" })?;
+        res.write_binary(data)?;
+        Ok(())
+    }
+
+    /// Append `feature` (e.g: `"swbreak+"`) to a `qSupported` response, unless
+    /// the target suppresses it.
+    fn write_feature(
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        feature: &str,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        let name_len = feature
+            .find(['+', '-', '?', '='].as_ref())
+            .unwrap_or(feature.len());
+        if !Self::advertise_feature(target, &feature[..name_len]) {
+            return Ok(());
+        }
+
+        if res.is_started() {
+            res.write_str(";")?;
+        }
+        res.write_str(feature)?;
+        Ok(())
+    }
+
+    /// Reply to a corrupted packet (i.e: one with a mismatched checksum) with a
+    /// nack, asking GDB to re-send it.
+    fn send_nack(&mut self, conn: &mut C) -> Result<(), Error<T::Error, C::Error>> {
+        debug!("<-- packet with mismatched checksum");
+        conn.write(b'-').map_err(Error::ConnectionRead)?;
+        self.stats.nack_sent();
+        if let Some(trace) = self.packet_trace {
+            trace(PacketDirection::Outgoing, b"-");
+        }
+        Ok(())
+    }
+
+    /// Re-transmit the last response, after GDB nack'd it.
+    ///
+    /// Keeping a copy of the last response requires the `alloc` feature, and
+    /// is only done for responses which fit in the packet buffer.
+    fn retransmit(&mut self, conn: &mut C) -> Result<(), Error<T::Error, C::Error>> {
+        #[cfg(feature = "alloc")]
+        {
+            use crate::protocol::ResponseWriterError;
+
+            if self.last_response.is_empty() {
+                return Err(Error::ClientSentNack);
+            }
+
+            for b in self.last_response.iter() {
+                conn.write(*b)
+                    .map_err(|e| Error::ConnectionWrite(ResponseWriterError(e)))?;
+            }
+            if let Some(trace) = self.packet_trace {
+                trace(PacketDirection::Outgoing, &self.last_response);
+            }
+            self.stats.retransmitted(self.last_response.len());
+            Ok(())
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = conn;
+            Err(Error::ClientSentNack)
+        }
+    }
+
+    fn handle_interrupt(
+        &mut self,
+        target: &mut T,
+        conn: &mut C,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        debug!("<-- interrupt packet");
+
+        let stop_reason = match self.stopped_interrupt(target)? {
+            Some(stop_reason) => stop_reason,
+            None => return Ok(None),
+        };
+
+        // keep a copy of the response, in case it needs to be re-transmitted
+        #[cfg(feature = "alloc")]
+        let mut last_response = core::mem::take(&mut self.last_response);
+
+        let res = ResponseWriter::new(conn, self.rle_enabled).with_trace(self.packet_trace);
+        #[cfg(feature = "alloc")]
+        let res = res.with_record(&mut last_response, self.packet_buffer_len);
+        let mut res = res;
+
+        let disconnect = match stop_reason {
+            Some(stop_reason) => self.finish_vcont(stop_reason, &mut res, target)?,
+            None => {
+                res.write_str("S05")?;
+                None
+            }
+        };
+        let len = res.flush()?;
+        self.stats.sent(len);
+
+        #[cfg(feature = "alloc")]
+        {
+            self.last_response = last_response;
+        }
+
+        Ok(disconnect)
+    }
+
+    /// Ask the target how to handle an interrupt which arrived while it was
+    /// already stopped.
+    ///
+    /// Returns `None` if the target ignored the interrupt, or `Some(None)` if a
+    /// plain `SIGTRAP` should be reported.
+    #[allow(clippy::type_complexity)]
+    fn stopped_interrupt(
+        &mut self,
+        target: &mut T,
+    ) -> Result<Option<Option<ThreadStopReason<<T::Arch as Arch>::Usize>>>, Error<T::Error, C::Error>>
+    {
+        match target.ctrl_c_interrupt() {
+            Some(ops) => match ops.on_ctrl_c().map_err(Error::TargetError)? {
+                Some(stop_reason) => Ok(Some(Some(stop_reason))),
+                None => {
+                    debug!("target ignored interrupt");
+                    Ok(None)
                 }
-            };
+            },
+            None => Ok(Some(None)),
+        }
+    }
+
+    /// Handle an (already acknowledged) command, and send the response.
+    fn handle_command_packet(
+        &mut self,
+        target: &mut T,
+        conn: &mut C,
+        command: Command<'_>,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        // keep a copy of the response, in case it needs to be re-transmitted
+        #[cfg(feature = "alloc")]
+        let mut last_response = core::mem::take(&mut self.last_response);
+
+        let res = ResponseWriter::new(conn, self.rle_enabled).with_trace(self.packet_trace);
+        #[cfg(feature = "alloc")]
+        let res = res.with_record(&mut last_response, self.packet_buffer_len);
+        let mut res = res;
+
+        let disconnect = match self.handle_command(&mut res, target, command) {
+            Ok(HandlerStatus::Handled) => None,
+            Ok(HandlerStatus::NeedsOK) => {
+                res.write_str("OK")?;
+                None
+            }
+            Ok(HandlerStatus::NoResponse) => {
+                // nothing was sent, so the previous response is still the last one
+                #[cfg(feature = "alloc")]
+                {
+                    self.last_response = last_response;
+                }
+                return Ok(None);
+            }
+            Ok(HandlerStatus::Disconnect(reason)) => Some(reason),
+            // HACK: handling this "dummy" error is required as part of the
+            // `TargetResultExt::handle_error()` machinery.
+            Err(Error::NonFatalError(code)) => {
+                res.write_str("E")?;
+                res.write_num(code)?;
+                None
+            }
+            Err(Error::TargetError(e)) => {
+                // unlike all other errors which are "unrecoverable" in the sense that
+                // the GDB session cannot continue, there's still a chance that a target
+                // might want to keep the debugging session alive to do a "post-mortem"
+                // analysis. As such, we simply report a standard TRAP stop reason.
+                let res = ResponseWriter::new(conn, self.rle_enabled).with_trace(self.packet_trace);
+                #[cfg(feature = "alloc")]
+                let res = res.with_record(&mut self.last_response, self.packet_buffer_len);
+                let mut res = res;
+                res.write_str("S05")?;
+                let len = res.flush()?;
+                self.stats.sent(len);
+                return Err(Error::TargetError(e));
+            }
+            Err(e) => return Err(e),
+        };
+
+        // `k` packets don't have a response (not even an empty one)
+        if disconnect != Some(DisconnectReason::Kill) || res.is_started() {
+            let len = res.flush()?;
+            self.stats.sent(len);
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            self.last_response = last_response;
         }
+
+        Ok(disconnect)
     }
 
     fn recv_packet<'a>(
         conn: &mut C,
         target: &mut T,
         pkt_buf: &'a mut ManagedSlice<u8>,
+        trace: Option<PacketTraceFn>,
+        stats: &StatsCounter,
     ) -> Result<Packet<'a>, Error<T::Error, C::Error>> {
-        let header_byte = conn.read().map_err(Error::ConnectionRead)?;
+        let header_byte = loop {
+            match target.idle() {
+                None => break conn.read().map_err(Error::ConnectionRead)?,
+                Some(ops) => match conn
+                    .read_timeout(ops.idle_timeout())
+                    .map_err(Error::ConnectionRead)?
+                {
+                    Some(b) => break b,
+                    None => ops.on_idle().map_err(Error::TargetError)?,
+                },
+            }
+        };
 
         // Wrap the buf in a `ManagedVec` to keep the code readable.
         let mut buf = ManagedVec::new(pkt_buf);
@@ -231,6 +602,12 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
             buf.push(conn.read().map_err(Error::ConnectionRead)?)?;
         }
 
+        // trace the packet _before_ parsing it, as parsing decodes the packet in-place
+        if let Some(trace) = trace {
+            trace(PacketDirection::Incoming, buf.as_slice());
+        }
+        stats.received(buf.as_slice());
+
         match Packet::from_buf(target, pkt_buf.as_mut()) {
             Ok(packet) => Ok(packet),
             Err(e) => Err(Error::PacketParse(e)),
@@ -243,6 +620,10 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
         target: &mut T,
         cmd: Command<'_>,
     ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        if target.read_only().is_some() && is_write_command(&cmd) {
+            return Err(Error::NonFatalError(1));
+        }
+
         match cmd {
             Command::Unknown(cmd) => {
                 info!("Unknown command: {}", cmd);
@@ -252,6 +633,27 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
             Command::ExtendedMode(cmd) => self.handle_extended_mode(res, target, cmd),
             Command::MonitorCmd(cmd) => self.handle_monitor_cmd(res, target, cmd),
             Command::SectionOffsets(cmd) => self.handle_section_offsets(res, target, cmd),
+            Command::NonStopMode(cmd) => self.handle_non_stop_mode(res, target, cmd),
+            Command::ReverseExec(cmd) => self.handle_reverse_exec(res, target, cmd),
+            Command::Tracepoints(cmd) => self.handle_tracepoints(res, target, cmd),
+            Command::HostIo(cmd) => self.handle_host_io(res, target, cmd),
+            Command::Auxv(cmd) => self.handle_auxv(res, target, cmd),
+            Command::Siginfo(cmd) => self.handle_siginfo(res, target, cmd),
+            Command::ExecFile(cmd) => self.handle_exec_file(res, target, cmd),
+            Command::LibrariesSvr4(cmd) => self.handle_libraries_svr4(res, target, cmd),
+            Command::ThreadListXml(cmd) => self.handle_thread_list_xml(res, target, cmd),
+            Command::MemoryMap(cmd) => self.handle_memory_map(res, target, cmd),
+            Command::Flash(cmd) => self.handle_flash(res, target, cmd),
+            Command::MemorySearch(cmd) => self.handle_memory_search(res, target, cmd),
+            Command::MemoryTags(cmd) => self.handle_memory_tags(res, target, cmd),
+            Command::Allow(cmd) => self.handle_allow(res, target, cmd),
+            Command::ThreadExtraInfo(cmd) => self.handle_thread_extra_info(res, target, cmd),
+            Command::Signals(cmd) => self.handle_signals(res, target, cmd),
+            Command::Tls(cmd) => self.handle_tls(res, target, cmd),
+            Command::Osdata(cmd) => self.handle_osdata(res, target, cmd),
+            Command::Btrace(cmd) => self.handle_btrace(res, target, cmd),
+            Command::CustomXfer(cmd) => self.handle_custom_xfer(res, target, cmd),
+            Command::HostSyscall(cmd) => self.handle_host_syscall(res, target, cmd),
         }
     }
 
@@ -264,70 +666,311 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
         let handler_status = match command {
             // ------------------ Handshaking and Queries ------------------- //
             ext::Base::qSupported(cmd) => {
-                // XXX: actually read what the client supports, and enable/disable features
+                // XXX: only fork / vfork events and multiprocess are currently negotiated. The rest
+                // of the client's features should be read, and used to enable/disable features
                 // appropriately
-                let _features = cmd.features.into_iter();
+                let mut gdb_fork_events = false;
+                let mut gdb_vfork_events = false;
+                let mut gdb_multiprocess = false;
+                let mut gdb_swbreak = false;
+                let mut gdb_hwbreak = false;
+                if let Some(ops) = target.qsupported() {
+                    for feature in cmd.features.tokens() {
+                        ops.on_gdb_feature(feature).map_err(Error::TargetError)?;
+                    }
+                }
+                for feature in cmd.features.into_iter().flatten() {
+                    use crate::protocol::commands::_qSupported::FeatureSupported;
+                    let supported = matches!(feature.status, FeatureSupported::Yes);
+                    match feature.name.as_ref() {
+                        b"fork-events" => gdb_fork_events = supported,
+                        b"vfork-events" => gdb_vfork_events = supported,
+                        b"multiprocess" => gdb_multiprocess = supported,
+                        b"swbreak" => gdb_swbreak = supported,
+                        b"hwbreak" => gdb_hwbreak = supported,
+                        _ => {}
+                    }
+                }
 
-                res.write_str("PacketSize=")?;
-                res.write_num(self.packet_buffer_len)?;
+                // Any GDB which sends `qSupported` knows how to decode run-length encoded
+                // responses, so it's safe to start using RLE for all subsequent responses.
+                self.rle_enabled = true;
+
+                self.fork_events = false;
+                self.vfork_events = false;
+                gdb_fork_events &= Self::advertise_feature(target, "fork-events");
+                gdb_vfork_events &= Self::advertise_feature(target, "vfork-events");
+                if let Some(ops) = target.catch_fork() {
+                    ops.set_fork_events(gdb_fork_events, gdb_vfork_events)
+                        .handle_error()?;
+                    self.fork_events = gdb_fork_events;
+                    self.vfork_events = gdb_vfork_events;
+                }
 
-                res.write_str(";vContSupported+")?;
-                res.write_str(";multiprocess+")?;
-                res.write_str(";QStartNoAckMode+")?;
+                // GDB's `PacketSize` includes the packet's framing (i.e: the leading `$`, and the
+                // trailing `#` + checksum), which are stored in the packet buffer alongside the
+                // (still escaped) packet body. As such, even a maximum-size packet fits as-is.
+                if Self::advertise_feature(target, "PacketSize") {
+                    res.write_str("PacketSize=")?;
+                    res.write_num(self.packet_buffer_len)?;
+                }
 
-                if let Some(ops) = target.extended_mode() {
-                    if ops.configure_aslr().is_some() {
-                        res.write_str(";QDisableRandomization+")?;
-                    }
+                // thread-ids only need to include a pid when debugging multiple processes (which
+                // includes following the children reported via fork / vfork events).
+                let multiprocess = target
+                    .extended_mode()
+                    .map(|ops| ops.supports_multiprocess())
+                    .unwrap_or(false)
+                    || target.catch_fork().is_some();
+                let multiprocess = multiprocess && Self::advertise_feature(target, "multiprocess");
+                self.multiprocess = multiprocess && gdb_multiprocess;
+
+                Self::write_feature(res, target, "vContSupported+")?;
+                if multiprocess {
+                    Self::write_feature(res, target, "multiprocess+")?;
+                }
+                Self::write_feature(res, target, "QStartNoAckMode+")?;
+
+                let (aslr, env, startup_shell, working_dir) = match target.extended_mode() {
+                    Some(ops) => (
+                        ops.configure_aslr().is_some(),
+                        ops.configure_env().is_some(),
+                        ops.configure_startup_shell().is_some(),
+                        ops.configure_working_dir().is_some(),
+                    ),
+                    None => (false, false, false, false),
+                };
 
-                    if ops.configure_env().is_some() {
-                        res.write_str(";QEnvironmentHexEncoded+")?;
-                        res.write_str(";QEnvironmentUnset+")?;
-                        res.write_str(";QEnvironmentReset+")?;
-                    }
+                if aslr {
+                    Self::write_feature(res, target, "QDisableRandomization+")?;
+                }
+
+                if env {
+                    Self::write_feature(res, target, "QEnvironmentHexEncoded+")?;
+                    Self::write_feature(res, target, "QEnvironmentUnset+")?;
+                    Self::write_feature(res, target, "QEnvironmentReset+")?;
+                }
+
+                if startup_shell {
+                    Self::write_feature(res, target, "QStartupWithShell+")?;
+                }
+
+                if working_dir {
+                    Self::write_feature(res, target, "QSetWorkingDir+")?;
+                }
+
+                // GDB only accepts `swbreak` / `hwbreak` stop reasons if both sides agreed to
+                // use them
+                Self::write_feature(res, target, "swbreak+")?;
+                self.swbreak = gdb_swbreak && Self::advertise_feature(target, "swbreak");
+                let hwbreak = target.hw_breakpoint().is_some() || target.hw_watchpoint().is_some();
+                if hwbreak {
+                    Self::write_feature(res, target, "hwbreak+")?;
+                }
+                self.hwbreak = hwbreak && gdb_hwbreak && Self::advertise_feature(target, "hwbreak");
+
+                let sw_conds =
+                    (target.sw_breakpoint()).map(|op| op.sw_breakpoint_conditions().is_some());
+                let hw_conds =
+                    (target.hw_breakpoint()).map(|op| op.hw_breakpoint_conditions().is_some());
+                if sw_conds == Some(true) || hw_conds == Some(true) {
+                    Self::write_feature(res, target, "ConditionalBreakpoints+")?;
+                }
+
+                let sw_cmds =
+                    (target.sw_breakpoint()).map(|op| op.sw_breakpoint_commands().is_some());
+                let hw_cmds =
+                    (target.hw_breakpoint()).map(|op| op.hw_breakpoint_commands().is_some());
+                if sw_cmds == Some(true) || hw_cmds == Some(true) {
+                    Self::write_feature(res, target, "BreakpointCommands+")?;
+                }
+
+                if T::Arch::target_description_xml().is_some()
+                    || target.target_description_xml_override().is_some()
+                {
+                    Self::write_feature(res, target, "qXfer:features:read+")?;
+                }
+
+                if target.memory_map().is_some() {
+                    Self::write_feature(res, target, "qXfer:memory-map:read+")?;
+                }
+
+                if target.auxv().is_some() {
+                    Self::write_feature(res, target, "qXfer:auxv:read+")?;
+                }
+
+                if target.siginfo().is_some() {
+                    Self::write_feature(res, target, "qXfer:siginfo:read+")?;
+                    Self::write_feature(res, target, "qXfer:siginfo:write+")?;
+                }
+
+                if target.memory_tags().is_some() {
+                    Self::write_feature(res, target, "memory-tagging+")?;
+                }
+
+                if target.signals().is_some() {
+                    Self::write_feature(res, target, "QPassSignals+")?;
+                    Self::write_feature(res, target, "QProgramSignals+")?;
+                }
+
+                if target.exec_file().is_some() {
+                    Self::write_feature(res, target, "qXfer:exec-file:read+")?;
+                }
 
-                    if ops.configure_startup_shell().is_some() {
-                        res.write_str(";QStartupWithShell+")?;
+                if target.libraries_svr4().is_some() {
+                    Self::write_feature(res, target, "qXfer:libraries-svr4:read+")?;
+                }
+
+                if target.thread_list_xml().is_some() {
+                    Self::write_feature(res, target, "qXfer:threads:read+")?;
+                }
+
+                if let BaseOps::MultiThread(_) = target.base_ops() {
+                    Self::write_feature(res, target, "QThreadEvents+")?;
+                }
+
+                if target.non_stop_mode().is_some() {
+                    Self::write_feature(res, target, "QNonStop+")?;
+                }
+
+                if target.reverse_exec().is_some() {
+                    Self::write_feature(res, target, "ReverseStep+")?;
+                    Self::write_feature(res, target, "ReverseContinue+")?;
+                }
+
+                if target.allow().is_some() {
+                    Self::write_feature(res, target, "QAllow+")?;
+                }
+
+                if target.osdata().is_some() {
+                    Self::write_feature(res, target, "qXfer:osdata:read+")?;
+                }
+
+                if let Some(ops) = target.btrace() {
+                    use crate::target::ext::btrace::BtraceFormat;
+
+                    let bts = ops.supports_format(BtraceFormat::Bts);
+                    let pt = ops.supports_format(BtraceFormat::Pt);
+                    if bts {
+                        Self::write_feature(res, target, "Qbtrace:bts+")?;
+                        Self::write_feature(res, target, "Qbtrace-conf:bts:size+")?;
+                    }
+                    if pt {
+                        Self::write_feature(res, target, "Qbtrace:pt+")?;
+                        Self::write_feature(res, target, "Qbtrace-conf:pt:size+")?;
                     }
+                    if bts || pt {
+                        Self::write_feature(res, target, "Qbtrace:off+")?;
+                        Self::write_feature(res, target, "qXfer:btrace:read+")?;
+                    }
+                }
 
-                    if ops.configure_working_dir().is_some() {
-                        res.write_str(";QSetWorkingDir+")?;
+                if let Some(ops) = target.custom_xfer() {
+                    for object in ops.custom_xfer_objects() {
+                        let modes = [(object.read, ":read+"), (object.write, ":write+")];
+                        for (_, mode) in modes.iter().filter(|(supported, _)| *supported) {
+                            if res.is_started() {
+                                res.write_str(";")?;
+                            }
+                            res.write_str("qXfer:")?;
+                            res.write_str(object.name)?;
+                            res.write_str(mode)?;
+                        }
                     }
                 }
 
-                res.write_str(";swbreak+")?;
-                if target.hw_breakpoint().is_some() || target.hw_watchpoint().is_some() {
-                    res.write_str(";hwbreak+")?;
+                if self.fork_events {
+                    Self::write_feature(res, target, "fork-events+")?;
                 }
 
-                // TODO: implement conditional breakpoint support (since that's kool).
-                // res.write_str("ConditionalBreakpoints+;")?;
+                if self.vfork_events {
+                    Self::write_feature(res, target, "vfork-events+")?;
+                }
 
-                if T::Arch::target_description_xml().is_some() {
-                    res.write_str(";qXfer:features:read+")?;
+                if let Some(ops) = target.qsupported() {
+                    let mut err = Ok(());
+                    ops.extra_features(&mut |feature| {
+                        let mut write = || {
+                            if res.is_started() {
+                                res.write_str(";")?;
+                            }
+                            res.write_all(feature)
+                        };
+                        if let Err(e) = write() {
+                            err = Err(e);
+                        }
+                    })
+                    .map_err(Error::TargetError)?;
+                    err?;
                 }
 
                 HandlerStatus::Handled
             }
             ext::Base::QStartNoAckMode(_) => {
+                // The `QStartNoAckMode` packet itself has already been acked, and the `OK`
+                // response is acked by GDB, after which neither side sends acks.
                 self.no_ack_mode = true;
                 HandlerStatus::NeedsOK
             }
+            ext::Base::vCtrlC(_) if self.non_stop => {
+                // the interrupted thread(s) are reported asynchronously, via `%Stop`
+                let ops = target.non_stop_mode().ok_or(Error::PacketUnexpected)?;
+                ops.stop_threads(self.current_resume_tid)
+                    .map_err(Error::TargetError)?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Base::vCtrlC(_) => {
+                // all-stop targets are always stopped while handling packets, so this is
+                // equivalent to receiving the interrupt byte while the target is stopped
+                match self.stopped_interrupt(target)? {
+                    Some(Some(stop_reason)) => match self.finish_vcont(stop_reason, res, target)? {
+                        Some(dc) => HandlerStatus::Disconnect(dc),
+                        None => HandlerStatus::Handled,
+                    },
+                    Some(None) => {
+                        res.write_str("S05")?;
+                        HandlerStatus::Handled
+                    }
+                    None => HandlerStatus::NeedsOK,
+                }
+            }
+            ext::Base::QThreadEvents(cmd) => {
+                self.thread_events = cmd.value;
+                HandlerStatus::NeedsOK
+            }
             ext::Base::qXferFeaturesRead(cmd) => {
+                if let Some(ops) = target.target_description_xml_override() {
+                    let annex = cmd.annex;
+                    Self::write_xfer_chunk(
+                        res,
+                        cmd.offset,
+                        cmd.len,
+                        cmd.buf,
+                        |offset, len, buf| {
+                            ops.target_description_xml(annex, offset, len, buf)
+                                .handle_error()
+                        },
+                    )?;
+                    return Ok(HandlerStatus::Handled);
+                }
+
                 match T::Arch::target_description_xml() {
+                    // the static target description doesn't include any other files
+                    Some(_) if cmd.annex != b"target.xml" => return Err(Error::NonFatalError(0)),
                     Some(xml) => {
-                        let xml = xml.trim();
-                        if cmd.offset >= xml.len() {
+                        let xml = xml.trim().as_bytes();
+                        let offset = cmd.offset as usize;
+                        if offset >= xml.len() {
                             // no more data
                             res.write_str("l")?;
-                        } else if cmd.offset + cmd.len >= xml.len() {
+                        } else if offset + cmd.len >= xml.len() {
                             // last little bit of data
                             res.write_str("l")?;
-                            res.write_binary(&xml.as_bytes()[cmd.offset..])?
+                            res.write_binary(&xml[offset..])?
                         } else {
                             // still more data
                             res.write_str("m")?;
-                            res.write_binary(&xml.as_bytes()[cmd.offset..(cmd.offset + cmd.len)])?
+                            res.write_binary(&xml[offset..(offset + cmd.len)])?
                         }
                     }
                     // If the target hasn't provided their own XML, then the initial response to
@@ -339,29 +982,79 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
             }
 
             // -------------------- "Core" Functionality -------------------- //
-            // TODO: Improve the '?' response based on last-sent stop reason.
             ext::Base::QuestionMark(_) => {
-                res.write_str("S05")?;
+                if self.non_stop {
+                    // Report the first stopped thread (or "OK" if every thread is running). The
+                    // rest are reported in response to subsequent `vStopped` packets.
+                    return self.write_stopped_thread(res, target, 0);
+                } else {
+                    let stop_reason = match target.last_stop_reason() {
+                        Some(ops) => ops.get_last_stop_reason().map_err(Error::TargetError)?,
+                        None => ThreadStopReason::DoneStep,
+                    };
+
+                    // always report the stop on a specific thread, so that GDB can recover
+                    // the stop context after (re)connecting. Events which GDB has already
+                    // been told about (e.g: a fork, or a process exiting) are reported as a
+                    // plain stop, instead of being re-issued.
+                    let sigtrap = Self::native_signal(target, Signal::SIGTRAP);
+                    let plain_stop = match stop_reason {
+                        ThreadStopReason::DoneStep
+                        | ThreadStopReason::GdbInterrupt
+                        | ThreadStopReason::Exited { .. }
+                        | ThreadStopReason::Terminated { .. } => {
+                            Some((self.current_mem_tid, sigtrap))
+                        }
+                        ThreadStopReason::Signal(signal) => Some((self.current_mem_tid, signal)),
+                        ThreadStopReason::Fork { tid, .. }
+                        | ThreadStopReason::Vfork { tid, .. }
+                        | ThreadStopReason::VforkDone(tid)
+                        | ThreadStopReason::ThreadCreated(tid)
+                        | ThreadStopReason::HostSyscall { tid, .. } => Some((tid, sigtrap)),
+                        _ => None,
+                    };
+
+                    let disconnect = match plain_stop {
+                        Some((tid, signal)) => {
+                            self.current_mem_tid = tid;
+                            self.current_resume_tid = TidSelector::WithID(tid);
+                            let stop_reason = ThreadStopReason::SignalWithThread { tid, signal };
+                            self.write_stop_reason(res, target, stop_reason)?
+                        }
+                        None => self.finish_vcont(stop_reason, res, target)?,
+                    };
+                    if let Some(reason) = disconnect {
+                        return Ok(HandlerStatus::Disconnect(reason));
+                    }
+                }
                 HandlerStatus::Handled
             }
             ext::Base::qAttached(cmd) => {
+                // `qAttached` without a pid refers to the current process
+                let pid = match cmd.pid {
+                    Some(pid) => pid,
+                    None => match target.base_ops() {
+                        BaseOps::SingleThread(_) => FAKE_PID,
+                        BaseOps::MultiThread(ops) => ops
+                            .thread_pid(self.current_mem_tid)
+                            .map_err(Error::TargetError)?,
+                    },
+                };
+
                 let is_attached = match target.extended_mode() {
                     // when _not_ running in extended mode, just report that we're attaching to an
                     // existing process.
                     None => true, // assume attached to an existing process
                     // When running in extended mode, we must defer to the target
                     Some(ops) => {
-                        let pid: Pid = cmd.pid.ok_or(Error::PacketUnexpected)?;
-
                         #[cfg(feature = "alloc")]
-                        {
-                            let _ = ops; // doesn't actually query the target
-                            *self.attached_pids.get(&pid).unwrap_or(&true)
-                        }
-
+                        let tracked = self.attached_pids.get(&pid).copied();
                         #[cfg(not(feature = "alloc"))]
-                        {
-                            ops.query_if_attached(pid).handle_error()?.was_attached()
+                        let tracked = None;
+
+                        match tracked {
+                            Some(is_attached) => is_attached,
+                            None => ops.query_if_attached(pid).handle_error()?.was_attached(),
                         }
                     }
                 };
@@ -378,8 +1071,13 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 }
                 .handle_error()?;
 
+                let endian = target
+                    .endianness_override()
+                    .map(|ops| ops.endianness())
+                    .unwrap_or_else(T::Arch::endianness);
+
                 let mut err = Ok(());
-                regs.gdb_serialize(|val| {
+                regs.gdb_serialize(endian, |val| {
                     let res = match val {
                         Some(b) => res.write_hex_buf(&[b]),
                         None => res.write_str("xx"),
@@ -392,8 +1090,13 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 HandlerStatus::Handled
             }
             ext::Base::G(cmd) => {
+                let endian = target
+                    .endianness_override()
+                    .map(|ops| ops.endianness())
+                    .unwrap_or_else(T::Arch::endianness);
+
                 let mut regs: <T::Arch as Arch>::Registers = Default::default();
-                regs.gdb_deserialize(cmd.vals)
+                regs.gdb_deserialize(endian, cmd.vals)
                     .map_err(|_| Error::TargetMismatch)?;
 
                 match target.base_ops() {
@@ -409,6 +1112,10 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
                     .ok_or(Error::TargetMismatch)?;
 
+                // Stream the read through the unused tail of the packet buffer, hex-encoding
+                // each chunk directly into the response. This keeps peak memory use bounded by
+                // the packet buffer, regardless of how much memory GDB asked for (responses
+                // larger than the packet buffer aren't kept around for re-transmission).
                 let mut i = 0;
                 let mut n = cmd.len;
                 while n != 0 {
@@ -447,32 +1154,74 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
 
                 HandlerStatus::NeedsOK
             }
+            ext::Base::X(cmd) => {
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+
+                // the (unescaped) payload must match the advertised length
+                if cmd.val.len() != cmd.len {
+                    return Err(Error::NonFatalError(22));
+                }
+
+                // GDB probes for `X` support using a zero-length write
+                if !cmd.val.is_empty() {
+                    match target.base_ops() {
+                        BaseOps::SingleThread(ops) => ops.write_addrs(addr, cmd.val),
+                        BaseOps::MultiThread(ops) => {
+                            ops.write_addrs(addr, cmd.val, self.current_mem_tid)
+                        }
+                    }
+                    .handle_error()?;
+                }
+
+                HandlerStatus::NeedsOK
+            }
             ext::Base::k(_) | ext::Base::vKill(_) => {
-                match target.extended_mode() {
-                    // When not running in extended mode, stop the `GdbStub` and disconnect.
-                    None => HandlerStatus::Disconnect(DisconnectReason::Kill),
+                // Unlike `vKill`, the `k` packet doesn't have a reply.
+                let (pid, reply) = match command {
+                    ext::Base::vKill(cmd) => (Some(cmd.pid), true),
+                    _ => (None, false),
+                };
 
+                let should_terminate = match target.extended_mode() {
+                    // When not running in extended mode, stop the `GdbStub` and disconnect.
+                    None => true,
                     // When running in extended mode, a kill command does not necessarily result in
                     // a disconnect...
-                    Some(ops) => {
-                        let pid = match command {
-                            ext::Base::vKill(cmd) => Some(cmd.pid),
-                            _ => None,
-                        };
+                    Some(ops) => ops.kill(pid).handle_error()?.into(),
+                };
 
-                        let should_terminate = ops.kill(pid).handle_error()?;
-                        if should_terminate.into() {
-                            // manually write OK, since we need to return a DisconnectReason
-                            res.write_str("OK")?;
-                            HandlerStatus::Disconnect(DisconnectReason::Kill)
-                        } else {
-                            HandlerStatus::NeedsOK
-                        }
+                match (should_terminate, reply) {
+                    (true, true) => {
+                        // manually write OK, since we need to return a DisconnectReason
+                        res.write_str("OK")?;
+                        HandlerStatus::Disconnect(DisconnectReason::Kill)
                     }
+                    (true, false) => HandlerStatus::Disconnect(DisconnectReason::Kill),
+                    (false, true) => HandlerStatus::NeedsOK,
+                    (false, false) => HandlerStatus::NoResponse,
                 }
             }
-            ext::Base::D(_) => {
-                // TODO: plumb-through Pid when exposing full multiprocess + extended mode
+            ext::Base::D(cmd) => {
+                // Detaching from a single process (in extended mode) doesn't necessarily
+                // result in a disconnect...
+                if let (Some(pid), Some(ops)) = (cmd.pid, target.extended_mode()) {
+                    let should_terminate = ops.detach(pid).handle_error()?;
+                    #[cfg(feature = "alloc")]
+                    self.attached_pids.remove(&pid);
+                    if !bool::from(should_terminate) {
+                        return Ok(HandlerStatus::NeedsOK);
+                    }
+                }
+
+                // ...but once the session is over, the target is left running without
+                // the debugger attached.
+                match target.base_ops() {
+                    BaseOps::SingleThread(ops) => ops.detach(),
+                    BaseOps::MultiThread(ops) => ops.detach(),
+                }
+                .handle_error()?;
+
                 res.write_str("OK")?; // manually write OK, since we need to return a DisconnectReason
                 HandlerStatus::Disconnect(DisconnectReason::Disconnect)
             }
@@ -481,34 +1230,166 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                     .ok_or(Error::TargetMismatch)?;
 
                 use crate::target::ext::breakpoints::WatchKind::*;
-                let supported = match cmd.type_ {
-                    0 => (target.sw_breakpoint()).map(|op| op.add_sw_breakpoint(addr)),
-                    1 => (target.hw_breakpoint()).map(|op| op.add_hw_breakpoint(addr)),
-                    2 => (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, Write)),
-                    3 => (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, Read)),
-                    4 => (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, ReadWrite)),
-                    // only 5 types in the protocol
-                    _ => None,
-                };
 
-                match supported {
-                    None => HandlerStatus::Handled,
-                    Some(Err(e)) => {
-                        Err(e).handle_error()?;
-                        HandlerStatus::Handled
-                    }
-                    Some(Ok(true)) => HandlerStatus::NeedsOK,
-                    Some(Ok(false)) => return Err(Error::NonFatalError(22)),
+                if let Some(mask) = cmd.mask {
+                    let mask = <T::Arch as Arch>::Usize::from_be_bytes(mask)
+                        .ok_or(Error::TargetMismatch)?;
+                    let kind = match cmd.type_ {
+                        2 => Write,
+                        3 => Read,
+                        4 => ReadWrite,
+                        _ => return Err(Error::PacketUnexpected),
+                    };
+                    let supported =
+                        (target.hw_watchpoint()).map(|op| match op.hw_watchpoint_masked() {
+                            Some(op) => op.add_hw_watchpoint_masked(addr, mask, kind),
+                            None => Ok(false),
+                        });
+                    return Self::breakpoint_status(supported);
+                }
+
+                // GDB may insert the same breakpoint several times (e.g: two logical
+                // breakpoints at the same address), in which case only the first insertion is
+                // forwarded to the target. Insertions with conditions / commands always are,
+                // since they update the existing breakpoint.
+                #[cfg(feature = "alloc")]
+                let refs_key = (cmd.type_, addr, cmd.kind);
+                #[cfg(feature = "alloc")]
+                if matches!(cmd.type_, 0 | 1) && cmd.conds.is_none() && cmd.cmds.is_none() {
+                    if let Some(count) = self.breakpoint_refs.get_mut(&refs_key) {
+                        *count += 1;
+                        return Ok(HandlerStatus::NeedsOK);
+                    }
+                }
+
+                // the breakpoint kind is only meaningful for sw / hw breakpoints (as opposed
+                // to watchpoints, where it's the length of the watched region)
+                let kind = <T::Arch as Arch>::BreakpointKind::from_usize(cmd.kind)
+                    .ok_or(Error::NonFatalError(22));
+                let supported = match (cmd.type_, cmd.conds) {
+                    (0, None) => {
+                        let kind = kind?;
+                        (target.sw_breakpoint()).map(|op| op.add_sw_breakpoint(addr, kind))
+                    }
+                    (1, None) => {
+                        let kind = kind?;
+                        (target.hw_breakpoint()).map(|op| op.add_hw_breakpoint(addr, kind))
+                    }
+                    (0, Some(conds)) => {
+                        let kind = kind?;
+                        // conditions may have been advertised for the sake of hw breakpoints, in
+                        // which case GDB evaluates the sw breakpoint's conditions itself
+                        (target.sw_breakpoint()).map(|op| match op.sw_breakpoint_conditions() {
+                            Some(op) => op.add_sw_breakpoint_with_conds(addr, kind, conds),
+                            None => op.add_sw_breakpoint(addr, kind),
+                        })
+                    }
+                    (1, Some(conds)) => {
+                        let kind = kind?;
+                        (target.hw_breakpoint()).map(|op| {
+                            let programmed = match op.hw_breakpoint_conditions() {
+                                Some(op) => op.add_hw_breakpoint_with_conds(addr, kind, conds)?,
+                                None => false,
+                            };
+                            if programmed {
+                                return Ok(true);
+                            }
+
+                            // fall back to a plain hardware breakpoint. GDB re-checks breakpoint
+                            // conditions itself whenever the target stops, so they still apply.
+                            match <T::Arch as Arch>::BreakpointKind::from_usize(cmd.kind) {
+                                Some(kind) => op.add_hw_breakpoint(addr, kind),
+                                None => Ok(false),
+                            }
+                        })
+                    }
+                    (_, Some(_)) => return Err(Error::PacketUnexpected),
+                    (2, None) => {
+                        (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, Write))
+                    }
+                    (3, None) => {
+                        (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, Read))
+                    }
+                    (4, None) => {
+                        (target.hw_watchpoint()).map(|op| op.add_hw_watchpoint(addr, ReadWrite))
+                    }
+                    // only 5 types in the protocol
+                    _ => None,
+                };
+
+                // attach any commands to the newly added breakpoint
+                let supported = match (supported, cmd.cmds) {
+                    (Some(Ok(true)), Some(cmds)) => {
+                        let kind = <T::Arch as Arch>::BreakpointKind::from_usize(cmd.kind)
+                            .ok_or(Error::NonFatalError(22))?;
+                        let persist = cmd.persist;
+                        let set_cmds = match cmd.type_ {
+                            0 => (target.sw_breakpoint())
+                                .and_then(|op| op.sw_breakpoint_commands())
+                                .map(|op| op.set_sw_breakpoint_cmds(addr, kind, cmds, persist)),
+                            _ => (target.hw_breakpoint())
+                                .and_then(|op| op.hw_breakpoint_commands())
+                                .map(|op| op.set_hw_breakpoint_cmds(addr, kind, cmds, persist)),
+                        };
+                        // targets which don't support commands never advertised them
+                        set_cmds.or(Some(Ok(true)))
+                    }
+                    (supported, _) => supported,
+                };
+
+                #[cfg(feature = "alloc")]
+                if matches!(cmd.type_, 0 | 1) && matches!(supported, Some(Ok(true))) {
+                    self.breakpoint_refs.entry(refs_key).or_insert(1);
                 }
+
+                Self::breakpoint_status(supported)?
             }
             ext::Base::z(cmd) => {
                 let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
                     .ok_or(Error::TargetMismatch)?;
 
                 use crate::target::ext::breakpoints::WatchKind::*;
+
+                if let Some(mask) = cmd.mask {
+                    let mask = <T::Arch as Arch>::Usize::from_be_bytes(mask)
+                        .ok_or(Error::TargetMismatch)?;
+                    let kind = match cmd.type_ {
+                        2 => Write,
+                        3 => Read,
+                        4 => ReadWrite,
+                        _ => return Err(Error::PacketUnexpected),
+                    };
+                    let supported =
+                        (target.hw_watchpoint()).map(|op| match op.hw_watchpoint_masked() {
+                            Some(op) => op.remove_hw_watchpoint_masked(addr, mask, kind),
+                            None => Ok(false),
+                        });
+                    return Self::breakpoint_status(supported);
+                }
+
+                // only remove the breakpoint from the target once every insertion of it has
+                // been removed
+                #[cfg(feature = "alloc")]
+                let refs_key = (cmd.type_, addr, cmd.kind);
+                #[cfg(feature = "alloc")]
+                if let Some(count) = self.breakpoint_refs.get_mut(&refs_key) {
+                    if *count > 1 {
+                        *count -= 1;
+                        return Ok(HandlerStatus::NeedsOK);
+                    }
+                }
+
+                let kind = <T::Arch as Arch>::BreakpointKind::from_usize(cmd.kind)
+                    .ok_or(Error::NonFatalError(22));
                 let supported = match cmd.type_ {
-                    0 => (target.sw_breakpoint()).map(|op| op.remove_sw_breakpoint(addr)),
-                    1 => (target.hw_breakpoint()).map(|op| op.remove_hw_breakpoint(addr)),
+                    0 => {
+                        let kind = kind?;
+                        (target.sw_breakpoint()).map(|op| op.remove_sw_breakpoint(addr, kind))
+                    }
+                    1 => {
+                        let kind = kind?;
+                        (target.hw_breakpoint()).map(|op| op.remove_hw_breakpoint(addr, kind))
+                    }
                     2 => (target.hw_watchpoint()).map(|op| op.remove_hw_watchpoint(addr, Write)),
                     3 => (target.hw_watchpoint()).map(|op| op.remove_hw_watchpoint(addr, Read)),
                     4 => {
@@ -518,26 +1399,27 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                     _ => None,
                 };
 
-                match supported {
-                    None => HandlerStatus::Handled,
-                    Some(Err(e)) => {
-                        Err(e).handle_error()?;
-                        HandlerStatus::Handled
-                    }
-                    Some(Ok(true)) => HandlerStatus::NeedsOK,
-                    Some(Ok(false)) => return Err(Error::NonFatalError(22)),
+                #[cfg(feature = "alloc")]
+                if matches!(supported, Some(Ok(true))) {
+                    self.breakpoint_refs.remove(&refs_key);
                 }
+
+                Self::breakpoint_status(supported)?
             }
             ext::Base::p(p) => {
-                let mut dst = [0u8; 32]; // enough for 256-bit registers
                 let reg = <T::Arch as Arch>::RegId::from_raw_id(p.reg_id);
                 let (reg_id, reg_size) = match reg {
                     Some(v) => v,
                     // empty packet indicates unrecognized query
                     None => return Ok(HandlerStatus::Handled),
                 };
-                let dst = &mut dst[0..reg_size];
-                match target.base_ops() {
+                // the packet buffer is re-used as scratch space for the register's value
+                let dst = p
+                    .buf
+                    .get_mut(0..reg_size)
+                    .ok_or(Error::PacketBufferOverlow)?;
+                dst.iter_mut().for_each(|b| *b = 0);
+                let len = match target.base_ops() {
                     BaseOps::SingleThread(ops) => ops.read_register(reg_id, dst),
                     BaseOps::MultiThread(ops) => {
                         ops.read_register(reg_id, dst, self.current_mem_tid)
@@ -545,7 +1427,7 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 }
                 .handle_error()?;
 
-                res.write_hex_buf(dst)?;
+                res.write_hex_buf(dst.get(..len).ok_or(Error::TargetMismatch)?)?;
                 HandlerStatus::Handled
             }
             ext::Base::P(p) => {
@@ -563,158 +1445,231 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 HandlerStatus::NeedsOK
             }
             ext::Base::vCont(cmd) => {
-                use crate::protocol::commands::_vCont::{vCont, VContKind};
+                use crate::protocol::commands::_vCont::{vCont, VContAction, VContKind};
 
                 let actions = match cmd {
                     vCont::Query => {
-                        res.write_str("vCont;c;C;s;S")?;
+                        // GDB won't use `vCont` at all unless both `c` and `C` are
+                        // advertised, so `C` is reported even if the target can't deliver
+                        // signals (in which case resuming with a signal is rejected).
+                        let caps = resume_capabilities(target);
+                        res.write_str("vCont;c;C")?;
+                        if caps.step {
+                            res.write_str(";s;S")?;
+                        }
+                        if target.non_stop_mode().is_some() {
+                            res.write_str(";t")?;
+                        }
+                        // range stepping is only supported in all-stop mode
+                        if target.range_step().is_some() && !self.non_stop {
+                            res.write_str(";r")?;
+                        }
                         return Ok(HandlerStatus::Handled);
                     }
                     vCont::Actions(actions) => actions,
                 };
 
+                let mut actions = actions.into_iter().peekable();
+
+                // In non-stop mode, GDB sends requests to stop threads separately from
+                // requests to resume threads. In all-stop mode, stop actions are meaningless
+                // (and must be rejected before the target gets resumed).
+                if let Some(Some(VContAction {
+                    kind: VContKind::Stop,
+                    ..
+                })) = actions.peek()
+                {
+                    if !self.non_stop {
+                        return Err(Error::PacketUnexpected);
+                    }
+                    return self.do_vcont_stop(target, actions);
+                }
+
+                // GDB lists thread-specific actions before the default action, so a
+                // range-step action is always the first action in the packet.
+                let range_step = match actions.peek() {
+                    Some(Some(VContAction {
+                        kind: VContKind::RangeStep(start, end),
+                        thread,
+                    })) => {
+                        if self.non_stop || target.range_step().is_none() {
+                            return Err(Error::PacketUnexpected);
+                        }
+
+                        let start = <T::Arch as Arch>::Usize::from_be_bytes(start)
+                            .ok_or(Error::TargetMismatch)?;
+                        let end = <T::Arch as Arch>::Usize::from_be_bytes(end)
+                            .ok_or(Error::TargetMismatch)?;
+                        // fall back to the current thread if a specific thread wasn't
+                        // given (e.g: when debugging a single threaded target)
+                        let tid = match vcont_tid_selector(*thread) {
+                            Some(TidSelector::WithID(tid)) => tid,
+                            _ => match self.current_resume_tid {
+                                TidSelector::WithID(tid) => tid,
+                                _ => self.current_mem_tid,
+                            },
+                        };
+                        actions.next();
+                        Some((tid, start, end))
+                    }
+                    _ => None,
+                };
+
                 // map raw vCont action iterator to a format the `Target` expects
-                let mut err = Ok(());
-                let mut actions = actions.into_iter().filter_map(|action| {
+                //
+                // Any malformed / unsupported action invalidates the entire packet, and is
+                // reported as EINVAL.
+                let caps = resume_capabilities(target);
+                let mut err: Result<(), Error<T::Error, C::Error>> = Ok(());
+                let actions = actions.filter_map(|action| {
                     let action = match action {
                         Some(action) => action,
                         None => {
-                            err = Err(Error::PacketParse(
-                                crate::protocol::PacketParseError::MalformedCommand,
-                            ));
+                            err = Err(Error::NonFatalError(22));
                             return None;
                         }
                     };
 
                     let resume_action = match action.kind {
-                        VContKind::Step => ResumeAction::Step,
-                        VContKind::Continue => ResumeAction::Continue,
+                        VContKind::Step => ResumeAction::Step(None),
+                        VContKind::Continue => ResumeAction::Continue(None),
+                        VContKind::StepWithSig(sig) | VContKind::ContinueWithSig(sig)
+                            if Signal::from_raw(sig).is_none() =>
+                        {
+                            err = Err(Error::NonFatalError(22));
+                            return None;
+                        }
+                        VContKind::StepWithSig(sig) => ResumeAction::Step(Signal::from_raw(sig)),
+                        VContKind::ContinueWithSig(sig) => {
+                            ResumeAction::Continue(Signal::from_raw(sig))
+                        }
+                        // only a single (leading) range-step action is supported, and stop
+                        // actions cannot be mixed with resume actions
                         _ => {
-                            // there seems to be a GDB bug where it doesn't use `vCont` unless
-                            // `vCont?` returns support for resuming with a signal.
-                            //
-                            // This error case can be removed once "Resume with Signal" is
-                            // implemented
-                            err = Err(Error::ResumeWithSignalUnimplemented);
+                            err = Err(Error::NonFatalError(22));
                             return None;
                         }
                     };
 
-                    let tid = match action.thread {
-                        Some(thread) => match thread.tid {
-                            IdKind::Any => {
-                                err = Err(Error::PacketUnexpected);
-                                return None;
-                            }
-                            IdKind::All => TidSelector::All,
-                            IdKind::WithID(tid) => TidSelector::WithID(tid),
-                        },
-                        // An action with no thread-id matches all threads
-                        None => TidSelector::All,
+                    if !caps.supports(resume_action) {
+                        err = Err(Error::NonFatalError(22));
+                        return None;
+                    }
+
+                    let tid = match vcont_tid_selector(action.thread) {
+                        Some(tid) => tid,
+                        None => {
+                            err = Err(Error::NonFatalError(22));
+                            return None;
+                        }
                     };
 
                     Some((tid, resume_action))
                 });
 
-                let ret = match self.do_vcont(res, target, &mut actions) {
-                    Ok(None) => HandlerStatus::Handled,
-                    Ok(Some(dc)) => HandlerStatus::Disconnect(dc),
-                    Err(e) => return Err(e),
+                // With `alloc`, every action is validated before the target gets resumed.
+                //
+                // Threads may exit between GDB listing them and resuming them, so actions
+                // targeting dead threads are dropped (instead of being handed to the target).
+                #[cfg(feature = "alloc")]
+                let mut actions = {
+                    let actions = actions.collect::<Vec<_>>();
+                    err?;
+                    Self::live_vcont_actions(target, actions)?.into_iter()
                 };
-                err?;
+                #[cfg(not(feature = "alloc"))]
+                let mut actions = actions;
+                let actions = &mut actions;
+
+                let ret = if let Some((tid, start, end)) = range_step {
+                    match self.do_range_step(res, target, tid, start, end, actions) {
+                        Ok(None) => HandlerStatus::Handled,
+                        Ok(Some(dc)) => HandlerStatus::Disconnect(dc),
+                        Err(e) => return Err(e),
+                    }
+                } else if self.non_stop {
+                    self.do_vcont_non_stop(target, actions)?;
+                    HandlerStatus::NeedsOK
+                } else {
+                    match self.do_vcont(res, target, actions) {
+                        Ok(None) => HandlerStatus::Handled,
+                        Ok(Some(dc)) => HandlerStatus::Disconnect(dc),
+                        Err(e) => return Err(e),
+                    }
+                };
+                // Without `alloc`, actions are only validated as the target consumes them,
+                // at which point the stop reply has already been sent (and it's too late to
+                // reply with an error).
+                #[cfg(not(feature = "alloc"))]
+                err.map_err(|_| Error::PacketUnexpected)?;
                 ret
             }
-            // TODO?: support custom resume addr in 'c' and 's'
-            ext::Base::c(_) => {
-                match self.do_vcont(
-                    res,
-                    target,
-                    &mut core::iter::once((self.current_resume_tid, ResumeAction::Continue)),
-                ) {
-                    Ok(None) => HandlerStatus::Handled,
-                    Ok(Some(dc)) => HandlerStatus::Disconnect(dc),
-                    Err(e) => return Err(e),
-                }
+            // TODO?: support custom resume addr in 'c', 'C', 's', and 'S'
+            ext::Base::c(_) => self.do_resume(res, target, ResumeAction::Continue(None))?,
+            ext::Base::C(cmd) => {
+                let sig = Signal::from_raw(cmd.sig).ok_or(Error::NonFatalError(22))?;
+                let action = ResumeAction::Continue(Some(sig));
+                self.do_resume(res, target, action)?
             }
-            ext::Base::s(_) => {
-                match self.do_vcont(
-                    res,
-                    target,
-                    &mut core::iter::once((self.current_resume_tid, ResumeAction::Step)),
-                ) {
-                    Ok(None) => HandlerStatus::Handled,
-                    Ok(Some(dc)) => HandlerStatus::Disconnect(dc),
-                    Err(e) => return Err(e),
-                }
+            ext::Base::s(_) => self.do_resume(res, target, ResumeAction::Step(None))?,
+            ext::Base::S(cmd) => {
+                let sig = Signal::from_raw(cmd.sig).ok_or(Error::NonFatalError(22))?;
+                let action = ResumeAction::Step(Some(sig));
+                self.do_resume(res, target, action)?
             }
 
             // ------------------- Multi-threading Support ------------------ //
             ext::Base::H(cmd) => {
                 use crate::protocol::commands::_h_upcase::Op;
                 match cmd.kind {
+                    // "All" threads doesn't make sense for memory accesses, so
+                    // it's treated the same as "Any" (i.e: reuse the old tid)
                     Op::Other => match cmd.thread.tid {
-                        IdKind::Any => {} // reuse old tid
-                        // "All" threads doesn't make sense for memory accesses
-                        IdKind::All => return Err(Error::PacketUnexpected),
-                        IdKind::WithID(tid) => self.current_mem_tid = tid,
+                        IdKind::Any | IdKind::All => {}
+                        IdKind::WithID(tid) => {
+                            if !Self::thread_is_alive(target, tid)? {
+                                return Err(Error::NonFatalError(1));
+                            }
+                            self.current_mem_tid = tid
+                        }
                     },
                     // technically, this variant is deprecated in favor of vCont...
                     Op::StepContinue => match cmd.thread.tid {
                         IdKind::Any => {} // reuse old tid
-                        IdKind::All => self.current_resume_tid = TidSelector::All,
-                        IdKind::WithID(tid) => self.current_resume_tid = TidSelector::WithID(tid),
+                        IdKind::All => match cmd.thread.pid {
+                            Some(IdKind::WithID(pid)) => {
+                                self.current_resume_tid = TidSelector::Process(pid)
+                            }
+                            _ => self.current_resume_tid = TidSelector::All,
+                        },
+                        IdKind::WithID(tid) => {
+                            if !Self::thread_is_alive(target, tid)? {
+                                return Err(Error::NonFatalError(1));
+                            }
+                            self.current_resume_tid = TidSelector::WithID(tid)
+                        }
                     },
                 }
                 HandlerStatus::NeedsOK
             }
+            ext::Base::qC(_) => {
+                res.write_str("QC")?;
+                let id = self.thread_id(target, self.current_mem_tid)?;
+                res.write_thread_id(id)?;
+                HandlerStatus::Handled
+            }
             ext::Base::qfThreadInfo(_) => {
-                res.write_str("m")?;
-
-                match target.base_ops() {
-                    BaseOps::SingleThread(_) => res.write_thread_id(ThreadId {
-                        pid: Some(IdKind::WithID(FAKE_PID)),
-                        tid: IdKind::WithID(SINGLE_THREAD_TID),
-                    })?,
-                    BaseOps::MultiThread(ops) => {
-                        let mut err: Result<_, Error<T::Error, C::Error>> = Ok(());
-                        let mut first = true;
-                        ops.list_active_threads(&mut |tid| {
-                            // TODO: replace this with a try block (once stabilized)
-                            let e = (|| {
-                                if !first {
-                                    res.write_str(",")?
-                                }
-                                first = false;
-                                res.write_thread_id(ThreadId {
-                                    pid: Some(IdKind::WithID(FAKE_PID)),
-                                    tid: IdKind::WithID(tid),
-                                })?;
-                                Ok(())
-                            })();
-
-                            if let Err(e) = e {
-                                err = Err(e)
-                            }
-                        })
-                        .map_err(Error::TargetError)?;
-                        err?;
-                    }
-                }
-
+                self.thread_list_offset = 0;
+                self.write_thread_list_page(res, target)?;
                 HandlerStatus::Handled
             }
             ext::Base::qsThreadInfo(_) => {
-                res.write_str("l")?;
+                self.write_thread_list_page(res, target)?;
                 HandlerStatus::Handled
             }
             ext::Base::T(cmd) => {
                 let alive = match cmd.thread.tid {
-                    IdKind::WithID(tid) => match target.base_ops() {
-                        BaseOps::SingleThread(_) => tid == SINGLE_THREAD_TID,
-                        BaseOps::MultiThread(ops) => {
-                            ops.is_thread_alive(tid).map_err(Error::TargetError)?
-                        }
-                    },
+                    IdKind::WithID(tid) => Self::thread_is_alive(target, tid)?,
                     // TODO: double-check if GDB ever sends other variants
                     // Even after ample testing, this arm has never been hit...
                     _ => return Err(Error::PacketUnexpected),
@@ -722,14 +1677,91 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 if alive {
                     HandlerStatus::NeedsOK
                 } else {
-                    // any error code will do
-                    return Err(Error::NonFatalError(1));
+                    // GDB only checks for an error reply, so any code will do
+                    return Err(Error::NonFatalError(0));
                 }
             }
         };
         Ok(handler_status)
     }
 
+    /// Report the next page of active threads in response to a `qfThreadInfo`
+    /// / `qsThreadInfo` packet, replying with `l` once all threads have been
+    /// reported.
+    ///
+    /// Pages are sized such that each reply fits within GDB's packet size. To
+    /// avoid holding on to the target between packets, each page re-lists the
+    /// target's active threads, skipping over those which were already
+    /// reported.
+    fn write_thread_list_page(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        let multiprocess = self.multiprocess;
+        let thread_id = |pid, tid| ThreadId {
+            pid: if multiprocess {
+                Some(IdKind::WithID(pid))
+            } else {
+                None
+            },
+            tid: IdKind::WithID(tid),
+        };
+
+        // worst-case length of a single (comma-separated) thread-id, i.e: "p<pid>.<tid>,"
+        let hex_len = 2 * core::mem::size_of::<usize>();
+        let id_len = if multiprocess {
+            1 + hex_len + 1 + hex_len + 1
+        } else {
+            hex_len + 1
+        };
+        // leave room for the "$", "m", and "#xx" framing bytes
+        let page_len = (self.packet_buffer_len.saturating_sub(5) / id_len).max(1);
+
+        let skip = self.thread_list_offset;
+        let mut written = 0;
+        match target.base_ops() {
+            BaseOps::SingleThread(_) => {
+                if skip == 0 {
+                    res.write_str("m")?;
+                    res.write_thread_id(thread_id(FAKE_PID, SINGLE_THREAD_TID))?;
+                    written = 1;
+                }
+            }
+            BaseOps::MultiThread(ops) => {
+                let mut err: Result<_, Error<T::Error, C::Error>> = Ok(());
+                let mut idx = 0;
+                ops.list_active_threads_with_pid(&mut |pid, tid| {
+                    let in_page = idx >= skip && written < page_len;
+                    idx += 1;
+                    if !in_page || err.is_err() {
+                        return;
+                    }
+
+                    // TODO: replace this with a try block (once stabilized)
+                    let e = (|| {
+                        res.write_str(if written == 0 { "m" } else { "," })?;
+                        res.write_thread_id(thread_id(pid, tid))?;
+                        Ok(())
+                    })();
+
+                    match e {
+                        Ok(()) => written += 1,
+                        Err(e) => err = Err(e),
+                    }
+                })
+                .map_err(Error::TargetError)?;
+                err?;
+            }
+        }
+
+        if written == 0 {
+            res.write_str("l")?;
+        }
+        self.thread_list_offset += written;
+        Ok(())
+    }
+
     fn handle_monitor_cmd<'a>(
         &mut self,
         res: &mut ResponseWriter<C>,
@@ -747,17 +1779,9 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
 
                 let mut err: Result<_, Error<T::Error, C::Error>> = Ok(());
                 let mut callback = |msg: &[u8]| {
-                    // TODO: replace this with a try block (once stabilized)
-                    let e = (|| {
-                        let mut res = ResponseWriter::new(res.as_conn());
-                        res.write_str("O")?;
-                        res.write_hex_buf(msg)?;
-                        res.flush()?;
-                        Ok(())
-                    })();
-
-                    if let Err(e) = e {
-                        err = Err(e)
+                    // skip any further output once a connection error occurred
+                    if err.is_ok() {
+                        err = self.write_console_output(res.as_conn(), msg);
                     }
                 };
 
@@ -825,134 +1849,1502 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
         Ok(handler_status)
     }
 
-    fn handle_extended_mode<'a>(
+    fn handle_non_stop_mode(
         &mut self,
         res: &mut ResponseWriter<C>,
         target: &mut T,
-        command: ext::ExtendedMode<'a>,
+        command: ext::NonStopMode,
     ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
-        let ops = match target.extended_mode() {
+        let ops = match target.non_stop_mode() {
             Some(ops) => ops,
             None => return Ok(HandlerStatus::Handled),
         };
 
         let handler_status = match command {
-            ext::ExtendedMode::ExclamationMark(_cmd) => {
-                ops.on_start().map_err(Error::TargetError)?;
+            ext::NonStopMode::QNonStop(cmd) => {
+                crate::__dead_code_marker!("QNonStop", "impl");
+
+                self.non_stop = cmd.value;
+                self.stop_notification_pending = false;
+                self.stopped_thread_offset = None;
+                self.deferred_stop = None;
                 HandlerStatus::NeedsOK
             }
-            ext::ExtendedMode::R(_cmd) => {
-                ops.restart().map_err(Error::TargetError)?;
-                HandlerStatus::Handled
-            }
-            ext::ExtendedMode::vAttach(cmd) => {
-                ops.attach(cmd.pid).handle_error()?;
+            ext::NonStopMode::vStopped(_cmd) => {
+                crate::__dead_code_marker!("vStopped", "impl");
 
-                #[cfg(feature = "alloc")]
-                self.attached_pids.insert(cmd.pid, true);
+                // continue reporting stopped threads, if GDB sent a `?`
+                if let Some(offset) = self.stopped_thread_offset {
+                    return self.write_stopped_thread(res, target, offset);
+                }
 
-                // TODO: sends OK when running in Non-Stop mode
-                HandlerStatus::Handled
+                // report the next queued stop event (if any), without waiting for any new
+                // stop events to occur.
+                match ops
+                    .poll_stop_event(&mut || true)
+                    .map_err(Error::TargetError)?
+                {
+                    Some(stop_reason) => match self.write_stop_reason(res, target, stop_reason)? {
+                        Some(dc) => HandlerStatus::Disconnect(dc),
+                        None => HandlerStatus::Handled,
+                    },
+                    None => {
+                        self.stop_notification_pending = false;
+                        HandlerStatus::NeedsOK
+                    }
+                }
             }
-            ext::ExtendedMode::vRun(cmd) => {
-                use crate::target::ext::extended_mode::Args;
+        };
 
-                let mut pid = ops
-                    .run(cmd.filename, Args::new(&mut cmd.args.into_iter()))
-                    .handle_error()?;
+        Ok(handler_status)
+    }
 
-                // on single-threaded systems, we'll ignore the provided PID and keep
-                // using the FAKE_PID.
-                if let BaseOps::SingleThread(_) = target.base_ops() {
-                    pid = FAKE_PID;
-                }
+    fn handle_reverse_exec(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::ReverseExec,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        if target.reverse_exec().is_none() {
+            return Ok(HandlerStatus::Handled);
+        }
 
-                let _ = pid; // squelch warning on no_std targets
-                #[cfg(feature = "alloc")]
-                self.attached_pids.insert(pid, false);
+        let disconnect = match command {
+            ext::ReverseExec::bc(_) => {
+                crate::__dead_code_marker!("bc", "impl");
 
-                // TODO: send a more descriptive stop packet?
-                res.write_str("S05")?;
-                HandlerStatus::Handled
+                self.run_target(res, target, |target, check_gdb_interrupt, _console| {
+                    let ops = target.reverse_exec().unwrap();
+                    ops.reverse_cont(check_gdb_interrupt)
+                        .map_err(Error::TargetError)
+                })?
             }
-            // --------- ASLR --------- //
-            ext::ExtendedMode::QDisableRandomization(cmd) if ops.configure_aslr().is_some() => {
-                let ops = ops.configure_aslr().unwrap();
-                ops.cfg_aslr(cmd.value).handle_error()?;
-                HandlerStatus::NeedsOK
+            ext::ReverseExec::bs(_) => {
+                crate::__dead_code_marker!("bs", "impl");
+
+                // step the thread selected via `Hc` (falling back to the current thread)
+                let tid = match self.current_resume_tid {
+                    TidSelector::WithID(tid) => tid,
+                    _ => self.current_mem_tid,
+                };
+                self.run_target(res, target, |target, check_gdb_interrupt, _console| {
+                    let ops = target.reverse_exec().unwrap();
+                    ops.reverse_step(tid, check_gdb_interrupt)
+                        .map_err(Error::TargetError)
+                })?
             }
-            // --------- Environment --------- //
-            ext::ExtendedMode::QEnvironmentHexEncoded(cmd) if ops.configure_env().is_some() => {
-                let ops = ops.configure_env().unwrap();
-                ops.set_env(cmd.key, cmd.value).handle_error()?;
-                HandlerStatus::NeedsOK
+        };
+
+        Ok(match disconnect {
+            None => HandlerStatus::Handled,
+            Some(dc) => HandlerStatus::Disconnect(dc),
+        })
+    }
+
+    fn handle_host_io<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::HostIo<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use crate::target::ext::host_io::HostIoError;
+
+        let ops = match target.host_io() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        macro_rules! handle_hostio_result {
+            ( if let Ok($val:pat) = $ret:expr => $callback:block ) => {
+                match $ret {
+                    Ok($val) => $callback,
+                    Err(HostIoError::Errno(errno)) => {
+                        res.write_str("F-1,")?;
+                        res.write_num(errno as u32)?;
+                    }
+                    Err(HostIoError::Fatal(e)) => return Err(Error::TargetError(e)),
+                }
+            };
+        }
+
+        match command {
+            ext::HostIo::vFileOpen(cmd) => {
+                crate::__dead_code_marker!("vFile:open", "impl");
+
+                handle_hostio_result! {
+                    if let Ok(fd) = ops.open(cmd.filename, cmd.flags, cmd.mode) => {
+                        res.write_str("F")?;
+                        res.write_num(fd)?;
+                    }
+                }
             }
-            ext::ExtendedMode::QEnvironmentUnset(cmd) if ops.configure_env().is_some() => {
-                let ops = ops.configure_env().unwrap();
-                ops.remove_env(cmd.key).handle_error()?;
-                HandlerStatus::NeedsOK
+            ext::HostIo::vFileClose(cmd) => {
+                crate::__dead_code_marker!("vFile:close", "impl");
+
+                handle_hostio_result! {
+                    if let Ok(()) = ops.close(cmd.fd) => {
+                        res.write_str("F0")?;
+                    }
+                }
             }
-            ext::ExtendedMode::QEnvironmentReset(_cmd) if ops.configure_env().is_some() => {
-                let ops = ops.configure_env().unwrap();
-                ops.reset_env().handle_error()?;
-                HandlerStatus::NeedsOK
+            ext::HostIo::vFilePread(cmd) => {
+                crate::__dead_code_marker!("vFile:pread", "impl");
+
+                let count = cmd.count.min(cmd.buf.len());
+                let buf = &mut cmd.buf[..count];
+                handle_hostio_result! {
+                    if let Ok(len) = ops.pread(cmd.fd, cmd.offset, buf) => {
+                        let data = buf.get(..len).ok_or(Error::PacketBufferOverlow)?;
+                        res.write_str("F")?;
+                        res.write_num(len)?;
+                        res.write_str(";")?;
+                        res.write_binary(data)?;
+                    }
+                }
             }
-            // --------- Working Dir --------- //
-            ext::ExtendedMode::QSetWorkingDir(cmd) if ops.configure_working_dir().is_some() => {
-                let ops = ops.configure_working_dir().unwrap();
-                ops.cfg_working_dir(cmd.dir).handle_error()?;
-                HandlerStatus::NeedsOK
+            ext::HostIo::vFileFstat(cmd) => {
+                crate::__dead_code_marker!("vFile:fstat", "impl");
+
+                handle_hostio_result! {
+                    if let Ok(stat) = ops.fstat(cmd.fd) => {
+                        let stat = stat.to_be_bytes();
+                        res.write_str("F")?;
+                        res.write_num(stat.len())?;
+                        res.write_str(";")?;
+                        res.write_binary(&stat)?;
+                    }
+                }
             }
-            // --------- Startup Shell --------- //
-            ext::ExtendedMode::QStartupWithShell(cmd)
-                if ops.configure_startup_shell().is_some() =>
-            {
-                let ops = ops.configure_startup_shell().unwrap();
-                ops.cfg_startup_with_shell(cmd.value).handle_error()?;
-                HandlerStatus::NeedsOK
+            ext::HostIo::vFileSetfs(cmd) => {
+                crate::__dead_code_marker!("vFile:setfs", "impl");
+
+                handle_hostio_result! {
+                    if let Ok(()) = ops.setfs(cmd.fs) => {
+                        res.write_str("F0")?;
+                    }
+                }
             }
-            _ => HandlerStatus::Handled,
         };
 
-        Ok(handler_status)
+        Ok(HandlerStatus::Handled)
     }
 
-    fn do_vcont(
+    fn handle_auxv<'a>(
         &mut self,
         res: &mut ResponseWriter<C>,
         target: &mut T,
-        actions: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
-    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
-        let mut err = Ok(());
+        command: ext::Auxv<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.auxv() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
 
-        let mut check_gdb_interrupt = || match res.as_conn().peek() {
-            Ok(Some(0x03)) => true, // 0x03 is the interrupt byte
-            Ok(Some(_)) => false,   // it's nothing that can't wait...
-            Ok(None) => false,
-            Err(e) => {
-                err = Err(Error::ConnectionRead(e));
-                true // break ASAP if a connection error occurred
+        let handler_status = match command {
+            ext::Auxv::qXferAuxvRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:auxv:read", "impl");
+
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.get_auxv(offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
             }
         };
 
-        let stop_reason = match target.base_ops() {
+        Ok(handler_status)
+    }
+
+    fn handle_siginfo<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Siginfo<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.siginfo() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Siginfo::qXferSiginfoRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:siginfo:read", "impl");
+
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.read_siginfo(self.current_mem_tid, offset, len, buf)
+                        .handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+            ext::Siginfo::qXferSiginfoWrite(cmd) => {
+                crate::__dead_code_marker!("qXfer:siginfo:write", "impl");
+
+                let ret = ops
+                    .write_siginfo(self.current_mem_tid, cmd.offset, cmd.data)
+                    .handle_error()?;
+                res.write_num(ret)?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_exec_file<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::ExecFile<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.exec_file() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::ExecFile::qXferExecFileRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:exec-file:read", "impl");
+
+                let pid = cmd.pid;
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.get_exec_file(pid, offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_libraries_svr4<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::LibrariesSvr4<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.libraries_svr4() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::LibrariesSvr4::qXferLibrariesSvr4Read(cmd) => {
+                crate::__dead_code_marker!("qXfer:libraries-svr4:read", "impl");
+
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.get_libraries_svr4(offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_thread_list_xml<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::ThreadListXml<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.thread_list_xml() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::ThreadListXml::qXferThreadsRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:threads:read", "impl");
+
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.get_thread_list_xml(offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_memory_map<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::MemoryMap<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.memory_map() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::MemoryMap::qXferMemoryMapRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:memory-map:read", "impl");
+
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.memory_map_xml(offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_flash<'a>(
+        &mut self,
+        _res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Flash<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.flash() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Flash::vFlashErase(cmd) => {
+                crate::__dead_code_marker!("vFlashErase", "impl");
+
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+                let length = <T::Arch as Arch>::Usize::from_be_bytes(cmd.length)
+                    .ok_or(Error::TargetMismatch)?;
+
+                ops.erase(addr, length).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Flash::vFlashWrite(cmd) => {
+                crate::__dead_code_marker!("vFlashWrite", "impl");
+
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+
+                ops.write(addr, cmd.val).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Flash::vFlashDone(_) => {
+                crate::__dead_code_marker!("vFlashDone", "impl");
+
+                // all pending writes must be committed before GDB is told that
+                // flash programming has finished.
+                ops.done().handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_memory_search<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::MemorySearch<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.memory_search() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::MemorySearch::qSearchMemory(cmd) => {
+                crate::__dead_code_marker!("qSearch:memory", "impl");
+
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+                let len = <T::Arch as Arch>::Usize::from_be_bytes(cmd.len)
+                    .ok_or(Error::TargetMismatch)?;
+
+                match ops.search(addr, len, cmd.pattern).handle_error()? {
+                    Some(addr) => {
+                        res.write_str("1,")?;
+                        res.write_num(addr)?;
+                    }
+                    None => res.write_str("0")?,
+                }
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_memory_tags<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::MemoryTags<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use num_traits::NumCast;
+
+        let ops = match target.memory_tags() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::MemoryTags::qMemTags(cmd) => {
+                crate::__dead_code_marker!("qMemTags", "impl");
+
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+                let len = NumCast::from(cmd.len).ok_or(Error::TargetMismatch)?;
+
+                let ret = ops
+                    .read_tags(addr, len, cmd.tag_type, cmd.buf)
+                    .handle_error()?;
+                let tags = cmd.buf.get(..ret).ok_or(Error::PacketBufferOverlow)?;
+
+                res.write_str("m")?;
+                res.write_hex_buf(tags)?;
+                HandlerStatus::Handled
+            }
+            ext::MemoryTags::QMemTags(cmd) => {
+                crate::__dead_code_marker!("QMemTags", "impl");
+
+                let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                    .ok_or(Error::TargetMismatch)?;
+                let len = <T::Arch as Arch>::Usize::from_be_bytes(cmd.len)
+                    .ok_or(Error::TargetMismatch)?;
+
+                ops.write_tags(addr, len, cmd.tag_type, cmd.tags)
+                    .handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_thread_extra_info<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::ThreadExtraInfo<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.thread_extra_info() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::ThreadExtraInfo::qThreadExtraInfo(cmd) => {
+                crate::__dead_code_marker!("qThreadExtraInfo", "impl");
+
+                let tid = match cmd.id.tid {
+                    IdKind::WithID(tid) => tid,
+                    _ => return Err(Error::PacketUnexpected),
+                };
+
+                // the description is sent hex-encoded, so limit it to half the
+                // packet buffer to keep the response within GDB's packet size.
+                let len = cmd.buf.len() / 2;
+                let buf = &mut cmd.buf[..len];
+                let ret = ops.thread_info(tid, buf).handle_error()?;
+                let data = buf.get(..ret).ok_or(Error::PacketBufferOverlow)?;
+
+                // drop any partial UTF-8 character left over from truncation
+                let data = match core::str::from_utf8(data) {
+                    Err(e) if e.error_len().is_none() => &data[..e.valid_up_to()],
+                    _ => data,
+                };
+
+                res.write_hex_buf(data)?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_signals<'a>(
+        &mut self,
+        _res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Signals<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use crate::target::ext::signals::SignalList;
+
+        let ops = match target.signals() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Signals::QPassSignals(cmd) => {
+                crate::__dead_code_marker!("QPassSignals", "impl");
+
+                ops.pass_signals(SignalList::new(cmd.signals))
+                    .handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Signals::QProgramSignals(cmd) => {
+                crate::__dead_code_marker!("QProgramSignals", "impl");
+
+                ops.program_signals(SignalList::new(cmd.signals))
+                    .handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_tls<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Tls<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.tls() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Tls::qGetTLSAddr(cmd) => {
+                crate::__dead_code_marker!("qGetTLSAddr", "impl");
+
+                let tid = match cmd.id.tid {
+                    IdKind::WithID(tid) => tid,
+                    _ => return Err(Error::PacketUnexpected),
+                };
+                let offset = <T::Arch as Arch>::Usize::from_be_bytes(cmd.offset)
+                    .ok_or(Error::TargetMismatch)?;
+                let lm =
+                    <T::Arch as Arch>::Usize::from_be_bytes(cmd.lm).ok_or(Error::TargetMismatch)?;
+
+                let addr = ops.get_tls_addr(tid, offset, lm).handle_error()?;
+                res.write_num(addr)?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_osdata<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Osdata<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.osdata() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Osdata::qXferOsdataRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:osdata:read", "impl");
+
+                let annex = cmd.annex;
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.read_osdata(annex, offset, len, buf).handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_custom_xfer<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::CustomXfer<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use crate::protocol::commands::_qXfer_custom::XferOp;
+
+        let ops = match target.custom_xfer() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::CustomXfer::qXferCustom(cmd) => {
+                crate::__dead_code_marker!("qXfer", "impl");
+
+                let object = ops
+                    .custom_xfer_objects()
+                    .iter()
+                    .find(|object| object.name.as_bytes() == cmd.object)
+                    .copied();
+
+                let annex = cmd.annex;
+                match (object, cmd.op) {
+                    (Some(object), XferOp::Read { offset, len, buf }) if object.read => {
+                        Self::write_xfer_chunk(res, offset, len, buf, |offset, len, buf| {
+                            ops.read_object(object.name, annex, offset, len, buf)
+                                .handle_error()
+                        })?;
+                    }
+                    (Some(object), XferOp::Write { offset, data }) if object.write => {
+                        let ret = ops
+                            .write_object(object.name, annex, offset, data)
+                            .handle_error()?;
+                        res.write_num(ret)?;
+                    }
+                    // unknown objects (and unsupported operations) are reported as
+                    // unsupported, via an empty response
+                    _ => {}
+                }
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_btrace<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Btrace<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.btrace() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Btrace::Qbtrace(cmd) => {
+                crate::__dead_code_marker!("Qbtrace", "impl");
+
+                match cmd.format {
+                    Some(format) if !ops.supports_format(format) => {
+                        return Err(Error::PacketUnexpected)
+                    }
+                    Some(format) => ops.enable(self.current_mem_tid, format),
+                    None => ops.disable(self.current_mem_tid),
+                }
+                .handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Btrace::QbtraceConf(cmd) => {
+                crate::__dead_code_marker!("Qbtrace-conf", "impl");
+
+                if !ops.supports_format(cmd.format) {
+                    return Err(Error::PacketUnexpected);
+                }
+                ops.set_buffer_size(cmd.format, cmd.size).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Btrace::qXferBtraceRead(cmd) => {
+                crate::__dead_code_marker!("qXfer:btrace:read", "impl");
+
+                let kind = cmd.kind;
+                Self::write_xfer_chunk(res, cmd.offset, cmd.len, cmd.buf, |offset, len, buf| {
+                    ops.read_btrace(self.current_mem_tid, kind, offset, len, buf)
+                        .handle_error()
+                })?;
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_host_syscall(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::HostSyscall,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use crate::target::ext::host_syscall::HostSyscallReply;
+
+        let ops = match target.host_syscall() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::HostSyscall::F(cmd) => {
+                crate::__dead_code_marker!("F", "impl");
+
+                // GDB only sends `F` replies in response to `F` requests
+                if !self.host_syscall_pending {
+                    return Err(Error::PacketUnexpected);
+                }
+                self.host_syscall_pending = false;
+
+                ops.host_syscall_reply(HostSyscallReply {
+                    retval: cmd.retval,
+                    errno: cmd.errno,
+                    ctrl_c: cmd.ctrl_c,
+                })
+                .map_err(Error::TargetError)?;
+
+                let disconnect = if cmd.ctrl_c {
+                    // the target must not be resumed, and instead reports a SIGINT
+                    let sigint = Self::native_signal(target, Signal::SIGINT);
+                    self.finish_vcont(ThreadStopReason::Signal(sigint), res, target)?
+                } else {
+                    self.run_target(res, target, |target, check_gdb_interrupt, _console| {
+                        let ops = target.host_syscall().ok_or(Error::PacketUnexpected)?;
+                        ops.resume_after_host_syscall(check_gdb_interrupt)
+                            .map_err(Error::TargetError)
+                    })?
+                };
+
+                match disconnect {
+                    Some(dc) => HandlerStatus::Disconnect(dc),
+                    None => HandlerStatus::Handled,
+                }
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_allow(
+        &mut self,
+        _res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Allow,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.allow() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Allow::QAllow(cmd) => {
+                crate::__dead_code_marker!("QAllow", "impl");
+
+                ops.set_allowed(cmd.flags).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_tracepoints<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::Tracepoints<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.tracepoints() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::Tracepoints::QTinit(_) => {
+                crate::__dead_code_marker!("QTinit", "impl");
+
+                ops.tracepoints_init().handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Tracepoints::QTDP(cmd) => {
+                use crate::protocol::commands::_QTDP::QTDP;
+                use crate::target::ext::tracepoints::NewTracepoint;
+
+                crate::__dead_code_marker!("QTDP", "impl");
+
+                match cmd {
+                    QTDP::Create(cmd) => {
+                        let addr = <T::Arch as Arch>::Usize::from_be_bytes(cmd.addr)
+                            .ok_or(Error::TargetMismatch)?;
+
+                        ops.define_tracepoint(NewTracepoint {
+                            number: cmd.number,
+                            addr,
+                            enabled: cmd.enabled,
+                            step_count: cmd.step,
+                            pass_count: cmd.pass,
+                        })
+                        .handle_error()?;
+                        HandlerStatus::NeedsOK
+                    }
+                    // TODO: support tracepoint actions
+                    QTDP::Actions => HandlerStatus::Handled,
+                }
+            }
+            ext::Tracepoints::QTDV(cmd) => {
+                crate::__dead_code_marker!("QTDV", "impl");
+
+                ops.define_trace_state_variable(
+                    cmd.number,
+                    // GDB sends the initial value as a two's complement hex number
+                    cmd.value as i64,
+                    cmd.builtin,
+                    cmd.name,
+                )
+                .handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Tracepoints::QTStart(_) => {
+                crate::__dead_code_marker!("QTStart", "impl");
+
+                ops.start_tracing().handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Tracepoints::QTStop(_) => {
+                crate::__dead_code_marker!("QTStop", "impl");
+
+                ops.stop_tracing().handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::Tracepoints::qTStatus(_) => {
+                crate::__dead_code_marker!("qTStatus", "impl");
+
+                let status = ops.tracepoint_status().handle_error()?;
+                res.write_str(if status.running { "T1" } else { "T0" })?;
+                if let Some(frames) = status.frames {
+                    res.write_str(";tframes:")?;
+                    res.write_num(frames)?;
+                }
+                if let Some(created) = status.created {
+                    res.write_str(";tcreated:")?;
+                    res.write_num(created)?;
+                }
+                HandlerStatus::Handled
+            }
+        };
+
+        Ok(handler_status)
+    }
+
+    fn handle_extended_mode<'a>(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        command: ext::ExtendedMode<'a>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = match target.extended_mode() {
+            Some(ops) => ops,
+            None => return Ok(HandlerStatus::Handled),
+        };
+
+        let handler_status = match command {
+            ext::ExtendedMode::ExclamationMark(_cmd) => {
+                ops.on_start().map_err(Error::TargetError)?;
+                HandlerStatus::NeedsOK
+            }
+            ext::ExtendedMode::R(_cmd) => {
+                ops.restart().map_err(Error::TargetError)?;
+                // unlike most packets, `R` has no reply
+                HandlerStatus::NoResponse
+            }
+            ext::ExtendedMode::vAttach(cmd) => {
+                // EPERM, mirroring `ptrace(PTRACE_ATTACH)` on an already-traced process
+                #[cfg(feature = "alloc")]
+                if self.attached_pids.contains_key(&cmd.pid) {
+                    return Err(Error::NonFatalError(1));
+                }
+
+                let stop_reason = ops.attach(cmd.pid).handle_error()?;
+
+                #[cfg(feature = "alloc")]
+                self.attached_pids.insert(cmd.pid, true);
+
+                // switch over to the attached process's first thread
+                if let BaseOps::MultiThread(ops) = target.base_ops() {
+                    let mut first_tid = None;
+                    ops.list_active_threads_with_pid(&mut |pid, tid| {
+                        if pid == cmd.pid && first_tid.is_none() {
+                            first_tid = Some(tid);
+                        }
+                    })
+                    .map_err(Error::TargetError)?;
+
+                    if let Some(tid) = first_tid {
+                        self.current_mem_tid = tid;
+                        self.current_resume_tid = TidSelector::WithID(tid);
+                    }
+                }
+
+                if self.non_stop {
+                    // in non-stop mode, vAttach is acknowledged with "OK", and the attached
+                    // process's stop is reported via a `%Stop` notification.
+                    self.deferred_stop = Some(match stop_reason {
+                        ThreadStopReason::Signal(signal) => ThreadStopReason::SignalWithThread {
+                            tid: self.current_mem_tid,
+                            signal,
+                        },
+                        stop_reason => stop_reason,
+                    });
+                    return Ok(HandlerStatus::NeedsOK);
+                }
+
+                match self.finish_vcont(stop_reason, res, target)? {
+                    Some(dc) => HandlerStatus::Disconnect(dc),
+                    None => HandlerStatus::Handled,
+                }
+            }
+            ext::ExtendedMode::vRun(cmd) => {
+                use crate::target::ext::extended_mode::Args;
+
+                #[cfg(feature = "alloc")]
+                let filename = cmd.filename.or(self.last_run_filename.as_deref());
+                #[cfg(not(feature = "alloc"))]
+                let filename = cmd.filename;
+
+                let mut pid = ops
+                    .run(filename, Args::new(&mut cmd.args.into_iter()))
+                    .handle_error()?;
+
+                #[cfg(feature = "alloc")]
+                if let Some(filename) = cmd.filename {
+                    self.last_run_filename = Some(filename.to_vec());
+                }
+
+                // on single-threaded systems, we'll ignore the provided PID and keep
+                // using the FAKE_PID.
+                if let BaseOps::SingleThread(_) = target.base_ops() {
+                    pid = FAKE_PID;
+                }
+
+                let _ = pid; // squelch warning on no_std targets
+                #[cfg(feature = "alloc")]
+                self.attached_pids.insert(pid, false);
+
+                // the newly spawned process starts off stopped
+                let sigtrap = Self::native_signal(target, Signal::SIGTRAP);
+                match self.write_stop_reason(res, target, ThreadStopReason::Signal(sigtrap))? {
+                    Some(dc) => HandlerStatus::Disconnect(dc),
+                    None => HandlerStatus::Handled,
+                }
+            }
+            // --------- ASLR --------- //
+            ext::ExtendedMode::QDisableRandomization(cmd) if ops.configure_aslr().is_some() => {
+                let ops = ops.configure_aslr().unwrap();
+                // GDB asks for randomization to be _disabled_
+                ops.cfg_aslr(!cmd.value).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            // --------- Environment --------- //
+            ext::ExtendedMode::QEnvironmentHexEncoded(cmd) if ops.configure_env().is_some() => {
+                let ops = ops.configure_env().unwrap();
+                ops.set_env(cmd.key, cmd.value).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::ExtendedMode::QEnvironmentUnset(cmd) if ops.configure_env().is_some() => {
+                let ops = ops.configure_env().unwrap();
+                ops.remove_env(cmd.key).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            ext::ExtendedMode::QEnvironmentReset(_cmd) if ops.configure_env().is_some() => {
+                let ops = ops.configure_env().unwrap();
+                ops.reset_env().handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            // --------- Working Dir --------- //
+            ext::ExtendedMode::QSetWorkingDir(cmd) if ops.configure_working_dir().is_some() => {
+                let ops = ops.configure_working_dir().unwrap();
+                ops.cfg_working_dir(cmd.dir).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            // --------- Startup Shell --------- //
+            ext::ExtendedMode::QStartupWithShell(cmd)
+                if ops.configure_startup_shell().is_some() =>
+            {
+                let ops = ops.configure_startup_shell().unwrap();
+                ops.cfg_startup_with_shell(cmd.value).handle_error()?;
+                HandlerStatus::NeedsOK
+            }
+            _ => HandlerStatus::Handled,
+        };
+
+        Ok(handler_status)
+    }
+
+    /// Resume the current thread using a legacy resume packet (e.g: 'c', 's').
+    fn do_resume(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        action: ResumeAction,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let caps = resume_capabilities(target);
+        // stepping is never advertised in "vCont?" if it's unsupported...
+        if let ResumeAction::Step(_) = action {
+            if !caps.step {
+                return Err(Error::PacketUnexpected);
+            }
+        }
+        // ...but resuming with a signal always is
+        if !caps.supports(action) {
+            return Err(Error::NonFatalError(22));
+        }
+
+        let mut actions = core::iter::once((self.current_resume_tid, action));
+        let status = match self.do_vcont(res, target, &mut actions)? {
+            None => HandlerStatus::Handled,
+            Some(dc) => HandlerStatus::Disconnect(dc),
+        };
+        Ok(status)
+    }
+
+    fn do_vcont(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        actions: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        let thread_events = self.thread_events;
+        self.run_target(res, target, |target, check_gdb_interrupt, console| {
+            let steps_by_instruction = target.instruction_step().is_some();
+            let stop_reason = match target.base_ops() {
+                BaseOps::SingleThread(ops) => {
+                    // use the first action which applies to the only thread (skipping any
+                    // actions targeting non-existent threads)
+                    let action = loop {
+                        match actions.next() {
+                            Some((TidSelector::WithID(tid), _)) if tid != SINGLE_THREAD_TID => {}
+                            Some((_, action)) => break action,
+                            None => return Err(Error::PacketUnexpected),
+                        }
+                    };
+                    if let ResumeAction::Step(signal) = action {
+                        if !ops.resume_capabilities().step {
+                            return Self::step_via_breakpoints(
+                                target,
+                                signal,
+                                check_gdb_interrupt,
+                                console,
+                            );
+                        }
+                        if steps_by_instruction {
+                            return Self::step_instruction(
+                                target,
+                                signal,
+                                check_gdb_interrupt,
+                                console,
+                            );
+                        }
+                    }
+
+                    ops.resume_with_console_output(action, check_gdb_interrupt, console)
+                        .map_err(Error::TargetError)?
+                        .into()
+                }
+                BaseOps::MultiThread(_) => Self::resume_multithread(
+                    target,
+                    thread_events,
+                    actions,
+                    check_gdb_interrupt,
+                    console,
+                )?,
+            };
+            Ok(stop_reason)
+        })
+    }
+
+    /// Resume a multi threaded target according to `actions`.
+    ///
+    /// Unless GDB enabled thread events, it isn't told about threads exiting,
+    /// so the target is resumed again (with the same actions) whenever a thread
+    /// exits while the process still has other live threads. Replaying the
+    /// actions requires the `alloc` feature, so without it, such exits are
+    /// reported as a plain `SIGTRAP` instead.
+    #[allow(clippy::type_complexity)]
+    fn resume_multithread(
+        target: &mut T,
+        thread_events: bool,
+        actions: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        mut console: ConsoleOutput<'_>,
+    ) -> Result<ThreadStopReason<<T::Arch as Arch>::Usize>, Error<T::Error, C::Error>> {
+        #[cfg(feature = "alloc")]
+        let actions = actions.collect::<Vec<_>>();
+
+        loop {
+            #[cfg(feature = "alloc")]
+            let actions = &mut actions.iter().copied();
+
+            let ops = match target.base_ops() {
+                BaseOps::MultiThread(ops) => ops,
+                BaseOps::SingleThread(_) => return Err(Error::PacketUnexpected),
+            };
+            let stop_reason = ops
+                .resume_with_console_output(
+                    Actions::new(&mut *actions),
+                    check_gdb_interrupt,
+                    console.reborrow(),
+                )
+                .map_err(Error::TargetError)?;
+
+            let silent_exit = cfg!(feature = "alloc")
+                && !thread_events
+                && matches!(stop_reason, ThreadStopReason::ThreadExited { .. });
+            if !silent_exit {
+                return Ok(stop_reason);
+            }
+
+            // the exit is reported if it was the process' last thread
+            let mut any_alive = false;
+            ops.list_active_threads(&mut |_| any_alive = true)
+                .map_err(Error::TargetError)?;
+            if !any_alive {
+                return Ok(stop_reason);
+            }
+        }
+    }
+
+    /// Check whether `tid` refers to one of the target's live threads.
+    fn thread_is_alive(target: &mut T, tid: Tid) -> Result<bool, Error<T::Error, C::Error>> {
+        match target.base_ops() {
+            BaseOps::SingleThread(_) => Ok(tid == SINGLE_THREAD_TID),
+            BaseOps::MultiThread(ops) => ops.is_thread_alive(tid).map_err(Error::TargetError),
+        }
+    }
+
+    /// Drop any of a multi threaded target's `vCont` actions which target
+    /// threads that are no longer alive.
+    ///
+    /// Single threaded targets' actions are returned as-is. Returns a
+    /// non-fatal error if _every_ action targeted a dead thread.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    fn live_vcont_actions(
+        target: &mut T,
+        actions: Vec<(TidSelector, ResumeAction)>,
+    ) -> Result<Vec<(TidSelector, ResumeAction)>, Error<T::Error, C::Error>> {
+        let ops = match target.base_ops() {
+            BaseOps::SingleThread(_) => return Ok(actions),
+            BaseOps::MultiThread(ops) => ops,
+        };
+
+        let mut live_actions = Vec::new();
+        let mut any_stale = false;
+        for (tid, action) in actions {
+            if let TidSelector::WithID(id) = tid {
+                if !ops.is_thread_alive(id).map_err(Error::TargetError)? {
+                    any_stale = true;
+                    continue;
+                }
+            }
+            live_actions.push((tid, action));
+        }
+
+        if any_stale && live_actions.is_empty() {
+            return Err(Error::NonFatalError(3)); // ESRCH
+        }
+
+        Ok(live_actions)
+    }
+
+    fn do_range_step(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        tid: Tid,
+        start: <T::Arch as Arch>::Usize,
+        end: <T::Arch as Arch>::Usize,
+        other_actions: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        self.run_target(res, target, |target, check_gdb_interrupt, _console| {
+            let ops = target.range_step().ok_or(Error::PacketUnexpected)?;
+            ops.resume_range_step(
+                tid,
+                start,
+                end,
+                Actions::new(other_actions),
+                check_gdb_interrupt,
+            )
+            .map_err(Error::TargetError)
+        })
+    }
+
+    /// Emulate a single-step of a single threaded target, by continuing it
+    /// until it hits a temporary breakpoint at one of its possible next PCs.
+    #[allow(clippy::type_complexity)]
+    fn step_via_breakpoints(
+        target: &mut T,
+        signal: Option<Signal>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        console: ConsoleOutput<'_>,
+    ) -> Result<ThreadStopReason<<T::Arch as Arch>::Usize>, Error<T::Error, C::Error>> {
+        use crate::target::ext::base::single_step_via_breakpoints::MAX_NEXT_PCS;
+
+        let ops = target
+            .single_step_via_breakpoints()
+            .ok_or(Error::PacketUnexpected)?;
+
+        let mut next_pcs = [None; MAX_NEXT_PCS];
+        let mut overflow = false;
+        ops.next_pcs(&mut |pc| {
+            if next_pcs.contains(&Some(pc)) {
+                return;
+            }
+            match next_pcs.iter_mut().find(|slot| slot.is_none()) {
+                Some(slot) => *slot = Some(pc),
+                None => overflow = true,
+            }
+        })
+        .map_err(Error::TargetError)?;
+        if overflow {
+            return Err(Error::TargetMismatch);
+        }
+
+        let mut installed = 0;
+        let mut res = Ok(());
+        for pc in next_pcs.iter().flatten() {
+            res = ops.add_step_breakpoint(*pc).map_err(Error::TargetError);
+            if res.is_err() {
+                break;
+            }
+            installed += 1;
+        }
+
+        let stop_reason = res.and_then(|()| match target.base_ops() {
             BaseOps::SingleThread(ops) => ops
-                .resume(
-                    // TODO?: add a more descriptive error if vcont has multiple threads in
-                    // single-threaded mode?
-                    actions.next().ok_or(Error::PacketUnexpected)?.1,
-                    &mut check_gdb_interrupt,
+                .resume_with_console_output(
+                    ResumeAction::Continue(signal),
+                    check_gdb_interrupt,
+                    console,
                 )
-                .map_err(Error::TargetError)?
-                .into(),
-            BaseOps::MultiThread(ops) => ops
-                .resume(Actions::new(actions), &mut check_gdb_interrupt)
-                .map_err(Error::TargetError)?,
+                .map_err(Error::TargetError),
+            BaseOps::MultiThread(_) => Err(Error::PacketUnexpected),
+        });
+
+        // remove every breakpoint which was installed, even if installing the
+        // rest of them (or resuming the target) failed
+        let ops = target
+            .single_step_via_breakpoints()
+            .ok_or(Error::PacketUnexpected)?;
+        for pc in next_pcs.iter().flatten().take(installed) {
+            ops.remove_step_breakpoint(*pc)
+                .map_err(Error::TargetError)?;
+        }
+        let stop_reason = stop_reason?;
+
+        // hitting one of the temporary breakpoints means the step completed
+        let stop_reason = match stop_reason {
+            StopReason::SwBreak | StopReason::HwBreak => StopReason::DoneStep,
+            other => other,
+        };
+        Ok(stop_reason.into())
+    }
+
+    /// Single-step a single threaded target until it lands on an instruction
+    /// boundary, reporting any other stop reason as soon as it occurs.
+    #[allow(clippy::type_complexity)]
+    fn step_instruction(
+        target: &mut T,
+        mut signal: Option<Signal>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        mut console: ConsoleOutput<'_>,
+    ) -> Result<ThreadStopReason<<T::Arch as Arch>::Usize>, Error<T::Error, C::Error>> {
+        loop {
+            let stop_reason = match target.base_ops() {
+                BaseOps::SingleThread(ops) => ops.resume_with_console_output(
+                    ResumeAction::Step(signal.take()),
+                    check_gdb_interrupt,
+                    console.reborrow(),
+                ),
+                BaseOps::MultiThread(_) => return Err(Error::PacketUnexpected),
+            }
+            .map_err(Error::TargetError)?;
+
+            if !matches!(stop_reason, StopReason::DoneStep) {
+                return Ok(stop_reason.into());
+            }
+
+            let ops = target.instruction_step().ok_or(Error::PacketUnexpected)?;
+            if ops.at_instruction_boundary().map_err(Error::TargetError)? {
+                return Ok(ThreadStopReason::DoneStep);
+            }
+            if check_gdb_interrupt() {
+                return Ok(ThreadStopReason::GdbInterrupt);
+            }
+        }
+    }
+
+    /// Run the target (e.g: resuming it, or executing it in reverse) via
+    /// `run`, reporting any console output / the final stop reason to GDB.
+    fn run_target(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        run: impl FnOnce(
+            &mut T,
+            &mut dyn FnMut() -> bool,
+            ConsoleOutput<'_>,
+        ) -> Result<
+            ThreadStopReason<<T::Arch as Arch>::Usize>,
+            Error<T::Error, C::Error>,
+        >,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        use core::cell::RefCell;
+
+        // both the interrupt check and the console output callback need access to the
+        // connection (and a place to stash any errors) while the target is running.
+        let conn = RefCell::new(res.as_conn());
+        let err: RefCell<Result<_, Error<T::Error, C::Error>>> = RefCell::new(Ok(()));
+
+        let mut check_gdb_interrupt = || {
+            let mut conn = conn.borrow_mut();
+            match conn.peek() {
+                // 0x03 is the interrupt byte, which must be consumed here (otherwise, it
+                // would be handled as a separate interrupt packet once the target stops,
+                // resulting in a spurious stop reply)
+                Ok(Some(0x03)) => match conn.read() {
+                    Ok(_) => true,
+                    Err(e) => {
+                        *err.borrow_mut() = Err(Error::ConnectionRead(e));
+                        true // break ASAP if a connection error occurred
+                    }
+                },
+                Ok(Some(_)) => false, // it's nothing that can't wait...
+                Ok(None) => false,
+                Err(e) => {
+                    *err.borrow_mut() = Err(Error::ConnectionRead(e));
+                    true // break ASAP if a connection error occurred
+                }
+            }
+        };
+
+        // console output is sent as a series of complete `O` packets, which are always
+        // flushed before the stop reply is written
+        let mut console_callback = |msg: &[u8]| {
+            let mut err = err.borrow_mut();
+            // skip any further output once a connection error occurred
+            if err.is_ok() {
+                *err = self.write_console_output(&mut **conn.borrow_mut(), msg);
+            }
+        };
+
+        let stop_reason = run(
+            target,
+            &mut check_gdb_interrupt,
+            ConsoleOutput::new(&mut console_callback),
+        )?;
+
+        err.into_inner()?;
+        self.finish_vcont(stop_reason, res, target)
+    }
+
+    fn do_vcont_non_stop(
+        &mut self,
+        target: &mut T,
+        actions: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        let ops = target.non_stop_mode().ok_or(Error::PacketUnexpected)?;
+        ops.resume_non_stop(Actions::new(actions))
+            .map_err(Error::TargetError)
+    }
+
+    fn do_vcont_stop<'a>(
+        &mut self,
+        target: &mut T,
+        actions: impl Iterator<Item = Option<crate::protocol::commands::_vCont::VContAction<'a>>>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        use crate::protocol::commands::_vCont::VContKind;
+
+        let ops = target.non_stop_mode().ok_or(Error::PacketUnexpected)?;
+        for action in actions {
+            let action = action.ok_or(Error::PacketParse(PacketParseError::MalformedCommand(
+                PacketSnippet::new(b"vCont"),
+            )))?;
+
+            match action.kind {
+                VContKind::Stop => {}
+                // stop and resume actions cannot be mixed
+                _ => return Err(Error::PacketUnexpected),
+            }
+
+            let tid = vcont_tid_selector(action.thread).ok_or(Error::PacketUnexpected)?;
+            ops.stop_threads(tid).map_err(Error::TargetError)?;
+        }
+
+        Ok(HandlerStatus::NeedsOK)
+    }
+
+    /// In non-stop mode, report the `offset`-th stopped thread in response to
+    /// a `?` or `vStopped` packet, or reply "OK" once every stopped thread has
+    /// been reported.
+    fn write_stopped_thread(
+        &mut self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        offset: usize,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let ops = target.non_stop_mode().ok_or(Error::PacketUnexpected)?;
+
+        let mut i = 0;
+        let mut stopped = None;
+        ops.list_stopped_threads(&mut |stop_reason| {
+            if i == offset {
+                stopped = Some(stop_reason);
+            }
+            i += 1;
+        })
+        .map_err(Error::TargetError)?;
+
+        match stopped {
+            Some(stop_reason) => {
+                self.stopped_thread_offset = Some(offset + 1);
+                match self.write_stop_reason(res, target, stop_reason)? {
+                    Some(dc) => Ok(HandlerStatus::Disconnect(dc)),
+                    None => Ok(HandlerStatus::Handled),
+                }
+            }
+            None => {
+                self.stopped_thread_offset = None;
+                Ok(HandlerStatus::NeedsOK)
+            }
+        }
+    }
+
+    /// When running in non-stop mode, poll the target for stop events (or
+    /// take any deferred stop event), and report them to GDB using a `%Stop`
+    /// notification.
+    fn report_stop_events(
+        &mut self,
+        target: &mut T,
+        conn: &mut C,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        if !self.non_stop || self.stop_notification_pending || self.stopped_thread_offset.is_some()
+        {
+            return Ok(None);
+        }
+
+        let stop_reason = match self.deferred_stop.take() {
+            Some(stop_reason) => stop_reason,
+            None => {
+                let ops = match target.non_stop_mode() {
+                    Some(ops) => ops,
+                    None => return Ok(None),
+                };
+
+                let mut err = Ok(());
+                let mut incoming_data = || match conn.peek() {
+                    Ok(Some(_)) => true,
+                    Ok(None) => false,
+                    Err(e) => {
+                        err = Err(Error::ConnectionRead(e));
+                        true // break ASAP if a connection error occurred
+                    }
+                };
+
+                let stop_reason = ops
+                    .poll_stop_event(&mut incoming_data)
+                    .map_err(Error::TargetError)?;
+                err?;
+
+                match stop_reason {
+                    Some(stop_reason) => stop_reason,
+                    None => return Ok(None),
+                }
+            }
         };
 
-        err?;
+        self.stop_notification_pending = true;
+
+        let mut res =
+            ResponseWriter::new_notification(conn, self.rle_enabled).with_trace(self.packet_trace);
+        res.write_str("Stop:")?;
+        let disconnect = self.write_stop_reason(&mut res, target, stop_reason)?;
+        let len = res.flush()?;
+        self.stats.sent(len);
 
-        self.finish_vcont(stop_reason, res)
+        Ok(disconnect)
     }
 
     // DEVNOTE: `do_vcont` and `finish_vcont` could be merged into a single
@@ -964,37 +3356,139 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
         &mut self,
         stop_reason: ThreadStopReason<<T::Arch as Arch>::Usize>,
         res: &mut ResponseWriter<C>,
+        target: &mut T,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        match stop_reason {
+            ThreadStopReason::SwBreak(tid)
+            | ThreadStopReason::HwBreak(tid)
+            | ThreadStopReason::Watch { tid, .. }
+            | ThreadStopReason::SignalWithThread { tid, .. }
+            | ThreadStopReason::Fork { tid, .. }
+            | ThreadStopReason::Vfork { tid, .. }
+            | ThreadStopReason::VforkDone(tid)
+            | ThreadStopReason::ThreadCreated(tid) => {
+                self.current_mem_tid = tid;
+                self.current_resume_tid = TidSelector::WithID(tid);
+            }
+            ThreadStopReason::HostSyscall { tid, .. } => {
+                // GDB accesses the syscall's buffers using the requesting thread's memory
+                self.current_mem_tid = tid;
+                self.host_syscall_pending = true;
+            }
+            _ => {}
+        }
+
+        self.write_stop_reason(res, target, stop_reason)
+    }
+
+    /// Send `msg` to the GDB console, split across as many `O` packets as
+    /// required to keep each packet within the advertised packet size.
+    fn write_console_output(
+        &self,
+        conn: &mut C,
+        msg: &[u8],
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        // leave room for the "$", "O", and "#xx" framing bytes, with each byte of the
+        // message being hex-encoded
+        let chunk_len = (self.packet_buffer_len.saturating_sub(5) / 2).max(1);
+        for chunk in msg.chunks(chunk_len) {
+            let mut res = ResponseWriter::new(conn, self.rle_enabled).with_trace(self.packet_trace);
+            res.write_str("O")?;
+            res.write_hex_buf(chunk)?;
+            let len = res.flush()?;
+            self.stats.sent(len);
+        }
+        Ok(())
+    }
+
+    /// Convert a native signal number (as reported in a stop reason) into
+    /// GDB's signal numbering.
+    fn gdb_signal(target: &mut T, native: u8) -> u8 {
+        match target.signal_mapping() {
+            Some(ops) => ops.native_to_gdb(native).as_raw(),
+            None => native,
+        }
+    }
+
+    /// Convert a GDB signal into the target's native signal numbering, for use
+    /// in stop reasons synthesized by `gdbstub` itself.
+    fn native_signal(target: &mut T, signal: Signal) -> u8 {
+        match target.signal_mapping() {
+            Some(ops) => ops.gdb_to_native(signal).unwrap_or_else(|| signal.as_raw()),
+            None => signal.as_raw(),
+        }
+    }
+
+    fn write_stop_reason(
+        &self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        stop_reason: ThreadStopReason<<T::Arch as Arch>::Usize>,
     ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
         match stop_reason {
+            ThreadStopReason::DoneStep | ThreadStopReason::GdbInterrupt
+                if !<T::Arch as Arch>::expedited_registers().is_empty() =>
+            {
+                res.write_str("T05")?;
+                self.write_expedited_registers(res, target, self.current_mem_tid)?;
+                Ok(None)
+            }
             ThreadStopReason::DoneStep | ThreadStopReason::GdbInterrupt => {
                 res.write_str("S05")?;
                 Ok(None)
             }
+            ThreadStopReason::Signal(code)
+                if !<T::Arch as Arch>::expedited_registers().is_empty() =>
+            {
+                res.write_str("T")?;
+                res.write_num(Self::gdb_signal(target, code))?;
+                self.write_expedited_registers(res, target, self.current_mem_tid)?;
+                Ok(None)
+            }
             ThreadStopReason::Signal(code) => {
                 res.write_str("S")?;
-                res.write_num(code)?;
+                res.write_num(Self::gdb_signal(target, code))?;
                 Ok(None)
             }
             ThreadStopReason::Halted => {
                 res.write_str("W19")?; // SIGSTOP
                 Ok(Some(DisconnectReason::TargetHalted))
             }
+            ThreadStopReason::Exited { pid, code } => {
+                res.write_str("W")?;
+                res.write_num(code)?;
+                self.write_exit_suffix(res, target, pid, DisconnectReason::TargetExited(code))
+            }
+            ThreadStopReason::Terminated { pid, signal } => {
+                res.write_str("X")?;
+                res.write_num(Self::gdb_signal(target, signal))?;
+                self.write_exit_suffix(res, target, pid, DisconnectReason::TargetTerminated(signal))
+            }
+            ThreadStopReason::SignalWithThread { tid, signal } => {
+                res.write_str("T")?;
+                res.write_num(Self::gdb_signal(target, signal))?;
+                res.write_str("thread:")?;
+                res.write_thread_id(self.thread_id(target, tid)?)?;
+                res.write_str(";")?;
+                self.write_expedited_registers(res, target, tid)?;
+                Ok(None)
+            }
             ThreadStopReason::SwBreak(tid)
             | ThreadStopReason::HwBreak(tid)
             | ThreadStopReason::Watch { tid, .. } => {
-                self.current_mem_tid = tid;
-                self.current_resume_tid = TidSelector::WithID(tid);
-
                 res.write_str("T05")?;
 
                 res.write_str("thread:")?;
-                res.write_thread_id(ThreadId {
-                    pid: Some(IdKind::WithID(FAKE_PID)),
-                    tid: IdKind::WithID(tid),
-                })?;
+                res.write_thread_id(self.thread_id(target, tid)?)?;
                 res.write_str(";")?;
+                self.write_expedited_registers(res, target, tid)?;
 
                 match stop_reason {
+                    // GDB rejects `swbreak` / `hwbreak` stop replies unless they were negotiated
+                    // via `qSupported`, so fall back to a plain SIGTRAP (which GDB then matches
+                    // against its own breakpoints using the stop PC)
+                    ThreadStopReason::SwBreak(_) if !self.swbreak => return Ok(None),
+                    ThreadStopReason::HwBreak(_) if !self.hwbreak => return Ok(None),
                     // don't include addr on sw/hw break
                     ThreadStopReason::SwBreak(_) => res.write_str("swbreak:")?,
                     ThreadStopReason::HwBreak(_) => res.write_str("hwbreak:")?,
@@ -1013,7 +3507,348 @@ impl<T: Target, C: Connection> GdbStubImpl<T, C> {
                 res.write_str(";")?;
                 Ok(None)
             }
+            ThreadStopReason::Fork { tid, .. }
+            | ThreadStopReason::Vfork { tid, .. }
+            | ThreadStopReason::VforkDone(tid) => {
+                res.write_str("T05")?;
+
+                res.write_str("thread:")?;
+                res.write_thread_id(self.thread_id(target, tid)?)?;
+                res.write_str(";")?;
+                self.write_expedited_registers(res, target, tid)?;
+
+                // fall back to reporting a plain SIGTRAP if GDB didn't enable the event
+                match stop_reason {
+                    ThreadStopReason::Fork {
+                        new_pid, new_tid, ..
+                    } if self.fork_events => {
+                        res.write_str("fork:")?;
+                        res.write_thread_id(ThreadId {
+                            pid: Some(IdKind::WithID(new_pid)),
+                            tid: IdKind::WithID(new_tid),
+                        })?;
+                        res.write_str(";")?;
+                    }
+                    ThreadStopReason::Vfork {
+                        new_pid, new_tid, ..
+                    } if self.vfork_events => {
+                        res.write_str("vfork:")?;
+                        res.write_thread_id(ThreadId {
+                            pid: Some(IdKind::WithID(new_pid)),
+                            tid: IdKind::WithID(new_tid),
+                        })?;
+                        res.write_str(";")?;
+                    }
+                    ThreadStopReason::VforkDone(_) if self.vfork_events => {
+                        res.write_str("vforkdone:;")?;
+                    }
+                    _ => {}
+                }
+
+                Ok(None)
+            }
+            ThreadStopReason::ThreadCreated(tid) => {
+                res.write_str("T05")?;
+
+                // fall back to reporting a plain SIGTRAP if GDB didn't enable thread events
+                if self.thread_events {
+                    res.write_str("create:;")?;
+                }
+
+                res.write_str("thread:")?;
+                res.write_thread_id(self.thread_id(target, tid)?)?;
+                res.write_str(";")?;
+                self.write_expedited_registers(res, target, tid)?;
+                Ok(None)
+            }
+            ThreadStopReason::ThreadExited { .. } if !self.thread_events => {
+                // the thread no longer exists, so there's no thread to report the SIGTRAP on
+                let sigtrap = Self::native_signal(target, Signal::SIGTRAP);
+                self.write_stop_reason(res, target, ThreadStopReason::Signal(sigtrap))
+            }
+            ThreadStopReason::ThreadExited { tid, exit_status } => {
+                res.write_str("w")?;
+                res.write_num(exit_status)?;
+                res.write_str(";")?;
+                res.write_thread_id(self.thread_id(target, tid)?)?;
+                Ok(None)
+            }
+            ThreadStopReason::HostSyscall { request, .. } => {
+                Self::write_host_syscall(res, request)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Write a host syscall request (e.g: `Fopen,1000/06,00,01a4`).
+    fn write_host_syscall(
+        res: &mut ResponseWriter<C>,
+        request: HostSyscallRequest<<T::Arch as Arch>::Usize>,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        // arguments are either integers, or pointer/length pairs referring to a
+        // buffer in the target's memory
+        enum Arg<U> {
+            Num(u64),
+            Signed(i64),
+            Ptr(U),
+            Buf(U, usize),
+        }
+
+        use HostSyscallRequest::*;
+        let (name, args) = match request {
+            Open {
+                path,
+                path_len,
+                flags,
+                mode,
+            } => (
+                "open",
+                [
+                    Some(Arg::Buf(path, path_len)),
+                    Some(Arg::Num(flags as u64)),
+                    Some(Arg::Num(mode as u64)),
+                ],
+            ),
+            Close { fd } => ("close", [Some(Arg::Num(fd as u64)), None, None]),
+            Read { fd, buf, count } => (
+                "read",
+                [
+                    Some(Arg::Num(fd as u64)),
+                    Some(Arg::Ptr(buf)),
+                    Some(Arg::Num(count as u64)),
+                ],
+            ),
+            Write { fd, buf, count } => (
+                "write",
+                [
+                    Some(Arg::Num(fd as u64)),
+                    Some(Arg::Ptr(buf)),
+                    Some(Arg::Num(count as u64)),
+                ],
+            ),
+            Lseek { fd, offset, flag } => (
+                "lseek",
+                [
+                    Some(Arg::Num(fd as u64)),
+                    Some(Arg::Signed(offset)),
+                    Some(Arg::Num(flag as u64)),
+                ],
+            ),
+            Rename {
+                old_path,
+                old_path_len,
+                new_path,
+                new_path_len,
+            } => (
+                "rename",
+                [
+                    Some(Arg::Buf(old_path, old_path_len)),
+                    Some(Arg::Buf(new_path, new_path_len)),
+                    None,
+                ],
+            ),
+            Unlink { path, path_len } => ("unlink", [Some(Arg::Buf(path, path_len)), None, None]),
+            Stat {
+                path,
+                path_len,
+                buf,
+            } => (
+                "stat",
+                [Some(Arg::Buf(path, path_len)), Some(Arg::Ptr(buf)), None],
+            ),
+            Fstat { fd, buf } => (
+                "fstat",
+                [Some(Arg::Num(fd as u64)), Some(Arg::Ptr(buf)), None],
+            ),
+            Gettimeofday { tv, tz } => (
+                "gettimeofday",
+                [Some(Arg::Ptr(tv)), Some(Arg::Ptr(tz)), None],
+            ),
+            Isatty { fd } => ("isatty", [Some(Arg::Num(fd as u64)), None, None]),
+            System { cmd, cmd_len } => ("system", [Some(Arg::Buf(cmd, cmd_len)), None, None]),
+        };
+
+        res.write_str("F")?;
+        res.write_str(name)?;
+        for arg in args.iter().flatten() {
+            res.write_str(",")?;
+            match *arg {
+                Arg::Num(n) => res.write_num(n)?,
+                Arg::Signed(n) if n < 0 => {
+                    res.write_str("-")?;
+                    res.write_num(n.wrapping_neg() as u64)?;
+                }
+                Arg::Signed(n) => res.write_num(n as u64)?,
+                Arg::Ptr(ptr) => res.write_num(ptr)?,
+                Arg::Buf(ptr, len) => {
+                    res.write_num(ptr)?;
+                    res.write_str("/")?;
+                    res.write_num(len)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convert the result of adding/removing a breakpoint into the
+    /// appropriate response (where `None` indicates that the breakpoint type
+    /// isn't supported).
+    fn breakpoint_status(
+        supported: Option<TargetResult<bool, T>>,
+    ) -> Result<HandlerStatus, Error<T::Error, C::Error>> {
+        let status = match supported {
+            None => HandlerStatus::Handled,
+            Some(Err(e)) => {
+                Err(e).handle_error()?;
+                HandlerStatus::Handled
+            }
+            Some(Ok(true)) => HandlerStatus::NeedsOK,
+            Some(Ok(false)) => return Err(Error::NonFatalError(22)),
+        };
+        Ok(status)
+    }
+
+    /// Return the thread-id used to report `tid` to GDB, which only includes
+    /// the thread's pid if the multiprocess extensions were negotiated.
+    fn thread_id(&self, target: &mut T, tid: Tid) -> Result<ThreadId, Error<T::Error, C::Error>> {
+        if !self.multiprocess {
+            return Ok(ThreadId {
+                pid: None,
+                tid: IdKind::WithID(tid),
+            });
+        }
+
+        let pid = match target.base_ops() {
+            BaseOps::SingleThread(_) => FAKE_PID,
+            BaseOps::MultiThread(ops) => ops.thread_pid(tid).map_err(Error::TargetError)?,
+        };
+
+        Ok(ThreadId {
+            pid: Some(IdKind::WithID(pid)),
+            tid: IdKind::WithID(tid),
+        })
+    }
+
+    /// Append the `process:PID` suffix (if any) to a `W` / `X` stop reply, and
+    /// decide whether the exit ends the debugging session.
+    fn write_exit_suffix(
+        &self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        pid: Pid,
+        reason: DisconnectReason,
+    ) -> Result<Option<DisconnectReason>, Error<T::Error, C::Error>> {
+        if self.multiprocess {
+            res.write_str(";process:")?;
+            res.write_num(pid.get())?;
+        }
+
+        // in extended mode, GDB stays connected (e.g: to `run` the program again)
+        if target.extended_mode().is_some() {
+            return Ok(None);
+        }
+        Ok(Some(reason))
+    }
+
+    /// Append the arch's expedited registers (if any) to a `T` stop reply.
+    fn write_expedited_registers(
+        &self,
+        res: &mut ResponseWriter<C>,
+        target: &mut T,
+        tid: Tid,
+    ) -> Result<(), Error<T::Error, C::Error>> {
+        use crate::target::TargetError;
+
+        for &raw_id in <T::Arch as Arch>::expedited_registers() {
+            let (reg_id, reg_size) = match <T::Arch as Arch>::RegId::from_raw_id(raw_id) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let mut buf = [0; 64];
+            let dst = match buf.get_mut(..reg_size) {
+                Some(dst) => dst,
+                None => continue,
+            };
+
+            let ret = match target.base_ops() {
+                BaseOps::SingleThread(ops) => ops.read_register(reg_id, dst),
+                BaseOps::MultiThread(ops) => ops.read_register(reg_id, dst, tid),
+            };
+            let len = match ret {
+                Ok(len) => len,
+                Err(TargetError::Fatal(e)) => return Err(Error::TargetError(e)),
+                // unavailable registers are simply omitted from the stop reply
+                Err(_) => continue,
+            };
+
+            res.write_num(raw_id)?;
+            res.write_str(":")?;
+            res.write_hex_buf(dst.get(..len).ok_or(Error::TargetMismatch)?)?;
+            res.write_str(";")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Query which resume actions the target supports.
+fn resume_capabilities<T: Target>(target: &mut T) -> ResumeCapabilities {
+    let mut caps = match target.base_ops() {
+        BaseOps::SingleThread(ops) => ops.resume_capabilities(),
+        BaseOps::MultiThread(ops) => return ops.resume_capabilities(),
+    };
+    // single threaded targets can also be stepped using temporary breakpoints
+    caps.step |= target.single_step_via_breakpoints().is_some();
+    caps
+}
+
+/// Returns `true` if `cmd` would modify or resume the target, and should
+/// therefore be rejected by read-only targets.
+fn is_write_command(cmd: &Command<'_>) -> bool {
+    use crate::protocol::commands::_vCont::vCont;
+
+    match cmd {
+        Command::Base(cmd) => matches!(
+            cmd,
+            ext::Base::G(_)
+                | ext::Base::M(_)
+                | ext::Base::X(_)
+                | ext::Base::P(_)
+                | ext::Base::Z(_)
+                | ext::Base::c(_)
+                | ext::Base::C(_)
+                | ext::Base::s(_)
+                | ext::Base::S(_)
+                | ext::Base::vCont(vCont::Actions(_))
+        ),
+        Command::MemoryTags(cmd) => matches!(cmd, ext::MemoryTags::QMemTags(_)),
+        Command::Siginfo(cmd) => matches!(cmd, ext::Siginfo::qXferSiginfoWrite(_)),
+        Command::CustomXfer(ext::CustomXfer::qXferCustom(cmd)) => {
+            use crate::protocol::commands::_qXfer_custom::XferOp;
+            matches!(cmd.op, XferOp::Write { .. })
         }
+        Command::Flash(_) | Command::ReverseExec(_) => true,
+        _ => false,
+    }
+}
+
+/// Map the thread-id of a vCont action to a `TidSelector`, returning `None`
+/// if the thread-id is invalid in the context of a vCont action.
+fn vcont_tid_selector(thread: Option<ThreadId>) -> Option<TidSelector> {
+    match thread {
+        Some(thread) => match thread.tid {
+            IdKind::Any => None,
+            IdKind::All => match thread.pid {
+                // all threads of a specific process
+                Some(IdKind::WithID(pid)) => Some(TidSelector::Process(pid)),
+                _ => Some(TidSelector::All),
+            },
+            IdKind::WithID(tid) => Some(TidSelector::WithID(tid)),
+        },
+        // An action with no thread-id matches all threads
+        None => Some(TidSelector::All),
     }
 }
 
@@ -1024,6 +3859,14 @@ impl<U> From<StopReason<U>> for ThreadStopReason<U> {
             StopReason::DoneStep => ThreadStopReason::DoneStep,
             StopReason::GdbInterrupt => ThreadStopReason::GdbInterrupt,
             StopReason::Halted => ThreadStopReason::Halted,
+            StopReason::Exited { code } => ThreadStopReason::Exited {
+                pid: FAKE_PID,
+                code,
+            },
+            StopReason::Terminated { signal } => ThreadStopReason::Terminated {
+                pid: FAKE_PID,
+                signal,
+            },
             StopReason::SwBreak => ThreadStopReason::SwBreak(SINGLE_THREAD_TID),
             StopReason::HwBreak => ThreadStopReason::HwBreak(SINGLE_THREAD_TID),
             StopReason::Watch { kind, addr } => ThreadStopReason::Watch {
@@ -1032,6 +3875,23 @@ impl<U> From<StopReason<U>> for ThreadStopReason<U> {
                 addr,
             },
             StopReason::Signal(sig) => ThreadStopReason::Signal(sig),
+            // single threaded targets report the child process as having a single thread, much
+            // like the target itself
+            StopReason::Fork { new_pid } => ThreadStopReason::Fork {
+                tid: SINGLE_THREAD_TID,
+                new_pid,
+                new_tid: SINGLE_THREAD_TID,
+            },
+            StopReason::Vfork { new_pid } => ThreadStopReason::Vfork {
+                tid: SINGLE_THREAD_TID,
+                new_pid,
+                new_tid: SINGLE_THREAD_TID,
+            },
+            StopReason::VforkDone => ThreadStopReason::VforkDone(SINGLE_THREAD_TID),
+            StopReason::HostSyscall(request) => ThreadStopReason::HostSyscall {
+                tid: SINGLE_THREAD_TID,
+                request,
+            },
         }
     }
 }
@@ -0,0 +1,62 @@
+use core::ops::{Deref, DerefMut};
+
+/// A fixed-capacity packet buffer, which can be stored inline (e.g: as part of
+/// a larger struct) without requiring an allocator.
+///
+/// Unlike a borrowed `&mut [u8]` buffer, a `PacketBuffer` is moved into the
+/// [`GdbStub`](super::GdbStub) it's used with (see
+/// [`GdbStubBuilder::with_owned_packet_buffer`](super::GdbStubBuilder::with_owned_packet_buffer)),
+/// which avoids having to keep the buffer alive separately from the stub.
+///
+/// # Example
+///
+/// ```
+/// use gdbstub::target::Target;
+/// use gdbstub::{Connection, GdbStub, GdbStubBuilderError, PacketBuffer};
+///
+/// /// A bare-metal debug monitor, which owns its `GdbStub` (and therefore its
+/// /// packet buffer) without using the heap.
+/// struct DebugMonitor<T: Target, C: Connection> {
+///     stub: GdbStub<'static, T, C, PacketBuffer<4096>>,
+/// }
+///
+/// impl<T: Target, C: Connection> DebugMonitor<T, C> {
+///     fn new(conn: C) -> Result<Self, GdbStubBuilderError> {
+///         let stub = GdbStub::builder(conn)
+///             .with_owned_packet_buffer(PacketBuffer::new())
+///             .build()?;
+///         assert_eq!(stub.packet_buffer_size(), 4096);
+///         Ok(DebugMonitor { stub })
+///     }
+/// }
+/// ```
+pub struct PacketBuffer<const N: usize> {
+    buf: [u8; N],
+}
+
+impl<const N: usize> PacketBuffer<N> {
+    /// Create a new (zeroed) `N` byte packet buffer.
+    pub const fn new() -> PacketBuffer<N> {
+        PacketBuffer { buf: [0; N] }
+    }
+}
+
+impl<const N: usize> Default for PacketBuffer<N> {
+    fn default() -> PacketBuffer<N> {
+        PacketBuffer::new()
+    }
+}
+
+impl<const N: usize> Deref for PacketBuffer<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl<const N: usize> DerefMut for PacketBuffer<N> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
@@ -0,0 +1,136 @@
+/// A snapshot of the traffic exchanged with GDB over the course of a
+/// debugging session.
+///
+/// All byte counts include the packet framing (i.e: the leading `$` / `%`,
+/// and the trailing `#` + checksum).
+///
+/// _Note:_ `SessionStats` is only available when the `stats` feature is
+/// enabled.
+#[cfg(feature = "stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionStats {
+    /// Number of packets received from GDB (excluding acks and interrupts).
+    pub packets_received: u64,
+    /// Number of packets sent to GDB (excluding acks and retransmissions).
+    pub packets_sent: u64,
+    /// Total number of bytes received from GDB.
+    pub bytes_received: u64,
+    /// Total number of bytes sent to GDB (including acks and
+    /// retransmissions).
+    pub bytes_sent: u64,
+    /// Number of acks (`+`) received from GDB.
+    pub acks_received: u64,
+    /// Number of acks (`+`) sent to GDB.
+    pub acks_sent: u64,
+    /// Number of nacks (`-`) received from GDB.
+    pub nacks_received: u64,
+    /// Number of nacks (`-`) sent to GDB (i.e: in response to a packet with
+    /// a mismatched checksum).
+    pub nacks_sent: u64,
+    /// Number of responses which were re-transmitted after GDB nack'd them.
+    pub retransmits: u64,
+    /// Size of the largest packet sent or received.
+    pub largest_packet: usize,
+}
+
+/// Accumulates [`SessionStats`] as packets flow through the stub.
+///
+/// Updated through a shared reference, so that the counters can be bumped from
+/// anywhere a response is sent.
+#[cfg(feature = "stats")]
+pub(crate) struct StatsCounter(core::cell::Cell<SessionStats>);
+
+#[cfg(feature = "stats")]
+impl StatsCounter {
+    pub fn new() -> StatsCounter {
+        StatsCounter(core::cell::Cell::new(SessionStats::default()))
+    }
+
+    pub fn snapshot(&self) -> SessionStats {
+        self.0.get()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut SessionStats)) {
+        let mut stats = self.0.get();
+        f(&mut stats);
+        self.0.set(stats);
+    }
+
+    /// Record the raw bytes of an incoming packet / ack / interrupt.
+    pub fn received(&self, raw: &[u8]) {
+        self.update(|stats| {
+            stats.bytes_received += raw.len() as u64;
+            match raw.first() {
+                Some(b'$') | Some(b'%') => {
+                    stats.packets_received += 1;
+                    stats.largest_packet = stats.largest_packet.max(raw.len());
+                }
+                Some(b'+') => stats.acks_received += 1,
+                Some(b'-') => stats.nacks_received += 1,
+                _ => {}
+            }
+        })
+    }
+
+    /// Record an outgoing packet of `len` bytes.
+    pub fn sent(&self, len: usize) {
+        self.update(|stats| {
+            stats.packets_sent += 1;
+            stats.bytes_sent += len as u64;
+            stats.largest_packet = stats.largest_packet.max(len);
+        })
+    }
+
+    pub fn ack_sent(&self) {
+        self.update(|stats| {
+            stats.acks_sent += 1;
+            stats.bytes_sent += 1;
+        })
+    }
+
+    pub fn nack_sent(&self) {
+        self.update(|stats| {
+            stats.nacks_sent += 1;
+            stats.bytes_sent += 1;
+        })
+    }
+
+    /// Record the re-transmission of a `len` byte response.
+    #[cfg(feature = "alloc")]
+    pub fn retransmitted(&self, len: usize) {
+        self.update(|stats| {
+            stats.retransmits += 1;
+            stats.bytes_sent += len as u64;
+        })
+    }
+}
+
+/// No-op stand-in for the real `StatsCounter`, used when the `stats` feature
+/// is disabled.
+#[cfg(not(feature = "stats"))]
+pub(crate) struct StatsCounter;
+
+#[cfg(not(feature = "stats"))]
+impl StatsCounter {
+    #[inline(always)]
+    pub fn new() -> StatsCounter {
+        StatsCounter
+    }
+
+    #[inline(always)]
+    pub fn received(&self, _raw: &[u8]) {}
+
+    #[inline(always)]
+    pub fn sent(&self, _len: usize) {}
+
+    #[inline(always)]
+    pub fn ack_sent(&self) {}
+
+    #[inline(always)]
+    pub fn nack_sent(&self) {}
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn retransmitted(&self, _len: usize) {}
+}
@@ -0,0 +1,60 @@
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::allow::{Allow, AllowFlags, AllowOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+struct AllowTarget {
+    flags: Option<AllowFlags>,
+}
+
+impl Target for AllowTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn allow(&mut self) -> Option<AllowOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(AllowTarget);
+
+impl Allow for AllowTarget {
+    fn set_allowed(&mut self, flags: AllowFlags) -> TargetResult<(), Self> {
+        self.flags = Some(flags);
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_qallow() {
+    let mut target = AllowTarget { flags: None };
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";QAllow+"));
+}
+
+#[test]
+fn parses_flags() {
+    let mut target = AllowTarget { flags: None };
+    let (res, _) = run_session(
+        &mut target,
+        &["QAllow:WriteReg:0;WriteMem:0;InsertBreak:1;InsertTrace:0;InsertFastTrace:0;Stop:1"],
+    );
+    assert_eq!(res, ["OK"]);
+
+    let flags = target.flags.unwrap();
+    assert_eq!(flags, AllowFlags::INSERT_BREAK | AllowFlags::STOP);
+    assert!(!flags.contains(AllowFlags::WRITE_MEM));
+}
+
+#[test]
+fn ignores_unknown_ops() {
+    let mut target = AllowTarget { flags: None };
+    let (res, _) = run_session(&mut target, &["QAllow:WriteMem:1;Frobnicate:1"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.flags, Some(AllowFlags::WRITE_MEM));
+}
@@ -0,0 +1,135 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::{AsyncConnection, AsyncGdbStub, Connection, DisconnectReason};
+
+/// Drive a future to completion. `MockConnection` never blocks, so the future
+/// should always be ready when polled.
+fn block_on<F: Future>(fut: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    // SAFETY: the waker's vtable functions are all no-ops
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = Box::pin(fut);
+    loop {
+        if let Poll::Ready(res) = Pin::as_mut(&mut fut).poll(&mut cx) {
+            return res;
+        }
+    }
+}
+
+struct AsyncMockConnection<'a>(&'a mut MockConnection);
+
+impl AsyncConnection for AsyncMockConnection<'_> {
+    type Error = &'static str;
+
+    async fn read(&mut self) -> Result<u8, Self::Error> {
+        self.0.read()
+    }
+
+    async fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.0.write(byte)
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        self.0.peek()
+    }
+
+    fn read_peeked(&mut self) -> Result<u8, Self::Error> {
+        self.0.read()
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+struct AsyncTarget;
+
+impl Target for AsyncTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(AsyncTarget);
+
+#[test]
+fn async_session() {
+    let mut conn = MockConnection::new(&["?", "m0,4", "c", "D"]);
+    let res = block_on(AsyncGdbStub::new(AsyncMockConnection(&mut conn)).run(&mut AsyncTarget));
+    assert_eq!(res.unwrap(), DisconnectReason::Disconnect);
+
+    let output = &conn.output;
+    assert_eq!(output.iter().filter(|b| **b == b'+').count(), 4);
+    assert_eq!(
+        split_responses(output),
+        ["T05thread:01;", "00000000", "T05thread:01;", "OK"]
+    );
+}
+
+/// A target which runs until GDB interrupts it.
+struct InterruptTarget;
+
+impl Target for InterruptTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for InterruptTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        while !check_gdb_interrupt() {}
+        Ok(StopReason::GdbInterrupt)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+#[test]
+fn async_interrupt_during_continue() {
+    let mut conn = MockConnection::new(&["c", "\x03", "D"]);
+    let res = block_on(AsyncGdbStub::new(AsyncMockConnection(&mut conn)).run(&mut InterruptTarget));
+    assert_eq!(res.unwrap(), DisconnectReason::Disconnect);
+    // the interrupt is consumed, and only results in a single stop reply
+    assert_eq!(split_responses(&conn.output), ["S05", "OK"]);
+}
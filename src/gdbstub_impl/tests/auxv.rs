@@ -0,0 +1,65 @@
+use std::vec::Vec;
+
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::auxv::{Auxv, AuxvOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+struct AuxvTarget {
+    auxv: Vec<u8>,
+}
+
+impl AuxvTarget {
+    fn new() -> AuxvTarget {
+        let entries: [(u32, u32); 5] = [
+            (3, 0x8034),      // AT_PHDR
+            (6, 0x1000),      // AT_PAGESZ
+            (9, 0x8000_0023), // AT_ENTRY (includes a '#')
+            (25, 0x7d2a2404), // AT_RANDOM (includes a '}', '*', and '$')
+            (0, 0),           // AT_NULL
+        ];
+
+        let mut auxv = Vec::new();
+        for (a_type, a_val) in entries.iter() {
+            auxv.extend_from_slice(&a_type.to_le_bytes());
+            auxv.extend_from_slice(&a_val.to_le_bytes());
+        }
+        AuxvTarget { auxv }
+    }
+}
+
+impl Target for AuxvTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn auxv(&mut self) -> Option<AuxvOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(AuxvTarget);
+
+impl Auxv for AuxvTarget {
+    fn get_auxv(&self, offset: u64, length: usize, buf: &mut [u8]) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(&self.auxv, offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_auxv() {
+    let mut target = AuxvTarget::new();
+    assert!(advertises(&mut target, "qXfer:auxv:read+"));
+}
+
+#[test]
+fn paged_read() {
+    let mut target = AuxvTarget::new();
+    let auxv = target.auxv.clone();
+    let data = read_xfer_paged(&mut target, "qXfer:auxv:read::", auxv.len(), 0x18, None);
+    assert_eq!(data, auxv);
+}
@@ -0,0 +1,155 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, ArmBreakpointKind, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::breakpoints::{HwBreakpoint, HwBreakpointOps};
+use crate::target::ext::qsupported::{QSupported, QSupportedOps};
+use crate::target::{Target, TargetResult};
+
+/// A target which reports a fixed breakpoint stop reason whenever it's
+/// resumed.
+struct BreakTarget {
+    stop: StopReason<u32>,
+    supports_hw: bool,
+    suppress: Vec<&'static str>,
+}
+
+impl BreakTarget {
+    fn new(stop: StopReason<u32>) -> BreakTarget {
+        BreakTarget {
+            stop,
+            supports_hw: false,
+            suppress: Vec::new(),
+        }
+    }
+}
+
+impl Target for BreakTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn hw_breakpoint(&mut self) -> Option<HwBreakpointOps<'_, Self>> {
+        if self.supports_hw {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn qsupported(&mut self) -> Option<QSupportedOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for BreakTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(self.stop)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl HwBreakpoint for BreakTarget {
+    fn add_hw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(true)
+    }
+
+    fn remove_hw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(true)
+    }
+}
+
+impl QSupported for BreakTarget {
+    fn advertise_feature(&mut self, name: &[u8]) -> bool {
+        !self.suppress.iter().any(|s| s.as_bytes() == name)
+    }
+}
+
+#[test]
+fn swbreak_negotiated() {
+    let mut target = BreakTarget::new(StopReason::SwBreak);
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+", "c"]);
+    assert!(res[0].split(';').any(|f| f == "swbreak+"));
+    assert_eq!(res[1], "T05thread:01;swbreak:;");
+}
+
+#[test]
+fn swbreak_without_qsupported() {
+    let mut target = BreakTarget::new(StopReason::SwBreak);
+    let (res, _) = run_session(&mut target, &["c"]);
+    assert_eq!(res, ["T05thread:01;"]);
+}
+
+#[test]
+fn swbreak_not_offered_by_gdb() {
+    let mut target = BreakTarget::new(StopReason::SwBreak);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+;swbreak-", "c"]);
+    assert_eq!(res[1], "T05thread:01;");
+}
+
+#[test]
+fn swbreak_suppressed_by_target() {
+    let mut target = BreakTarget::new(StopReason::SwBreak);
+    target.suppress.push("swbreak");
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+", "c"]);
+    assert!(!res[0].contains("swbreak"));
+    assert_eq!(res[1], "T05thread:01;");
+}
+
+#[test]
+fn hwbreak_negotiated() {
+    let mut target = BreakTarget::new(StopReason::HwBreak);
+    target.supports_hw = true;
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+;hwbreak+", "c"]);
+    assert!(res[0].split(';').any(|f| f == "hwbreak+"));
+    assert_eq!(res[1], "T05thread:01;hwbreak:;");
+}
+
+#[test]
+fn hwbreak_not_offered_by_gdb() {
+    let mut target = BreakTarget::new(StopReason::HwBreak);
+    target.supports_hw = true;
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+", "c"]);
+    assert_eq!(res[1], "T05thread:01;");
+}
+
+#[test]
+fn hwbreak_without_hw_support() {
+    let mut target = BreakTarget::new(StopReason::HwBreak);
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+;hwbreak+", "c"]);
+    assert!(!res[0].contains("hwbreak"));
+    assert_eq!(res[1], "T05thread:01;");
+}
@@ -0,0 +1,468 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, ArmBreakpointKind, Armv4t};
+use crate::target::ext::agent::{self, AgentContext};
+use crate::target::ext::base::singlethread::{SingleThreadOps, StopReason};
+use crate::target::ext::base::{BaseOps, ResumeAction};
+use crate::target::ext::breakpoints::{
+    HwBreakpoint, HwBreakpointConditions, HwBreakpointConditionsOps, HwBreakpointOps, SwBreakpoint,
+    SwBreakpointCommands, SwBreakpointCommandsOps, SwBreakpointConditions,
+    SwBreakpointConditionsOps, SwBreakpointOps,
+};
+use crate::target::{Target, TargetResult};
+
+/// A target which runs a loop incrementing `r0` from 0 to 9, passing through
+/// the breakpoint address once per iteration.
+struct LoopTarget {
+    supports_conds: bool,
+    r0: u32,
+    bp: Option<(u32, Vec<Vec<u8>>)>,
+    /// the breakpoint's commands, and whether they persist
+    cmds: Option<(Vec<Vec<u8>>, bool)>,
+    kinds: Vec<ArmBreakpointKind>,
+}
+
+impl LoopTarget {
+    fn new(supports_conds: bool) -> LoopTarget {
+        LoopTarget {
+            supports_conds,
+            r0: 0,
+            bp: None,
+            cmds: None,
+            kinds: Vec::new(),
+        }
+    }
+}
+
+struct Regs(u32);
+
+impl AgentContext for Regs {
+    fn read_register(&mut self, regno: u16) -> Option<u64> {
+        match regno {
+            0 => Some(self.0 as u64),
+            _ => None,
+        }
+    }
+
+    fn read_memory(&mut self, _addr: u64, _buf: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl Target for LoopTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for LoopTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        while self.r0 < 10 {
+            let r0 = self.r0;
+            self.r0 += 1;
+
+            let conds = match &self.bp {
+                Some((_, conds)) => conds,
+                None => continue,
+            };
+            let hit = conds.is_empty()
+                || conds
+                    .iter()
+                    .any(|c| agent::evaluate(c, &mut Regs(r0)) != Ok(0));
+            if hit {
+                self.r0 = r0;
+                return Ok(StopReason::SwBreak);
+            }
+        }
+        Ok(StopReason::Halted)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl SwBreakpoint for LoopTarget {
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        self.bp = Some((addr, Vec::new()));
+        self.kinds.push(kind);
+        Ok(true)
+    }
+
+    fn remove_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(self.bp.take().is_some())
+    }
+
+    fn sw_breakpoint_conditions(&mut self) -> Option<SwBreakpointConditionsOps<'_, Self>> {
+        if self.supports_conds {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn sw_breakpoint_commands(&mut self) -> Option<SwBreakpointCommandsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SwBreakpointConditions for LoopTarget {
+    fn add_sw_breakpoint_with_conds(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+        conds: agent::AgentExprs<'_>,
+    ) -> TargetResult<bool, Self> {
+        self.bp = Some((addr, conds.map(|c| c.to_vec()).collect()));
+        Ok(true)
+    }
+}
+
+impl SwBreakpointCommands for LoopTarget {
+    fn set_sw_breakpoint_cmds(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+        cmds: agent::AgentExprs<'_>,
+        persist: bool,
+    ) -> TargetResult<bool, Self> {
+        self.cmds = Some((cmds.map(|c| c.to_vec()).collect(), persist));
+        Ok(true)
+    }
+}
+
+// reg 0; const8 5; equal; end
+const REG0_EQ_5: &str = "X7,2600002205132";
+
+#[test]
+fn advertises_conditional_breakpoints() {
+    let (res, _) = run_session(&mut LoopTarget::new(true), &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";ConditionalBreakpoints+"));
+
+    let (res, _) = run_session(&mut LoopTarget::new(false), &["qSupported:multiprocess+"]);
+    assert!(!res[0].contains("ConditionalBreakpoints"));
+}
+
+#[test]
+fn parses_conds() {
+    let mut target = LoopTarget::new(true);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4;X7,26000022051327;X1,27"]);
+    assert_eq!(res, ["OK"]);
+    let (addr, conds) = target.bp.unwrap();
+    assert_eq!(addr, 0x1000);
+    assert_eq!(
+        conds,
+        [vec![0x26, 0x00, 0x00, 0x22, 0x05, 0x13, 0x27], vec![0x27]]
+    );
+}
+
+#[test]
+fn stops_only_when_condition_holds() {
+    let mut target = LoopTarget::new(true);
+    let z = format!("Z0,1000,4;{}7", REG0_EQ_5);
+    let (res, _) = run_session(&mut target, &[&z, "c"]);
+    assert_eq!(res[0], "OK");
+    assert!(res[1].starts_with("T05"), "{:?}", res);
+    assert_eq!(target.r0, 5);
+}
+
+#[test]
+fn unconditional_without_conds() {
+    let mut target = LoopTarget::new(true);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4", "c"]);
+    assert_eq!(res[0], "OK");
+    assert_eq!(target.r0, 0);
+}
+
+#[test]
+fn ignores_conds_when_unsupported() {
+    let mut target = LoopTarget::new(false);
+    let z = format!("Z0,1000,4;{}7", REG0_EQ_5);
+    let (res, _) = run_session(&mut target, &[&z]);
+    // added as a plain breakpoint, leaving GDB to evaluate the conditions
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.bp, Some((0x1000, Vec::new())));
+}
+
+#[test]
+fn decodes_arm_breakpoint_kinds() {
+    let mut target = LoopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["Z0,1000,2", "Z0,1000,3", "Z0,1000,4", "Z0,1000,5"],
+    );
+    // kind 5 isn't a valid ARM breakpoint kind
+    assert_eq!(res, ["OK", "OK", "OK", "E16"]);
+    assert_eq!(
+        target.kinds,
+        [
+            ArmBreakpointKind::Thumb16,
+            ArmBreakpointKind::Thumb32,
+            ArmBreakpointKind::Arm32
+        ]
+    );
+}
+
+#[test]
+fn advertises_breakpoint_commands() {
+    let (res, _) = run_session(&mut LoopTarget::new(false), &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";BreakpointCommands+"));
+}
+
+#[test]
+fn parses_conds_and_cmds() {
+    let mut target = LoopTarget::new(true);
+    // GDB concatenates the expressions in each list back-to-back
+    let (res, _) = run_session(
+        &mut target,
+        &["Z0,1000,4;X7,26000022051327X1,27;cmds:1,X2,2627X1,27"],
+    );
+    assert_eq!(res, ["OK"]);
+    let (addr, conds) = target.bp.unwrap();
+    assert_eq!(addr, 0x1000);
+    assert_eq!(
+        conds,
+        [vec![0x26, 0x00, 0x00, 0x22, 0x05, 0x13, 0x27], vec![0x27]]
+    );
+    assert_eq!(
+        target.cmds,
+        Some((vec![vec![0x26, 0x27], vec![0x27]], true))
+    );
+}
+
+#[test]
+fn cmds_without_conds() {
+    // commands don't require support for conditions
+    let mut target = LoopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4;cmds:0,X1,27"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.bp, Some((0x1000, Vec::new())));
+    assert_eq!(target.cmds, Some((vec![vec![0x27]], false)));
+}
+
+#[test]
+fn malformed_cmds() {
+    for packet in [
+        "Z0,1000,4;cmds:1,X2,26",
+        "Z0,1000,4;cmds:X1,27",
+        "Z0,1000,4;X1,27Y",
+    ]
+    .iter()
+    {
+        let mut target = LoopTarget::new(true);
+        let (_, result) = run_session(&mut target, &[packet]);
+        assert!(
+            matches!(result, Err(crate::GdbStubError::PacketParse(_))),
+            "{}",
+            packet
+        );
+        assert!(target.cmds.is_none());
+    }
+}
+
+#[test]
+fn duplicate_insertions_are_refcounted() {
+    let mut target = LoopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4", "Z0,1000,4", "z0,1000,4"]);
+    assert_eq!(res, ["OK", "OK", "OK"]);
+    // the second logical breakpoint is still set
+    assert_eq!(target.bp, Some((0x1000, Vec::new())));
+    assert_eq!(target.kinds.len(), 1);
+
+    let mut target = LoopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "Z0,1000,4",
+            "Z0,1000,4",
+            "z0,1000,4",
+            "z0,1000,4",
+            "z0,1000,4",
+        ],
+    );
+    // the target only sees the last removal
+    assert_eq!(res, ["OK", "OK", "OK", "OK", "E16"]);
+    assert!(target.bp.is_none());
+}
+
+#[test]
+fn refcount_distinguishes_kinds() {
+    let mut target = LoopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4", "Z0,1000,2"]);
+    assert_eq!(res, ["OK", "OK"]);
+    assert_eq!(target.kinds.len(), 2);
+}
+
+#[test]
+fn conds_update_existing_breakpoint() {
+    let mut target = LoopTarget::new(true);
+    let (res, _) = run_session(&mut target, &["Z0,1000,4", "Z0,1000,4;X1,27", "z0,1000,4"]);
+    // the insertion with conditions is forwarded to the target, but doesn't
+    // count as a separate breakpoint
+    assert_eq!(res, ["OK", "OK", "OK"]);
+    assert!(target.bp.is_none());
+}
+
+/// A target with a single hardware breakpoint comparator, which may (or may
+/// not) be able to evaluate breakpoint conditions. Software breakpoints are
+/// supported, but without conditions.
+struct ComparatorTarget {
+    can_match_conds: bool,
+    /// the programmed address, and any conditions the comparator evaluates
+    comparator: Option<(u32, Vec<Vec<u8>>)>,
+    sw_bp: Option<u32>,
+}
+
+impl ComparatorTarget {
+    fn new(can_match_conds: bool) -> ComparatorTarget {
+        ComparatorTarget {
+            can_match_conds,
+            comparator: None,
+            sw_bp: None,
+        }
+    }
+}
+
+impl Target for ComparatorTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn hw_breakpoint(&mut self) -> Option<HwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ComparatorTarget);
+
+impl SwBreakpoint for ComparatorTarget {
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        self.sw_bp = Some(addr);
+        Ok(true)
+    }
+
+    fn remove_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(self.sw_bp.take().is_some())
+    }
+}
+
+impl HwBreakpoint for ComparatorTarget {
+    fn add_hw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        self.comparator = Some((addr, Vec::new()));
+        Ok(true)
+    }
+
+    fn remove_hw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(self.comparator.take().is_some())
+    }
+
+    fn hw_breakpoint_conditions(&mut self) -> Option<HwBreakpointConditionsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl HwBreakpointConditions for ComparatorTarget {
+    fn add_hw_breakpoint_with_conds(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+        conds: agent::AgentExprs<'_>,
+    ) -> TargetResult<bool, Self> {
+        if !self.can_match_conds {
+            return Ok(false);
+        }
+        self.comparator = Some((addr, conds.map(|c| c.to_vec()).collect()));
+        Ok(true)
+    }
+}
+
+#[test]
+fn hw_conds_programmed_by_target() {
+    let mut target = ComparatorTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+", "Z1,1000,4;X1,27"],
+    );
+    assert!(res[0].contains(";ConditionalBreakpoints+"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(target.comparator, Some((0x1000, vec![vec![0x27]])));
+}
+
+#[test]
+fn hw_conds_declined_by_target() {
+    let mut target = ComparatorTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z1,1000,4;X1,27"]);
+    // a plain breakpoint is added instead, and GDB evaluates the conditions
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.comparator, Some((0x1000, Vec::new())));
+}
+
+#[test]
+fn sw_conds_on_hw_conds_only_target() {
+    let mut target = ComparatorTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+", "Z0,2000,4;X1,27"],
+    );
+    // conditions are advertised for the hw breakpoints, but the sw breakpoint
+    // is added without them (leaving GDB to evaluate the conditions)
+    assert!(res[0].contains(";ConditionalBreakpoints+"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(target.sw_bp, Some(0x2000));
+}
@@ -0,0 +1,181 @@
+use std::vec::Vec;
+
+use super::mock::{copy_xfer_chunk, read_xfer_paged, run_session, supported_features};
+use crate::arch::arm::Armv4t;
+use crate::common::Tid;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::btrace::{Btrace, BtraceFormat, BtraceOps, BtraceReadKind};
+use crate::target::{Target, TargetError, TargetResult};
+
+const TRACE: &[u8] = br#"<btrace version="1.0"><block begin="0x1000" end="0x1010"/></btrace>"#;
+
+/// A target which only supports BTS, and records the trace requests it
+/// received.
+#[derive(Default)]
+struct BtraceTarget {
+    enabled: Option<(Tid, BtraceFormat)>,
+    buffer_size: Option<u64>,
+    reads: Vec<(BtraceReadKind, u64)>,
+}
+
+impl Target for BtraceTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn btrace(&mut self) -> Option<BtraceOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(BtraceTarget);
+
+impl Btrace for BtraceTarget {
+    fn supports_format(&self, format: BtraceFormat) -> bool {
+        format == BtraceFormat::Bts
+    }
+
+    fn enable(&mut self, tid: Tid, format: BtraceFormat) -> TargetResult<(), Self> {
+        self.enabled = Some((tid, format));
+        Ok(())
+    }
+
+    fn disable(&mut self, _tid: Tid) -> TargetResult<(), Self> {
+        self.enabled.take().ok_or(TargetError::NonFatal)?;
+        Ok(())
+    }
+
+    fn set_buffer_size(&mut self, _format: BtraceFormat, size: u64) -> TargetResult<(), Self> {
+        self.buffer_size = Some(size);
+        Ok(())
+    }
+
+    fn read_btrace(
+        &mut self,
+        _tid: Tid,
+        kind: BtraceReadKind,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        if self.enabled.is_none() {
+            return Err(TargetError::NonFatal);
+        }
+        self.reads.push((kind, offset));
+
+        Ok(copy_xfer_chunk(TRACE, offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_supported_formats() {
+    let features = supported_features(&mut BtraceTarget::default());
+    for feature in [
+        "Qbtrace:bts+",
+        "Qbtrace-conf:bts:size+",
+        "Qbtrace:off+",
+        "qXfer:btrace:read+",
+    ]
+    .iter()
+    {
+        assert!(features.iter().any(|f| f == feature), "{:?}", features);
+    }
+    assert!(!features.iter().any(|f| f.starts_with("Qbtrace:pt")));
+    assert!(!features.iter().any(|f| f.starts_with("Qbtrace-conf:pt")));
+}
+
+#[test]
+fn enable_bts_and_page_trace() {
+    let mut target = BtraceTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["Qbtrace-conf:bts:size=0x10000", "Qbtrace:bts"],
+    );
+    assert_eq!(res, ["OK", "OK"]);
+
+    let data = read_xfer_paged(
+        &mut target,
+        "qXfer:btrace:read:all:",
+        TRACE.len(),
+        0x20,
+        None,
+    );
+    assert_eq!(data, TRACE);
+
+    let (res, _) = run_session(&mut target, &["Qbtrace:off"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.buffer_size, Some(0x10000));
+    assert_eq!(target.enabled, None);
+    assert_eq!(
+        target.reads,
+        [
+            (BtraceReadKind::All, 0),
+            (BtraceReadKind::All, 0x20),
+            (BtraceReadKind::All, 0x40)
+        ]
+    );
+}
+
+#[test]
+fn enable_passes_current_thread() {
+    let mut target = BtraceTarget::default();
+    let (res, _) = run_session(&mut target, &["Qbtrace:bts"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(
+        target.enabled,
+        Some((Tid::new(1).unwrap(), BtraceFormat::Bts))
+    );
+}
+
+#[test]
+fn read_kinds() {
+    let mut target = BtraceTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "Qbtrace:bts",
+            "qXfer:btrace:read:new:0,1000",
+            "qXfer:btrace:read:delta:0,1000",
+        ],
+    );
+    assert_eq!(res.len(), 3);
+    assert_eq!(
+        target.reads,
+        [(BtraceReadKind::New, 0), (BtraceReadKind::Delta, 0)]
+    );
+}
+
+#[test]
+fn errors() {
+    let mut target = BtraceTarget::default();
+    // reading / disabling the trace before it's been enabled
+    let (res, _) = run_session(&mut target, &["qXfer:btrace:read:all:0,20", "Qbtrace:off"]);
+    assert_eq!(res, ["E79", "E79"]);
+
+    // unsupported formats
+    let (_, result) = run_session(&mut target, &["Qbtrace:pt"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketUnexpected)));
+    assert_eq!(target.enabled, None);
+}
+
+#[test]
+fn malformed() {
+    for packet in [
+        "Qbtrace:foo",
+        "Qbtrace-conf:bts:count=10",
+        "Qbtrace-conf:bts:size=zz",
+        "qXfer:btrace:read:some:0,20",
+    ]
+    .iter()
+    {
+        let (_, result) = run_session(&mut BtraceTarget::default(), &[packet]);
+        assert!(
+            matches!(result, Err(crate::GdbStubError::PacketParse(_))),
+            "{}",
+            packet
+        );
+    }
+}
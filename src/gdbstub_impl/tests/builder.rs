@@ -0,0 +1,169 @@
+use std::cell::RefCell;
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{
+    Connection, GdbStub, GdbStubBuilderError, GdbStubError, PacketBuffer, PacketDirection,
+};
+
+struct BuilderTarget;
+
+impl Target for BuilderTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(BuilderTarget);
+
+std::thread_local! {
+    static TRACE: RefCell<Vec<(PacketDirection, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(direction: PacketDirection, packet: &[u8]) {
+    TRACE.with(|t| t.borrow_mut().push((direction, packet.to_vec())));
+}
+
+#[test]
+fn default_no_ack_mode() {
+    let mut conn = MockConnection::new(&["?", "g"]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .no_ack_mode(true)
+        .build()
+        .unwrap()
+        .run(&mut BuilderTarget);
+
+    assert!(!conn.output.contains(&b'+'));
+    assert_eq!(split_responses(&conn.output).len(), 2);
+}
+
+#[test]
+fn trace_packets() {
+    TRACE.with(|t| t.borrow_mut().clear());
+
+    let mut conn = MockConnection::new(&["+", "?", "m0,2"]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .trace_packets(record)
+        .build()
+        .unwrap()
+        .run(&mut BuilderTarget);
+
+    use PacketDirection::*;
+    let trace = TRACE.with(|t| t.take());
+    let trace = trace
+        .iter()
+        .map(|(d, p)| (*d, std::str::from_utf8(p).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        trace,
+        [
+            (Incoming, "+"),
+            (Incoming, "$?#3f"),
+            (Outgoing, "+"),
+            (Outgoing, "$T05thread:01;#07"),
+            (Incoming, "$m0,2#fb"),
+            (Outgoing, "+"),
+            (Outgoing, "$0000#c0"),
+        ]
+    );
+}
+
+/// Run a session using a packet buffer of `len` bytes.
+fn run_with_buffer(len: usize, packets: &[&str]) -> (Vec<String>, bool) {
+    let mut buf = vec![0; len];
+    let mut conn = MockConnection::new(packets);
+    let result = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .with_packet_buffer(&mut buf)
+        .build()
+        .unwrap()
+        .run(&mut BuilderTarget);
+    let overflowed = matches!(result, Err(GdbStubError::PacketBufferOverlow));
+    (split_responses(&conn.output), overflowed)
+}
+
+#[test]
+fn advertises_packet_buffer_size() {
+    let (res, _) = run_with_buffer(0x40, &["qSupported:swbreak+"]);
+    assert!(res[0].starts_with("PacketSize=40;"));
+}
+
+#[test]
+fn packet_buffer_size_truncates_buffer() {
+    let mut buf = [0; 0x100];
+    let mut conn = MockConnection::new(&["qSupported:swbreak+"]);
+    let mut gdb = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .with_packet_buffer(&mut buf)
+        .packet_buffer_size(0x20)
+        .build()
+        .unwrap();
+    assert_eq!(gdb.packet_buffer_size(), 0x20);
+
+    let _ = gdb.run(&mut BuilderTarget);
+    assert!(split_responses(&conn.output)[0].starts_with("PacketSize=20;"));
+}
+
+#[test]
+fn max_size_packet_fits() {
+    // "$" + body + "#xx" is exactly 0x40 bytes
+    let body = format!("M100,1a:{}", "ab".repeat(0x1a));
+    assert_eq!(body.len() + 4, 0x40);
+
+    let (res, overflowed) = run_with_buffer(0x40, &[&body]);
+    assert_eq!(res, ["OK"]);
+    assert!(!overflowed);
+
+    // ...but anything larger doesn't
+    let body = format!("M100,1a:{}0", "ab".repeat(0x1a));
+    let (res, overflowed) = run_with_buffer(0x40, &[&body]);
+    assert!(res.is_empty());
+    assert!(overflowed);
+}
+
+#[test]
+fn owned_packet_buffer() {
+    let mut conn = MockConnection::new(&["m100,4", "qSupported:swbreak+"]);
+    let mut gdb = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .with_owned_packet_buffer(PacketBuffer::<0x40>::new())
+        .build()
+        .unwrap();
+    assert_eq!(gdb.packet_buffer_size(), 0x40);
+
+    let _ = gdb.run(&mut BuilderTarget);
+    let res = split_responses(&conn.output);
+    assert_eq!(res[0], "00000000");
+    assert!(res[1].starts_with("PacketSize=40;"));
+}
+
+#[test]
+fn owned_packet_buffer_custom_size() {
+    // "$" + body + "#xx" is 0x21 bytes, which overflows a 0x20 byte buffer
+    let body = format!("M100,b:{}", "ab".repeat(0xb));
+    assert_eq!(body.len() + 4, 0x21);
+
+    let mut conn = MockConnection::new(&[&body]);
+    let mut gdb = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(0x20)
+        .with_owned_packet_buffer(PacketBuffer::<0x100>::new())
+        .build()
+        .unwrap();
+    assert_eq!(gdb.packet_buffer_size(), 0x20);
+    let result = gdb.run(&mut BuilderTarget);
+    assert!(matches!(result, Err(GdbStubError::PacketBufferOverlow)));
+
+    let mut conn = MockConnection::new(&[]);
+    let result = GdbStub::<BuilderTarget, _>::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .with_owned_packet_buffer(PacketBuffer::<0x10>::new())
+        .packet_buffer_size(0x20)
+        .build();
+    assert!(matches!(
+        result,
+        Err(GdbStubBuilderError::PacketBufSizeMismatch)
+    ));
+}
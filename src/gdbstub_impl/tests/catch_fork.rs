@@ -0,0 +1,114 @@
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Pid;
+use crate::target::ext::base::singlethread::{SingleThreadOps, StopReason};
+use crate::target::ext::base::{BaseOps, ResumeAction};
+use crate::target::ext::catch_fork::{CatchFork, CatchForkOps};
+use crate::target::{Target, TargetResult};
+
+/// A target which forks (or vforks) as soon as it's resumed.
+struct ForkTarget {
+    vfork: bool,
+    events: Option<(bool, bool)>,
+}
+
+impl ForkTarget {
+    fn new(vfork: bool) -> ForkTarget {
+        ForkTarget {
+            vfork,
+            events: None,
+        }
+    }
+}
+
+impl Target for ForkTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn catch_fork(&mut self) -> Option<CatchForkOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for ForkTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        let new_pid = Pid::new(0x42).unwrap();
+        if self.vfork {
+            Ok(StopReason::Vfork { new_pid })
+        } else {
+            Ok(StopReason::Fork { new_pid })
+        }
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl CatchFork for ForkTarget {
+    fn set_fork_events(&mut self, fork: bool, vfork: bool) -> TargetResult<(), Self> {
+        self.events = Some((fork, vfork));
+        Ok(())
+    }
+}
+
+#[test]
+fn negotiates_fork_events() {
+    let mut target = ForkTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;fork-events+;vfork-events-"],
+    );
+    assert!(res[0].contains(";fork-events+"));
+    assert!(!res[0].contains("vfork-events"));
+    assert_eq!(target.events, Some((true, false)));
+}
+
+#[test]
+fn reports_fork() {
+    let mut target = ForkTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;fork-events+;vfork-events+", "c"],
+    );
+    assert_eq!(res[1], "T05thread:p01.01;fork:p42.01;");
+}
+
+#[test]
+fn reports_vfork() {
+    let mut target = ForkTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;fork-events+;vfork-events+", "c"],
+    );
+    assert_eq!(res[1], "T05thread:p01.01;vfork:p42.01;");
+}
+
+#[test]
+fn falls_back_to_sigtrap() {
+    let mut target = ForkTarget::new(false);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "c"]);
+    assert!(!res[0].contains("fork-events"));
+    assert_eq!(res[1], "T05thread:p01.01;");
+}
@@ -0,0 +1,174 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{
+    ConsoleOutput, ResumeAction, SingleThreadOps, StopReason,
+};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::{Connection, GdbStub};
+
+/// A target which logs a few messages to the GDB console while running, before
+/// hitting a breakpoint.
+struct LoggingTarget;
+
+impl Target for LoggingTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for LoggingTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::SwBreak)
+    }
+
+    fn resume_with_console_output(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        mut console: ConsoleOutput<'_>,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        crate::outputln!(console, "hello");
+        console.flush();
+        crate::outputln!(console, "world");
+        console.flush();
+        // left for `gdbstub` to flush
+        console.write_raw(b"!");
+        Ok(StopReason::SwBreak)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+/// A target which writes a single message to the GDB console while running.
+struct MessageTarget {
+    msg: Vec<u8>,
+}
+
+impl Target for MessageTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for MessageTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::SwBreak)
+    }
+
+    fn resume_with_console_output(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        mut console: ConsoleOutput<'_>,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        console.write_raw(&self.msg);
+        Ok(StopReason::SwBreak)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+#[test]
+fn output_precedes_stop_reply() {
+    let mut conn = MockConnection::new(&["c"]);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut LoggingTarget);
+
+    assert_eq!(
+        split_responses(&conn.output),
+        ["O68656c6c6f0a", "O776f726c640a", "O21", "T05thread:01;"]
+    );
+
+    // each `O` packet is a complete, separately framed packet
+    let output = String::from_utf8(conn.output).unwrap();
+    assert_eq!(
+        output,
+        "+$O68656c6c6f0a#87$O776f726c640a#56$O21#b2$T05thread:01;#07"
+    );
+}
+
+#[test]
+fn output_fits_packet_buffer() {
+    // "$O" + 30 hex-encoded bytes + "#xx" exactly fills the 65 byte buffer
+    const PACKET_BUFFER_SIZE: usize = 65;
+    const MAX_CHUNK: usize = 30;
+
+    for (len, packets) in [(MAX_CHUNK, 1), (MAX_CHUNK + 1, 2)].iter().copied() {
+        // distinct bytes, so that the output can't be run-length encoded
+        let msg = (0..len as u8).collect::<Vec<_>>();
+        let mut target = MessageTarget { msg: msg.clone() };
+
+        let mut conn = MockConnection::new(&["c"]);
+        let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+            .packet_buffer_size(PACKET_BUFFER_SIZE)
+            .build()
+            .unwrap()
+            .run(&mut target);
+
+        let output = String::from_utf8(conn.output).unwrap();
+        let framed = output
+            .split('$')
+            .filter(|p| p.starts_with('O'))
+            .collect::<Vec<_>>();
+        assert_eq!(framed.len(), packets, "{:?}", framed);
+        assert_eq!(framed[0].len() + 1, PACKET_BUFFER_SIZE);
+        assert!(framed.iter().all(|p| p.len() < PACKET_BUFFER_SIZE));
+
+        let hex = framed
+            .iter()
+            .map(|p| &p[1..p.len() - 3])
+            .collect::<String>();
+        let decoded = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, msg);
+    }
+}
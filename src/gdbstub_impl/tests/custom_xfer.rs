@@ -0,0 +1,186 @@
+use std::vec::Vec;
+
+use super::mock::{copy_xfer_chunk, read_xfer_paged, run_session, supported_features};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::auxv::{Auxv, AuxvOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::custom_xfer::{CustomXfer, CustomXferObject, CustomXferOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+const OBJECTS: &[CustomXferObject] = &[
+    CustomXferObject {
+        name: "spu",
+        read: true,
+        write: true,
+    },
+    CustomXferObject {
+        name: "vendor-info",
+        read: true,
+        write: false,
+    },
+    CustomXferObject {
+        name: "auxv",
+        read: true,
+        write: false,
+    },
+];
+
+const VENDOR_INFO: &[u8] = b"<vendor><chip rev=\"2\"/></vendor>";
+
+/// A target with a writable per-SPU local store (selected by the annex), and
+/// a read-only vendor-specific XML document.
+struct XferTarget {
+    local_store: [Vec<u8>; 2],
+    implements_auxv: bool,
+}
+
+impl XferTarget {
+    fn new() -> XferTarget {
+        XferTarget {
+            local_store: [vec![0; 8], vec![0; 8]],
+            implements_auxv: false,
+        }
+    }
+}
+
+impl Target for XferTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn auxv(&mut self) -> Option<AuxvOps<'_, Self>> {
+        if self.implements_auxv {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn custom_xfer(&mut self) -> Option<CustomXferOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(XferTarget);
+
+impl Auxv for XferTarget {
+    fn get_auxv(&self, offset: u64, length: usize, buf: &mut [u8]) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(b"builtin", offset, length, buf))
+    }
+}
+
+impl XferTarget {
+    fn spu(&mut self, annex: &[u8]) -> Result<&mut Vec<u8>, TargetError<&'static str>> {
+        match annex {
+            b"0" => Ok(&mut self.local_store[0]),
+            b"1" => Ok(&mut self.local_store[1]),
+            _ => Err(TargetError::Errno(2)),
+        }
+    }
+}
+
+impl CustomXfer for XferTarget {
+    fn custom_xfer_objects(&self) -> &[CustomXferObject] {
+        OBJECTS
+    }
+
+    fn read_object(
+        &mut self,
+        object: &str,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        match object {
+            "spu" => Ok(copy_xfer_chunk(self.spu(annex)?, offset, length, buf)),
+            "vendor-info" => Ok(copy_xfer_chunk(VENDOR_INFO, offset, length, buf)),
+            "auxv" => Ok(copy_xfer_chunk(b"custom", offset, length, buf)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_object(
+        &mut self,
+        object: &str,
+        annex: &[u8],
+        offset: u64,
+        data: &[u8],
+    ) -> TargetResult<usize, Self> {
+        assert_eq!(object, "spu");
+        let store = self.spu(annex)?;
+        let dst = store
+            .get_mut(offset as usize..)
+            .ok_or(TargetError::Errno(22))?;
+        let len = dst.len().min(data.len());
+        dst[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+}
+
+#[test]
+fn advertises_objects() {
+    let features = supported_features(&mut XferTarget::new());
+    assert!(features.iter().any(|f| f == "qXfer:spu:read+"));
+    assert!(features.iter().any(|f| f == "qXfer:spu:write+"));
+    assert!(features.iter().any(|f| f == "qXfer:vendor-info:read+"));
+    assert!(!features.iter().any(|f| f == "qXfer:vendor-info:write+"));
+}
+
+#[test]
+fn pages_large_objects() {
+    let data = read_xfer_paged(
+        &mut XferTarget::new(),
+        "qXfer:vendor-info:read::",
+        VENDOR_INFO.len(),
+        0x10,
+        None,
+    );
+    assert_eq!(data, VENDOR_INFO);
+}
+
+#[test]
+fn writes_use_annex() {
+    let mut target = XferTarget::new();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qXfer:spu:write:1:2:ab:c",
+            "qXfer:spu:read:1:0,8",
+            "qXfer:spu:write:1:6:wxyz",
+            "qXfer:spu:write:7:0:a",
+        ],
+    );
+    // writes past the end of the local store are truncated
+    assert_eq!(res, ["04", "m\0\0ab:c\0\0", "02", "E02"]);
+    assert_eq!(target.local_store[0], [0; 8]);
+    assert_eq!(target.local_store[1], b"\0\0ab:cwx");
+}
+
+#[test]
+fn unsupported_requests_are_empty() {
+    let mut target = XferTarget::new();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qXfer:unknown:read::0,10",
+            "qXfer:vendor-info:write::0:abc",
+            "qXfer:vendor-info:read::0,4",
+        ],
+    );
+    assert_eq!(res, ["", "", "m<ven"]);
+}
+
+#[test]
+fn builtin_objects_take_precedence() {
+    let mut target = XferTarget::new();
+    let (res, _) = run_session(&mut target, &["qXfer:auxv:read::0,10"]);
+    assert_eq!(res, ["lcustom"]);
+
+    target.implements_auxv = true;
+    let (res, _) = run_session(&mut target, &["qXfer:auxv:read::0,10"]);
+    assert_eq!(res, ["lbuiltin"]);
+}
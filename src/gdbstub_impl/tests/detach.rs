@@ -0,0 +1,127 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Pid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{Args, ExtendedMode, ExtendedModeOps, ShouldTerminate};
+use crate::target::{Target, TargetError, TargetResult};
+use crate::DisconnectReason;
+
+/// A target which records whether it was detached from or killed.
+#[derive(Default)]
+struct DetachTarget {
+    detaches: usize,
+    kills: Vec<Option<Pid>>,
+    fail_detach: bool,
+}
+
+impl Target for DetachTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for DetachTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn detach(&mut self) -> TargetResult<(), Self> {
+        if self.fail_detach {
+            self.fail_detach = false;
+            return Err(TargetError::Errno(1));
+        }
+        self.detaches += 1;
+        Ok(())
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl ExtendedMode for DetachTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        Ok(ThreadStopReason::Signal(0))
+    }
+
+    fn kill(&mut self, pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        self.kills.push(pid);
+        Ok(ShouldTerminate::Yes)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn detach_leaves_target_running() {
+    let mut target = DetachTarget::default();
+    let (res, result) = run_session(&mut target, &["D", "g"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(result.unwrap(), DisconnectReason::Disconnect);
+    assert_eq!(target.detaches, 1);
+    assert!(target.kills.is_empty());
+}
+
+#[test]
+fn detach_last_pid_detaches_target() {
+    let mut target = DetachTarget::default();
+    let (res, result) = run_session(&mut target, &["qSupported:multiprocess+", "D;1"]);
+    assert_eq!(res[1..], ["OK"]);
+    assert_eq!(result.unwrap(), DisconnectReason::Disconnect);
+    assert_eq!(target.detaches, 1);
+}
+
+#[test]
+fn detach_error_keeps_session_open() {
+    let mut target = DetachTarget {
+        fail_detach: true,
+        ..Default::default()
+    };
+    let (res, result) = run_session(&mut target, &["D", "D"]);
+    assert_eq!(res, ["E01", "OK"]);
+    assert_eq!(result.unwrap(), DisconnectReason::Disconnect);
+    assert_eq!(target.detaches, 1);
+}
+
+#[test]
+fn kill_does_not_detach() {
+    let mut target = DetachTarget::default();
+    let (_, result) = run_session(&mut target, &["vKill;1"]);
+    assert_eq!(result.unwrap(), DisconnectReason::Kill);
+    assert_eq!(target.kills, [Some(Pid::new(1).unwrap())]);
+    assert_eq!(target.detaches, 0);
+}
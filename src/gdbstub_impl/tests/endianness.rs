@@ -0,0 +1,116 @@
+use std::string::String;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::arch::Endian;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::endianness_override::{EndiannessOverride, EndiannessOverrideOps};
+use crate::target::{Target, TargetResult};
+
+/// A bi-endian target whose byte order can be switched at runtime.
+struct BiEndianTarget {
+    regs: ArmCoreRegs,
+    endian: Option<Endian>,
+}
+
+impl BiEndianTarget {
+    fn new(endian: Option<Endian>) -> BiEndianTarget {
+        let mut regs = ArmCoreRegs::default();
+        regs.r[0] = 0x1122_3344;
+        BiEndianTarget { regs, endian }
+    }
+}
+
+impl Target for BiEndianTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn endianness_override(&mut self) -> Option<EndiannessOverrideOps<'_, Self>> {
+        if self.endian.is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl EndiannessOverride for BiEndianTarget {
+    fn endianness(&self) -> Endian {
+        self.endian.unwrap()
+    }
+}
+
+impl SingleThreadOps for BiEndianTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        *regs = self.regs.clone();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        self.regs = regs.clone();
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+/// A `G` packet which sets `r0` to `r0` (and every other register to zero).
+fn g_packet(r0: &str) -> String {
+    format!("G{}{}", r0, "00".repeat(41 * 4))
+}
+
+#[test]
+fn defaults_to_arch_endianness() {
+    let mut target = BiEndianTarget::new(None);
+    let (res, _) = run_session(&mut target, &["g"]);
+    assert!(res[0].starts_with("44332211"));
+}
+
+#[test]
+fn override_little_endian() {
+    let mut target = BiEndianTarget::new(Some(Endian::Little));
+    let (res, _) = run_session(&mut target, &["g", &g_packet("efbeadde")]);
+    assert!(res[0].starts_with("44332211"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(target.regs.r[0], 0xdead_beef);
+}
+
+#[test]
+fn override_big_endian() {
+    let mut target = BiEndianTarget::new(Some(Endian::Big));
+    let (res, _) = run_session(&mut target, &["g", &g_packet("deadbeef")]);
+    assert!(res[0].starts_with("11223344"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(target.regs.r[0], 0xdead_beef);
+}
+
+#[test]
+fn override_switched_at_runtime() {
+    let mut target = BiEndianTarget::new(Some(Endian::Little));
+    let (res, _) = run_session(&mut target, &["g"]);
+    assert!(res[0].starts_with("44332211"));
+
+    target.endian = Some(Endian::Big);
+    let (res, _) = run_session(&mut target, &["g"]);
+    assert!(res[0].starts_with("11223344"));
+}
@@ -0,0 +1,97 @@
+use std::string::String;
+
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::common::Pid;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::exec_file::{ExecFile, ExecFileOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+struct ExecFileTarget {
+    path: String,
+}
+
+impl Target for ExecFileTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn exec_file(&mut self) -> Option<ExecFileOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ExecFileTarget);
+
+impl ExecFile for ExecFileTarget {
+    fn get_exec_file(
+        &self,
+        pid: Option<Pid>,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        // the target only knows about a single process (pid 1)
+        if pid.map(|pid| pid.get() != 1).unwrap_or(false) {
+            return Err(TargetError::Errno(2));
+        }
+
+        Ok(copy_xfer_chunk(self.path.as_bytes(), offset, length, buf))
+    }
+}
+
+fn target() -> ExecFileTarget {
+    ExecFileTarget {
+        path: "/usr/bin/true".into(),
+    }
+}
+
+#[test]
+fn advertises_exec_file() {
+    assert!(advertises(&mut target(), "qXfer:exec-file:read+"));
+}
+
+#[test]
+fn read_with_pid() {
+    let (res, _) = run_session(&mut target(), &["qXfer:exec-file:read:1:0,fff"]);
+    assert_eq!(res, ["l/usr/bin/true"]);
+}
+
+#[test]
+fn read_current_process() {
+    let (res, _) = run_session(&mut target(), &["qXfer:exec-file:read::0,fff"]);
+    assert_eq!(res, ["l/usr/bin/true"]);
+}
+
+#[test]
+fn unknown_pid() {
+    let (res, _) = run_session(
+        &mut target(),
+        &[
+            "qXfer:exec-file:read:2a:0,fff",
+            "qXfer:exec-file:read:1:0,4",
+        ],
+    );
+    assert_eq!(res, ["E02", "m/usr"]);
+}
+
+#[test]
+fn paged_long_path() {
+    let mut target = ExecFileTarget {
+        path: format!("/opt/{}/bin/program", "very-long-directory-name/".repeat(8)),
+    };
+    let path = target.path.clone();
+
+    // the 64 byte packet buffer bounds the size of each chunk
+    let data = read_xfer_paged(
+        &mut target,
+        "qXfer:exec-file:read:1:",
+        path.len(),
+        64,
+        Some(64),
+    );
+    assert_eq!(data, path.as_bytes());
+}
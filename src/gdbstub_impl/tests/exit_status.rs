@@ -0,0 +1,137 @@
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Pid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{Args, ExtendedMode, ExtendedModeOps, ShouldTerminate};
+use crate::target::{Target, TargetResult};
+use crate::DisconnectReason;
+
+/// A target which exits (or is terminated) as soon as it's resumed.
+struct ExitTarget {
+    stop_reason: StopReason<u32>,
+    extended: bool,
+}
+
+impl ExitTarget {
+    fn new(stop_reason: StopReason<u32>) -> ExitTarget {
+        ExitTarget {
+            stop_reason,
+            extended: false,
+        }
+    }
+}
+
+impl Target for ExitTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        if self.extended {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl SingleThreadOps for ExitTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(self.stop_reason)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl ExtendedMode for ExitTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        Ok(ShouldTerminate::No)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn supports_multiprocess(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn clean_exit() {
+    let mut target = ExitTarget::new(StopReason::Exited { code: 0 });
+    let (res, result) = run_session(&mut target, &["vCont;c", "g"]);
+    // the session ends right after the exit is reported
+    assert_eq!(res, ["W00"]);
+    assert_eq!(result.unwrap(), DisconnectReason::TargetExited(0));
+}
+
+#[test]
+fn nonzero_exit() {
+    let mut target = ExitTarget::new(StopReason::Exited { code: 42 });
+    let (res, result) = run_session(&mut target, &["c"]);
+    assert_eq!(res, ["W2a"]);
+    assert_eq!(result.unwrap(), DisconnectReason::TargetExited(42));
+}
+
+#[test]
+fn terminated_by_signal() {
+    let mut target = ExitTarget::new(StopReason::Terminated { signal: 9 });
+    let (res, result) = run_session(&mut target, &["vCont;c"]);
+    assert_eq!(res, ["X09"]);
+    assert_eq!(result.unwrap(), DisconnectReason::TargetTerminated(9));
+}
+
+#[test]
+fn multiprocess_exit() {
+    let mut target = ExitTarget::new(StopReason::Exited { code: 1 });
+    target.extended = true;
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "vCont;c"]);
+    assert_eq!(res[1], "W01;process:01");
+
+    let mut target = ExitTarget::new(StopReason::Terminated { signal: 11 });
+    target.extended = true;
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "vCont;c"]);
+    assert_eq!(res[1], "X0b;process:01");
+}
+
+#[test]
+fn extended_mode_stays_connected() {
+    let mut target = ExitTarget::new(StopReason::Exited { code: 0 });
+    target.extended = true;
+    let (res, result) = run_session(&mut target, &["!", "vCont;c", "vRun;"]);
+    assert_eq!(res, ["OK", "W00", "S05"]);
+    assert!(result.is_err()); // eof
+}
@@ -0,0 +1,95 @@
+use super::mock::run_session;
+use crate::arch::arm::reg::id::ArmCoreRegId;
+use crate::arch::arm::reg::ArmCoreRegs;
+use crate::arch::Arch;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetError, TargetResult};
+
+/// An ARM arch which expedites the SP, LR, and PC.
+enum ExpeditedArm {}
+
+impl Arch for ExpeditedArm {
+    type Usize = u32;
+    type Registers = ArmCoreRegs;
+    type RegId = ArmCoreRegId;
+    type BreakpointKind = crate::arch::arm::ArmBreakpointKind;
+
+    fn expedited_registers() -> &'static [usize] {
+        &[13, 14, 15]
+    }
+}
+
+/// A target where stepping completes a step, and continuing hits a
+/// breakpoint. The LR is unavailable.
+struct ExpeditedTarget;
+
+impl Target for ExpeditedTarget {
+    type Arch = ExpeditedArm;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for ExpeditedTarget {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        match action {
+            ResumeAction::Step(_) => Ok(StopReason::DoneStep),
+            _ => Ok(StopReason::SwBreak),
+        }
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_register(&mut self, reg_id: ArmCoreRegId, dst: &mut [u8]) -> TargetResult<usize, Self> {
+        let val: u32 = match reg_id {
+            ArmCoreRegId::Sp => 0x1000,
+            ArmCoreRegId::Pc => 0xdead_beef,
+            _ => return Err(TargetError::NonFatal),
+        };
+        dst.copy_from_slice(&val.to_le_bytes());
+        Ok(dst.len())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+#[test]
+fn breakpoint_stop_includes_expedited_registers() {
+    let (res, _) = run_session(&mut ExpeditedTarget, &["c"]);
+    assert_eq!(res, ["T05thread:01;0d:00100000;0f:efbeadde;"]);
+}
+
+#[test]
+fn step_stop_includes_expedited_registers() {
+    let (res, _) = run_session(&mut ExpeditedTarget, &["s", "vCont;s:p1.1"]);
+    assert_eq!(
+        res,
+        ["T050d:00100000;0f:efbeadde;", "T050d:00100000;0f:efbeadde;"]
+    );
+}
+
+#[test]
+fn stop_query_includes_expedited_registers() {
+    let (res, _) = run_session(&mut ExpeditedTarget, &["?"]);
+    assert_eq!(res, ["T05thread:01;0d:00100000;0f:efbeadde;"]);
+}
@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use super::mock::{run_session, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::common::Pid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{
+    Args, AttachKind, ConfigureASLR, ConfigureASLROps, ConfigureEnv, ConfigureEnvOps,
+    ConfigureStartupShell, ConfigureStartupShellOps, ConfigureWorkingDir, ConfigureWorkingDirOps,
+    ExtendedMode, ExtendedModeOps, ShouldTerminate,
+};
+use crate::target::{Target, TargetError, TargetResult};
+use crate::{Connection, GdbStub};
+
+type Env = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// A target which records the filename + args (and environment) of each
+/// spawned process.
+#[derive(Default)]
+struct RunTarget {
+    runs: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
+    env: Env,
+    run_envs: Vec<Env>,
+    restarts: usize,
+    kills: Vec<Option<Pid>>,
+    startup_with_shell: bool,
+    run_with_shell: Vec<bool>,
+    working_dir: Option<Vec<u8>>,
+    run_dirs: Vec<Option<Vec<u8>>>,
+    aslr: bool,
+    run_with_aslr: Vec<bool>,
+    /// The target can't configure ASLR.
+    fixed_aslr: bool,
+}
+
+impl Target for RunTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(RunTarget);
+
+impl ExtendedMode for RunTarget {
+    fn run(&mut self, filename: Option<&[u8]>, args: Args) -> TargetResult<Pid, Self> {
+        let filename = filename.ok_or(TargetError::Errno(2))?;
+        self.runs
+            .push((filename.to_vec(), args.map(|a| a.to_vec()).collect()));
+        self.run_envs.push(self.env.clone());
+        self.run_with_shell.push(self.startup_with_shell);
+        self.run_dirs.push(self.working_dir.clone());
+        self.run_with_aslr.push(self.aslr);
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        // only pid 7 exists
+        if pid.get() != 7 {
+            return Err(TargetError::Errno(3));
+        }
+        Ok(ThreadStopReason::Signal(0))
+    }
+
+    fn query_if_attached(&mut self, pid: Pid) -> TargetResult<AttachKind, Self> {
+        // pid 9 was spawned before GDB connected
+        Ok(match pid.get() {
+            9 => AttachKind::Run,
+            _ => AttachKind::Attach,
+        })
+    }
+
+    fn kill(&mut self, pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        self.kills.push(pid);
+        Ok(ShouldTerminate::Yes)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        self.restarts += 1;
+        Ok(())
+    }
+
+    fn configure_aslr(&mut self) -> Option<ConfigureASLROps<'_, Self>> {
+        if self.fixed_aslr {
+            return None;
+        }
+        Some(self)
+    }
+
+    fn configure_env(&mut self) -> Option<ConfigureEnvOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn configure_startup_shell(&mut self) -> Option<ConfigureStartupShellOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn configure_working_dir(&mut self) -> Option<ConfigureWorkingDirOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl ConfigureASLR for RunTarget {
+    fn cfg_aslr(&mut self, enabled: bool) -> TargetResult<(), Self> {
+        self.aslr = enabled;
+        Ok(())
+    }
+}
+
+impl ConfigureWorkingDir for RunTarget {
+    fn cfg_working_dir(&mut self, dir: Option<&[u8]>) -> TargetResult<(), Self> {
+        self.working_dir = dir.map(|d| d.to_vec());
+        Ok(())
+    }
+}
+
+impl ConfigureStartupShell for RunTarget {
+    fn cfg_startup_with_shell(&mut self, enabled: bool) -> TargetResult<(), Self> {
+        self.startup_with_shell = enabled;
+        Ok(())
+    }
+}
+
+impl ConfigureEnv for RunTarget {
+    fn set_env(&mut self, key: &[u8], val: Option<&[u8]>) -> TargetResult<(), Self> {
+        self.env
+            .insert(key.to_vec(), val.unwrap_or_default().to_vec());
+        Ok(())
+    }
+
+    fn remove_env(&mut self, key: &[u8]) -> TargetResult<(), Self> {
+        self.env.remove(key);
+        Ok(())
+    }
+
+    fn reset_env(&mut self) -> TargetResult<(), Self> {
+        self.env.clear();
+        Ok(())
+    }
+}
+
+fn env(vars: &[(&str, &str)]) -> Env {
+    vars.iter()
+        .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+        .collect()
+}
+
+#[test]
+fn run_with_args() {
+    let mut target = RunTarget::default();
+    // run /bin/prog foo "" bar
+    let (res, _) = run_session(&mut target, &["vRun;2f62696e2f70726f67;666f6f;;626172"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(
+        target.runs,
+        [(
+            b"/bin/prog".to_vec(),
+            vec![b"foo".to_vec(), vec![], b"bar".to_vec()]
+        )]
+    );
+}
+
+#[test]
+fn rerun_last_program() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["vRun;70726f67;61", "vRun;;62"]);
+    assert_eq!(res, ["S05", "S05"]);
+    assert_eq!(
+        target.runs,
+        [
+            (b"prog".to_vec(), vec![b"a".to_vec()]),
+            (b"prog".to_vec(), vec![b"b".to_vec()]),
+        ]
+    );
+}
+
+#[test]
+fn rerun_without_previous_program() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["vRun;"]);
+    assert_eq!(res, ["E02"]);
+    assert!(target.runs.is_empty());
+}
+
+#[test]
+fn advertises_env() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";QEnvironmentHexEncoded+;QEnvironmentUnset+;QEnvironmentReset+"));
+}
+
+#[test]
+fn env_applies_to_next_run() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            // GDB resets the environment before sending any variables
+            "QEnvironmentReset",
+            "QEnvironmentHexEncoded:464f4f3d31", // FOO=1
+            "QEnvironmentHexEncoded:4241523d",   // BAR=
+            "QEnvironmentHexEncoded:42415a3d32", // BAZ=2
+            "QEnvironmentUnset:42415a",          // BAZ
+            "vRun;70726f67",
+            "QEnvironmentReset",
+            "QEnvironmentHexEncoded:42415a3d333d34", // BAZ=3=4
+            "vRun;70726f67",
+        ],
+    );
+    assert_eq!(
+        res,
+        ["OK", "OK", "OK", "OK", "OK", "S05", "OK", "OK", "S05"]
+    );
+    assert_eq!(
+        target.run_envs,
+        [env(&[("FOO", "1"), ("BAR", "")]), env(&[("BAZ", "3=4")])]
+    );
+}
+
+#[test]
+fn startup_with_shell_applies_to_next_run() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qSupported:multiprocess+",
+            "QStartupWithShell:1",
+            "vRun;70726f67",
+            "QStartupWithShell:0",
+            "vRun;70726f67",
+        ],
+    );
+    assert!(res[0].contains(";QStartupWithShell+"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(res[3], "OK");
+    assert_eq!(target.run_with_shell, [true, false]);
+}
+
+#[test]
+fn disable_randomization_applies_to_next_run() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "QDisableRandomization:1",
+            "vRun;70726f67",
+            "QDisableRandomization:0",
+            "vRun;70726f67",
+        ],
+    );
+    assert_eq!(res, ["OK", "S05", "OK", "S05"]);
+    assert_eq!(target.run_with_aslr, [false, true]);
+}
+
+#[test]
+fn advertises_disable_randomization() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";QDisableRandomization+"));
+
+    let mut target = RunTarget {
+        fixed_aslr: true,
+        ..RunTarget::default()
+    };
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+", "QDisableRandomization:1"],
+    );
+    assert!(!res[0].contains("QDisableRandomization"));
+    // unsupported packets get an empty reply
+    assert_eq!(res[1], "");
+}
+
+#[test]
+fn startup_with_shell_malformed() {
+    let mut target = RunTarget::default();
+    let (_, result) = run_session(&mut target, &["QStartupWithShell:2"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketParse(_))));
+    assert!(!target.startup_with_shell);
+}
+
+#[test]
+fn working_dir_applies_to_next_run() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qSupported:multiprocess+",
+            // "/tmp/caf\xc3\xa9/\xff"
+            "QSetWorkingDir:2f746d702f636166c3a92fff",
+            "vRun;70726f67",
+            // reset to the default
+            "QSetWorkingDir:",
+            "vRun;70726f67",
+        ],
+    );
+    assert!(res[0].contains(";QSetWorkingDir+"));
+    assert_eq!(res[1], "OK");
+    assert_eq!(res[3], "OK");
+    assert_eq!(
+        target.run_dirs,
+        [Some(b"/tmp/caf\xc3\xa9/\xff".to_vec()), None]
+    );
+}
+
+#[test]
+fn working_dir_malformed() {
+    let mut target = RunTarget::default();
+    let (_, result) = run_session(&mut target, &["QSetWorkingDir:2fzz"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketParse(_))));
+    assert!(target.working_dir.is_none());
+}
+
+#[test]
+fn attach() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["vAttach;7", "vAttach;8"]);
+    assert_eq!(res, ["S00", "E03"]);
+}
+
+#[test]
+fn query_attached() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["qAttached:9", "qAttached:3", "vAttach;7", "qAttached:7"],
+    );
+    assert_eq!(res, ["0", "1", "S00", "1"]);
+}
+
+#[test]
+fn query_attached_spawned_process() {
+    let mut target = RunTarget::default();
+    let (res, _) = run_session(&mut target, &["vRun;70726f67", "qAttached:1", "qAttached"]);
+    // processes spawned via `vRun` are tracked by gdbstub (and `qAttached`
+    // without a pid refers to the current process)
+    assert_eq!(res[1..], ["0", "0"]);
+}
+
+#[test]
+fn restart_has_no_reply() {
+    let mut target = RunTarget::default();
+    let mut conn = MockConnection::new(&["R00", "?"]);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut target);
+
+    assert_eq!(target.restarts, 1);
+    // the `R` packet is acked, but the only response is the reply to `?`
+    assert_eq!(conn.output, b"++$T05thread:01;#07");
+}
+
+#[test]
+fn retransmit_after_restart() {
+    let mut target = RunTarget::default();
+    let mut conn = MockConnection::new(&["?", "R00", "-"]);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut target);
+
+    // `R` has no response, so the nack refers to the reply to `?`
+    assert_eq!(conn.output, b"+$T05thread:01;#07+$T05thread:01;#07");
+}
+
+#[test]
+fn kill_has_no_reply() {
+    let mut target = RunTarget::default();
+    let mut conn = MockConnection::new(&["k"]);
+    let result = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut target);
+    assert!(matches!(result, Ok(crate::DisconnectReason::Kill)));
+    // the packet is acked, but no response is sent
+    assert_eq!(conn.output, b"+");
+    assert_eq!(target.kills, [None]);
+}
+
+#[test]
+fn vkill_replies_ok() {
+    let mut target = RunTarget::default();
+    let (res, result) = run_session(&mut target, &["vKill;1"]);
+    assert!(matches!(result, Ok(crate::DisconnectReason::Kill)));
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.kills, [Some(Pid::new(1).unwrap())]);
+}
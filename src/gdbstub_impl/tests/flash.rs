@@ -0,0 +1,100 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::flash::{Flash, FlashOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A target with 0x100 bytes of flash at address 0x1000, which buffers writes
+/// until `done` is called.
+struct FlashTarget {
+    flash: Vec<u8>,
+    pending: Vec<(u32, Vec<u8>)>,
+}
+
+impl FlashTarget {
+    fn new() -> FlashTarget {
+        FlashTarget {
+            flash: vec![0; 0x100],
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Target for FlashTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn flash(&mut self) -> Option<FlashOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(FlashTarget);
+
+impl Flash for FlashTarget {
+    fn erase(&mut self, start_addr: u32, length: u32) -> TargetResult<(), Self> {
+        let start = start_addr
+            .checked_sub(0x1000)
+            .ok_or(TargetError::Errno(14))? as usize;
+        let region = self
+            .flash
+            .get_mut(start..start + length as usize)
+            .ok_or(TargetError::Errno(14))?;
+        region.iter_mut().for_each(|b| *b = 0xff);
+        Ok(())
+    }
+
+    fn write(&mut self, start_addr: u32, data: &[u8]) -> TargetResult<(), Self> {
+        self.pending.push((start_addr, data.to_vec()));
+        Ok(())
+    }
+
+    fn done(&mut self) -> TargetResult<(), Self> {
+        for (addr, data) in self.pending.drain(..) {
+            let start = (addr - 0x1000) as usize;
+            self.flash[start..start + data.len()].copy_from_slice(&data);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn erase_write_done() {
+    let mut target = FlashTarget::new();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "vFlashErase:1000,10",
+            // "}]" and "}\x03" are the escaped forms of '}' and '#'
+            "vFlashWrite:1004:a}]b}\x03:é",
+            "vFlashDone",
+        ],
+    );
+    assert_eq!(res, ["OK", "OK", "OK"]);
+
+    assert_eq!(&target.flash[..4], [0xff; 4]);
+    assert_eq!(&target.flash[4..11], b"a}b#:\xc3\xa9");
+    assert_eq!(&target.flash[11..0x10], [0xff; 5]);
+    assert_eq!(target.flash[0x10], 0);
+}
+
+#[test]
+fn writes_deferred_until_done() {
+    let mut target = FlashTarget::new();
+    let (res, _) = run_session(&mut target, &["vFlashErase:1000,10", "vFlashWrite:1000:ab"]);
+    assert_eq!(res, ["OK", "OK"]);
+    assert_eq!(&target.flash[..2], [0xff; 2]);
+}
+
+#[test]
+fn erase_error() {
+    let mut target = FlashTarget::new();
+    let (res, _) = run_session(&mut target, &["vFlashErase:2000,10"]);
+    assert_eq!(res, ["E0e"]);
+}
@@ -0,0 +1,163 @@
+use super::mock::{run_session, split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::host_io::{
+    FsKind, HostIo, HostIoErrno, HostIoError, HostIoOps, HostIoResult, HostIoStat,
+};
+use crate::target::Target;
+use crate::{Connection, GdbStub};
+
+const FILE: &[u8] = b"hello $world#";
+const BIG_FILE: &[u8] = &[b'a'; 100];
+
+#[derive(Default)]
+struct HostIoTarget {
+    fs: Option<FsKind>,
+}
+
+impl Target for HostIoTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn host_io(&mut self) -> Option<HostIoOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(HostIoTarget);
+
+impl HostIo for HostIoTarget {
+    fn open(&mut self, filename: &[u8], _flags: u32, _mode: u32) -> HostIoResult<u32, Self> {
+        match filename {
+            b"/file" => Ok(0),
+            b"/big" => Ok(1),
+            _ => Err(HostIoError::Errno(HostIoErrno::ENOENT)),
+        }
+    }
+
+    fn close(&mut self, _fd: u32) -> HostIoResult<(), Self> {
+        Ok(())
+    }
+
+    fn pread(&mut self, fd: u32, offset: u64, buf: &mut [u8]) -> HostIoResult<usize, Self> {
+        let file = if fd == 1 { BIG_FILE } else { FILE };
+        let data = file.get(offset as usize..).unwrap_or(&[]);
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+
+    fn fstat(&mut self, fd: u32) -> HostIoResult<HostIoStat, Self> {
+        if fd != 0 {
+            return Err(HostIoError::Errno(HostIoErrno::EBADF));
+        }
+
+        Ok(HostIoStat {
+            st_dev: 1,
+            st_ino: 0x23,
+            st_mode: 0o100644,
+            st_nlink: 1,
+            st_uid: 0,
+            st_gid: 0,
+            st_rdev: 0,
+            st_size: FILE.len() as u64,
+            st_blksize: 0x24,
+            st_blocks: 1,
+            st_atime: 0x7d,
+            st_mtime: 0x2a,
+            st_ctime: 0,
+        })
+    }
+
+    fn setfs(&mut self, fs: FsKind) -> HostIoResult<(), Self> {
+        self.fs = Some(fs);
+        Ok(())
+    }
+}
+
+#[test]
+fn fstat() {
+    let mut target = HostIoTarget::default();
+    let (res, _) = run_session(&mut target, &["vFile:setfs:0", "vFile:fstat:0"]);
+
+    assert_eq!(target.fs, Some(FsKind::Stub));
+    assert_eq!(res[0], "F0");
+
+    let expected = [
+        "F40;",
+        "\0\0\0\x01",          // st_dev
+        "\0\0\0}\x03",         // st_ino (0x23 is escaped as "}\x03")
+        "\0\0\u{81}\u{a4}",    // st_mode
+        "\0\0\0\x01",          // st_nlink
+        "\0\0\0\0",            // st_uid
+        "\0\0\0\0",            // st_gid
+        "\0\0\0\0",            // st_rdev
+        "\0\0\0\0\0\0\0\x0d",  // st_size
+        "\0\0\0\0\0\0\0}\x04", // st_blksize (0x24 is escaped as "}\x04")
+        "\0\0\0\0\0\0\0\x01",  // st_blocks
+        "\0\0\0}]",            // st_atime (0x7d is escaped as "}]")
+        "\0\0\0}\x0a",         // st_mtime (0x2a is escaped as "}\x0a")
+        "\0\0\0\0",            // st_ctime
+    ]
+    .concat();
+    assert_eq!(res[1], expected);
+}
+
+#[test]
+fn fstat_bad_fd() {
+    let mut target = HostIoTarget::default();
+    let (res, _) = run_session(&mut target, &["vFile:fstat:5"]);
+    assert_eq!(res, ["F-1,09"]);
+}
+
+#[test]
+fn open_pread_close() {
+    let mut target = HostIoTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "vFile:setfs:2a",
+            "vFile:open:2f66696c65,0,0",
+            "vFile:pread:0,6,0",
+            "vFile:pread:0,100,6",
+            "vFile:close:0",
+            "vFile:open:2f6e6f7065,0,0",
+        ],
+    );
+
+    assert_eq!(
+        target.fs,
+        Some(FsKind::Pid(crate::common::Pid::new(0x2a).unwrap()))
+    );
+    assert_eq!(
+        res,
+        [
+            "F0",
+            "F00",
+            "F06;hello ",
+            "F07;}\x04world}\x03",
+            "F0",
+            "F-1,02"
+        ]
+    );
+}
+
+#[test]
+fn pread_bounded_by_packet_buffer() {
+    let mut target = HostIoTarget::default();
+
+    // GDB requests more data than fits in the (tiny) packet buffer
+    let mut conn = MockConnection::new(&["vFile:pread:1,1000,0"]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(32)
+        .build()
+        .unwrap()
+        .run(&mut target);
+
+    let res = split_responses(&conn.output);
+    assert_eq!(res, [format!("F20;{}", "a".repeat(32))]);
+}
@@ -0,0 +1,202 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::host_syscall::{
+    HostSyscall, HostSyscallOps, HostSyscallReply, HostSyscallRequest,
+};
+use crate::target::{Target, TargetResult};
+
+const PATH: u32 = 0x1000;
+
+/// A target which requests a single host syscall, and then hits a breakpoint.
+struct SyscallTarget {
+    request: HostSyscallRequest<u32>,
+    replies: Vec<HostSyscallReply>,
+    resumes: usize,
+}
+
+impl SyscallTarget {
+    fn new(request: HostSyscallRequest<u32>) -> SyscallTarget {
+        SyscallTarget {
+            request,
+            replies: Vec::new(),
+            resumes: 0,
+        }
+    }
+}
+
+impl Target for SyscallTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn host_syscall(&mut self) -> Option<HostSyscallOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for SyscallTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::HostSyscall(self.request))
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        let path = b"hello\0";
+        for (addr, b) in (start_addr..).zip(data.iter_mut()) {
+            *b = addr
+                .checked_sub(PATH)
+                .and_then(|i| path.get(i as usize))
+                .copied()
+                .unwrap_or(0);
+        }
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl HostSyscall for SyscallTarget {
+    fn host_syscall_reply(&mut self, reply: HostSyscallReply) -> Result<(), Self::Error> {
+        self.replies.push(reply);
+        Ok(())
+    }
+
+    fn resume_after_host_syscall(
+        &mut self,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.resumes += 1;
+        Ok(StopReason::SwBreak.into())
+    }
+}
+
+fn open() -> HostSyscallRequest<u32> {
+    HostSyscallRequest::Open {
+        path: PATH,
+        path_len: 6,
+        flags: 0,
+        mode: 0o644,
+    }
+}
+
+#[test]
+fn open_round_trip() {
+    let mut target = SyscallTarget::new(open());
+    let (res, _) = run_session(&mut target, &["c", "m1000,6", "F3"]);
+    assert_eq!(
+        res,
+        [
+            "Fopen,1000/06,00,01a4",
+            // GDB reads the path from the target's memory...
+            "68656c6c6f00",
+            // ...and the target resumes once it receives the result
+            "T05thread:01;",
+        ]
+    );
+    assert_eq!(
+        target.replies,
+        [HostSyscallReply {
+            retval: 3,
+            errno: None,
+            ctrl_c: false
+        }]
+    );
+    assert_eq!(target.resumes, 1);
+}
+
+#[test]
+fn failed_syscall() {
+    let mut target = SyscallTarget::new(open());
+    let (res, _) = run_session(&mut target, &["c", "F-1,2"]);
+    assert_eq!(res, ["Fopen,1000/06,00,01a4", "T05thread:01;"]);
+    assert_eq!(
+        target.replies,
+        [HostSyscallReply {
+            retval: -1,
+            errno: Some(2),
+            ctrl_c: false
+        }]
+    );
+}
+
+#[test]
+fn interrupted_syscall() {
+    let mut target = SyscallTarget::new(open());
+    let (res, _) = run_session(&mut target, &["c", "F-1,4,C"]);
+    // the target isn't resumed, and instead reports a SIGINT
+    assert_eq!(res, ["Fopen,1000/06,00,01a4", "S02"]);
+    assert!(target.replies[0].ctrl_c);
+    assert_eq!(target.resumes, 0);
+}
+
+#[test]
+fn serializes_syscall_args() {
+    let reqs = [
+        (
+            HostSyscallRequest::Lseek {
+                fd: 3,
+                offset: -0x10,
+                flag: 2,
+            },
+            "Flseek,03,-10,02",
+        ),
+        (
+            HostSyscallRequest::Write {
+                fd: 1,
+                buf: 0x2000,
+                count: 0xc,
+            },
+            "Fwrite,01,2000,0c",
+        ),
+        (
+            HostSyscallRequest::Rename {
+                old_path: 0x1000,
+                old_path_len: 4,
+                new_path: 0x1010,
+                new_path_len: 5,
+            },
+            "Frename,1000/04,1010/05",
+        ),
+        (
+            HostSyscallRequest::Gettimeofday { tv: 0x3000, tz: 0 },
+            "Fgettimeofday,3000,00",
+        ),
+        (HostSyscallRequest::Isatty { fd: 0 }, "Fisatty,00"),
+    ];
+
+    for (req, expected) in reqs.iter() {
+        let mut target = SyscallTarget::new(*req);
+        let (res, _) = run_session(&mut target, &["c"]);
+        assert_eq!(res, [*expected]);
+    }
+}
+
+#[test]
+fn unexpected_reply() {
+    let mut target = SyscallTarget::new(open());
+    let (res, result) = run_session(&mut target, &["F0"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketUnexpected)));
+    assert!(res.is_empty());
+    assert!(target.replies.is_empty());
+}
@@ -0,0 +1,88 @@
+use core::time::Duration;
+
+use super::mock::{run_session, IDLE};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::idle::{Idle, IdleOps};
+use crate::target::Target;
+
+/// A target which counts how often it's idle, optionally failing once it's
+/// been idle `fail_after` times.
+struct IdleTarget {
+    enabled: bool,
+    idle_count: usize,
+    fail_after: Option<usize>,
+}
+
+impl IdleTarget {
+    fn new(enabled: bool) -> IdleTarget {
+        IdleTarget {
+            enabled,
+            idle_count: 0,
+            fail_after: None,
+        }
+    }
+}
+
+impl Target for IdleTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn idle(&mut self) -> Option<IdleOps<'_, Self>> {
+        if self.enabled {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl_noop_single_thread_ops!(IdleTarget);
+
+impl Idle for IdleTarget {
+    fn idle_timeout(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    fn on_idle(&mut self) -> Result<(), Self::Error> {
+        self.idle_count += 1;
+        if Some(self.idle_count) == self.fail_after {
+            return Err("housekeeping failed");
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn idle_between_packets() {
+    let mut target = IdleTarget::new(true);
+    let (res, result) = run_session(&mut target, &[IDLE, "?", IDLE, IDLE, "m0,1"]);
+    assert!(result.is_err()); // eof
+    assert_eq!(res, ["T05thread:01;", "00"]);
+    assert_eq!(target.idle_count, 3);
+}
+
+#[test]
+fn idle_requires_extension() {
+    let mut target = IdleTarget::new(false);
+    let (res, _) = run_session(&mut target, &["?", IDLE, "m0,1"]);
+    // without the extension, reads block (i.e: never time out)
+    assert_eq!(res, ["T05thread:01;", "00"]);
+    assert_eq!(target.idle_count, 0);
+}
+
+#[test]
+fn idle_error_ends_session() {
+    let mut target = IdleTarget::new(true);
+    target.fail_after = Some(2);
+    let (res, result) = run_session(&mut target, &[IDLE, IDLE, "?"]);
+    assert!(matches!(
+        result,
+        Err(crate::GdbStubError::TargetError("housekeeping failed"))
+    ));
+    assert!(res.is_empty());
+}
@@ -0,0 +1,293 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::reg::id::ArmCoreRegId;
+use crate::arch::arm::{reg::ArmCoreRegs, ArmBreakpointKind, Armv4t};
+use crate::arch::{Endian, Registers};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::breakpoints::{SwBreakpoint, SwBreakpointOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+/// Address of the `add(r0, r1)` function.
+const ADD: u32 = 0x1000;
+/// Address of GDB's call dummy (i.e: where the function returns to).
+const DUMMY: u32 = 0x8000;
+/// Where the program was stopped before GDB called the function.
+const ORIG_PC: u32 = 0x2000;
+
+/// A target which "executes" a single function: when resumed at `ADD`, it
+/// stores `r0 + r1` into `r0` and returns to the address in `lr`.
+struct CallTarget {
+    regs: ArmCoreRegs,
+    breakpoints: Vec<u32>,
+    resumes: usize,
+    /// Report breakpoint hits as `HwBreak` (even though the target doesn't
+    /// support hardware breakpoints).
+    report_hw: bool,
+}
+
+impl CallTarget {
+    fn new() -> CallTarget {
+        let mut r = [0; 13];
+        r[0] = 0xdead;
+        let regs = ArmCoreRegs {
+            r,
+            sp: 0x4000,
+            pc: ORIG_PC,
+            ..Default::default()
+        };
+        CallTarget {
+            regs,
+            breakpoints: Vec::new(),
+            resumes: 0,
+            report_hw: false,
+        }
+    }
+}
+
+impl Target for CallTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for CallTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        self.resumes += 1;
+        if self.regs.pc != ADD {
+            return Err("resumed at the wrong address");
+        }
+
+        self.regs.r[0] = self.regs.r[0].wrapping_add(self.regs.r[1]);
+        self.regs.pc = self.regs.lr;
+
+        if self.breakpoints.contains(&self.regs.pc) && self.report_hw {
+            Ok(StopReason::HwBreak)
+        } else if self.breakpoints.contains(&self.regs.pc) {
+            Ok(StopReason::SwBreak)
+        } else {
+            Ok(StopReason::Halted)
+        }
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        *regs = self.regs.clone();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        self.regs = regs.clone();
+        Ok(())
+    }
+
+    fn read_register(&mut self, reg_id: ArmCoreRegId, dst: &mut [u8]) -> TargetResult<usize, Self> {
+        let val = match reg_id {
+            ArmCoreRegId::Gpr(i) => self.regs.r[i as usize],
+            ArmCoreRegId::Sp => self.regs.sp,
+            ArmCoreRegId::Lr => self.regs.lr,
+            ArmCoreRegId::Pc => self.regs.pc,
+            ArmCoreRegId::Cpsr => self.regs.cpsr,
+            _ => return Err(TargetError::NonFatal),
+        };
+        dst.copy_from_slice(&val.to_le_bytes());
+        Ok(dst.len())
+    }
+
+    fn write_register(&mut self, reg_id: ArmCoreRegId, val: &[u8]) -> TargetResult<(), Self> {
+        let mut buf = [0; 4];
+        buf.copy_from_slice(val);
+        let val = u32::from_le_bytes(buf);
+        match reg_id {
+            ArmCoreRegId::Gpr(i) => self.regs.r[i as usize] = val,
+            ArmCoreRegId::Sp => self.regs.sp = val,
+            ArmCoreRegId::Lr => self.regs.lr = val,
+            ArmCoreRegId::Pc => self.regs.pc = val,
+            ArmCoreRegId::Cpsr => self.regs.cpsr = val,
+            _ => return Err(TargetError::NonFatal),
+        }
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl SwBreakpoint for CallTarget {
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        self.breakpoints.push(addr);
+        Ok(true)
+    }
+
+    fn remove_sw_breakpoint(
+        &mut self,
+        addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        match self.breakpoints.iter().position(|x| *x == addr) {
+            None => return Ok(false),
+            Some(pos) => self.breakpoints.remove(pos),
+        };
+        Ok(true)
+    }
+}
+
+/// Encode `regs` the same way GDB does when sending a `G` packet.
+fn g_packet(regs: &ArmCoreRegs) -> String {
+    let mut s = String::new();
+    regs.gdb_serialize(Endian::Little, |b| s += &format!("{:02x}", b.unwrap_or(0)));
+    s
+}
+
+/// Decode a `g` reply, treating unavailable registers as zero.
+fn parse_g_reply(reply: &str) -> ArmCoreRegs {
+    let bytes = reply
+        .as_bytes()
+        .chunks(2)
+        .map(|b| match b {
+            b"xx" => 0,
+            b => u8::from_str_radix(std::str::from_utf8(b).unwrap(), 16).unwrap(),
+        })
+        .collect::<Vec<_>>();
+    let mut regs = ArmCoreRegs::default();
+    regs.gdb_deserialize(Endian::Little, &bytes).unwrap();
+    regs
+}
+
+fn hex32(val: u32) -> String {
+    val.to_le_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[test]
+fn call_dummy_via_g_packets() {
+    let mut target = CallTarget::new();
+    let saved = target.regs.clone();
+
+    // GDB sets up the call: args in r0/r1, return address in lr, pc at the
+    // function's entry point.
+    let mut call = saved.clone();
+    call.r[0] = 1;
+    call.r[1] = 2;
+    call.lr = DUMMY;
+    call.pc = ADD;
+    call.sp -= 8;
+
+    let set_call = format!("G{}", g_packet(&call));
+    let restore = format!("G{}", g_packet(&saved));
+    let (res, result) = run_session(
+        &mut target,
+        &[
+            "g",
+            &format!("Z0,{:x},4", DUMMY),
+            &set_call,
+            "c",
+            "g",
+            &format!("z0,{:x},4", DUMMY),
+            &restore,
+            "g",
+        ],
+    );
+    assert!(result.is_err()); // eof
+
+    assert_eq!(parse_g_reply(&res[0]), saved);
+    assert_eq!(res[1], "OK");
+    assert_eq!(res[2], "OK");
+    // GDB never offered `swbreak`, so the stop is reported as a plain SIGTRAP
+    assert_eq!(res[3], "T05thread:01;");
+
+    let after_call = parse_g_reply(&res[4]);
+    assert_eq!(after_call.pc, DUMMY);
+    assert_eq!(after_call.r[0], 3);
+
+    assert_eq!(res[5], "OK");
+    assert_eq!(res[6], "OK");
+    assert_eq!(parse_g_reply(&res[7]), saved);
+
+    assert_eq!(target.resumes, 1);
+    assert!(target.breakpoints.is_empty());
+}
+
+#[test]
+fn call_dummy_via_p_packets() {
+    let mut target = CallTarget::new();
+
+    let (res, result) = run_session(
+        &mut target,
+        &[
+            &format!("Z0,{:x},4", DUMMY),
+            &format!("P0={}", hex32(20)),
+            &format!("P1={}", hex32(22)),
+            &format!("Pe={}", hex32(DUMMY)),
+            &format!("Pf={}", hex32(ADD)),
+            "c",
+            "pf",
+            "p0",
+            &format!("Pf={}", hex32(ORIG_PC)),
+            "pf",
+        ],
+    );
+    assert!(result.is_err()); // eof
+
+    assert_eq!(
+        res,
+        [
+            "OK",
+            "OK",
+            "OK",
+            "OK",
+            "OK",
+            "T05thread:01;",
+            &hex32(DUMMY),
+            &hex32(42),
+            "OK",
+            &hex32(ORIG_PC),
+        ]
+    );
+}
+
+#[test]
+fn call_dummy_unadvertised_hwbreak() {
+    let mut target = CallTarget::new();
+    target.report_hw = true;
+
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qSupported:swbreak+;hwbreak+",
+            &format!("Z0,{:x},4", DUMMY),
+            &format!("Pe={}", hex32(DUMMY)),
+            &format!("Pf={}", hex32(ADD)),
+            "c",
+        ],
+    );
+
+    // GDB rejects `hwbreak` stop reasons it wasn't told about, so the stop
+    // is reported as a plain SIGTRAP
+    assert!(!res[0].contains("hwbreak+"));
+    assert_eq!(res[4], "T05thread:01;");
+}
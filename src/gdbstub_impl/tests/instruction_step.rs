@@ -0,0 +1,189 @@
+use std::vec::Vec;
+
+use super::mock::{run_session, MockConnection};
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Signal;
+use crate::gdbstub_impl::GdbStubImpl;
+use crate::protocol::ConsoleOutput;
+use crate::target::ext::base::instruction_step::{InstructionStep, InstructionStepOps};
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+/// Address of the (single-cycle) interrupt vector.
+const IRQ_VECTOR: u32 = 0x18;
+
+/// A cycle-accurate toy CPU, where each step executes a single cycle of the
+/// following program:
+///
+/// ```text
+/// 0x00: mov  (1 cycle)
+/// 0x04: ldm  (3 cycles)
+/// 0x08: mul  (2 cycles)
+/// ```
+struct CycleCpu {
+    pc: u32,
+    /// Number of cycles spent executing the instruction at `pc`.
+    cycle: u32,
+    steps: Vec<ResumeAction>,
+    /// Raise an interrupt once this many cycles have been executed.
+    irq_at: Option<usize>,
+    /// Step individual cycles (e.g: as toggled via a `monitor` command).
+    cycle_mode: bool,
+}
+
+impl CycleCpu {
+    fn new() -> CycleCpu {
+        CycleCpu {
+            pc: 0,
+            cycle: 0,
+            steps: Vec::new(),
+            irq_at: None,
+            cycle_mode: false,
+        }
+    }
+
+    fn cycles(&self, addr: u32) -> u32 {
+        match addr {
+            0x04 => 3,
+            0x08 => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Target for CycleCpu {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn instruction_step(&mut self) -> Option<InstructionStepOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for CycleCpu {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        if let ResumeAction::Continue(_) = action {
+            return Err("only stepping is implemented");
+        }
+        self.steps.push(action);
+
+        if self.irq_at == Some(self.steps.len()) {
+            // the interrupt abandons the in-flight instruction
+            self.pc = IRQ_VECTOR;
+            self.cycle = 0;
+            return Ok(StopReason::DoneStep);
+        }
+
+        self.cycle += 1;
+        if self.cycle == self.cycles(self.pc) {
+            self.pc += 4;
+            self.cycle = 0;
+        }
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        regs.pc = self.pc;
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl InstructionStep for CycleCpu {
+    fn at_instruction_boundary(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.cycle_mode || self.cycle == 0)
+    }
+}
+
+#[test]
+fn step_multi_cycle_instruction() {
+    let mut target = CycleCpu::new();
+    let (res, _) = run_session(&mut target, &["s", "s"]);
+    assert_eq!(res, ["S05", "S05"]);
+    // `mov` completes in a single step, while `ldm` takes three
+    assert_eq!(target.pc, 0x08);
+    assert_eq!(target.steps.len(), 4);
+}
+
+#[test]
+fn step_signal_delivered_once() {
+    let mut target = CycleCpu::new();
+    target.pc = 0x08;
+    let (res, _) = run_session(&mut target, &["S0b"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(target.pc, 0x0c);
+    assert_eq!(
+        target.steps,
+        [
+            ResumeAction::Step(Some(Signal::SIGSEGV)),
+            ResumeAction::Step(None)
+        ]
+    );
+}
+
+#[test]
+fn step_interrupted_instruction() {
+    let mut target = CycleCpu::new();
+    target.pc = 0x04;
+    target.irq_at = Some(2);
+    let (res, _) = run_session(&mut target, &["s"]);
+    assert_eq!(res, ["S05"]);
+    // the step ends on the interrupt vector, instead of after the `ldm`
+    assert_eq!(target.pc, IRQ_VECTOR);
+    assert_eq!(target.steps.len(), 2);
+}
+
+#[test]
+fn step_single_cycle() {
+    let mut target = CycleCpu::new();
+    target.pc = 0x04;
+    target.cycle_mode = true;
+    let (res, _) = run_session(&mut target, &["s"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!((target.pc, target.cycle), (0x04, 1));
+    assert_eq!(target.steps.len(), 1);
+}
+
+#[test]
+fn step_interrupted_by_gdb() {
+    let mut target = CycleCpu::new();
+    target.pc = 0x04;
+    let (res, _) = run_session(&mut target, &["s", "\x03", "m0,1"]);
+    // the interrupt is consumed, and stops the `ldm` after its first cycle
+    assert_eq!(res, ["S05", "00"]);
+    assert_eq!((target.pc, target.cycle), (0x04, 1));
+    assert_eq!(target.steps.len(), 1);
+
+    let mut target = CycleCpu::new();
+    target.pc = 0x04;
+    let mut callback = |_: &[u8]| {};
+    let stop_reason = GdbStubImpl::<CycleCpu, MockConnection>::step_instruction(
+        &mut target,
+        None,
+        &mut || true,
+        ConsoleOutput::new(&mut callback),
+    );
+    assert_eq!(stop_reason.unwrap(), ThreadStopReason::GdbInterrupt);
+}
@@ -0,0 +1,162 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::ctrl_c_interrupt::{CtrlCInterrupt, CtrlCInterruptOps};
+use crate::target::{Target, TargetResult};
+
+/// A target which polls for interrupts a few times while resuming, and records
+/// the results.
+struct InterruptTarget {
+    ignore_interrupts: bool,
+    polls: Vec<bool>,
+    /// If set, implement `CtrlCInterrupt`, reporting the given stop reason
+    /// (or ignoring the interrupt, if `None`).
+    on_ctrl_c: Option<Option<ThreadStopReason<u32>>>,
+    ctrl_c_count: usize,
+}
+
+impl InterruptTarget {
+    fn new(ignore_interrupts: bool) -> InterruptTarget {
+        InterruptTarget {
+            ignore_interrupts,
+            polls: Vec::new(),
+            on_ctrl_c: None,
+            ctrl_c_count: 0,
+        }
+    }
+}
+
+impl Target for InterruptTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn ctrl_c_interrupt(&mut self) -> Option<CtrlCInterruptOps<'_, Self>> {
+        if self.on_ctrl_c.is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl CtrlCInterrupt for InterruptTarget {
+    fn on_ctrl_c(&mut self) -> Result<Option<ThreadStopReason<u32>>, Self::Error> {
+        self.ctrl_c_count += 1;
+        Ok(self.on_ctrl_c.unwrap())
+    }
+}
+
+impl SingleThreadOps for InterruptTarget {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        for _ in 0..3 {
+            let interrupted = check_gdb_interrupt();
+            self.polls.push(interrupted);
+            if interrupted && !self.ignore_interrupts {
+                return Ok(match action {
+                    // the step completed before the target noticed the interrupt
+                    ResumeAction::Step(_) => StopReason::DoneStep,
+                    ResumeAction::Continue(_) => StopReason::GdbInterrupt,
+                });
+            }
+        }
+        Ok(StopReason::SwBreak)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+#[test]
+fn interrupt_during_continue() {
+    let mut target = InterruptTarget::new(false);
+    let (res, _) = run_session(&mut target, &["c", "\x03", "m0,1"]);
+    // the interrupt is consumed, and only results in a single stop reply
+    assert_eq!(res, ["S05", "00"]);
+    assert_eq!(target.polls, [true]);
+}
+
+#[test]
+fn interrupt_during_step() {
+    let mut target = InterruptTarget::new(false);
+    let (res, _) = run_session(&mut target, &["s", "\x03", "m0,1"]);
+    assert_eq!(res, ["S05", "00"]);
+    assert_eq!(target.polls, [true]);
+}
+
+#[test]
+fn ignored_interrupt() {
+    let mut target = InterruptTarget::new(true);
+    let (res, _) = run_session(&mut target, &["c", "\x03", "m0,1"]);
+    // GDB is only notified once the target stops on its own
+    assert_eq!(res, ["T05thread:01;", "00"]);
+    assert_eq!(target.polls, [true, false, false]);
+}
+
+#[test]
+fn interrupt_while_stopped() {
+    let mut target = InterruptTarget::new(false);
+    let (res, _) = run_session(&mut target, &["\x03", "m0,1"]);
+    assert_eq!(res, ["S05", "00"]);
+    assert!(target.polls.is_empty());
+}
+
+#[test]
+fn interrupt_while_stopped_custom_stop_reason() {
+    let mut target = InterruptTarget::new(false);
+    target.on_ctrl_c = Some(Some(ThreadStopReason::SignalWithThread {
+        tid: Tid::new(1).unwrap(),
+        signal: 2,
+    }));
+    let (res, _) = run_session(&mut target, &["\x03", "m0,1"]);
+    assert_eq!(res, ["T02thread:01;", "00"]);
+    assert_eq!(target.ctrl_c_count, 1);
+}
+
+#[test]
+fn interrupt_while_stopped_ignored() {
+    let mut target = InterruptTarget::new(false);
+    target.on_ctrl_c = Some(None);
+    let (res, _) = run_session(&mut target, &["\x03", "m0,1"]);
+    // no stop reply is sent for the ignored interrupt
+    assert_eq!(res, ["00"]);
+    assert_eq!(target.ctrl_c_count, 1);
+}
+
+#[test]
+fn interrupt_during_resume_bypasses_ctrl_c_handler() {
+    let mut target = InterruptTarget::new(false);
+    target.on_ctrl_c = Some(None);
+    let (res, _) = run_session(&mut target, &["c", "\x03", "\x03", "m0,1"]);
+    // the first interrupt stops the running target, while the second one
+    // arrives once it's already stopped, and is handed to the target
+    assert_eq!(res, ["S05", "00"]);
+    assert_eq!(target.polls, [true]);
+    assert_eq!(target.ctrl_c_count, 1);
+}
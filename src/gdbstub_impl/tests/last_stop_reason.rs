@@ -0,0 +1,175 @@
+use std::string::String;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::{Pid, Tid};
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::breakpoints::WatchKind;
+use crate::target::ext::last_stop_reason::{LastStopReason, LastStopReasonOps};
+use crate::target::{Target, TargetResult};
+use crate::DisconnectReason;
+
+/// A two-thread target which remembers why it last stopped (e.g: from a
+/// previous debugging session).
+struct StoppedTarget {
+    last_stop: Option<ThreadStopReason<u32>>,
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for StoppedTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+
+    fn last_stop_reason(&mut self) -> Option<LastStopReasonOps<'_, Self>> {
+        if self.last_stop.is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl MultiThreadOps for StoppedTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = tid.get() as u8);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+}
+
+impl LastStopReason for StoppedTarget {
+    fn get_last_stop_reason(&mut self) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(self.last_stop.unwrap())
+    }
+}
+
+fn stop_query(last_stop: Option<ThreadStopReason<u32>>) -> String {
+    let mut target = StoppedTarget { last_stop };
+    let (res, _) = run_session(&mut target, &["?"]);
+    res[0].clone()
+}
+
+#[test]
+fn default_reports_current_thread() {
+    assert_eq!(stop_query(None), "T05thread:01;");
+}
+
+#[test]
+fn reports_stopping_thread() {
+    let last_stop = ThreadStopReason::SwBreak(tid(2));
+    assert_eq!(stop_query(Some(last_stop)), "T05thread:02;");
+
+    let last_stop = ThreadStopReason::Watch {
+        tid: tid(2),
+        kind: WatchKind::Write,
+        addr: 0x1000,
+    };
+    assert_eq!(stop_query(Some(last_stop)), "T05thread:02;watch:1000;");
+}
+
+#[test]
+fn reports_signal() {
+    let last_stop = ThreadStopReason::SignalWithThread {
+        tid: tid(2),
+        signal: 11,
+    };
+    assert_eq!(stop_query(Some(last_stop)), "T0bthread:02;");
+
+    // a signal without a thread is reported on the current thread
+    let last_stop = ThreadStopReason::Signal(11);
+    assert_eq!(stop_query(Some(last_stop)), "T0bthread:01;");
+}
+
+#[test]
+fn selects_stopping_thread() {
+    let mut target = StoppedTarget {
+        last_stop: Some(ThreadStopReason::SwBreak(tid(2))),
+    };
+    let (res, _) = run_session(&mut target, &["?", "m0,1"]);
+    // subsequent memory accesses are issued on the stopping thread
+    assert_eq!(res, ["T05thread:02;", "02"]);
+}
+
+#[test]
+fn reports_exit() {
+    let mut target = StoppedTarget {
+        last_stop: Some(ThreadStopReason::Halted),
+    };
+    let (res, result) = run_session(&mut target, &["?", "m0,1"]);
+    assert_eq!(res, ["W19"]);
+    assert_eq!(result.unwrap(), DisconnectReason::TargetHalted);
+}
+
+#[test]
+fn events_are_not_reissued() {
+    let fork = ThreadStopReason::Fork {
+        tid: tid(2),
+        new_pid: Pid::new(2).unwrap(),
+        new_tid: tid(3),
+    };
+    assert_eq!(stop_query(Some(fork)), "T05thread:02;");
+    assert_eq!(
+        stop_query(Some(ThreadStopReason::ThreadCreated(tid(2)))),
+        "T05thread:02;"
+    );
+}
+
+#[test]
+fn process_exit_is_a_plain_stop() {
+    let mut target = StoppedTarget {
+        last_stop: Some(ThreadStopReason::Exited {
+            pid: Pid::new(1).unwrap(),
+            code: 0,
+        }),
+    };
+    // the session keeps going
+    let (res, _) = run_session(&mut target, &["?", "m0,1"]);
+    assert_eq!(res, ["T05thread:01;", "01"]);
+
+    let terminated = ThreadStopReason::Terminated {
+        pid: Pid::new(1).unwrap(),
+        signal: 9,
+    };
+    assert_eq!(stop_query(Some(terminated)), "T05thread:01;");
+}
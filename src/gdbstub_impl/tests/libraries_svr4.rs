@@ -0,0 +1,62 @@
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::libraries_svr4::{LibrariesSvr4, LibrariesSvr4Ops};
+use crate::target::{Target, TargetResult};
+
+const LIBRARIES: &str = r#"<library-list-svr4 version="1.0" main-lm="0x10000"><library name="/lib/libc.so.6" lm="0x20000" l_addr="0x40000000" l_ld="0x40100000"/><library name="/lib/libm.so.6" lm="0x20100" l_addr="0x40200000" l_ld="0x40280000"/></library-list-svr4>"#;
+
+struct Svr4Target;
+
+impl Target for Svr4Target {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn libraries_svr4(&mut self) -> Option<LibrariesSvr4Ops<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(Svr4Target);
+
+impl LibrariesSvr4 for Svr4Target {
+    fn get_libraries_svr4(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(LIBRARIES.as_bytes(), offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_libraries_svr4() {
+    assert!(advertises(&mut Svr4Target, "qXfer:libraries-svr4:read+"));
+}
+
+#[test]
+fn full_read() {
+    let (res, _) = run_session(&mut Svr4Target, &["qXfer:libraries-svr4:read::0,1000"]);
+    assert_eq!(res, [format!("l{}", LIBRARIES)]);
+}
+
+#[test]
+fn incremental_read() {
+    let data = read_xfer_paged(
+        &mut Svr4Target,
+        "qXfer:libraries-svr4:read::",
+        LIBRARIES.len(),
+        0x80,
+        None,
+    );
+    assert_eq!(data, LIBRARIES.as_bytes());
+
+    let end = format!("qXfer:libraries-svr4:read::{:x},80", LIBRARIES.len());
+    let (res, _) = run_session(&mut Svr4Target, &[&end]);
+    assert_eq!(res, ["l"]);
+}
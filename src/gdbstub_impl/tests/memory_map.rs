@@ -0,0 +1,100 @@
+use std::string::String;
+
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::memory_map::{MemoryMap, MemoryMapOps};
+use crate::target::{Target, TargetResult};
+
+struct MemoryMapTarget {
+    xml: String,
+}
+
+impl MemoryMapTarget {
+    /// Create a target whose memory map contains `regions` RAM regions.
+    fn new(regions: usize) -> MemoryMapTarget {
+        let mut xml = String::from(r#"<?xml version="1.0"?><memory-map>"#);
+        for i in 0..regions {
+            xml += &format!(
+                r#"<memory type="ram" start="{:#x}" length="0x1000"/>"#,
+                i * 0x1000
+            );
+        }
+        xml += "</memory-map>";
+        MemoryMapTarget { xml }
+    }
+}
+
+impl Target for MemoryMapTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn memory_map(&mut self) -> Option<MemoryMapOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(MemoryMapTarget);
+
+impl MemoryMap for MemoryMapTarget {
+    fn memory_map_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(self.xml.as_bytes(), offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_memory_map() {
+    let mut target = MemoryMapTarget::new(1);
+    assert!(advertises(&mut target, "qXfer:memory-map:read+"));
+}
+
+#[test]
+fn paged_read() {
+    let mut target = MemoryMapTarget::new(1);
+    let xml = target.xml.clone();
+    let data = read_xfer_paged(
+        &mut target,
+        "qXfer:memory-map:read::",
+        xml.len(),
+        0x10,
+        None,
+    );
+    assert_eq!(data, xml.as_bytes());
+
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qXfer:memory-map:read::10,1000",
+            &format!("qXfer:memory-map:read::{:x},10", xml.len()),
+        ],
+    );
+    assert_eq!(res, [format!("l{}", &xml[0x10..]), "l".into()]);
+}
+
+#[test]
+fn read_larger_than_packet_buffer() {
+    // a memory map which can't fit in the default 4096 byte packet buffer
+    let mut target = MemoryMapTarget::new(256);
+    let xml = target.xml.clone();
+    assert!(xml.len() > 2 * 4096);
+
+    // reads are clamped to the size of the packet buffer, but still indicate
+    // that more data is available
+    let data = read_xfer_paged(
+        &mut target,
+        "qXfer:memory-map:read::",
+        xml.len(),
+        4096,
+        Some(4096),
+    );
+    assert_eq!(data, xml.as_bytes());
+}
@@ -0,0 +1,104 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::{Connection, GdbStub};
+
+/// A target where the byte at each address is the low byte of the address,
+/// which records the size of every memory read it was asked to perform.
+#[derive(Default)]
+struct ReadTarget {
+    reads: Vec<(u32, usize)>,
+}
+
+impl Target for ReadTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for ReadTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        self.reads.push((start_addr, data.len()));
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = start_addr.wrapping_add(i as u32) as u8;
+        }
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+fn expected_hex(start: u32, len: usize) -> String {
+    (0..len as u32)
+        .map(|i| format!("{:02x}", start.wrapping_add(i) as u8))
+        .collect()
+}
+
+#[test]
+fn read_larger_than_packet_buffer() {
+    let mut target = ReadTarget::default();
+    let mut packet_buffer = [0; 32];
+
+    let mut conn = MockConnection::new(&["m1000,100"]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .with_packet_buffer(&mut packet_buffer)
+        .build()
+        .unwrap()
+        .run(&mut target);
+
+    // the full region is returned, even though it's far larger than the packet
+    // buffer...
+    let res = split_responses(&conn.output);
+    assert_eq!(res, [expected_hex(0x1000, 0x100)]);
+
+    // ...as the stub read it in chunks which fit in the packet buffer
+    assert!(target.reads.len() > 1);
+    assert!(target.reads.iter().all(|&(_, len)| len < 32));
+    assert_eq!(
+        target.reads.iter().map(|&(_, len)| len).sum::<usize>(),
+        0x100
+    );
+
+    // each chunk picks up where the previous one left off
+    let mut addr = 0x1000;
+    for &(start, len) in target.reads.iter() {
+        assert_eq!(start, addr);
+        addr += len as u32;
+    }
+}
+
+#[test]
+fn read_fits_in_packet_buffer() {
+    let mut target = ReadTarget::default();
+    let mut conn = MockConnection::new(&["m2000,10"]);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut target);
+
+    assert_eq!(split_responses(&conn.output), [expected_hex(0x2000, 0x10)]);
+    assert_eq!(target.reads, [(0x2000, 0x10)]);
+}
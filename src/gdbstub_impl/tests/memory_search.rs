@@ -0,0 +1,68 @@
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::memory_search::{MemorySearch, MemorySearchOps};
+use crate::target::{Target, TargetResult};
+
+const BASE: u32 = 0x1000;
+const MEMORY: &[u8] = b"\xde\xad\xbe\xef hello world \xca\xfe";
+
+struct SearchTarget;
+
+impl Target for SearchTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn memory_search(&mut self) -> Option<MemorySearchOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(SearchTarget);
+
+impl MemorySearch for SearchTarget {
+    fn search(
+        &mut self,
+        start: u32,
+        length: u32,
+        pattern: &[u8],
+    ) -> TargetResult<Option<u32>, Self> {
+        let start_idx = start.checked_sub(BASE).ok_or(())? as usize;
+        let end_idx = (start_idx + length as usize).min(MEMORY.len());
+        let pos = MEMORY[start_idx..end_idx]
+            .windows(pattern.len())
+            .position(|w| w == pattern);
+        Ok(pos.map(|pos| start + pos as u32))
+    }
+}
+
+#[test]
+fn match_at_start() {
+    let (res, _) = run_session(&mut SearchTarget, &["qSearch:memory:1000;14;deadbeef"]);
+    assert_eq!(res, ["1,1000"]);
+}
+
+#[test]
+fn match_in_middle() {
+    // "world"
+    let (res, _) = run_session(&mut SearchTarget, &["qSearch:memory:1000;14;776f726c64"]);
+    assert_eq!(res, ["1,100b"]);
+}
+
+#[test]
+fn miss() {
+    let (res, _) = run_session(
+        &mut SearchTarget,
+        &[
+            // not present anywhere
+            "qSearch:memory:1000;14;1234",
+            // present, but outside the searched range
+            "qSearch:memory:1004;10;dead",
+        ],
+    );
+    assert_eq!(res, ["0", "0"]);
+}
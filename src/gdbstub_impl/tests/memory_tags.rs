@@ -0,0 +1,121 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::memory_tags::{MemoryTags, MemoryTagsOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+const GRANULE_SIZE: u32 = 16;
+
+/// A target with 0x100 bytes of tagged memory starting at 0x1000, where each
+/// 16-byte granule is initially tagged with its index.
+struct MteTarget {
+    tags: Vec<u8>,
+}
+
+impl MteTarget {
+    fn new() -> MteTarget {
+        MteTarget {
+            tags: (0..0x10).collect(),
+        }
+    }
+
+    /// Return the range of granule indices overlapping `addr..addr + len`.
+    fn granules(&self, addr: u32, len: u32) -> Option<core::ops::Range<usize>> {
+        let start = (addr.checked_sub(0x1000)? / GRANULE_SIZE) as usize;
+        let end = (addr - 0x1000 + len).div_ceil(GRANULE_SIZE) as usize;
+        if end > self.tags.len() {
+            return None;
+        }
+        Some(start..end)
+    }
+}
+
+impl Target for MteTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn memory_tags(&mut self) -> Option<MemoryTagsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(MteTarget);
+
+impl MemoryTags for MteTarget {
+    fn read_tags(
+        &mut self,
+        addr: u32,
+        len: u32,
+        tag_type: u32,
+        tags: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let granules = match (tag_type, self.granules(addr, len)) {
+            (1, Some(granules)) => granules,
+            _ => return Err(TargetError::Errno(22)),
+        };
+
+        let src = &self.tags[granules];
+        tags[..src.len()].copy_from_slice(src);
+        Ok(src.len())
+    }
+
+    fn write_tags(
+        &mut self,
+        addr: u32,
+        len: u32,
+        tag_type: u32,
+        tags: &[u8],
+    ) -> TargetResult<(), Self> {
+        let granules = match (tag_type, self.granules(addr, len)) {
+            (1, Some(granules)) if !tags.is_empty() => granules,
+            _ => return Err(TargetError::Errno(22)),
+        };
+
+        for (dst, tag) in self.tags[granules].iter_mut().zip(tags.iter().cycle()) {
+            *dst = *tag;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_memory_tagging() {
+    let (res, _) = run_session(&mut MteTarget::new(), &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";memory-tagging+"));
+}
+
+#[test]
+fn read_multiple_granules() {
+    let (res, _) = run_session(
+        &mut MteTarget::new(),
+        &[
+            "qMemTags:1000,10:1",
+            "qMemTags:1010,30:1",
+            "qMemTags:1018,10:1",
+        ],
+    );
+    // the final request is unaligned, and straddles two granules
+    assert_eq!(res, ["m00", "m010203", "m0102"]);
+}
+
+#[test]
+fn read_invalid_tag_type() {
+    let (res, _) = run_session(&mut MteTarget::new(), &["qMemTags:1000,10:2"]);
+    assert_eq!(res, ["E16"]);
+}
+
+#[test]
+fn write_repeats_tags() {
+    let mut target = MteTarget::new();
+    let (res, _) = run_session(
+        &mut target,
+        &["QMemTags:1020,40:1:0a0b", "qMemTags:1000,70:1"],
+    );
+    assert_eq!(res, ["OK", "m00010a0b0a0b06"]);
+}
@@ -0,0 +1,126 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetError, TargetResult};
+
+const RAM_BASE: u32 = 0x1000;
+const RAM_LEN: usize = 0x100;
+
+/// A target with a small region of RAM, which records the size of every
+/// memory write it was asked to perform.
+struct WriteTarget {
+    ram: Vec<u8>,
+    writes: Vec<(u32, usize)>,
+}
+
+impl WriteTarget {
+    fn new() -> WriteTarget {
+        WriteTarget {
+            ram: vec![0; RAM_LEN],
+            writes: Vec::new(),
+        }
+    }
+}
+
+impl Target for WriteTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for WriteTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, start_addr: u32, data: &[u8]) -> TargetResult<(), Self> {
+        self.writes.push((start_addr, data.len()));
+        let start = start_addr
+            .checked_sub(RAM_BASE)
+            .ok_or(TargetError::NonFatal)? as usize;
+        self.ram
+            .get_mut(start..start + data.len())
+            .ok_or(TargetError::NonFatal)?
+            .copy_from_slice(data);
+        Ok(())
+    }
+}
+
+#[test]
+fn x_bulk_write() {
+    let mut target = WriteTarget::new();
+    let payload: String = (0..0x80u8).map(|i| (b'a' + i % 26) as char).collect();
+    let (res, _) = run_session(&mut target, &[&format!("X1000,80:{}", payload)]);
+    assert_eq!(res, ["OK"]);
+    // the entire payload is handed to the target in a single call
+    assert_eq!(target.writes, [(0x1000, 0x80)]);
+    assert_eq!(&target.ram[..0x80], payload.as_bytes());
+}
+
+#[test]
+fn x_unescapes_payload() {
+    let mut target = WriteTarget::new();
+    let (res, _) = run_session(&mut target, &["X1010,9:a}]}\x03}\x04}\x0a:,é"]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(&target.ram[0x10..0x19], "a}#$*:,é".as_bytes());
+}
+
+#[test]
+fn x_zero_length_probe() {
+    let mut target = WriteTarget::new();
+    let (res, _) = run_session(&mut target, &["X1000,0:"]);
+    assert_eq!(res, ["OK"]);
+    assert!(target.writes.is_empty());
+}
+
+#[test]
+fn x_out_of_range() {
+    let mut target = WriteTarget::new();
+    let (res, result) = run_session(&mut target, &["X10fe,4:abcd", "X1000,1:a"]);
+    assert!(result.is_err()); // eof
+
+    // the first write straddles the end of RAM, and is rejected by the target
+    assert_eq!(res, ["E79", "OK"]);
+    assert_eq!(target.writes, [(0x10fe, 4), (0x1000, 1)]);
+    assert_eq!(target.ram[0xfe..], [0, 0]);
+}
+
+#[test]
+fn x_length_mismatch() {
+    let mut target = WriteTarget::new();
+    let (res, _) = run_session(&mut target, &["X1000,4:abc", "X1000,3:abc"]);
+    // the session carries on after the malformed write
+    assert_eq!(res, ["E16", "OK"]);
+    assert_eq!(target.writes, [(0x1000, 3)]);
+}
+
+#[test]
+fn m_out_of_range() {
+    let mut target = WriteTarget::new();
+    let (res, _) = run_session(&mut target, &["M10ff,2:abcd"]);
+    assert_eq!(res, ["E79"]);
+}
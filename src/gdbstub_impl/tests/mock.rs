@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::string::String;
+use std::time::Duration;
+use std::vec::Vec;
+
+use crate::target::Target;
+use crate::{Connection, DisconnectReason, GdbStub, GdbStubError};
+
+/// A pseudo-packet which makes `read_timeout` time out once (i.e: GDB sends
+/// nothing for a while). Blocking reads skip straight past it.
+pub const IDLE: &str = "<idle>";
+
+/// A `Connection` which replays a canned sequence of GDB packets, and records
+/// everything written by the `GdbStub`.
+///
+/// Much like a real GDB client, the next packet only becomes "visible" to
+/// `peek` once the stub starts reading it (i.e: GDB waits for a response before
+/// sending the next packet), with the exception of a raw `"\x03"` interrupt.
+pub struct MockConnection {
+    packets: VecDeque<Vec<u8>>,
+    current: VecDeque<u8>,
+    pub output: Vec<u8>,
+}
+
+impl MockConnection {
+    pub fn new(packets: &[&str]) -> MockConnection {
+        MockConnection {
+            packets: packets.iter().map(|p| frame_packet(p)).collect(),
+            current: VecDeque::new(),
+            output: Vec::new(),
+        }
+    }
+}
+
+impl Connection for MockConnection {
+    type Error = &'static str;
+
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        while self.current.is_empty() {
+            self.current = self.packets.pop_front().ok_or("eof")?.into();
+        }
+        self.current.pop_front().ok_or("eof")
+    }
+
+    fn read_timeout(&mut self, _timeout: Duration) -> Result<Option<u8>, Self::Error> {
+        if self.current.is_empty() && self.packets.front().map(Vec::is_empty) == Some(true) {
+            self.packets.pop_front();
+            return Ok(None);
+        }
+        self.read().map(Some)
+    }
+
+    fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.output.push(byte);
+        Ok(())
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        // unlike regular packets, GDB sends interrupts without waiting for the
+        // stub to respond to the previous packet
+        if self.current.is_empty()
+            && self.packets.front().map(|p| p.as_slice()) == Some(&b"\x03"[..])
+        {
+            self.current = self.packets.pop_front().unwrap().into();
+        }
+        Ok(self.current.front().copied())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Wrap a packet body with a '$' header and '#xx' checksum. Raw interrupts /
+/// acks, and packets which are already framed (e.g: to send a packet with a
+/// bad checksum), are sent as-is.
+fn frame_packet(body: &str) -> Vec<u8> {
+    if body == IDLE {
+        return Vec::new();
+    }
+    if body.starts_with('\x03') || body.starts_with('$') || body == "+" || body == "-" {
+        return body.as_bytes().to_vec();
+    }
+
+    let checksum = body.bytes().fold(0u8, |a, b| a.wrapping_add(b));
+    format!("${}#{:02x}", body, checksum).into_bytes()
+}
+
+/// Split the raw output of a `GdbStub` into individual packets, discarding
+/// any acks. Notification packets retain their leading '%'.
+///
+/// Binary data is preserved by mapping each byte to the `char` with the same
+/// value (e.g: `0x81` becomes `'\u{81}'`).
+pub fn split_responses(output: &[u8]) -> Vec<String> {
+    let mut responses = Vec::new();
+    let mut i = 0;
+    while i < output.len() {
+        match output[i] {
+            b'+' => i += 1,
+            header @ b'$' | header @ b'%' => {
+                let end = i + output[i..].iter().position(|b| *b == b'#').unwrap();
+                // map each byte to a char 1:1, as responses may include binary data
+                let body: String = output[i + 1..end].iter().map(|b| *b as char).collect();
+                responses.push(match header {
+                    b'%' => format!("%{}", body),
+                    _ => body,
+                });
+                i = end + 3; // skip '#' and the checksum
+            }
+            other => panic!("unexpected byte in output: {:#x?}", other),
+        }
+    }
+    responses
+}
+
+/// The result of running a `GdbStub` against a `MockConnection`.
+pub type SessionResult<T> =
+    Result<DisconnectReason, GdbStubError<<T as Target>::Error, &'static str>>;
+
+/// Run a `GdbStub` against `target`, feeding it the given packets, and
+/// returning the stub's responses alongside the result of `GdbStub::run`.
+pub fn run_session<T: Target>(target: &mut T, packets: &[&str]) -> (Vec<String>, SessionResult<T>) {
+    let mut conn = MockConnection::new(packets);
+    let res = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(target);
+    (split_responses(&conn.output), res)
+}
+
+/// Return the features listed in the stub's response to `qSupported`.
+pub fn supported_features<T: Target>(target: &mut T) -> Vec<String> {
+    let (res, _) = run_session(target, &["qSupported:multiprocess+"]);
+    res[0].split(';').map(String::from).collect()
+}
+
+/// Check whether the stub advertises `feature` (e.g: `"qXfer:auxv:read+"`) in
+/// its response to `qSupported`.
+pub fn advertises<T: Target>(target: &mut T, feature: &str) -> bool {
+    supported_features(target).iter().any(|f| f == feature)
+}
+
+/// Copy the chunk of `data` requested by a `qXfer` read (i.e: up to `length`
+/// bytes, starting at `offset`) into `buf`, returning the number of bytes
+/// copied.
+pub fn copy_xfer_chunk(data: &[u8], offset: u64, length: usize, buf: &mut [u8]) -> usize {
+    let data = data.get(offset as usize..).unwrap_or(&[]);
+    let len = data.len().min(length);
+    buf[..len].copy_from_slice(&data[..len]);
+    len
+}
+
+/// Binary-encode `data`, escaping any bytes with special meaning.
+pub fn encode_binary(data: &[u8]) -> String {
+    let mut out = String::new();
+    for b in data {
+        match b {
+            b'#' | b'$' | b'}' | b'*' => {
+                out.push('}');
+                out.push((b ^ 0x20) as char);
+            }
+            _ => out.push(*b as char),
+        }
+    }
+    out
+}
+
+/// Decode a binary-encoded response.
+pub fn decode_binary(res: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut bytes = res.chars().map(|c| c as u8);
+    while let Some(b) = bytes.next() {
+        match b {
+            b'}' => out.push(bytes.next().unwrap() ^ 0x20),
+            _ => out.push(b),
+        }
+    }
+    out
+}
+
+/// Read a `len` byte `qXfer` object from `target` one `page` at a time,
+/// returning the reassembled object.
+///
+/// `prefix` is everything up to the offset (e.g: `"qXfer:auxv:read::"`). Every
+/// reply but the last must be a full `m` page, and the last must be an `l`.
+///
+/// If `packet_buffer_size` is set, each request asks for far more data than
+/// fits in the packet buffer, so `page` should be the size of the buffer.
+pub fn read_xfer_paged<T: Target>(
+    target: &mut T,
+    prefix: &str,
+    len: usize,
+    page: usize,
+    packet_buffer_size: Option<usize>,
+) -> Vec<u8> {
+    let request = if packet_buffer_size.is_some() {
+        0xffff
+    } else {
+        page
+    };
+    let packets = (0..=len / page)
+        .map(|i| format!("{}{:x},{:x}", prefix, i * page, request))
+        .collect::<Vec<_>>();
+    let packets = packets.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let mut conn = MockConnection::new(&packets);
+    let mut builder = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>);
+    if let Some(size) = packet_buffer_size {
+        builder = builder.packet_buffer_size(size);
+    }
+    let _ = builder.build().unwrap().run(target);
+
+    let res = split_responses(&conn.output);
+    assert_eq!(res.len(), packets.len(), "{:?}", res);
+
+    let (last, pages) = res.split_last().unwrap();
+    let mut data = Vec::new();
+    for r in pages {
+        assert!(r.starts_with('m'), "{:?}", res);
+        let chunk = decode_binary(&r[1..]);
+        assert_eq!(chunk.len(), page, "{:?}", res);
+        data.extend(chunk);
+    }
+    assert!(last.starts_with('l'), "{:?}", res);
+    data.extend(decode_binary(&last[1..]));
+    data
+}
+
+/// Implement a bare-bones `SingleThreadOps` for an `Armv4t` target, where
+/// resuming immediately hits a breakpoint, and memory / registers read as
+/// zero.
+macro_rules! impl_noop_single_thread_ops {
+    ($target:ty) => {
+        impl crate::target::ext::base::singlethread::SingleThreadOps for $target {
+            fn resume(
+                &mut self,
+                _action: crate::target::ext::base::ResumeAction,
+                _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+            ) -> Result<crate::target::ext::base::singlethread::StopReason<u32>, Self::Error> {
+                Ok(crate::target::ext::base::singlethread::StopReason::SwBreak)
+            }
+
+            fn read_registers(
+                &mut self,
+                _regs: &mut crate::arch::arm::reg::ArmCoreRegs,
+            ) -> crate::target::TargetResult<(), Self> {
+                Ok(())
+            }
+
+            fn write_registers(
+                &mut self,
+                _regs: &crate::arch::arm::reg::ArmCoreRegs,
+            ) -> crate::target::TargetResult<(), Self> {
+                Ok(())
+            }
+
+            fn read_addrs(
+                &mut self,
+                _start_addr: u32,
+                data: &mut [u8],
+            ) -> crate::target::TargetResult<(), Self> {
+                data.iter_mut().for_each(|b| *b = 0);
+                Ok(())
+            }
+
+            fn write_addrs(
+                &mut self,
+                _start_addr: u32,
+                _data: &[u8],
+            ) -> crate::target::TargetResult<(), Self> {
+                Ok(())
+            }
+        }
+    };
+}
@@ -0,0 +1,70 @@
+//! End-to-end tests which drive a `GdbStub` using canned GDB packets.
+
+#[macro_use]
+mod mock;
+
+mod allow;
+#[cfg(feature = "async")]
+mod async_stub;
+mod auxv;
+mod break_stop_reasons;
+mod breakpoints;
+mod btrace;
+mod builder;
+mod catch_fork;
+mod console_output;
+mod custom_xfer;
+mod detach;
+mod endianness;
+mod exec_file;
+mod exit_status;
+mod expedited;
+mod extended_mode;
+mod flash;
+mod host_io;
+mod host_syscall;
+mod idle;
+mod inferior_call;
+mod instruction_step;
+mod interrupt;
+mod last_stop_reason;
+mod libraries_svr4;
+mod memory_map;
+mod memory_read;
+mod memory_search;
+mod memory_tags;
+mod memory_write;
+mod monitor_cmd;
+mod multiprocess;
+mod no_ack;
+mod non_stop;
+mod osdata;
+mod packet_errors;
+mod packet_trace;
+mod qsupported;
+mod range_step;
+mod read_only;
+mod registers;
+mod resume;
+mod retransmit;
+mod reverse_exec;
+mod rle;
+mod section_offsets;
+mod siginfo;
+mod signal_mapping;
+mod signals;
+mod single_step_via_breakpoints;
+#[cfg(feature = "stats")]
+mod stats;
+mod sve;
+mod target_description;
+mod thread_alive;
+mod thread_events;
+mod thread_extra_info;
+mod thread_info;
+mod thread_list_xml;
+mod thread_select;
+mod tls;
+mod unknown_packets;
+mod vcont_actions;
+mod watchpoints;
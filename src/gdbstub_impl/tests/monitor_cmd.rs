@@ -0,0 +1,269 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{run_session, split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::monitor_cmd::{
+    dispatch_monitor_cmd, ConsoleOutput, MonitorCmd, MonitorCmdOps, MonitorSubcommand,
+};
+use crate::target::Target;
+use crate::{Connection, GdbStub};
+
+/// A target whose `dump` monitor command prints `lines` lines of output.
+struct MonitorTarget {
+    lines: usize,
+}
+
+impl Target for MonitorTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn monitor_cmd(&mut self) -> Option<MonitorCmdOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(MonitorTarget);
+
+impl MonitorCmd for MonitorTarget {
+    fn handle_monitor_cmd(
+        &mut self,
+        cmd: &[u8],
+        mut out: ConsoleOutput<'_>,
+    ) -> Result<(), Self::Error> {
+        match cmd {
+            b"dump" => {
+                for i in 0..self.lines {
+                    crate::outputln!(out, "line {:03}: {}", i, "x".repeat(40));
+                }
+            }
+            b"snapshot" => {
+                use core::fmt::Write;
+                let _ = out.write_str("snapshot:");
+                out.write_raw(&(0..=0xff).collect::<Vec<u8>>());
+            }
+            _ => crate::outputln!(out, "unknown command"),
+        }
+        Ok(())
+    }
+}
+
+fn hex(s: &str) -> String {
+    s.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(s: &str) -> String {
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn output_larger_than_packet_buffer() {
+    const PACKET_BUFFER_LEN: usize = 64;
+
+    let mut target = MonitorTarget { lines: 50 };
+    let cmd = format!("qRcmd,{}", hex("dump"));
+    let mut conn = MockConnection::new(&[&cmd]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(PACKET_BUFFER_LEN)
+        .build()
+        .unwrap()
+        .run(&mut target);
+
+    let res = split_responses(&conn.output);
+
+    // all output is sent before the final `OK`...
+    let (ok, output) = res.split_last().unwrap();
+    assert_eq!(ok, "OK");
+
+    // ...with each `O` packet fitting within the packet buffer
+    assert!(output.len() > 50);
+    for packet in output {
+        assert!(packet.starts_with('O'));
+        assert!(packet.len() <= PACKET_BUFFER_LEN);
+    }
+
+    let output = output.iter().map(|p| unhex(&p[1..])).collect::<String>();
+    let expected = (0..50)
+        .map(|i| format!("line {:03}: {}\n", i, "x".repeat(40)))
+        .collect::<String>();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn binary_output() {
+    let mut target = MonitorTarget { lines: 0 };
+    let (res, _) = run_session(&mut target, &[&format!("qRcmd,{}", hex("snapshot"))]);
+
+    let (ok, output) = res.split_last().unwrap();
+    assert_eq!(ok, "OK");
+
+    // every byte (including non-UTF-8 ones) is hex-encoded as-is
+    let output = output
+        .iter()
+        .map(|p| p.strip_prefix('O').unwrap())
+        .collect::<String>();
+    let expected = hex("snapshot:")
+        + &(0..=0xffu8)
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn output_macro_has_no_newline() {
+    let mut out = Vec::new();
+    {
+        let mut callback = |msg: &[u8]| out.extend_from_slice(msg);
+        let mut console = ConsoleOutput::new(&mut callback);
+        crate::output!(console, "{}", 1);
+        crate::outputln!(console, "{}", 2);
+    }
+    assert_eq!(out, b"12\n");
+}
+
+/// A target which uses `dispatch_monitor_cmd`, recording which subcommands
+/// were run (and with what arguments).
+#[derive(Default)]
+struct DispatchTarget {
+    log: Vec<(&'static str, Vec<u8>)>,
+}
+
+impl Target for DispatchTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn monitor_cmd(&mut self) -> Option<MonitorCmdOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(DispatchTarget);
+
+const SUBCOMMANDS: &[MonitorSubcommand<DispatchTarget>] = &[
+    MonitorSubcommand {
+        name: "reset",
+        help: "Reset the core.",
+        handler: |target, args, _out| {
+            target.log.push(("reset", args.to_vec()));
+            Ok(())
+        },
+    },
+    MonitorSubcommand {
+        name: "reset-all",
+        help: "Reset the core and all peripherals.",
+        handler: |target, args, _out| {
+            target.log.push(("reset-all", args.to_vec()));
+            Ok(())
+        },
+    },
+    MonitorSubcommand {
+        name: "stats",
+        help: "Print emulator statistics.",
+        handler: |target, args, mut out| {
+            target.log.push(("stats", args.to_vec()));
+            crate::outputln!(out, "cycles: 1234");
+            Ok(())
+        },
+    },
+];
+
+impl MonitorCmd for DispatchTarget {
+    fn handle_monitor_cmd(
+        &mut self,
+        cmd: &[u8],
+        out: ConsoleOutput<'_>,
+    ) -> Result<(), Self::Error> {
+        dispatch_monitor_cmd(self, SUBCOMMANDS, cmd, out)
+    }
+}
+
+/// Run `cmd` through `dispatch_monitor_cmd`, returning the console output.
+fn dispatch(target: &mut DispatchTarget, cmd: &str) -> String {
+    let mut out = Vec::new();
+    {
+        let mut callback = |msg: &[u8]| out.extend_from_slice(msg);
+        dispatch_monitor_cmd(
+            target,
+            SUBCOMMANDS,
+            cmd.as_bytes(),
+            ConsoleOutput::new(&mut callback),
+        )
+        .unwrap();
+    }
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn dispatch_prefix_matching() {
+    let mut target = DispatchTarget::default();
+    assert_eq!(dispatch(&mut target, "reset"), "");
+    assert_eq!(dispatch(&mut target, "reset- now"), "");
+    assert_eq!(dispatch(&mut target, "  st   -v  --all"), "cycles: 1234\n");
+    assert_eq!(
+        target.log,
+        [
+            ("reset", b"".to_vec()),
+            ("reset-all", b"now".to_vec()),
+            ("stats", b"-v  --all".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn dispatch_ambiguous_and_unknown() {
+    let mut target = DispatchTarget::default();
+    assert_eq!(
+        dispatch(&mut target, "r"),
+        "Ambiguous monitor command \"r\": reset, reset-all.\n"
+    );
+    assert_eq!(
+        dispatch(&mut target, "bogus 1 2"),
+        "Undefined monitor command: \"bogus\".  Try \"monitor help\".\n"
+    );
+    assert!(target.log.is_empty());
+}
+
+#[test]
+fn dispatch_help() {
+    let mut target = DispatchTarget::default();
+    let expected = "\
+List of monitor commands:
+
+help      -- Print this help message.
+reset     -- Reset the core.
+reset-all -- Reset the core and all peripherals.
+stats     -- Print emulator statistics.
+";
+    assert_eq!(dispatch(&mut target, "help"), expected);
+    assert_eq!(dispatch(&mut target, ""), expected);
+    assert_eq!(
+        dispatch(&mut target, "help sta"),
+        "stats -- Print emulator statistics.\n"
+    );
+    assert_eq!(
+        dispatch(&mut target, "help res"),
+        "Ambiguous monitor command \"res\": reset, reset-all.\n"
+    );
+    assert!(target.log.is_empty());
+}
+
+#[test]
+fn dispatch_via_qrcmd() {
+    let mut target = DispatchTarget::default();
+    let (res, _) = run_session(&mut target, &[&format!("qRcmd,{}", hex("stat"))]);
+    assert_eq!(res, [format!("O{}", hex("cycles: 1234\n")), "OK".into()]);
+    assert_eq!(target.log, [("stats", b"".to_vec())]);
+}
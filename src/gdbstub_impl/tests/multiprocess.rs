@@ -0,0 +1,271 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::{Pid, Tid};
+use crate::target::ext::base::multithread::{
+    Actions, MultiThreadOps, ThreadStopReason, TidSelector,
+};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{Args, ExtendedMode, ExtendedModeOps, ShouldTerminate};
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A target debugging two processes, each with a single thread (pid 1 owns
+/// tid 1, and pid 2 owns tid 2).
+struct MultiProcessTarget {
+    multiprocess: bool,
+    selectors: Vec<TidSelector>,
+    detached: Vec<Pid>,
+}
+
+impl MultiProcessTarget {
+    fn new(multiprocess: bool) -> MultiProcessTarget {
+        MultiProcessTarget {
+            multiprocess,
+            selectors: Vec::new(),
+            detached: Vec::new(),
+        }
+    }
+}
+
+fn pid(n: usize) -> Pid {
+    Pid::new(n).unwrap()
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for MultiProcessTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl MultiThreadOps for MultiProcessTarget {
+    fn resume(
+        &mut self,
+        actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.selectors.extend(actions.map(|(tid, _action)| tid));
+        Ok(ThreadStopReason::SwBreak(tid(2)))
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+
+    fn list_active_threads_with_pid(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Pid, Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(pid(1), tid(1));
+        thread_is_active(pid(2), tid(2));
+        Ok(())
+    }
+}
+
+impl ExtendedMode for MultiProcessTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(pid(1))
+    }
+
+    fn attach(&mut self, pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        match pid.get() {
+            // each process's tid matches its pid
+            n @ 1..=2 => Ok(ThreadStopReason::SignalWithThread {
+                tid: tid(n),
+                signal: 0,
+            }),
+            _ => Err(TargetError::Errno(3)), // ESRCH
+        }
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        Ok(ShouldTerminate::No)
+    }
+
+    fn detach(&mut self, pid: Pid) -> TargetResult<ShouldTerminate, Self> {
+        if !matches!(pid.get(), 1..=2) || self.detached.contains(&pid) {
+            return Err(TargetError::Errno(3)); // ESRCH
+        }
+        self.detached.push(pid);
+        // end the session once both processes have been detached
+        match self.detached.len() {
+            2 => Ok(ShouldTerminate::Yes),
+            _ => Ok(ShouldTerminate::No),
+        }
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn supports_multiprocess(&self) -> bool {
+        self.multiprocess
+    }
+}
+
+#[test]
+fn advertises_multiprocess() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";multiprocess+"));
+
+    let mut target = MultiProcessTarget::new(false);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(!res[0].contains("multiprocess"));
+}
+
+#[test]
+fn thread_ids_include_pid() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;swbreak+", "qfThreadInfo", "c"],
+    );
+    assert_eq!(res[1], "mp01.01,p02.02");
+    assert_eq!(res[2], "T05thread:p02.02;swbreak:;");
+}
+
+#[test]
+fn legacy_thread_ids() {
+    // GDB didn't ask for the multiprocess extensions
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+", "qfThreadInfo", "c"]);
+    assert_eq!(res[1], "m01,02");
+    assert_eq!(res[2], "T05thread:02;swbreak:;");
+
+    // the target only debugs a single process
+    let mut target = MultiProcessTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;swbreak+", "qfThreadInfo", "c"],
+    );
+    assert_eq!(res[1], "m01,02");
+    assert_eq!(res[2], "T05thread:02;swbreak:;");
+}
+
+#[test]
+fn vcont_selects_process() {
+    let mut target = MultiProcessTarget::new(true);
+    let (_, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+", "vCont;s:p1.1;c:p2.-1"],
+    );
+    assert_eq!(
+        target.selectors,
+        [TidSelector::WithID(tid(1)), TidSelector::Process(pid(2))]
+    );
+}
+
+#[test]
+fn attach_switches_process() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "vAttach;2", "c"]);
+    assert_eq!(res[1], "T00thread:p02.02;");
+    assert_eq!(target.selectors, [TidSelector::WithID(tid(2))]);
+}
+
+#[test]
+fn attach_errors() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "qSupported:multiprocess+",
+            "vAttach;2a",
+            "vAttach;1",
+            "vAttach;1",
+        ],
+    );
+    assert_eq!(res[1..], ["E03", "T00thread:p01.01;", "E01"]);
+}
+
+#[test]
+fn detach_single_process() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, result) = run_session(
+        &mut target,
+        &[
+            "qSupported:multiprocess+",
+            "D;2",
+            "D;2",
+            "D;5",
+            "D;1",
+            "qfThreadInfo",
+        ],
+    );
+    assert_eq!(res[1..], ["OK", "E03", "E03", "OK"]);
+    // detaching from the last process ends the session
+    assert!(matches!(result, Ok(crate::DisconnectReason::Disconnect)));
+    assert_eq!(target.detached, [pid(2), pid(1)]);
+}
+
+#[test]
+fn detach_all() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, result) = run_session(&mut target, &["qSupported:multiprocess+", "D", "D;1"]);
+    assert_eq!(res[1..], ["OK"]);
+    assert!(matches!(result, Ok(crate::DisconnectReason::Disconnect)));
+    assert!(target.detached.is_empty());
+}
+
+#[test]
+fn kill_keeps_session_alive() {
+    // the target keeps running after a kill, waiting for new run / attach requests
+    let mut target = MultiProcessTarget::new(true);
+    let (res, result) = run_session(&mut target, &["vKill;1", "k", "qfThreadInfo"]);
+    assert!(result.is_err()); // eof
+    assert_eq!(res, ["OK", "m01,02"]);
+}
+
+#[test]
+fn current_thread() {
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(&mut target, &["qC", "Hg2", "qC", "Hg1", "qC", "c", "qC"]);
+    // resuming the target switches over to the thread which stopped (tid 2)
+    assert_eq!(
+        res,
+        ["QC01", "OK", "QC02", "OK", "QC01", "T05thread:02;", "QC02"]
+    );
+
+    let mut target = MultiProcessTarget::new(true);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "Hgp2.2", "qC"]);
+    assert_eq!(res[1..], ["OK", "QCp02.02"]);
+}
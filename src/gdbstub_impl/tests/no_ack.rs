@@ -0,0 +1,59 @@
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{Connection, GdbStub};
+
+struct NoAckTarget;
+
+impl Target for NoAckTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(NoAckTarget);
+
+fn run_raw(packets: &[&str]) -> Vec<u8> {
+    let mut conn = MockConnection::new(packets);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut NoAckTarget);
+    conn.output
+}
+
+#[test]
+fn handshake() {
+    let output = run_raw(&[
+        "qSupported:multiprocess+",
+        "+",
+        "QStartNoAckMode",
+        "+",
+        "?",
+        "g",
+    ]);
+
+    let responses = split_responses(&output);
+    assert!(responses[0].contains(";QStartNoAckMode+"));
+    assert_eq!(responses[1], "OK");
+
+    // the `QStartNoAckMode` packet is acked, followed by the `OK` reply...
+    let ok = b"+$OK#9a";
+    let pos = output
+        .windows(ok.len())
+        .position(|w| w == ok)
+        .expect("missing acked OK");
+
+    // ...after which no more acks are sent
+    let rest = &output[pos + ok.len()..];
+    assert_eq!(rest[0], b'$');
+    assert_eq!(split_responses(rest).len(), 2);
+    assert!(!rest.windows(2).any(|w| w == b"#+" || w == b"+$"));
+}
+
+#[test]
+fn nacks_ignored_in_no_ack_mode() {
+    let output = run_raw(&["QStartNoAckMode", "-", "?"]);
+    assert_eq!(split_responses(&output), ["OK", "T05thread:01;"]);
+}
@@ -0,0 +1,323 @@
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::{Pid, Tid};
+use crate::target::ext::base::multithread::{
+    Actions, MultiThreadOps, ThreadStopReason, TidSelector,
+};
+use crate::target::ext::base::non_stop::{NonStopMode, NonStopModeOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{Args, ExtendedMode, ExtendedModeOps, ShouldTerminate};
+use crate::target::{Target, TargetResult};
+
+/// A two-thread target, where running threads immediately hit a breakpoint
+/// (if `breaks` is set) once GDB stops sending packets.
+struct NonStopTarget {
+    breaks: bool,
+    running: [bool; 2],
+    pending: VecDeque<ThreadStopReason<u32>>,
+}
+
+impl NonStopTarget {
+    fn new(breaks: bool) -> NonStopTarget {
+        NonStopTarget {
+            breaks,
+            running: [false; 2],
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for NonStopTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+
+    fn non_stop_mode(&mut self) -> Option<NonStopModeOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl MultiThreadOps for NonStopTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+}
+
+impl NonStopMode for NonStopTarget {
+    fn resume_non_stop(&mut self, actions: Actions) -> Result<(), Self::Error> {
+        for (tid, _action) in actions {
+            match tid {
+                TidSelector::All | TidSelector::Process(_) => self.running = [true; 2],
+                TidSelector::WithID(tid) => self.running[tid.get() - 1] = true,
+            }
+        }
+        Ok(())
+    }
+
+    fn stop_threads(&mut self, selector: TidSelector) -> Result<(), Self::Error> {
+        let tids: Vec<usize> = match selector {
+            TidSelector::All | TidSelector::Process(_) => vec![1, 2],
+            TidSelector::WithID(tid) => vec![tid.get()],
+        };
+        for n in tids {
+            if self.running[n - 1] {
+                self.running[n - 1] = false;
+                self.pending.push_back(ThreadStopReason::SignalWithThread {
+                    tid: tid(n),
+                    signal: 0,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_stop_event(
+        &mut self,
+        incoming_data: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<ThreadStopReason<u32>>, Self::Error> {
+        if let Some(stop_reason) = self.pending.pop_front() {
+            return Ok(Some(stop_reason));
+        }
+
+        if !self.running.iter().any(|r| *r) || incoming_data() || !self.breaks {
+            return Ok(None);
+        }
+
+        // "run" the target: every running thread hits a breakpoint
+        for n in 1..=2 {
+            if self.running[n - 1] {
+                self.running[n - 1] = false;
+                self.pending.push_back(ThreadStopReason::SwBreak(tid(n)));
+            }
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    fn list_stopped_threads(
+        &mut self,
+        thread_is_stopped: &mut dyn FnMut(ThreadStopReason<u32>),
+    ) -> Result<(), Self::Error> {
+        for n in 1..=2 {
+            if !self.running[n - 1] {
+                thread_is_stopped(ThreadStopReason::SignalWithThread {
+                    tid: tid(n),
+                    signal: 0,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ExtendedMode for NonStopTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        self.running = [false; 2];
+        Ok(ThreadStopReason::Signal(19))
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        Ok(ShouldTerminate::Yes)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_non_stop() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+", "vCont?"]);
+    assert!(res[0].contains(";QNonStop+"));
+    assert_eq!(res[1], "vCont;c;C;s;S;t");
+}
+
+#[test]
+fn vstopped_drains_queued_stops() {
+    let mut target = NonStopTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &["QNonStop:1", "vCont;c", "vStopped", "vStopped"],
+    );
+    assert_eq!(
+        res,
+        ["OK", "OK", "%Stop:T05thread:01;", "T05thread:02;", "OK",]
+    );
+}
+
+#[test]
+fn vcont_t_stops_single_thread() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["QNonStop:1", "vCont;c", "vCont;t:p1.2", "vStopped"],
+    );
+    assert_eq!(res, ["OK", "OK", "OK", "%Stop:T00thread:02;", "OK"]);
+    assert_eq!(target.running, [true, false]);
+}
+
+#[test]
+fn all_stop_by_default() {
+    let mut target = NonStopTarget::new(true);
+    let (res, _) = run_session(&mut target, &["vCont;c"]);
+    assert_eq!(res, ["S05"]);
+}
+
+#[test]
+fn vcont_t_without_pid() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["QNonStop:1", "vCont;c", "vCont;t:2", "vStopped"],
+    );
+    assert_eq!(res, ["OK", "OK", "OK", "%Stop:T00thread:02;", "OK"]);
+    assert_eq!(target.running, [true, false]);
+}
+
+#[test]
+fn vcont_t_rejected_in_all_stop() {
+    let mut target = NonStopTarget::new(false);
+    let (res, result) = run_session(&mut target, &["vCont;t:2"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketUnexpected)));
+    // the target must not have been resumed (which would have reported a stop)
+    assert!(res.is_empty());
+    assert!(target.pending.is_empty());
+}
+
+#[test]
+fn vctrlc_stops_threads() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["QNonStop:1", "vCont;c", "vCtrlC", "vStopped", "vStopped"],
+    );
+    assert_eq!(
+        res,
+        [
+            "OK",
+            "OK",
+            "OK",
+            "%Stop:T00thread:01;",
+            "T00thread:02;",
+            "OK"
+        ]
+    );
+    assert_eq!(target.running, [false, false]);
+}
+
+#[test]
+fn vctrlc_stops_selected_thread() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &["QNonStop:1", "vCont;c", "Hc2", "vCtrlC", "vStopped"],
+    );
+    assert_eq!(res, ["OK", "OK", "OK", "OK", "%Stop:T00thread:02;", "OK"]);
+    assert_eq!(target.running, [true, false]);
+}
+
+#[test]
+fn vctrlc_in_all_stop() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["vCtrlC"]);
+    // treated just like an interrupt byte received while the target is stopped
+    assert_eq!(res, ["S05"]);
+    assert!(target.pending.is_empty());
+}
+
+#[test]
+fn question_mark_lists_stopped_threads() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "QNonStop:1",
+            "vCont;c:p1.2",
+            "vCont;t:p1.2",
+            "vStopped",
+            "?",
+            "vStopped",
+            "vStopped",
+        ],
+    );
+    assert_eq!(
+        res,
+        [
+            "OK",
+            "OK",
+            "OK",
+            "%Stop:T00thread:02;",
+            "OK",
+            "T00thread:01;",
+            "T00thread:02;",
+            "OK"
+        ]
+    );
+}
+
+#[test]
+fn question_mark_all_threads_running() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["QNonStop:1", "vCont;c", "?"]);
+    assert_eq!(res, ["OK", "OK", "OK"]);
+}
+
+#[test]
+fn vattach_in_non_stop() {
+    let mut target = NonStopTarget::new(false);
+    let (res, _) = run_session(&mut target, &["QNonStop:1", "vAttach;1", "vStopped"]);
+    assert_eq!(res, ["OK", "OK", "%Stop:T13thread:01;", "OK"]);
+}
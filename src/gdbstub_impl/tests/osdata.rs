@@ -0,0 +1,73 @@
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::osdata::{OsData, OsDataOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+const TYPES: &str = r#"<osdata type="types"><item><column name="Type">processes</column><column name="Description">Listing of all processes</column><column name="Title">Processes</column></item></osdata>"#;
+
+const PROCESSES: &str = r#"<osdata type="processes"><item><column name="pid">1</column><column name="command">init</column></item><item><column name="pid">42</column><column name="command">shell</column></item></osdata>"#;
+
+struct OsDataTarget;
+
+impl Target for OsDataTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn osdata(&mut self) -> Option<OsDataOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(OsDataTarget);
+
+impl OsData for OsDataTarget {
+    fn read_osdata(
+        &self,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let xml = match annex {
+            b"" => TYPES,
+            b"processes" => PROCESSES,
+            _ => return Err(TargetError::NonFatal),
+        };
+
+        Ok(copy_xfer_chunk(xml.as_bytes(), offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_osdata() {
+    assert!(advertises(&mut OsDataTarget, "qXfer:osdata:read+"));
+}
+
+#[test]
+fn pages_processes_table() {
+    let data = read_xfer_paged(
+        &mut OsDataTarget,
+        "qXfer:osdata:read:processes:",
+        PROCESSES.len(),
+        PROCESSES.len() / 2 + 1,
+        None,
+    );
+    assert_eq!(data, PROCESSES.as_bytes());
+}
+
+#[test]
+fn empty_annex_lists_types() {
+    let (res, _) = run_session(&mut OsDataTarget, &["qXfer:osdata:read::0,1000"]);
+    assert_eq!(res, [format!("l{}", TYPES)]);
+}
+
+#[test]
+fn unknown_annex() {
+    let (res, _) = run_session(&mut OsDataTarget, &["qXfer:osdata:read:widgets:0,1000"]);
+    assert_eq!(res, ["E79"]);
+}
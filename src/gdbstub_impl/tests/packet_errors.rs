@@ -0,0 +1,85 @@
+use std::string::ToString;
+
+use super::mock::{run_session, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{Connection, GdbStub, GdbStubError, PacketParseError, PacketSnippet};
+
+struct ErrorTarget;
+
+impl Target for ErrorTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ErrorTarget);
+
+#[test]
+fn malformed_command_includes_packet() {
+    // `m` requires a length
+    let (res, result) = run_session(&mut ErrorTarget, &["m0,4", "m1000"]);
+    assert_eq!(res, ["00000000"]);
+
+    let err = result.unwrap_err();
+    match &err {
+        GdbStubError::PacketParse(PacketParseError::MalformedCommand(snippet)) => {
+            assert_eq!(snippet.as_bytes(), b"m1000");
+            assert!(!snippet.is_truncated());
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+    assert_eq!(
+        err.to_string(),
+        "Could not parse the packet into a valid command: malformed command: \"m1000\""
+    );
+}
+
+#[test]
+fn long_packets_are_truncated() {
+    // an agent expression with trailing garbage
+    let packet = format!("Z0,1000,4;X1,27{}", "y".repeat(64));
+    let (_, result) = run_session(&mut ErrorTarget, &[&packet]);
+    match result {
+        Err(GdbStubError::PacketParse(PacketParseError::MalformedCommand(snippet))) => {
+            assert_eq!(
+                snippet.as_bytes(),
+                &packet.as_bytes()[..PacketSnippet::MAX_LEN]
+            );
+            assert!(snippet.is_truncated());
+            assert!(snippet.to_string().ends_with("yyy..."));
+        }
+        e => panic!("unexpected result: {:?}", e),
+    }
+}
+
+#[test]
+fn snippet_escapes_binary_data() {
+    let snippet = PacketSnippet::new(b"X0,2:\x00\xff");
+    assert_eq!(snippet.to_string(), "X0,2:\\x00\\xff");
+    assert_eq!(
+        format!("{:?}", snippet),
+        "PacketSnippet(\"X0,2:\\x00\\xff\")"
+    );
+}
+
+#[test]
+fn checksum_mismatch() {
+    let mut conn = MockConnection::new(&["QStartNoAckMode", "$?#00"]);
+    let res = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut ErrorTarget);
+    let err = res.unwrap_err();
+    assert!(matches!(
+        err,
+        GdbStubError::PacketParse(PacketParseError::ChecksumMismatched {
+            checksum: 0x00,
+            calculated: 0x3f
+        })
+    ));
+    assert!(err
+        .to_string()
+        .ends_with("checksum mismatch (packet: 00, calculated: 3f)"));
+}
@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{Connection, GdbStub, PacketDirection};
+
+struct TraceTarget;
+
+impl Target for TraceTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(TraceTarget);
+
+std::thread_local! {
+    static TRACE: RefCell<Vec<(PacketDirection, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(direction: PacketDirection, packet: &[u8]) {
+    TRACE.with(|t| t.borrow_mut().push((direction, packet.to_vec())));
+}
+
+/// Run a session with packet tracing enabled, returning the stub's raw output,
+/// alongside the traced packets.
+fn run_traced(packets: &[&str]) -> (Vec<u8>, Vec<(PacketDirection, Vec<u8>)>) {
+    TRACE.with(|t| t.borrow_mut().clear());
+
+    let mut conn = MockConnection::new(packets);
+    let mut stub = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>);
+    stub.set_packet_trace(record);
+    let _ = stub.run(&mut TraceTarget);
+
+    (conn.output, TRACE.with(|t| t.take()))
+}
+
+fn concat(trace: &[(PacketDirection, Vec<u8>)], direction: PacketDirection) -> String {
+    let bytes = trace
+        .iter()
+        .filter(|(d, _)| *d == direction)
+        .flat_map(|(_, p)| p.iter().copied())
+        .collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn register_round_trip() {
+    let (_, res) = run_traced(&["g"]);
+    let regs = split_responses(&concat(&res, PacketDirection::Outgoing).into_bytes())
+        .pop()
+        .unwrap();
+    let write_regs = format!("G{}", regs);
+
+    for packets in [
+        &["+", "g", "+", &write_regs, "+"][..],
+        &["QStartNoAckMode", "+", "g", &write_regs],
+    ]
+    .iter()
+    {
+        let (output, trace) = run_traced(packets);
+
+        // every byte sent over the connection is traced, including acks...
+        assert_eq!(
+            concat(&trace, PacketDirection::Outgoing).as_bytes(),
+            &*output
+        );
+
+        // ...as is every packet received from the client
+        let incoming = trace
+            .iter()
+            .filter(|(d, _)| *d == PacketDirection::Incoming)
+            .map(|(_, p)| String::from_utf8(p.clone()).unwrap())
+            .collect::<Vec<_>>();
+        let expected = packets
+            .iter()
+            .map(|p| match *p {
+                "+" => String::from("+"),
+                p => {
+                    let checksum = p.bytes().fold(0u8, |a, b| a.wrapping_add(b));
+                    format!("${}#{:02x}", p, checksum)
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(incoming, expected);
+
+        let responses = split_responses(&output);
+        assert_eq!(responses[responses.len() - 2], regs);
+        assert_eq!(responses[responses.len() - 1], "OK");
+    }
+}
+
+#[test]
+fn long_responses_are_chunked() {
+    let (output, trace) = run_traced(&["g"]);
+
+    // the register file doesn't fit in a single trace chunk
+    let outgoing = trace
+        .iter()
+        .filter(|(d, _)| *d == PacketDirection::Outgoing)
+        .count();
+    assert!(outgoing > 2);
+    assert_eq!(
+        concat(&trace, PacketDirection::Outgoing).as_bytes(),
+        &*output
+    );
+}
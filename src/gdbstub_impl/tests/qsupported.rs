@@ -0,0 +1,91 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::qsupported::{QSupported, QSupportedOps};
+use crate::target::Target;
+
+/// A target which records GDB's features, and suppresses / adds features of
+/// its own.
+#[derive(Default)]
+struct FeatureTarget {
+    gdb_features: Vec<Vec<u8>>,
+    suppress: Vec<&'static str>,
+    extra: Vec<&'static str>,
+}
+
+impl Target for FeatureTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn qsupported(&mut self) -> Option<QSupportedOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(FeatureTarget);
+
+impl QSupported for FeatureTarget {
+    fn on_gdb_feature(&mut self, feature: &[u8]) -> Result<(), Self::Error> {
+        self.gdb_features.push(feature.to_vec());
+        Ok(())
+    }
+
+    fn advertise_feature(&mut self, name: &[u8]) -> bool {
+        !self.suppress.iter().any(|s| s.as_bytes() == name)
+    }
+
+    fn extra_features(&mut self, add_feature: &mut dyn FnMut(&[u8])) -> Result<(), Self::Error> {
+        for feature in self.extra.iter() {
+            add_feature(feature.as_bytes());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn receives_gdb_features() {
+    let mut target = FeatureTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["qSupported:multiprocess+;swbreak+;xmlRegisters=i386"],
+    );
+    assert!(res[0].starts_with("PacketSize="));
+    assert_eq!(
+        target.gdb_features,
+        [
+            &b"multiprocess+"[..],
+            &b"swbreak+"[..],
+            &b"xmlRegisters=i386"[..]
+        ]
+    );
+}
+
+#[test]
+fn suppress_and_add_features() {
+    let mut target = FeatureTarget {
+        suppress: vec!["QStartNoAckMode", "PacketSize"],
+        extra: vec!["PacketSize=2000", "foo+"],
+        ..Default::default()
+    };
+    let (res, _) = run_session(&mut target, &["qSupported:swbreak+"]);
+
+    let features = res[0].split(';').collect::<Vec<_>>();
+    assert!(!features.contains(&"QStartNoAckMode+"));
+    assert_eq!(
+        features
+            .iter()
+            .filter(|f| f.starts_with("PacketSize"))
+            .count(),
+        1
+    );
+    // the first feature isn't preceded by a separator
+    assert_eq!(features[0], "vContSupported+");
+    assert!(features.contains(&"swbreak+"));
+    assert_eq!(features[features.len() - 2..], ["PacketSize=2000", "foo+"]);
+}
@@ -0,0 +1,152 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{
+    Actions, MultiThreadOps, ResumeAction, ThreadStopReason, TidSelector,
+};
+use crate::target::ext::base::range_step::{RangeStep, RangeStepOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+type RangeStepCall = (Tid, u32, u32, Vec<(TidSelector, ResumeAction)>);
+
+/// A two-thread target which records any range-step requests.
+#[derive(Default)]
+struct RangeStepTarget {
+    unsupported: bool,
+    calls: Vec<RangeStepCall>,
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for RangeStepTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+
+    fn range_step(&mut self) -> Option<RangeStepOps<'_, Self>> {
+        if self.unsupported {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl MultiThreadOps for RangeStepTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+}
+
+impl RangeStep for RangeStepTarget {
+    fn resume_range_step(
+        &mut self,
+        tid: Tid,
+        start: u32,
+        end: u32,
+        other_actions: Actions<'_>,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.calls
+            .push((tid, start, end, other_actions.collect::<Vec<_>>()));
+        Ok(ThreadStopReason::DoneStep)
+    }
+}
+
+#[test]
+fn advertises_range_step() {
+    let (res, _) = run_session(&mut RangeStepTarget::default(), &["vCont?"]);
+    assert_eq!(res, ["vCont;c;C;s;S;r"]);
+
+    let mut target = RangeStepTarget {
+        unsupported: true,
+        ..Default::default()
+    };
+    let (res, _) = run_session(&mut target, &["vCont?"]);
+    assert_eq!(res, ["vCont;c;C;s;S"]);
+}
+
+#[test]
+fn range_step() {
+    let mut target = RangeStepTarget::default();
+    let (res, _) = run_session(&mut target, &["vCont;r1000,1010:2"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(target.calls, [(tid(2), 0x1000, 0x1010, Vec::new())]);
+}
+
+#[test]
+fn range_step_with_other_actions() {
+    let mut target = RangeStepTarget::default();
+    let (res, _) = run_session(&mut target, &["vCont;r2000,2008:1;c"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(
+        target.calls,
+        [(
+            tid(1),
+            0x2000,
+            0x2008,
+            vec![(TidSelector::All, ResumeAction::Continue(None))]
+        )]
+    );
+}
+
+#[test]
+fn range_step_current_thread() {
+    let mut target = RangeStepTarget::default();
+    let (res, _) = run_session(&mut target, &["Hc2", "vCont;r2000,2008"]);
+    assert_eq!(res, ["OK", "S05"]);
+    assert_eq!(target.calls, [(tid(2), 0x2000, 0x2008, Vec::new())]);
+}
+
+#[test]
+fn range_step_unsupported() {
+    let mut target = RangeStepTarget {
+        unsupported: true,
+        ..Default::default()
+    };
+    let (_, result) = run_session(&mut target, &["vCont;r1000,1010:1"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketUnexpected)));
+}
@@ -0,0 +1,145 @@
+use super::mock::run_session;
+use crate::arch::arm::reg::id::ArmCoreRegId;
+use crate::arch::arm::{reg::ArmCoreRegs, ArmBreakpointKind, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::breakpoints::{SwBreakpoint, SwBreakpointOps};
+use crate::target::ext::read_only::{ReadOnly, ReadOnlyOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+const MEM_BASE: u32 = 0x1000;
+
+/// A static "core dump", which must never be written to or resumed.
+struct CoreDump {
+    regs: ArmCoreRegs,
+    mem: [u8; 4],
+}
+
+impl CoreDump {
+    fn new() -> CoreDump {
+        let mut regs = ArmCoreRegs::default();
+        regs.r[0] = 0x1234_5678;
+        regs.pc = MEM_BASE;
+        CoreDump {
+            regs,
+            mem: [0xde, 0xad, 0xbe, 0xef],
+        }
+    }
+}
+
+impl Target for CoreDump {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn read_only(&mut self) -> Option<ReadOnlyOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl ReadOnly for CoreDump {}
+
+impl SingleThreadOps for CoreDump {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        panic!("resumed a core dump")
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        *regs = self.regs.clone();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        panic!("wrote registers of a core dump")
+    }
+
+    fn read_register(&mut self, reg_id: ArmCoreRegId, dst: &mut [u8]) -> TargetResult<usize, Self> {
+        match reg_id {
+            ArmCoreRegId::Gpr(0) => dst.copy_from_slice(&self.regs.r[0].to_le_bytes()),
+            _ => return Err(TargetError::NonFatal),
+        }
+        Ok(dst.len())
+    }
+
+    fn write_register(&mut self, _reg_id: ArmCoreRegId, _val: &[u8]) -> TargetResult<(), Self> {
+        panic!("wrote a register of a core dump")
+    }
+
+    fn read_addrs(&mut self, start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        for (i, b) in data.iter_mut().enumerate() {
+            let offset = (start_addr as usize + i).wrapping_sub(MEM_BASE as usize);
+            *b = *self.mem.get(offset).ok_or(TargetError::Errno(14))?;
+        }
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        panic!("wrote memory of a core dump")
+    }
+}
+
+impl SwBreakpoint for CoreDump {
+    fn add_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        panic!("inserted a breakpoint into a core dump")
+    }
+
+    fn remove_sw_breakpoint(
+        &mut self,
+        _addr: u32,
+        _kind: ArmBreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(false)
+    }
+}
+
+#[test]
+fn reads_succeed() {
+    let (res, result) = run_session(&mut CoreDump::new(), &["?", "p0", "m1000,4", "vCont?"]);
+    assert!(result.is_err()); // eof
+    assert_eq!(
+        res,
+        ["T05thread:01;", "78563412", "deadbeef", "vCont;c;C;s;S"]
+    );
+
+    let (res, _) = run_session(&mut CoreDump::new(), &["g"]);
+    assert!(res[0].starts_with("78563412"));
+}
+
+#[test]
+fn writes_rejected() {
+    let mut target = CoreDump::new();
+    let (res, result) = run_session(
+        &mut target,
+        &[
+            "G00000000",
+            "P0=00000000",
+            "M1000,1:00",
+            "Z0,1000,4",
+            "c",
+            "s",
+            "vCont;c",
+            "m1000,4",
+        ],
+    );
+    assert!(result.is_err()); // eof
+    assert_eq!(
+        res,
+        ["E01", "E01", "E01", "E01", "E01", "E01", "E01", "deadbeef"]
+    );
+    assert_eq!(target.mem, [0xde, 0xad, 0xbe, 0xef]);
+}
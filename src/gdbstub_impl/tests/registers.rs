@@ -0,0 +1,88 @@
+use super::mock::run_session;
+use crate::arch::arm::reg::id::ArmCoreRegId;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A target whose PC is advanced out-of-band (e.g: by a DMA engine) every
+/// time its registers are read.
+#[derive(Default)]
+struct DmaTarget {
+    pc: u32,
+    reads: usize,
+}
+
+impl Target for DmaTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for DmaTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        self.reads += 1;
+        self.pc += 4;
+        regs.pc = self.pc;
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_register(&mut self, reg_id: ArmCoreRegId, dst: &mut [u8]) -> TargetResult<usize, Self> {
+        if !matches!(reg_id, ArmCoreRegId::Pc) {
+            return Err(TargetError::NonFatal);
+        }
+        self.reads += 1;
+        self.pc += 4;
+        dst.copy_from_slice(&self.pc.to_le_bytes());
+        Ok(dst.len())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+/// Extract the PC (register 15) from a `g` reply.
+fn g_reply_pc(reply: &str) -> &str {
+    &reply[15 * 8..16 * 8]
+}
+
+#[test]
+fn g_always_rereads_registers() {
+    let mut target = DmaTarget::default();
+    let (res, _) = run_session(&mut target, &["g", "g"]);
+    assert_eq!(g_reply_pc(&res[0]), "04000000");
+    assert_eq!(g_reply_pc(&res[1]), "08000000");
+    assert_eq!(target.reads, 2);
+}
+
+#[test]
+fn p_always_rereads_register() {
+    let mut target = DmaTarget::default();
+    let (res, _) = run_session(&mut target, &["g", "pf", "pf", "g"]);
+    assert_eq!(g_reply_pc(&res[0]), "04000000");
+    assert_eq!(res[1], "08000000");
+    assert_eq!(res[2], "0c000000");
+    assert_eq!(g_reply_pc(&res[3]), "10000000");
+    assert_eq!(target.reads, 4);
+}
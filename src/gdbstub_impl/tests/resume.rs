@@ -0,0 +1,164 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Signal;
+use crate::target::ext::base::singlethread::{
+    ResumeAction, ResumeCapabilities, SingleThreadOps, StopReason,
+};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::GdbStubError;
+
+/// A target which records how it was asked to resume.
+struct ResumeTarget {
+    caps: ResumeCapabilities,
+    actions: Vec<ResumeAction>,
+}
+
+impl Default for ResumeTarget {
+    fn default() -> ResumeTarget {
+        ResumeTarget::new(ResumeCapabilities::ALL)
+    }
+}
+
+impl ResumeTarget {
+    fn new(caps: ResumeCapabilities) -> ResumeTarget {
+        ResumeTarget {
+            caps,
+            actions: Vec::new(),
+        }
+    }
+}
+
+impl Target for ResumeTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl SingleThreadOps for ResumeTarget {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        self.actions.push(action);
+        Ok(StopReason::DoneStep)
+    }
+
+    fn resume_capabilities(&self) -> ResumeCapabilities {
+        self.caps
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+#[test]
+fn resume_with_signal() {
+    let mut target = ResumeTarget::default();
+    let (res, _) = run_session(&mut target, &["c", "C0b", "s", "S0b;1000"]);
+    assert_eq!(res, ["S05", "S05", "S05", "S05"]);
+    assert_eq!(
+        target.actions,
+        [
+            ResumeAction::Continue(None),
+            ResumeAction::Continue(Some(Signal::SIGSEGV)),
+            ResumeAction::Step(None),
+            ResumeAction::Step(Some(Signal::SIGSEGV)),
+        ]
+    );
+}
+
+#[test]
+fn vcont_with_signal() {
+    let mut target = ResumeTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["vCont;c", "vCont;C0b:p1.1", "vCont;S02:p1.1"],
+    );
+    assert_eq!(res, ["S05", "S05", "S05"]);
+    assert_eq!(
+        target.actions,
+        [
+            ResumeAction::Continue(None),
+            ResumeAction::Continue(Some(Signal::SIGSEGV)),
+            ResumeAction::Step(Some(Signal::SIGINT)),
+        ]
+    );
+}
+
+#[test]
+fn vcont_query_full_featured() {
+    let (res, _) = run_session(&mut ResumeTarget::default(), &["vCont?"]);
+    assert_eq!(res, ["vCont;c;C;s;S"]);
+}
+
+#[test]
+fn vcont_query_continue_only() {
+    // GDB disables `vCont` entirely unless both `c` and `C` are advertised
+    let mut target = ResumeTarget::new(ResumeCapabilities::CONTINUE_ONLY);
+    let (res, _) = run_session(&mut target, &["vCont?"]);
+    assert_eq!(res, ["vCont;c;C"]);
+}
+
+#[test]
+fn vcont_query_without_signals() {
+    let mut target = ResumeTarget::new(ResumeCapabilities {
+        step: true,
+        signals: false,
+    });
+    let (res, _) = run_session(&mut target, &["vCont?", "C0b", "S0b", "vCont;C0b", "s"]);
+    assert_eq!(res, ["vCont;c;C;s;S", "E16", "E16", "E16", "S05"]);
+    // the signal is rejected without resuming the target
+    assert_eq!(target.actions, [ResumeAction::Step(None)]);
+}
+
+#[test]
+fn unsupported_actions_rejected() {
+    let mut target = ResumeTarget::new(ResumeCapabilities::CONTINUE_ONLY);
+    let (_, res) = run_session(&mut target, &["s"]);
+    assert!(matches!(res, Err(GdbStubError::PacketUnexpected)));
+    assert!(target.actions.is_empty());
+
+    for packet in ["C0b", "vCont;s", "vCont;C0b"].iter() {
+        let mut target = ResumeTarget::new(ResumeCapabilities::CONTINUE_ONLY);
+        let (res, _) = run_session(&mut target, &[packet]);
+        assert_eq!(res, ["E16"]);
+        assert!(target.actions.is_empty());
+    }
+
+    let mut target = ResumeTarget::new(ResumeCapabilities::CONTINUE_ONLY);
+    let (res, _) = run_session(&mut target, &["c", "vCont;c"]);
+    assert_eq!(res, ["S05", "S05"]);
+    assert_eq!(target.actions, [ResumeAction::Continue(None); 2]);
+}
+
+#[test]
+fn vcont_skips_nonexistent_threads() {
+    let mut target = ResumeTarget::default();
+    let (res, _) = run_session(&mut target, &["vCont;s:2;c", "vCont;s:1;c"]);
+    assert_eq!(res, ["S05", "S05"]);
+    assert_eq!(
+        target.actions,
+        [ResumeAction::Continue(None), ResumeAction::Step(None)]
+    );
+}
@@ -0,0 +1,65 @@
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{Connection, GdbStub};
+
+struct RetransmitTarget;
+
+impl Target for RetransmitTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(RetransmitTarget);
+
+fn run_raw(packets: &[&str]) -> Vec<u8> {
+    let mut conn = MockConnection::new(packets);
+    let res = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut RetransmitTarget);
+    assert!(res.is_err()); // eof
+    conn.output
+}
+
+#[test]
+fn corrupted_packet_is_nacked() {
+    // GDB re-sends the corrupted `?` packet after receiving the nack
+    let output = run_raw(&["$?#00", "?"]);
+    assert_eq!(output, b"-+$T05thread:01;#07");
+}
+
+#[test]
+fn corrupted_packet_in_no_ack_mode() {
+    // there's no way to ask GDB to re-send packets in no-ack mode, so corrupted
+    // packets are treated as a hard error
+    let mut conn = MockConnection::new(&["QStartNoAckMode", "$?#00"]);
+    let res = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut RetransmitTarget);
+    assert!(matches!(res, Err(crate::GdbStubError::PacketParse(_))));
+    assert_eq!(split_responses(&conn.output), ["OK"]);
+}
+
+#[test]
+fn nacked_response_is_retransmitted() {
+    let output = run_raw(&["?", "-", "m0,2", "-", "-", "+"]);
+    assert_eq!(
+        output,
+        b"+$T05thread:01;#07$T05thread:01;#07+$0000#c0$0000#c0$0000#c0"
+    );
+}
+
+#[test]
+fn oversized_response_is_not_retransmitted() {
+    // GDB asked for more memory than fits in the 64 byte packet buffer, so the
+    // streamed response is sent, but isn't kept around
+    let mut conn = MockConnection::new(&["m0,2", "m0,40", "-"]);
+    let res = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(64)
+        .build()
+        .unwrap()
+        .run(&mut RetransmitTarget);
+    assert!(matches!(res, Err(crate::GdbStubError::ClientSentNack)));
+    assert_eq!(split_responses(&conn.output), ["0000", &"0".repeat(0x80)]);
+}
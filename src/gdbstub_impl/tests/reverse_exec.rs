@@ -0,0 +1,132 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::reverse_exec::{ReverseExec, ReverseExecOps};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+#[derive(Debug, PartialEq)]
+enum Reverse {
+    Cont,
+    Step(Tid),
+}
+
+/// A two-thread target which records any reverse execution requests.
+#[derive(Default)]
+struct ReverseTarget {
+    history: Vec<Reverse>,
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for ReverseTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+
+    fn reverse_exec(&mut self) -> Option<ReverseExecOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl MultiThreadOps for ReverseTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+}
+
+impl ReverseExec for ReverseTarget {
+    fn reverse_cont(
+        &mut self,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.history.push(Reverse::Cont);
+        Ok(ThreadStopReason::SwBreak(tid(2)))
+    }
+
+    fn reverse_step(
+        &mut self,
+        tid: Tid,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.history.push(Reverse::Step(tid));
+        Ok(ThreadStopReason::DoneStep)
+    }
+}
+
+#[test]
+fn advertises_reverse_exec() {
+    let (res, _) = run_session(&mut ReverseTarget::default(), &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";ReverseStep+;ReverseContinue+"));
+}
+
+#[test]
+fn reverse_cont() {
+    let mut target = ReverseTarget::default();
+    let (res, _) = run_session(&mut target, &["bc", "bs"]);
+    assert_eq!(res, ["T05thread:02;", "S05"]);
+    // the thread which hit the breakpoint becomes the current thread
+    assert_eq!(target.history, [Reverse::Cont, Reverse::Step(tid(2))]);
+}
+
+#[test]
+fn reverse_step_selected_thread() {
+    let mut target = ReverseTarget::default();
+    let (res, _) = run_session(&mut target, &["Hc2", "bs", "Hc1", "bs"]);
+    assert_eq!(res, ["OK", "S05", "OK", "S05"]);
+    assert_eq!(
+        target.history,
+        [Reverse::Step(tid(2)), Reverse::Step(tid(1))]
+    );
+}
+
+#[test]
+fn reverse_exec_malformed() {
+    let mut target = ReverseTarget::default();
+    let (_, result) = run_session(&mut target, &["bc1000"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketParse(_))));
+    assert!(target.history.is_empty());
+}
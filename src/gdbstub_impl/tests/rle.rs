@@ -0,0 +1,28 @@
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+
+struct ZeroedTarget;
+
+impl Target for ZeroedTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ZeroedTarget);
+
+#[test]
+fn enabled_after_qsupported() {
+    let (res, _) = run_session(
+        &mut ZeroedTarget,
+        &["m1000,8", "qSupported:multiprocess+", "m1000,8"],
+    );
+    assert_eq!(res[0], "0000000000000000");
+    // 16 zeros: '0' + 15 repeats (15 + 29 = ',')
+    assert_eq!(res[2], "0*,");
+}
@@ -0,0 +1,82 @@
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::section_offsets::{Offsets, SectionOffsets, SectionOffsetsOps};
+use crate::target::Target;
+
+/// A target which reports a fixed set of section offsets.
+struct OffsetsTarget {
+    offsets: fn() -> Offsets<u32>,
+}
+
+impl Target for OffsetsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn section_offsets(&mut self) -> Option<SectionOffsetsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(OffsetsTarget);
+
+impl SectionOffsets for OffsetsTarget {
+    fn get_section_offsets(&mut self) -> Result<Offsets<u32>, Self::Error> {
+        Ok((self.offsets)())
+    }
+}
+
+#[test]
+fn sections() {
+    let mut target = OffsetsTarget {
+        offsets: || Offsets::Sections {
+            text: 0x10000,
+            data: 0x20000,
+            bss: Some(0x20000),
+        },
+    };
+    let (res, _) = run_session(&mut target, &["qOffsets"]);
+    assert_eq!(res, ["Text=010000;Data=020000;Bss=020000"]);
+}
+
+#[test]
+fn sections_without_bss() {
+    let mut target = OffsetsTarget {
+        offsets: || Offsets::Sections {
+            text: 0x1000,
+            data: 0x2000,
+            bss: None,
+        },
+    };
+    let (res, _) = run_session(&mut target, &["qOffsets"]);
+    // GDB requires `Bss=`, which defaults to the data offset
+    assert_eq!(res, ["Text=1000;Data=2000;Bss=2000"]);
+}
+
+#[test]
+fn single_segment() {
+    let mut target = OffsetsTarget {
+        offsets: || Offsets::Segments {
+            text_seg: 0x8000_0000,
+            data_seg: None,
+        },
+    };
+    let (res, _) = run_session(&mut target, &["qOffsets"]);
+    assert_eq!(res, ["TextSeg=80000000"]);
+}
+
+#[test]
+fn two_segments() {
+    let mut target = OffsetsTarget {
+        offsets: || Offsets::Segments {
+            text_seg: 0x8000_0000,
+            data_seg: Some(0x9000_0000),
+        },
+    };
+    let (res, _) = run_session(&mut target, &["qOffsets"]);
+    assert_eq!(res, ["TextSeg=80000000;DataSeg=90000000"]);
+}
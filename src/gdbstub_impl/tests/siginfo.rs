@@ -0,0 +1,85 @@
+use std::vec::Vec;
+
+use super::mock::{advertises, copy_xfer_chunk, encode_binary, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::common::Tid;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::siginfo::{Siginfo, SiginfoOps};
+use crate::target::{Target, TargetResult};
+
+/// A target with a fixed-size (16 byte) `siginfo`.
+#[derive(Default)]
+struct SiginfoTarget {
+    siginfo: [u8; 16],
+    tids: Vec<Tid>,
+}
+
+impl Target for SiginfoTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn siginfo(&mut self) -> Option<SiginfoOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(SiginfoTarget);
+
+impl Siginfo for SiginfoTarget {
+    fn read_siginfo(
+        &self,
+        _tid: Tid,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(&self.siginfo, offset, length, buf))
+    }
+
+    fn write_siginfo(&mut self, tid: Tid, offset: u64, data: &[u8]) -> TargetResult<usize, Self> {
+        self.tids.push(tid);
+        let dst = self.siginfo.get_mut(offset as usize..).unwrap_or(&mut []);
+        let len = dst.len().min(data.len());
+        dst[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+}
+
+#[test]
+fn advertises_siginfo() {
+    let mut target = SiginfoTarget::default();
+    assert!(advertises(&mut target, "qXfer:siginfo:read+"));
+    assert!(advertises(&mut target, "qXfer:siginfo:write+"));
+}
+
+#[test]
+fn write_then_read() {
+    // includes every byte which must be escaped, along with a ':'
+    let blob = b"\x0b\x00\x00\x00#$}*:\x01\x02\x03\x7f\x00\x00\x2a";
+
+    let mut target = SiginfoTarget::default();
+    let write = format!("qXfer:siginfo:write::0:{}", encode_binary(blob));
+    let (res, _) = run_session(&mut target, &[&write]);
+
+    assert_eq!(res, ["10"]);
+    assert_eq!(&target.siginfo, blob);
+    assert_eq!(target.tids, [Tid::new(1).unwrap()]);
+
+    let data = read_xfer_paged(&mut target, "qXfer:siginfo:read::", blob.len(), 8, None);
+    assert_eq!(data, blob);
+}
+
+#[test]
+fn partial_write() {
+    let mut target = SiginfoTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["qXfer:siginfo:write::e:abcd", "qXfer:siginfo:read::e,10"],
+    );
+    // only the first two bytes fit
+    assert_eq!(res, ["02", "lab"]);
+}
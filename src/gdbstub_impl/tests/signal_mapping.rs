@@ -0,0 +1,180 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::{Pid, Signal};
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{
+    Args, AttachKind, ExtendedMode, ExtendedModeOps, ShouldTerminate,
+};
+use crate::target::ext::signal_mapping::{SignalMapping, SignalMappingOps};
+use crate::target::{Target, TargetResult};
+
+/// Native signal numbers, which (deliberately) don't match GDB's numbering.
+const NATIVE_SIGNALS: &[(u8, Signal)] = &[
+    (2, Signal::SIGINT),
+    (7, Signal::SIGBUS),
+    (10, Signal::SIGUSR1),
+    (0x85, Signal::SIGTRAP),
+];
+
+/// A target which stops with a native signal every time it's resumed, and
+/// records the native signals it was asked to deliver.
+struct NativeSignalTarget {
+    stop_signal: u8,
+    delivered: Vec<Option<u8>>,
+}
+
+impl NativeSignalTarget {
+    fn new(stop_signal: u8) -> NativeSignalTarget {
+        NativeSignalTarget {
+            stop_signal,
+            delivered: Vec::new(),
+        }
+    }
+}
+
+impl Target for NativeSignalTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+
+    fn signal_mapping(&mut self) -> Option<SignalMappingOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for NativeSignalTarget {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        let signal = match action {
+            ResumeAction::Step(Some(signal)) | ResumeAction::Continue(Some(signal)) => {
+                Some(self.gdb_to_native(signal).ok_or("no native signal")?)
+            }
+            _ => None,
+        };
+        self.delivered.push(signal);
+        Ok(StopReason::Signal(self.stop_signal))
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl SignalMapping for NativeSignalTarget {
+    fn native_to_gdb(&self, native: u8) -> Signal {
+        NATIVE_SIGNALS
+            .iter()
+            .find(|(n, _)| *n == native)
+            .map(|(_, signal)| *signal)
+            .unwrap_or(Signal::SIGUNKNOWN)
+    }
+
+    fn gdb_to_native(&self, signal: Signal) -> Option<u8> {
+        NATIVE_SIGNALS
+            .iter()
+            .find(|(_, s)| *s == signal)
+            .map(|(native, _)| *native)
+    }
+}
+
+impl ExtendedMode for NativeSignalTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        Ok(ThreadStopReason::Signal(self.stop_signal))
+    }
+
+    fn query_if_attached(&mut self, _pid: Pid) -> TargetResult<AttachKind, Self> {
+        Ok(AttachKind::Attach)
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        Ok(ShouldTerminate::No)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn signal_raw_roundtrip() {
+    assert_eq!(Signal::from_raw(30), Some(Signal::SIGUSR1));
+    assert_eq!(Signal::SIGUSR1.as_raw(), 30);
+    assert_eq!(Signal::from_raw(77), Some(Signal::SIG32));
+    assert_eq!(Signal::from_raw(144), None);
+    for raw in 0..=255 {
+        if let Some(signal) = Signal::from_raw(raw) {
+            assert_eq!(signal.as_raw(), raw);
+        }
+    }
+}
+
+#[test]
+fn stop_reports_gdb_signal() {
+    // native SIGUSR1 (10) is GDB's SIGUSR1 (30)
+    let mut target = NativeSignalTarget::new(10);
+    let (res, _) = run_session(&mut target, &["c"]);
+    assert_eq!(res, ["S1e"]);
+}
+
+#[test]
+fn resume_delivers_native_signal() {
+    // native SIGBUS (7) is GDB's SIGBUS (10)
+    let mut target = NativeSignalTarget::new(7);
+    let (res, _) = run_session(&mut target, &["C1e", "S0a", "vCont;C02"]);
+    assert_eq!(res, ["S0a", "S0a", "S0a"]);
+    assert_eq!(target.delivered, [Some(10), Some(7), Some(2)]);
+}
+
+#[test]
+fn unmapped_native_signal() {
+    let mut target = NativeSignalTarget::new(0x7f);
+    let (res, _) = run_session(&mut target, &["c"]);
+    assert_eq!(res, ["S8f"]); // SIGUNKNOWN
+}
+
+#[test]
+fn synthesized_stops_use_gdb_signals() {
+    let mut target = NativeSignalTarget::new(10);
+    let (res, _) = run_session(&mut target, &["?", "vRun;"]);
+    assert_eq!(res, ["T05thread:01;", "S05"]);
+}
+
+#[test]
+fn rejects_unknown_gdb_signal() {
+    let mut target = NativeSignalTarget::new(10);
+    let (res, _) = run_session(&mut target, &["C98", "S98", "vCont;C98"]);
+    // rejected with the same (non-fatal) error, regardless of the packet used
+    assert_eq!(res, ["E16", "E16", "E16"]);
+    assert!(target.delivered.is_empty());
+}
@@ -0,0 +1,81 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::common::Signal;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::signals::{SignalList, Signals, SignalsOps};
+use crate::target::{Target, TargetResult};
+
+/// A target which records the most recent set of signals for each packet.
+#[derive(Default)]
+struct SignalsTarget {
+    pass: Vec<Signal>,
+    program: Vec<Signal>,
+}
+
+impl Target for SignalsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn signals(&mut self) -> Option<SignalsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(SignalsTarget);
+
+impl Signals for SignalsTarget {
+    fn pass_signals(&mut self, signals: SignalList<'_>) -> TargetResult<(), Self> {
+        self.pass = signals.collect();
+        Ok(())
+    }
+
+    fn program_signals(&mut self, signals: SignalList<'_>) -> TargetResult<(), Self> {
+        self.program = signals.collect();
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_signals() {
+    let mut target = SignalsTarget::default();
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].contains(";QPassSignals+;QProgramSignals+"));
+}
+
+#[test]
+fn parses_signal_lists() {
+    let mut target = SignalsTarget::default();
+    let (res, _) = run_session(
+        &mut target,
+        &["QPassSignals:e;14;1e;", "QProgramSignals:2;e"],
+    );
+    assert_eq!(res, ["OK", "OK"]);
+    assert_eq!(
+        target.pass,
+        [Signal::SIGALRM, Signal::SIGCHLD, Signal::SIGUSR1]
+    );
+    assert_eq!(target.program, [Signal::SIGINT, Signal::SIGALRM]);
+}
+
+#[test]
+fn empty_list_clears_signals() {
+    let mut target = SignalsTarget::default();
+    let (res, _) = run_session(&mut target, &["QPassSignals:e", "QPassSignals:"]);
+    assert_eq!(res, ["OK", "OK"]);
+    assert!(target.pass.is_empty());
+}
+
+#[test]
+fn rejects_malformed_list() {
+    let mut target = SignalsTarget::default();
+    let (res, result) = run_session(&mut target, &["QPassSignals:e;zz"]);
+    assert!(res.is_empty());
+    assert!(matches!(result, Err(crate::GdbStubError::PacketParse(_))));
+    assert!(target.pass.is_empty());
+}
@@ -0,0 +1,215 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::single_step_via_breakpoints::{
+    SingleStepViaBreakpoints, SingleStepViaBreakpointsOps,
+};
+use crate::target::ext::base::singlethread::{
+    ResumeAction, ResumeCapabilities, SingleThreadOps, StopReason,
+};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::GdbStubError;
+
+#[derive(Clone, Copy)]
+enum Insn {
+    Nop,
+    /// Branch to the given address if `r0 == 0`.
+    Beqz(u32),
+    Halt,
+}
+
+/// A toy CPU which can only continue, running the following program:
+///
+/// ```text
+/// 0x00: beqz r0, 0x10
+/// 0x04: nop
+/// 0x08: halt
+/// 0x10: nop
+/// 0x14: halt
+/// ```
+struct ToyCpu {
+    pc: u32,
+    r0: u32,
+    step_breakpoints: Vec<u32>,
+    inserted: Vec<u32>,
+    /// Fail to insert a step breakpoint at this address.
+    bad_breakpoint: Option<u32>,
+    fail_resume: bool,
+}
+
+impl ToyCpu {
+    fn new(r0: u32) -> ToyCpu {
+        ToyCpu {
+            pc: 0,
+            r0,
+            step_breakpoints: Vec::new(),
+            inserted: Vec::new(),
+            bad_breakpoint: None,
+            fail_resume: false,
+        }
+    }
+
+    fn fetch(&self, addr: u32) -> Insn {
+        match addr {
+            0x00 => Insn::Beqz(0x10),
+            0x04 | 0x10 => Insn::Nop,
+            _ => Insn::Halt,
+        }
+    }
+}
+
+impl Target for ToyCpu {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn single_step_via_breakpoints(&mut self) -> Option<SingleStepViaBreakpointsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for ToyCpu {
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        if action != ResumeAction::Continue(None) {
+            return Err("the toy cpu can only continue");
+        }
+        if self.fail_resume {
+            return Err("the toy cpu caught fire");
+        }
+
+        loop {
+            self.pc = match self.fetch(self.pc) {
+                Insn::Nop => self.pc + 4,
+                Insn::Beqz(target) if self.r0 == 0 => target,
+                Insn::Beqz(_) => self.pc + 4,
+                Insn::Halt => return Ok(StopReason::Halted),
+            };
+            if self.step_breakpoints.contains(&self.pc) {
+                return Ok(StopReason::SwBreak);
+            }
+        }
+    }
+
+    fn resume_capabilities(&self) -> ResumeCapabilities {
+        ResumeCapabilities::CONTINUE_ONLY
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl SingleStepViaBreakpoints for ToyCpu {
+    fn next_pcs(&mut self, next_pc: &mut dyn FnMut(u32)) -> Result<(), Self::Error> {
+        match self.fetch(self.pc) {
+            Insn::Nop => next_pc(self.pc + 4),
+            // both sides of the branch (without evaluating the condition)
+            Insn::Beqz(target) => {
+                next_pc(self.pc + 4);
+                next_pc(target);
+            }
+            Insn::Halt => {}
+        }
+        Ok(())
+    }
+
+    fn add_step_breakpoint(&mut self, addr: u32) -> Result<(), Self::Error> {
+        if self.bad_breakpoint == Some(addr) {
+            return Err("couldn't insert breakpoint");
+        }
+        self.step_breakpoints.push(addr);
+        self.inserted.push(addr);
+        Ok(())
+    }
+
+    fn remove_step_breakpoint(&mut self, addr: u32) -> Result<(), Self::Error> {
+        let pos = self
+            .step_breakpoints
+            .iter()
+            .position(|x| *x == addr)
+            .ok_or("removed a breakpoint which wasn't inserted")?;
+        self.step_breakpoints.remove(pos);
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_step() {
+    let (res, _) = run_session(&mut ToyCpu::new(0), &["vCont?"]);
+    assert_eq!(res, ["vCont;c;C;s;S"]);
+}
+
+#[test]
+fn step_branch_taken() {
+    let mut target = ToyCpu::new(0);
+    let (res, _) = run_session(&mut target, &["s", "vCont;s:1"]);
+    assert_eq!(res, ["S05", "S05"]);
+    assert_eq!(target.pc, 0x14);
+    assert_eq!(target.inserted, [0x04, 0x10, 0x14]);
+    assert!(target.step_breakpoints.is_empty());
+}
+
+#[test]
+fn step_branch_not_taken() {
+    let mut target = ToyCpu::new(1);
+    let (res, _) = run_session(&mut target, &["s", "s"]);
+    assert_eq!(res, ["S05", "S05"]);
+    assert_eq!(target.pc, 0x08);
+    assert_eq!(target.inserted, [0x04, 0x10, 0x08]);
+    assert!(target.step_breakpoints.is_empty());
+}
+
+#[test]
+fn continue_is_unaffected() {
+    let mut target = ToyCpu::new(0);
+    let (res, _) = run_session(&mut target, &["c"]);
+    assert_eq!(res, ["W19"]);
+    assert!(target.inserted.is_empty());
+}
+
+#[test]
+fn resume_failure_removes_breakpoints() {
+    let mut target = ToyCpu::new(0);
+    target.fail_resume = true;
+    let (res, result) = run_session(&mut target, &["s"]);
+    // fatal target errors are reported as a post-mortem `S05`
+    assert_eq!(res, ["S05"]);
+    assert!(matches!(result, Err(GdbStubError::TargetError(_))));
+    assert_eq!(target.inserted, [0x04, 0x10]);
+    assert!(target.step_breakpoints.is_empty());
+}
+
+#[test]
+fn add_failure_removes_breakpoints() {
+    let mut target = ToyCpu::new(0);
+    target.bad_breakpoint = Some(0x10);
+    let (res, result) = run_session(&mut target, &["s"]);
+    // fatal target errors are reported as a post-mortem `S05`
+    assert_eq!(res, ["S05"]);
+    assert!(matches!(result, Err(GdbStubError::TargetError(_))));
+    assert_eq!(target.inserted, [0x04]);
+    assert!(target.step_breakpoints.is_empty());
+    assert_eq!(target.pc, 0);
+}
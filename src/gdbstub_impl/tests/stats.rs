@@ -0,0 +1,79 @@
+use super::mock::{split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::Target;
+use crate::{Connection, GdbStub, SessionStats};
+
+struct StatsTarget;
+
+impl Target for StatsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(StatsTarget);
+
+/// Run a session, returning the stub's final stats alongside its raw output.
+fn run_stats(packets: &[&str]) -> (SessionStats, Vec<u8>) {
+    let mut conn = MockConnection::new(packets);
+    let mut stub = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>);
+    assert!(stub.run(&mut StatsTarget).is_err()); // eof
+    let stats = stub.stats();
+    drop(stub);
+    (stats, conn.output)
+}
+
+#[test]
+fn counts_packets_and_acks() {
+    let (stats, output) = run_stats(&["?", "+", "g", "-", "+"]);
+    let responses = split_responses(&output);
+    assert_eq!(responses[0], "T05thread:01;");
+
+    // "$?#3f" + "$g#67", alongside 3 acks / nacks
+    assert_eq!(stats.packets_received, 2);
+    assert_eq!(stats.bytes_received, 5 + 5 + 3);
+    assert_eq!(stats.acks_received, 2);
+    assert_eq!(stats.nacks_received, 1);
+
+    // the `g` reply is re-transmitted in response to the nack, but only
+    // counted as a single packet
+    let g_reply_len = responses[1].len() + 4;
+    assert_eq!(stats.packets_sent, 2);
+    assert_eq!(stats.acks_sent, 2);
+    assert_eq!(stats.retransmits, 1);
+    assert_eq!(stats.bytes_sent, output.len() as u64);
+    assert_eq!(stats.bytes_sent, 2 + 17 + 2 * g_reply_len as u64);
+    assert_eq!(stats.largest_packet, g_reply_len);
+}
+
+#[test]
+fn counts_packets_in_no_ack_mode() {
+    let (stats, output) = run_stats(&["QStartNoAckMode", "?", "?"]);
+    assert_eq!(
+        split_responses(&output),
+        ["OK", "T05thread:01;", "T05thread:01;"]
+    );
+
+    assert_eq!(stats.packets_received, 3);
+    assert_eq!(stats.bytes_received, 19 + 5 + 5);
+    assert_eq!(stats.packets_sent, 3);
+    // only `QStartNoAckMode` itself is acked
+    assert_eq!(stats.acks_sent, 1);
+    assert_eq!(stats.bytes_sent, output.len() as u64);
+    assert_eq!(stats.bytes_sent, 1 + 6 + 17 + 17);
+    assert_eq!(stats.largest_packet, 19);
+}
+
+#[test]
+fn counts_nacks_sent() {
+    let (stats, output) = run_stats(&["$?#00", "?"]);
+    assert_eq!(output, b"-+$T05thread:01;#07");
+    assert_eq!(stats.nacks_sent, 1);
+    assert_eq!(stats.packets_received, 2);
+    assert_eq!(stats.packets_sent, 1);
+    assert_eq!(stats.bytes_sent, output.len() as u64);
+}
@@ -0,0 +1,162 @@
+use std::string::String;
+
+use super::mock::run_session;
+use crate::arch::aarch64::reg::id::AArch64SveRegId;
+use crate::arch::aarch64::reg::AArch64SveRegs;
+use crate::arch::aarch64::{sve_target_description_xml, AArch64Sve};
+use crate::target::ext::base::singlethread::{SingleThreadOps, StopReason};
+use crate::target::ext::base::{BaseOps, ResumeAction};
+use crate::target::ext::target_description_xml_override::{
+    TargetDescriptionXmlOverride, TargetDescriptionXmlOverrideOps,
+};
+use crate::target::{Target, TargetError, TargetResult};
+
+struct SveTarget {
+    regs: AArch64SveRegs,
+}
+
+impl SveTarget {
+    fn new(vg: u64) -> SveTarget {
+        let mut regs = AArch64SveRegs {
+            vg,
+            ..Default::default()
+        };
+        let vg = vg as usize;
+        regs.z[1][..vg * 8].iter_mut().for_each(|b| *b = 0xab);
+        regs.p[0][..vg].iter_mut().for_each(|b| *b = 0xcd);
+        SveTarget { regs }
+    }
+}
+
+impl Target for SveTarget {
+    type Arch = AArch64Sve;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn target_description_xml_override(
+        &mut self,
+    ) -> Option<TargetDescriptionXmlOverrideOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for SveTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u64>, Self::Error> {
+        Ok(StopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, regs: &mut AArch64SveRegs) -> TargetResult<(), Self> {
+        *regs = self.regs.clone();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &AArch64SveRegs) -> TargetResult<(), Self> {
+        self.regs = regs.clone();
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        reg_id: AArch64SveRegId,
+        dst: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let vg = self.regs.vg as usize;
+        let src: &[u8] = match reg_id {
+            AArch64SveRegId::Z(n) => &self.regs.z[n as usize][..vg * 8],
+            AArch64SveRegId::P(n) => &self.regs.p[n as usize][..vg],
+            AArch64SveRegId::Vg => {
+                dst.copy_from_slice(&self.regs.vg.to_le_bytes());
+                return Ok(dst.len());
+            }
+            _ => return Err(TargetError::NonFatal),
+        };
+        dst[..src.len()].copy_from_slice(src);
+        Ok(src.len())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u64, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u64, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl TargetDescriptionXmlOverride for SveTarget {
+    fn target_description_xml(
+        &self,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        if annex != b"target.xml" {
+            return Err(TargetError::NonFatal);
+        }
+        Ok(sve_target_description_xml(
+            self.regs.vg,
+            offset,
+            &mut buf[..length],
+        ))
+    }
+}
+
+#[test]
+fn variable_sized_register_reads() {
+    // 256-bit vectors
+    let mut target = SveTarget::new(4);
+    let (res, _) = run_session(&mut target, &["p23", "p44", "p55"]);
+    assert_eq!(res[0], "ab".repeat(32));
+    assert_eq!(res[1], "cd".repeat(4));
+    assert_eq!(res[2], "0400000000000000");
+}
+
+#[test]
+fn g_packet_scales_with_vg() {
+    for &vg in &[2, 4] {
+        let (res, _) = run_session(&mut SveTarget::new(vg), &["g"]);
+        let len = 268 + 32 * 8 * vg as usize + 8 + 17 * vg as usize + 8;
+        assert_eq!(res[0].len(), len * 2);
+    }
+}
+
+#[test]
+fn g_packet_roundtrip() {
+    let mut target = SveTarget::new(4);
+    let (res, _) = run_session(&mut target, &["g"]);
+    let expected = target.regs.clone();
+    let (res, _) = run_session(&mut target, &[&format!("G{}", res[0])]);
+    assert_eq!(res, ["OK"]);
+    assert_eq!(target.regs, expected);
+}
+
+#[test]
+fn target_description_scales_with_vg() {
+    // read the xml in small chunks, to exercise paging
+    let mut target = SveTarget::new(4);
+    let mut xml = String::new();
+    loop {
+        let pkt = format!("qXfer:features:read:target.xml:{:x},40", xml.len());
+        let (res, _) = run_session(&mut target, &[&pkt]);
+        xml += &res[0][1..];
+        if res[0].starts_with('l') {
+            break;
+        }
+    }
+
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.ends_with("</target>"));
+    assert!(xml.contains(r#"<feature name="org.gnu.gdb.aarch64.sve">"#));
+    assert!(xml.contains(r#"<reg name="z0" bitsize="256" type="svev" regnum="34"/>"#));
+    assert!(xml.contains(r#"<reg name="p15" bitsize="32" type="svep"/>"#));
+    assert!(xml.contains(r#"<vector id="svevqu" type="uint128" count="2"/>"#));
+}
@@ -0,0 +1,102 @@
+use super::mock::{copy_xfer_chunk, run_session};
+use crate::arch::arm::Armv4t;
+use crate::arch::Arch;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::target_description_xml_override::{
+    TargetDescriptionXmlOverride, TargetDescriptionXmlOverrideOps,
+};
+use crate::target::{Target, TargetError, TargetResult};
+
+const TARGET_XML: &str = r#"<target version="1.0"><architecture>armv4t</architecture><xi:include href="core.xml"/></target>"#;
+const CORE_XML: &str =
+    r#"<feature name="org.gnu.gdb.arm.core"><reg name="r0" bitsize="32"/></feature>"#;
+
+struct OverrideTarget;
+
+impl Target for OverrideTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn target_description_xml_override(
+        &mut self,
+    ) -> Option<TargetDescriptionXmlOverrideOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(OverrideTarget);
+
+impl TargetDescriptionXmlOverride for OverrideTarget {
+    fn target_description_xml(
+        &self,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let xml = match annex {
+            b"target.xml" => TARGET_XML,
+            b"core.xml" => CORE_XML,
+            _ => return Err(TargetError::NonFatal),
+        };
+
+        Ok(copy_xfer_chunk(xml.as_bytes(), offset, length, buf))
+    }
+}
+
+/// A target which only relies on the static `Arch::target_description_xml`.
+struct StaticTarget;
+
+impl Target for StaticTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+}
+
+impl_noop_single_thread_ops!(StaticTarget);
+
+#[test]
+fn override_resolves_includes() {
+    let (res, _) = run_session(
+        &mut OverrideTarget,
+        &[
+            "qSupported:multiprocess+",
+            "qXfer:features:read:target.xml:0,10",
+            "qXfer:features:read:target.xml:10,400",
+            "qXfer:features:read:core.xml:0,400",
+        ],
+    );
+    assert!(res[0].contains(";qXfer:features:read+"));
+    assert_eq!(res[1], format!("m{}", &TARGET_XML[..0x10]));
+    assert_eq!(res[2], format!("l{}", &TARGET_XML[0x10..]));
+    assert_eq!(res[3], format!("l{}", CORE_XML));
+}
+
+#[test]
+fn override_unknown_annex() {
+    let (res, _) = run_session(
+        &mut OverrideTarget,
+        &["qXfer:features:read:missing.xml:0,400"],
+    );
+    assert_eq!(res, ["E79"]);
+}
+
+#[test]
+fn static_fallback() {
+    let xml = Armv4t::target_description_xml().unwrap();
+    let (res, _) = run_session(
+        &mut StaticTarget,
+        &[
+            "qXfer:features:read:target.xml:0,400",
+            "qXfer:features:read:core.xml:0,400",
+        ],
+    );
+    assert_eq!(res, [format!("l{}", xml), "E00".into()]);
+}
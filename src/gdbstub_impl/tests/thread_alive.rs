@@ -0,0 +1,96 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+/// A multi threaded target whose threads can be killed off.
+struct ThreadsTarget {
+    alive: Vec<Tid>,
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for ThreadsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+}
+
+impl MultiThreadOps for ThreadsTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        // thread 2 exits as soon as the target is resumed
+        self.alive.retain(|t| *t != tid(2));
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        self.alive.iter().copied().for_each(thread_is_active);
+        Ok(())
+    }
+}
+
+#[test]
+fn dead_thread() {
+    let mut target = ThreadsTarget {
+        alive: vec![tid(1), tid(2)],
+    };
+    let (res, _) = run_session(&mut target, &["T1", "T2", "T3", "c", "T1", "T2"]);
+    assert_eq!(res, ["OK", "OK", "E00", "S05", "OK", "E00"]);
+}
+
+#[test]
+fn single_thread() {
+    struct SingleThreadTarget;
+
+    impl Target for SingleThreadTarget {
+        type Arch = Armv4t;
+        type Error = &'static str;
+
+        fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+            BaseOps::SingleThread(self)
+        }
+    }
+
+    impl_noop_single_thread_ops!(SingleThreadTarget);
+
+    let (res, _) = run_session(&mut SingleThreadTarget, &["T1", "T2"]);
+    assert_eq!(res, ["OK", "E00"]);
+}
@@ -0,0 +1,158 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+/// A multi threaded target which reports a queue of stop events.
+struct EventTarget {
+    events: Vec<ThreadStopReason<u32>>,
+    threads: Vec<Tid>,
+    resumes: usize,
+}
+
+impl EventTarget {
+    fn new(events: &[ThreadStopReason<u32>]) -> EventTarget {
+        EventTarget {
+            events: events.iter().rev().copied().collect(),
+            threads: vec![tid(1), tid(2)],
+            resumes: 0,
+        }
+    }
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for EventTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+}
+
+impl MultiThreadOps for EventTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        self.resumes += 1;
+        let event = self.events.pop().ok_or("no more events")?;
+        if let ThreadStopReason::ThreadExited { tid, .. } = event {
+            self.threads.retain(|t| *t != tid);
+        }
+        Ok(event)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        self.threads.iter().copied().for_each(thread_is_active);
+        Ok(())
+    }
+}
+
+#[test]
+fn advertises_thread_events() {
+    let mut target = EventTarget::new(&[]);
+    let (res, _) = run_session(&mut target, &["qSupported:multiprocess+"]);
+    assert!(res[0].split(';').any(|f| f == "QThreadEvents+"));
+}
+
+#[test]
+fn thread_created() {
+    let mut target = EventTarget::new(&[ThreadStopReason::ThreadCreated(tid(2))]);
+    let (res, _) = run_session(&mut target, &["QThreadEvents:1", "c", "qC"]);
+    // the new thread becomes the current thread
+    assert_eq!(res, ["OK", "T05create:;thread:02;", "QC02"]);
+}
+
+#[test]
+fn thread_exited() {
+    let mut target = EventTarget::new(&[ThreadStopReason::ThreadExited {
+        tid: tid(2),
+        exit_status: 3,
+    }]);
+    let (res, _) = run_session(&mut target, &["QThreadEvents:1", "c"]);
+    assert_eq!(res, ["OK", "w03;02"]);
+}
+
+#[test]
+fn events_not_enabled() {
+    let mut target = EventTarget::new(&[
+        ThreadStopReason::ThreadCreated(tid(2)),
+        ThreadStopReason::ThreadExited {
+            tid: tid(2),
+            exit_status: 0,
+        },
+        ThreadStopReason::SwBreak(tid(1)),
+    ]);
+    let (res, _) = run_session(&mut target, &["c", "c"]);
+    // the exit isn't reported, and the target keeps running until it hits the
+    // breakpoint
+    assert_eq!(res, ["T05thread:02;", "T05thread:01;"]);
+    assert_eq!(target.resumes, 3);
+}
+
+#[test]
+fn events_not_enabled_last_thread_exited() {
+    let mut target = EventTarget::new(&[ThreadStopReason::ThreadExited {
+        tid: tid(1),
+        exit_status: 0,
+    }]);
+    target.threads = vec![tid(1)];
+    let (res, _) = run_session(&mut target, &["c"]);
+    // there's no thread left to report the stop on
+    assert_eq!(res, ["S05"]);
+    assert_eq!(target.resumes, 1);
+}
+
+#[test]
+fn events_disabled() {
+    let mut target = EventTarget::new(&[
+        ThreadStopReason::ThreadCreated(tid(2)),
+        ThreadStopReason::ThreadCreated(tid(3)),
+    ]);
+    let (res, _) = run_session(
+        &mut target,
+        &["QThreadEvents:1", "c", "QThreadEvents:0", "c"],
+    );
+    assert_eq!(res, ["OK", "T05create:;thread:02;", "OK", "T05thread:03;"]);
+}
+
+#[test]
+fn thread_events_malformed() {
+    let mut target = EventTarget::new(&[]);
+    let (_, result) = run_session(&mut target, &["QThreadEvents:2"]);
+    assert!(matches!(result, Err(crate::GdbStubError::PacketParse(_))));
+}
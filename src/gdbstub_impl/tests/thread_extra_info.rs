@@ -0,0 +1,68 @@
+use std::string::String;
+
+use super::mock::{run_session, split_responses, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::common::Tid;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::thread_extra_info::{ThreadExtraInfo, ThreadExtraInfoOps};
+use crate::target::{Target, TargetResult};
+use crate::{Connection, GdbStub};
+
+struct ThreadInfoTarget {
+    info: String,
+}
+
+impl Target for ThreadInfoTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn thread_extra_info(&mut self) -> Option<ThreadExtraInfoOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ThreadInfoTarget);
+
+impl ThreadExtraInfo for ThreadInfoTarget {
+    fn thread_info(&self, _tid: Tid, buf: &mut [u8]) -> TargetResult<usize, Self> {
+        let info = self.info.as_bytes();
+        let len = info.len().min(buf.len());
+        buf[..len].copy_from_slice(&info[..len]);
+        Ok(len)
+    }
+}
+
+fn hex(s: &str) -> String {
+    s.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn utf8_info() {
+    let info = "main «running»";
+    let mut target = ThreadInfoTarget { info: info.into() };
+    let (res, _) = run_session(&mut target, &["qThreadExtraInfo,p1.1"]);
+    assert_eq!(res, [hex(info)]);
+}
+
+#[test]
+fn truncated_info() {
+    let mut target = ThreadInfoTarget {
+        info: format!("a{}", "é".repeat(20)),
+    };
+
+    // a 64 byte packet buffer leaves 32 bytes for the description, which cuts
+    // the 16th 'é' in half.
+    let mut conn = MockConnection::new(&["qThreadExtraInfo,p1.1"]);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(64)
+        .build()
+        .unwrap()
+        .run(&mut target);
+
+    let res = split_responses(&conn.output);
+    assert_eq!(res, [hex(&format!("a{}", "é".repeat(15)))]);
+}
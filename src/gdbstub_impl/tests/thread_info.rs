@@ -0,0 +1,157 @@
+use std::string::String;
+use std::vec::Vec;
+
+use super::mock::{run_session, split_responses, MockConnection};
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+use crate::{Connection, GdbStub};
+
+/// A target with `threads` threads (numbered from 1).
+struct ThreadsTarget {
+    threads: usize,
+}
+
+impl Target for ThreadsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+}
+
+impl MultiThreadOps for ThreadsTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        (1..=self.threads)
+            .map(|n| Tid::new(n).unwrap())
+            .for_each(thread_is_active);
+        Ok(())
+    }
+}
+
+/// Concatenate the thread-ids reported across several pages, checking that
+/// the list is properly terminated.
+fn collect_pages(pages: &[String]) -> Vec<usize> {
+    let (last, pages) = pages.split_last().unwrap();
+    assert_eq!(last, "l");
+    pages
+        .iter()
+        .flat_map(|page| {
+            assert!(page.starts_with('m'));
+            page[1..]
+                .split(',')
+                .map(|id| usize::from_str_radix(id, 16).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn single_page() {
+    let mut target = ThreadsTarget { threads: 3 };
+    let (res, _) = run_session(&mut target, &["qfThreadInfo", "qsThreadInfo"]);
+    assert_eq!(res, ["m01,02,03", "l"]);
+}
+
+#[test]
+fn many_threads_span_several_pages() {
+    let mut target = ThreadsTarget { threads: 500 };
+    let mut packets = vec!["qfThreadInfo"];
+    packets.extend(std::iter::repeat_n("qsThreadInfo", 10));
+    let (res, _) = run_session(&mut target, &packets);
+
+    let end = res.iter().position(|page| page == "l").unwrap();
+    assert!(end > 1);
+    for page in &res[..end] {
+        // "$" + page + "#xx" must fit within the (default) 4096 byte packet buffer
+        assert!(page.len() + 4 <= 4096);
+    }
+    assert_eq!(collect_pages(&res[..=end]), (1..=500).collect::<Vec<_>>());
+    // any further `qsThreadInfo` packets keep reporting the end of the list
+    assert!(res[end..].iter().all(|page| page == "l"));
+}
+
+#[test]
+fn small_packet_buffer() {
+    let mut target = ThreadsTarget { threads: 10 };
+    let mut packets = vec!["qfThreadInfo"];
+    packets.extend(std::iter::repeat_n("qsThreadInfo", 4));
+    // restarting the enumeration starts from the first thread again
+    packets.extend(&["qfThreadInfo"]);
+
+    let mut conn = MockConnection::new(&packets);
+    let _ = GdbStub::builder(&mut conn as &mut dyn Connection<Error = _>)
+        .packet_buffer_size(0x40)
+        .build()
+        .unwrap()
+        .run(&mut target);
+    let res = split_responses(&conn.output);
+    assert_eq!(
+        res,
+        [
+            "m01,02,03",
+            "m04,05,06",
+            "m07,08,09",
+            "m0a",
+            "l",
+            "m01,02,03"
+        ]
+    );
+}
+
+#[test]
+fn single_thread() {
+    struct SingleThreadTarget;
+
+    impl Target for SingleThreadTarget {
+        type Arch = Armv4t;
+        type Error = &'static str;
+
+        fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+            BaseOps::SingleThread(self)
+        }
+    }
+
+    impl_noop_single_thread_ops!(SingleThreadTarget);
+
+    let (res, _) = run_session(
+        &mut SingleThreadTarget,
+        &["qfThreadInfo", "qsThreadInfo", "qfThreadInfo"],
+    );
+    assert_eq!(res, ["m01", "l", "m01"]);
+}
@@ -0,0 +1,79 @@
+use std::string::String;
+
+use super::mock::{advertises, copy_xfer_chunk, read_xfer_paged, run_session};
+use crate::arch::arm::Armv4t;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::thread_list_xml::{ThreadListXml, ThreadListXmlOps};
+use crate::target::{Target, TargetResult};
+
+struct ThreadListTarget {
+    xml: String,
+}
+
+impl ThreadListTarget {
+    fn new(num_threads: usize) -> ThreadListTarget {
+        let mut xml = String::from("<threads>");
+        for i in 1..=num_threads {
+            xml += &format!(
+                r#"<thread id="{:x}" core="{}" name="worker-{}"/>"#,
+                i,
+                i % 4,
+                i
+            );
+        }
+        xml += "</threads>";
+        ThreadListTarget { xml }
+    }
+}
+
+impl Target for ThreadListTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn thread_list_xml(&mut self) -> Option<ThreadListXmlOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(ThreadListTarget);
+
+impl ThreadListXml for ThreadListTarget {
+    fn get_thread_list_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(copy_xfer_chunk(self.xml.as_bytes(), offset, length, buf))
+    }
+}
+
+#[test]
+fn advertises_thread_list_xml() {
+    assert!(advertises(
+        &mut ThreadListTarget::new(1),
+        "qXfer:threads:read+"
+    ));
+}
+
+#[test]
+fn full_read() {
+    let mut target = ThreadListTarget::new(3);
+    let (res, _) = run_session(&mut target, &["qXfer:threads:read::0,1000"]);
+    assert_eq!(res, [format!("l{}", target.xml)]);
+}
+
+#[test]
+fn paged_across_packet_buffer() {
+    let mut target = ThreadListTarget::new(8);
+    let xml = target.xml.clone();
+
+    // GDB requests far more data than fits in the 64 byte packet buffer, so
+    // each reply is clamped to the size of the buffer.
+    let data = read_xfer_paged(&mut target, "qXfer:threads:read::", xml.len(), 64, Some(64));
+    assert_eq!(data, xml.as_bytes());
+}
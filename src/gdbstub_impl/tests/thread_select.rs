@@ -0,0 +1,177 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::reg::id::ArmCoreRegId;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, MultiThreadOps, ThreadStopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A two-thread target where each thread has its own register file, and
+/// memory reads return the id of the thread they were issued on.
+struct PerThreadTarget {
+    regs: [ArmCoreRegs; 2],
+    mem_writes: Vec<(u32, Tid)>,
+}
+
+impl PerThreadTarget {
+    fn new() -> PerThreadTarget {
+        let mut regs = [ArmCoreRegs::default(), ArmCoreRegs::default()];
+        regs[0].pc = 0x1000;
+        regs[1].pc = 0x2000;
+        PerThreadTarget {
+            regs,
+            mem_writes: Vec::new(),
+        }
+    }
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for PerThreadTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+}
+
+impl MultiThreadOps for PerThreadTarget {
+    fn resume(
+        &mut self,
+        _actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, regs: &mut ArmCoreRegs, tid: Tid) -> TargetResult<(), Self> {
+        *regs = self.regs[tid.get() - 1].clone();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &ArmCoreRegs, tid: Tid) -> TargetResult<(), Self> {
+        self.regs[tid.get() - 1] = regs.clone();
+        Ok(())
+    }
+
+    fn read_register(
+        &mut self,
+        reg_id: ArmCoreRegId,
+        dst: &mut [u8],
+        tid: Tid,
+    ) -> TargetResult<usize, Self> {
+        if !matches!(reg_id, ArmCoreRegId::Pc) {
+            return Err(TargetError::NonFatal);
+        }
+        dst.copy_from_slice(&self.regs[tid.get() - 1].pc.to_le_bytes());
+        Ok(dst.len())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = tid.get() as u8);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, start_addr: u32, _data: &[u8], tid: Tid) -> TargetResult<(), Self> {
+        self.mem_writes.push((start_addr, tid));
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        thread_is_active(tid(1));
+        thread_is_active(tid(2));
+        Ok(())
+    }
+}
+
+/// Extract the PC (register 15) from a `g` reply.
+fn g_reply_pc(reply: &str) -> &str {
+    &reply[15 * 8..16 * 8]
+}
+
+#[test]
+fn g_uses_hg_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["g", "Hg2", "g", "Hg1", "g"]);
+    assert_eq!(g_reply_pc(&res[0]), "00100000");
+    assert_eq!(res[1], "OK");
+    assert_eq!(g_reply_pc(&res[2]), "00200000");
+    assert_eq!(res[3], "OK");
+    assert_eq!(g_reply_pc(&res[4]), "00100000");
+}
+
+#[test]
+fn hc_does_not_affect_register_reads() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hc2", "g", "pf"]);
+    assert_eq!(res[0], "OK");
+    assert_eq!(g_reply_pc(&res[1]), "00100000");
+    assert_eq!(res[2], "00100000");
+}
+
+#[test]
+fn p_uses_hg_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hg2", "pf", "Hg0", "pf"]);
+    // `Hg0` selects an arbitrary thread, which reuses the current one
+    assert_eq!(res, ["OK", "00200000", "OK", "00200000"]);
+}
+
+#[test]
+fn big_g_uses_hg_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hg2", "g"]);
+    let mut set_pc = res[1].clone();
+    set_pc.replace_range(15 * 8..16 * 8, "00300000");
+
+    let (res, _) = run_session(&mut target, &["Hg2", &format!("G{}", set_pc)]);
+    assert_eq!(res, ["OK", "OK"]);
+    assert_eq!(target.regs[0].pc, 0x1000);
+    assert_eq!(target.regs[1].pc, 0x3000);
+}
+
+#[test]
+fn memory_uses_hg_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(
+        &mut target,
+        &["m100,2", "Hg2", "m100,2", "M200,1:ff", "Hg1", "M300,1:ff"],
+    );
+    assert_eq!(res, ["0101", "OK", "0202", "OK", "OK", "OK"]);
+    assert_eq!(target.mem_writes, [(0x200, tid(2)), (0x300, tid(1))]);
+}
+
+#[test]
+fn h_selects_live_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hg2", "Hc2", "qC"]);
+    assert_eq!(res, ["OK", "OK", "QC02"]);
+}
+
+#[test]
+fn h_rejects_unknown_thread() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hg2", "Hg99", "pf", "Hc99", "qC"]);
+    // the failed select leaves the previously selected thread in place
+    assert_eq!(res, ["OK", "E01", "00200000", "E01", "QC02"]);
+}
+
+#[test]
+fn h_accepts_all_threads() {
+    let mut target = PerThreadTarget::new();
+    let (res, _) = run_session(&mut target, &["Hg2", "Hg-1", "pf", "Hc-1"]);
+    assert_eq!(res, ["OK", "OK", "00200000", "OK"]);
+}
@@ -0,0 +1,49 @@
+use super::mock::run_session;
+use crate::arch::arm::Armv4t;
+use crate::common::Tid;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::tls::{Tls, TlsOps};
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A target with a single load module at 0x4000, whose TLS block for thread
+/// 1 lives at 0x7fff_0000.
+struct TlsTarget;
+
+impl Target for TlsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn tls(&mut self) -> Option<TlsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(TlsTarget);
+
+impl Tls for TlsTarget {
+    fn get_tls_addr(&mut self, tid: Tid, offset: u32, load_module: u32) -> TargetResult<u32, Self> {
+        match (tid.get(), load_module) {
+            (1, 0x4000) => Ok(0x7fff_0000 + offset),
+            _ => Err(TargetError::Errno(22)),
+        }
+    }
+}
+
+#[test]
+fn resolve_tls_addr() {
+    let (res, _) = run_session(
+        &mut TlsTarget,
+        &["qGetTLSAddr:p1.1,18,4000", "qGetTLSAddr:p1.1,0,4000"],
+    );
+    assert_eq!(res, ["7fff0018", "7fff0000"]);
+}
+
+#[test]
+fn unknown_load_module() {
+    let (res, _) = run_session(&mut TlsTarget, &["qGetTLSAddr:p1.1,18,8000"]);
+    assert_eq!(res, ["E16"]);
+}
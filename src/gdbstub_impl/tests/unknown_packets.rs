@@ -0,0 +1,69 @@
+use super::mock::{run_session, MockConnection};
+use crate::arch::arm::Armv4t;
+use crate::common::Pid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::extended_mode::{Args, ExtendedMode, ExtendedModeOps, ShouldTerminate};
+use crate::target::{Target, TargetResult};
+use crate::{Connection, GdbStub};
+
+/// An extended-mode target, which registers several `v` packets.
+struct UnknownTarget;
+
+impl Target for UnknownTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl_noop_single_thread_ops!(UnknownTarget);
+
+impl ExtendedMode for UnknownTarget {
+    fn run(&mut self, _filename: Option<&[u8]>, _args: Args) -> TargetResult<Pid, Self> {
+        Ok(Pid::new(1).unwrap())
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<ThreadStopReason<u32>, Self> {
+        Ok(ThreadStopReason::Signal(0))
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        Ok(ShouldTerminate::No)
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn must_reply_empty() {
+    let (res, result) = run_session(
+        &mut UnknownTarget,
+        &[
+            "vMustReplyEmpty",
+            "vFooBar",
+            "vFile:unlink:2f746d70",
+            "qFooBar",
+            // shares a prefix with `qC`
+            "qCRC:1000,4",
+        ],
+    );
+    assert!(result.is_err()); // eof
+    assert_eq!(res, ["", "", "", "", ""]);
+}
+
+#[test]
+fn empty_reply_on_the_wire() {
+    let mut conn = MockConnection::new(&["vMustReplyEmpty"]);
+    let _ = GdbStub::new(&mut conn as &mut dyn Connection<Error = _>).run(&mut UnknownTarget);
+    // an empty packet, rather than `OK`
+    assert_eq!(conn.output, b"+$#00");
+}
@@ -0,0 +1,179 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::common::{Signal, Tid};
+use crate::target::ext::base::multithread::{
+    Actions, MultiThreadOps, ResumeAction, ThreadStopReason, TidSelector,
+};
+use crate::target::ext::base::BaseOps;
+use crate::target::{Target, TargetResult};
+
+type ThreadActions = [(Tid, Option<ResumeAction>); 3];
+
+/// A three-thread target which records how each thread was asked to resume.
+#[derive(Default)]
+struct ThreadsTarget {
+    resumes: Vec<ThreadActions>,
+    /// the raw actions passed to each resume
+    raw_actions: Vec<Vec<(TidSelector, ResumeAction)>>,
+}
+
+fn tid(n: usize) -> Tid {
+    Tid::new(n).unwrap()
+}
+
+impl Target for ThreadsTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::MultiThread(self)
+    }
+}
+
+impl MultiThreadOps for ThreadsTarget {
+    fn resume(
+        &mut self,
+        actions: Actions,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<u32>, Self::Error> {
+        let raw_actions = actions.collect::<Vec<_>>();
+        let mut threads = [(tid(1), None), (tid(2), None), (tid(3), None)];
+        Actions::new(&mut raw_actions.iter().copied()).resolve(&mut threads);
+        self.raw_actions.push(raw_actions);
+        self.resumes.push(threads);
+        Ok(ThreadStopReason::DoneStep)
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs, _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(
+        &mut self,
+        _start_addr: u32,
+        data: &mut [u8],
+        _tid: Tid,
+    ) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8], _tid: Tid) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error> {
+        (1..=3).map(tid).for_each(thread_is_active);
+        Ok(())
+    }
+}
+
+fn resume(packet: &str) -> ThreadActions {
+    let mut target = ThreadsTarget::default();
+    let (res, _) = run_session(&mut target, &[packet]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(target.resumes.len(), 1);
+    target.resumes[0]
+}
+
+const STEP: Option<ResumeAction> = Some(ResumeAction::Step(None));
+const CONT: Option<ResumeAction> = Some(ResumeAction::Continue(None));
+
+#[test]
+fn step_one_continue_rest() {
+    assert_eq!(
+        resume("vCont;s:2;c"),
+        [(tid(1), CONT), (tid(2), STEP), (tid(3), CONT)]
+    );
+}
+
+#[test]
+fn leftmost_action_wins() {
+    assert_eq!(
+        resume("vCont;s:2;C0b:2;c:3;s"),
+        [(tid(1), STEP), (tid(2), STEP), (tid(3), CONT)]
+    );
+    assert_eq!(
+        resume("vCont;c;s:2"),
+        [(tid(1), CONT), (tid(2), CONT), (tid(3), CONT)]
+    );
+}
+
+#[test]
+fn unselected_threads_stay_stopped() {
+    assert_eq!(
+        resume("vCont;S0b:1;c:3"),
+        [
+            (tid(1), Some(ResumeAction::Step(Some(Signal::SIGSEGV)))),
+            (tid(2), None),
+            (tid(3), CONT)
+        ]
+    );
+}
+
+#[test]
+fn hc_selected_thread() {
+    let mut target = ThreadsTarget::default();
+    let (res, _) = run_session(&mut target, &["Hc3", "s", "Hc-1", "s"]);
+    assert_eq!(res, ["OK", "S05", "OK", "S05"]);
+    assert_eq!(
+        target.resumes,
+        [
+            [(tid(1), None), (tid(2), None), (tid(3), STEP)],
+            [(tid(1), STEP), (tid(2), STEP), (tid(3), STEP)],
+        ]
+    );
+}
+
+#[test]
+fn stale_thread_skipped() {
+    // thread 5 exited before GDB resumed it
+    let mut target = ThreadsTarget::default();
+    let (res, _) = run_session(&mut target, &["vCont;c:5;c"]);
+    assert_eq!(res, ["S05"]);
+    assert_eq!(
+        target.raw_actions,
+        [[(TidSelector::All, ResumeAction::Continue(None))]]
+    );
+    assert_eq!(
+        target.resumes,
+        [[(tid(1), CONT), (tid(2), CONT), (tid(3), CONT)]]
+    );
+}
+
+#[test]
+fn all_threads_stale() {
+    let mut target = ThreadsTarget::default();
+    let (res, result) = run_session(&mut target, &["vCont;s:5;c:6", "vCont;s:2"]);
+    assert!(result.is_err()); // eof
+    assert_eq!(res, ["E03", "S05"]);
+    assert_eq!(
+        target.resumes,
+        [[(tid(1), None), (tid(2), STEP), (tid(3), None)]]
+    );
+}
+
+#[test]
+fn invalid_action_rejected_before_resume() {
+    let mut target = ThreadsTarget::default();
+    let (res, result) = run_session(
+        &mut target,
+        &["vCont;s:2;r1000,1010", "vCont;c:2;c:-0", "vCont;c"],
+    );
+    assert!(result.is_err()); // eof
+                              // neither packet resumes the target, and the session carries on
+    assert_eq!(res, ["E16", "E16", "S05"]);
+    assert_eq!(
+        target.resumes,
+        [[(tid(1), CONT), (tid(2), CONT), (tid(3), CONT)]]
+    );
+}
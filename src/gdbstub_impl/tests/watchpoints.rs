@@ -0,0 +1,188 @@
+use std::vec::Vec;
+
+use super::mock::run_session;
+use crate::arch::arm::{reg::ArmCoreRegs, Armv4t};
+use crate::target::ext::base::singlethread::{ResumeAction, SingleThreadOps, StopReason};
+use crate::target::ext::base::BaseOps;
+use crate::target::ext::breakpoints::{
+    HwWatchpoint, HwWatchpointMasked, HwWatchpointMaskedOps, HwWatchpointOps, WatchKind,
+};
+use crate::target::{Target, TargetResult};
+
+/// A target which records every watchpoint (and its mask, if any) it was asked
+/// to add / remove. When resumed, it reports a hit on the most recently added
+/// watchpoint.
+struct WatchTarget {
+    supports_mask: bool,
+    added: Vec<(u32, Option<u32>, WatchKind)>,
+    removed: Vec<(u32, Option<u32>, WatchKind)>,
+}
+
+impl WatchTarget {
+    fn new(supports_mask: bool) -> WatchTarget {
+        WatchTarget {
+            supports_mask,
+            added: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl Target for WatchTarget {
+    type Arch = Armv4t;
+    type Error = &'static str;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn hw_watchpoint(&mut self) -> Option<HwWatchpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadOps for WatchTarget {
+    fn resume(
+        &mut self,
+        _action: ResumeAction,
+        _check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<u32>, Self::Error> {
+        match self.added.last() {
+            Some(&(addr, _, kind)) => Ok(StopReason::Watch { kind, addr }),
+            None => Ok(StopReason::DoneStep),
+        }
+    }
+
+    fn read_registers(&mut self, _regs: &mut ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _regs: &ArmCoreRegs) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, _start_addr: u32, data: &mut [u8]) -> TargetResult<(), Self> {
+        data.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn write_addrs(&mut self, _start_addr: u32, _data: &[u8]) -> TargetResult<(), Self> {
+        Ok(())
+    }
+}
+
+impl HwWatchpoint for WatchTarget {
+    fn add_hw_watchpoint(&mut self, addr: u32, kind: WatchKind) -> TargetResult<bool, Self> {
+        self.added.push((addr, None, kind));
+        Ok(true)
+    }
+
+    fn remove_hw_watchpoint(&mut self, addr: u32, kind: WatchKind) -> TargetResult<bool, Self> {
+        self.removed.push((addr, None, kind));
+        Ok(true)
+    }
+
+    fn hw_watchpoint_masked(&mut self) -> Option<HwWatchpointMaskedOps<'_, Self>> {
+        if self.supports_mask {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl HwWatchpointMasked for WatchTarget {
+    fn add_hw_watchpoint_masked(
+        &mut self,
+        addr: u32,
+        mask: u32,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self> {
+        self.added.push((addr, Some(mask), kind));
+        Ok(true)
+    }
+
+    fn remove_hw_watchpoint_masked(
+        &mut self,
+        addr: u32,
+        mask: u32,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self> {
+        self.removed.push((addr, Some(mask), kind));
+        Ok(true)
+    }
+}
+
+#[test]
+fn unmasked_watchpoint() {
+    let mut target = WatchTarget::new(true);
+    let (res, _) = run_session(&mut target, &["Z2,1000,4", "z2,1000,4"]);
+    assert_eq!(res, ["OK", "OK"]);
+    assert_eq!(target.added, [(0x1000, None, WatchKind::Write)]);
+    assert_eq!(target.removed, [(0x1000, None, WatchKind::Write)]);
+}
+
+#[test]
+fn masked_watchpoint() {
+    let mut target = WatchTarget::new(true);
+    let (res, _) = run_session(
+        &mut target,
+        &[
+            "Z2,1000,4;fffffff0",
+            "Z3,2000,4;ffffff00",
+            "z2,1000,4;fffffff0",
+        ],
+    );
+    assert_eq!(res, ["OK", "OK", "OK"]);
+    assert_eq!(
+        target.added,
+        [
+            (0x1000, Some(0xffff_fff0), WatchKind::Write),
+            (0x2000, Some(0xffff_ff00), WatchKind::Read),
+        ]
+    );
+    assert_eq!(
+        target.removed,
+        [(0x1000, Some(0xffff_fff0), WatchKind::Write)]
+    );
+}
+
+#[test]
+fn rejects_mask_when_unsupported() {
+    let mut target = WatchTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z2,1000,4;fffffff0", "Z2,1000,4"]);
+    assert_eq!(res, ["E16", "OK"]);
+    assert_eq!(target.added, [(0x1000, None, WatchKind::Write)]);
+}
+
+#[test]
+fn reports_write_watchpoint_hit() {
+    let mut target = WatchTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z2,1000,4", "c"]);
+    assert_eq!(res, ["OK", "T05thread:01;watch:1000;"]);
+}
+
+#[test]
+fn reports_read_watchpoint_hit() {
+    let mut target = WatchTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z3,2004,4", "c"]);
+    assert_eq!(res, ["OK", "T05thread:01;rwatch:2004;"]);
+}
+
+#[test]
+fn reports_access_watchpoint_hit() {
+    let mut target = WatchTarget::new(false);
+    let (res, _) = run_session(&mut target, &["Z4,deadbeef,1", "c"]);
+    assert_eq!(res, ["OK", "T05thread:01;awatch:deadbeef;"]);
+    assert_eq!(target.added, [(0xdead_beef, None, WatchKind::ReadWrite)]);
+}
+
+#[test]
+fn step_reports_watchpoint_hit() {
+    // the watchpoint hit takes precedence over the completed step
+    for step in ["s", "vCont;s:1", "vCont;s"].iter() {
+        let mut target = WatchTarget::new(false);
+        let (res, _) = run_session(&mut target, &["Z2,1000,4", step]);
+        assert_eq!(res, ["OK", "T05thread:01;watch:1000;"], "{}", step);
+    }
+}
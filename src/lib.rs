@@ -15,17 +15,23 @@
 //!       buffer).
 //!     - Provide built-in implementations for certain protocol features:
 //!         - Use a heap-allocated packet buffer in `GdbStub` (if none is
-//!           provided via `GdbStubBuilder::with_packet_buffer`).
-//!         - (Monitor Command) Use a heap-allocated output buffer in
-//!           `ConsoleOutput`.
-//!         - (Extended Mode) Automatically track Attached/Spawned PIDs without
-//!           implementing `ExtendedMode::query_if_attached`.
+//!           provided via `GdbStubBuilder::with_packet_buffer` /
+//!           `with_owned_packet_buffer`).
+//!         - (Extended Mode) Automatically track Attached/Spawned PIDs, only
+//!           falling back to `ExtendedMode::query_if_attached` for processes
+//!           which weren't created / attached to via GDB.
 //! - `std` (implies `alloc`)
 //!     - Implement `Connection` for [`TcpStream`](std::net::TcpStream) and
 //!       [`UnixStream`](std::os::unix::net::UnixStream).
 //!     - Implement [`std::error::Error`] for `gdbstub::Error`.
 //!     - Add a `TargetError::Io` error variant to simplify I/O Error handling
 //!       from `Target` methods.
+//! - `async` (implies `alloc`)
+//!     - Add the [`AsyncConnection`] trait, and an [`AsyncGdbStub`] which runs
+//!       the debugging session using `async` I/O.
+//! - `stats`
+//!     - Count the packets / bytes exchanged with GDB, which can be queried
+//!       via `GdbStub::stats`.
 //!
 //! ## Getting Started
 //!
@@ -110,6 +116,8 @@
 //!     Ok(disconnect_reason) => match disconnect_reason {
 //!         DisconnectReason::Disconnect => println!("GDB client disconnected."),
 //!         DisconnectReason::TargetHalted => println!("Target halted!"),
+//!         DisconnectReason::TargetExited(code) => println!("Target exited with code {}!", code),
+//!         DisconnectReason::TargetTerminated(sig) => println!("Target terminated with signal {}!", sig),
 //!         DisconnectReason::Kill => println!("GDB client sent a kill command!"),
 //!     }
 //!     // Handle any target-specific errors
@@ -144,12 +152,14 @@ pub mod arch;
 pub mod common;
 pub mod target;
 
+#[cfg(feature = "async")]
+pub use connection::AsyncConnection;
 pub use connection::Connection;
 pub use gdbstub_impl::*;
 
 /// (Internal) The fake Tid that's used when running in single-threaded mode.
 // SAFETY: 1 is clearly non-zero.
 const SINGLE_THREAD_TID: common::Tid = unsafe { common::Tid::new_unchecked(1) };
-/// (Internal) The fake Pid reported to GDB (since `gdbstub` only supports
-/// debugging a single process).
+/// (Internal) The fake Pid reported to GDB when the target doesn't support
+/// debugging multiple processes.
 const FAKE_PID: common::Pid = unsafe { common::Pid::new_unchecked(1) };
@@ -25,13 +25,13 @@ macro_rules! commands {
         )*
     ) => {paste! {
         $($(
-            #[allow(non_snake_case, non_camel_case_types)]
+            #[allow(non_snake_case, non_camel_case_types, clippy::upper_case_acronyms)]
             pub mod $mod;
         )*)*
 
         pub mod ext {
             $(
-                #[allow(non_camel_case_types)]
+                #[allow(non_camel_case_types, clippy::upper_case_acronyms, clippy::enum_variant_names)]
                 pub enum [<$ext:camel>] $(<$lt>)? {
                     $($command(super::$mod::$command<$($lifetime)?>),)*
                 }
@@ -71,9 +71,20 @@ macro_rules! commands {
                         $(_ if body.starts_with($name.as_bytes()) => {
                             crate::__dead_code_marker!($name, "prefix_match");
 
+                            // if the name continues past the prefix (e.g: `qCRC` vs. `qC`),
+                            // the packet is some other (unknown) command, as opposed to a
+                            // malformed instance of this one.
+                            let is_other_cmd = body
+                                .get($name.len())
+                                .map(u8::is_ascii_alphabetic)
+                                .unwrap_or(false);
+
                             let buf = buf.trim_start_body_bytes($name.len());
-                            let cmd = $mod::$command::from_packet(buf)
-                                .ok_or(CommandParseError::MalformedCommand($name))?;
+                            let cmd = match $mod::$command::from_packet(buf) {
+                                Some(cmd) => cmd,
+                                None if is_other_cmd => return Ok(Command::Unknown($name)),
+                                None => return Err(CommandParseError::MalformedCommand($name)),
+                            };
 
                             return Ok(
                                 Command::[<$ext:camel>](
@@ -104,6 +115,7 @@ commands! {
     base use 'a {
         "?" => question_mark::QuestionMark,
         "c" => _c::c<'a>,
+        "C" => _c_upcase::C,
         "D" => _d_upcase::D,
         "g" => _g::g,
         "G" => _g_upcase::G<'a>,
@@ -111,18 +123,23 @@ commands! {
         "k" => _k::k,
         "m" => _m::m<'a>,
         "M" => _m_upcase::M<'a>,
-        "p" => _p::p,
+        "p" => _p::p<'a>,
         "P" => _p_upcase::P<'a>,
         "qAttached" => _qAttached::qAttached,
+        "qC" => _qC::qC,
         "qfThreadInfo" => _qfThreadInfo::qfThreadInfo,
         "QStartNoAckMode" => _QStartNoAckMode::QStartNoAckMode,
         "qsThreadInfo" => _qsThreadInfo::qsThreadInfo,
         "qSupported" => _qSupported::qSupported<'a>,
-        "qXfer:features:read" => _qXfer_features_read::qXferFeaturesRead,
+        "QThreadEvents" => _QThreadEvents::QThreadEvents,
+        "qXfer:features:read" => _qXfer_features_read::qXferFeaturesRead<'a>,
         "s" => _s::s<'a>,
+        "S" => _s_upcase::S,
         "T" => _t_upcase::T,
         "vCont" => _vCont::vCont<'a>,
+        "vCtrlC" => _vCtrlC::vCtrlC,
         "vKill" => _vKill::vKill,
+        "X" => _x_upcase::X<'a>,
         "z" => _z::z<'a>,
         "Z" => _z_upcase::Z<'a>,
     }
@@ -147,4 +164,108 @@ commands! {
     section_offsets {
         "qOffsets" => _qOffsets::qOffsets,
     }
+
+    host_io use 'a {
+        "vFile:close" => _vFile_close::vFileClose,
+        "vFile:fstat" => _vFile_fstat::vFileFstat,
+        "vFile:open" => _vFile_open::vFileOpen<'a>,
+        "vFile:pread" => _vFile_pread::vFilePread<'a>,
+        "vFile:setfs" => _vFile_setfs::vFileSetfs,
+    }
+
+    tracepoints use 'a {
+        "QTDP" => _QTDP::QTDP<'a>,
+        "QTDV" => _QTDV::QTDV<'a>,
+        "QTinit" => _QTinit::QTinit,
+        "QTStart" => _QTStart::QTStart,
+        "QTStop" => _QTStop::QTStop,
+        "qTStatus" => _qTStatus::qTStatus,
+    }
+
+    auxv use 'a {
+        "qXfer:auxv:read" => _qXfer_auxv::qXferAuxvRead<'a>,
+    }
+
+    siginfo use 'a {
+        "qXfer:siginfo:read" => _qXfer_siginfo_read::qXferSiginfoRead<'a>,
+        "qXfer:siginfo:write" => _qXfer_siginfo_write::qXferSiginfoWrite<'a>,
+    }
+
+    exec_file use 'a {
+        "qXfer:exec-file:read" => _qXfer_exec_file::qXferExecFileRead<'a>,
+    }
+
+    libraries_svr4 use 'a {
+        "qXfer:libraries-svr4:read" => _qXfer_libraries_svr4::qXferLibrariesSvr4Read<'a>,
+    }
+
+    thread_list_xml use 'a {
+        "qXfer:threads:read" => _qXfer_threads::qXferThreadsRead<'a>,
+    }
+
+    memory_map use 'a {
+        "qXfer:memory-map:read" => _qXfer_memory_map::qXferMemoryMapRead<'a>,
+    }
+
+    flash use 'a {
+        "vFlashDone" => _vFlashDone::vFlashDone,
+        "vFlashErase" => _vFlashErase::vFlashErase<'a>,
+        "vFlashWrite" => _vFlashWrite::vFlashWrite<'a>,
+    }
+
+    memory_search use 'a {
+        "qSearch:memory" => _qSearch_memory::qSearchMemory<'a>,
+    }
+
+    memory_tags use 'a {
+        "qMemTags" => _qMemTags::qMemTags<'a>,
+        "QMemTags" => _QMemTags::QMemTags<'a>,
+    }
+
+    allow {
+        "QAllow" => _QAllow::QAllow,
+    }
+
+    thread_extra_info use 'a {
+        "qThreadExtraInfo" => _qThreadExtraInfo::qThreadExtraInfo<'a>,
+    }
+
+    signals use 'a {
+        "QPassSignals" => _QPassSignals::QPassSignals<'a>,
+        "QProgramSignals" => _QProgramSignals::QProgramSignals<'a>,
+    }
+
+    tls use 'a {
+        "qGetTLSAddr" => _qGetTLSAddr::qGetTLSAddr<'a>,
+    }
+
+    osdata use 'a {
+        "qXfer:osdata:read" => _qXfer_osdata::qXferOsdataRead<'a>,
+    }
+
+    btrace use 'a {
+        // must come before `Qbtrace`, as it shares the same prefix
+        "Qbtrace-conf" => _Qbtrace_conf::QbtraceConf,
+        "Qbtrace" => _Qbtrace::Qbtrace,
+        "qXfer:btrace:read" => _qXfer_btrace::qXferBtraceRead<'a>,
+    }
+
+    host_syscall {
+        "F" => _f_upcase::F,
+    }
+
+    non_stop_mode {
+        "QNonStop" => _QNonStop::QNonStop,
+        "vStopped" => _vStopped::vStopped,
+    }
+
+    reverse_exec {
+        "bc" => _bc::bc,
+        "bs" => _bs::bs,
+    }
+
+    // must come last, so that the built-in `qXfer` objects take precedence
+    custom_xfer use 'a {
+        "qXfer" => _qXfer_custom::qXferCustom<'a>,
+    }
 }
@@ -0,0 +1,37 @@
+use super::prelude::*;
+
+use crate::target::ext::allow::AllowFlags;
+
+#[derive(Debug)]
+pub struct QAllow {
+    pub flags: AllowFlags,
+}
+
+impl<'a> ParseCommand<'a> for QAllow {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QAllow", "from_packet");
+
+        let body = buf.into_body();
+        if body.first() != Some(&b':') {
+            return None;
+        }
+
+        let mut flags = AllowFlags::empty();
+        for op in body[1..].split(|&b| b == b';') {
+            let mut op = op.splitn(2, |&b| b == b':');
+            let name = op.next()?;
+            let allowed = match op.next()? {
+                b"0" => false,
+                b"1" => true,
+                _ => return None,
+            };
+
+            // unknown operations are ignored
+            if let (true, Some(flag)) = (allowed, AllowFlags::from_op_name(name)) {
+                flags = flags | flag;
+            }
+        }
+
+        Some(QAllow { flags })
+    }
+}
@@ -0,0 +1,33 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct QMemTags<'a> {
+    pub addr: &'a [u8],
+    pub len: &'a [u8],
+    pub tag_type: u32,
+    pub tags: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for QMemTags<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QMemTags", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        let mut body = body.split_mut(|b| *b == b',' || *b == b':');
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let len = decode_hex_buf(body.next()?).ok()?;
+        let tag_type = decode_hex(body.next()?).ok()?;
+        let tags = decode_hex_buf(body.next()?).ok()?;
+
+        Some(QMemTags {
+            addr,
+            len,
+            tag_type,
+            tags,
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct QPassSignals<'a> {
+    pub signals: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for QPassSignals<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QPassSignals", "from_packet");
+
+        let signals = parse_signal_list(buf.into_body())?;
+        Some(QPassSignals { signals })
+    }
+}
+
+/// Validate a `:`-prefixed, `;`-separated list of hex-encoded signal numbers,
+/// returning the list (sans the leading `:`).
+pub fn parse_signal_list(body: &[u8]) -> Option<&[u8]> {
+    let list = match body {
+        [b':', list @ ..] => list,
+        _ => return None,
+    };
+
+    for sig in list.split(|b| *b == b';').filter(|s| !s.is_empty()) {
+        decode_hex::<u8>(sig).ok()?;
+    }
+
+    Some(list)
+}
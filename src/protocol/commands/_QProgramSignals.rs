@@ -0,0 +1,17 @@
+use super::prelude::*;
+
+use super::_QPassSignals::parse_signal_list;
+
+#[derive(Debug)]
+pub struct QProgramSignals<'a> {
+    pub signals: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for QProgramSignals<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QProgramSignals", "from_packet");
+
+        let signals = parse_signal_list(buf.into_body())?;
+        Some(QProgramSignals { signals })
+    }
+}
@@ -0,0 +1,113 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub enum QTDP<'a> {
+    Create(CreateTDP<'a>),
+    /// A continuation packet, specifying additional actions for an existing
+    /// tracepoint (i.e: `QTDP:-...`).
+    Actions,
+}
+
+#[derive(Debug)]
+pub struct CreateTDP<'a> {
+    pub number: usize,
+    pub addr: &'a [u8],
+    pub enabled: bool,
+    pub step: usize,
+    pub pass: usize,
+}
+
+impl<'a> ParseCommand<'a> for QTDP<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QTDP", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', b'-', ..] => return Some(QTDP::Actions),
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        // a trailing '-' indicates that additional `QTDP:-...` packets will follow
+        let body = match body {
+            [body @ .., b'-'] => body,
+            body => body,
+        };
+
+        let mut body = body.split_mut(|b| *b == b':');
+        let number = decode_hex(body.next()?).ok()?;
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let enabled = match body.next()? as &[u8] {
+            b"E" => true,
+            b"D" => false,
+            _ => return None,
+        };
+        let step = decode_hex(body.next()?).ok()?;
+        let pass = decode_hex(body.next()?).ok()?;
+        // TODO: parse the optional fast tracepoint (`F`) and condition (`X`) fields
+
+        Some(QTDP::Create(CreateTDP {
+            number,
+            addr,
+            enabled,
+            step,
+            pass,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_buf {
+        ($bufname:ident, $body:literal) => {
+            let mut test = $body.to_vec();
+            let buf = PacketBuf::new_with_raw_body(&mut test).unwrap();
+            let $bufname = buf.trim_start_body_bytes(b"QTDP".len());
+        };
+    }
+
+    #[test]
+    fn valid_QTDP() {
+        test_buf!(buf, b"QTDP:1:00401000:E:0:3");
+
+        let pkt = match QTDP::from_packet(buf).unwrap() {
+            QTDP::Create(pkt) => pkt,
+            QTDP::Actions => panic!(),
+        };
+
+        assert_eq!(pkt.number, 1);
+        assert_eq!(pkt.addr, &[0x00, 0x40, 0x10, 0x00]);
+        assert!(pkt.enabled);
+        assert_eq!(pkt.step, 0);
+        assert_eq!(pkt.pass, 3);
+    }
+
+    #[test]
+    fn valid_QTDP_trailing_dash() {
+        test_buf!(buf, b"QTDP:2:8000:D:a:0-");
+
+        let pkt = match QTDP::from_packet(buf).unwrap() {
+            QTDP::Create(pkt) => pkt,
+            QTDP::Actions => panic!(),
+        };
+
+        assert_eq!(pkt.number, 2);
+        assert!(!pkt.enabled);
+        assert_eq!(pkt.step, 10);
+    }
+
+    #[test]
+    fn valid_QTDP_actions() {
+        test_buf!(buf, b"QTDP:-1:00401000:M9,4");
+
+        assert!(matches!(QTDP::from_packet(buf), Some(QTDP::Actions)));
+    }
+
+    #[test]
+    fn invalid_QTDP() {
+        test_buf!(buf, b"QTDP:1:00401000:Q:0:0");
+
+        assert!(QTDP::from_packet(buf).is_none());
+    }
+}
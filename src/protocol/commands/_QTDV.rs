@@ -0,0 +1,37 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct QTDV<'a> {
+    pub number: usize,
+    pub value: u64,
+    pub builtin: bool,
+    pub name: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for QTDV<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QTDV", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        let mut body = body.splitn_mut(4, |b| *b == b':');
+        let number = decode_hex(body.next()?).ok()?;
+        let value = decode_hex(body.next()?).ok()?;
+        let builtin = match body.next()? as &[u8] {
+            b"1" => true,
+            b"0" => false,
+            _ => return None,
+        };
+        let name = decode_hex_buf(body.next()?).ok()?;
+
+        Some(QTDV {
+            number,
+            value,
+            builtin,
+            name,
+        })
+    }
+}
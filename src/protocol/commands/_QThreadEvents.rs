@@ -0,0 +1,20 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct QThreadEvents {
+    pub value: bool,
+}
+
+impl<'a> ParseCommand<'a> for QThreadEvents {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("QThreadEvents", "from_packet");
+
+        let body = buf.into_body();
+        let value = match body as &[u8] {
+            b":0" => false,
+            b":1" => true,
+            _ => return None,
+        };
+        Some(QThreadEvents { value })
+    }
+}
@@ -0,0 +1,25 @@
+use super::prelude::*;
+
+use crate::target::ext::btrace::BtraceFormat;
+
+#[derive(Debug)]
+pub struct Qbtrace {
+    /// `None` disables branch tracing.
+    pub format: Option<BtraceFormat>,
+}
+
+impl<'a> ParseCommand<'a> for Qbtrace {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("Qbtrace", "from_packet");
+
+        let body = buf.into_body();
+        let format = match body as &[u8] {
+            b":bts" => Some(BtraceFormat::Bts),
+            b":pt" => Some(BtraceFormat::Pt),
+            b":off" => None,
+            _ => return None,
+        };
+
+        Some(Qbtrace { format })
+    }
+}
@@ -0,0 +1,36 @@
+use super::prelude::*;
+
+use crate::target::ext::btrace::BtraceFormat;
+
+#[derive(Debug)]
+pub struct QbtraceConf {
+    pub format: BtraceFormat,
+    pub size: u64,
+}
+
+impl<'a> ParseCommand<'a> for QbtraceConf {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("Qbtrace-conf", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        // e.g: `bts:size=0x10000`
+        let mut body = body.splitn(2, |b| *b == b'=');
+        let format = match body.next()? {
+            b"bts:size" => BtraceFormat::Bts,
+            b"pt:size" => BtraceFormat::Pt,
+            _ => return None,
+        };
+        // GDB includes a `0x` prefix on the size
+        let size = match body.next()? {
+            [b'0', b'x', size @ ..] => size,
+            size => size,
+        };
+        let size = decode_hex(size).ok()?;
+
+        Some(QbtraceConf { format, size })
+    }
+}
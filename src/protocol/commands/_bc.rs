@@ -0,0 +1,15 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct bc;
+
+impl<'a> ParseCommand<'a> for bc {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("bc", "from_packet");
+
+        if !buf.into_body().is_empty() {
+            return None;
+        }
+        Some(bc)
+    }
+}
@@ -0,0 +1,15 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct bs;
+
+impl<'a> ParseCommand<'a> for bs {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("bs", "from_packet");
+
+        if !buf.into_body().is_empty() {
+            return None;
+        }
+        Some(bs)
+    }
+}
@@ -0,0 +1,41 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct F {
+    pub retval: i64,
+    pub errno: Option<u32>,
+    pub ctrl_c: bool,
+}
+
+impl<'a> ParseCommand<'a> for F {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("F", "from_packet");
+
+        // Fretcode[,errno[,C]][;attachment]
+        let body = buf.into_body();
+        // GDB never sends a call-specific attachment for any of the supported syscalls
+        let body = body.split(|b| *b == b';').next()?;
+        let mut body = body.split(|b| *b == b',');
+
+        let retval = match body.next()? {
+            [] => return None,
+            [b'-', val @ ..] => -decode_hex::<i64>(val).ok()?,
+            val => decode_hex(val).ok()?,
+        };
+        let errno = match body.next() {
+            Some(errno) => Some(decode_hex(errno).ok()?),
+            None => None,
+        };
+        let ctrl_c = match body.next() {
+            Some(b"C") => true,
+            Some(_) => return None,
+            None => false,
+        };
+
+        Some(F {
+            retval,
+            errno,
+            ctrl_c,
+        })
+    }
+}
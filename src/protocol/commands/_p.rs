@@ -1,13 +1,18 @@
 use super::prelude::*;
 
 #[derive(Debug)]
-pub struct p {
+pub struct p<'a> {
     pub reg_id: usize,
+
+    pub buf: &'a mut [u8],
 }
 
-impl<'a> ParseCommand<'a> for p {
+impl<'a> ParseCommand<'a> for p<'a> {
     fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
-        let reg_id = decode_hex(buf.into_body()).ok()?;
-        Some(p { reg_id })
+        // the register id is decoded into an integer, which frees up the entire
+        // packet buffer to be re-used as a scratch buffer for the register's value.
+        let (buf, body_range) = buf.into_raw_buf();
+        let reg_id = decode_hex(&buf[body_range]).ok()?;
+        Some(p { reg_id, buf })
     }
 }
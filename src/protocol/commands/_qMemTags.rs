@@ -0,0 +1,44 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qMemTags<'a> {
+    pub addr: &'a [u8],
+    pub len: usize,
+    pub tag_type: u32,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qMemTags<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qMemTags", "from_packet");
+
+        // much like the `m` packet, the decoded addr is left in-place, and the
+        // rest of the packet buffer is re-used as a scratch buffer for the tags.
+        let (buf, body_range) = buf.into_raw_buf();
+        let start = body_range.start + 1;
+
+        let body = match &mut buf[body_range] {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        let mut body = body.split_mut(|b| *b == b',' || *b == b':');
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let addr_len = addr.len();
+        let len = decode_hex(body.next()?).ok()?;
+        let tag_type = decode_hex(body.next()?).ok()?;
+
+        drop(body);
+
+        let (addr, buf) = buf.split_at_mut(start + addr_len);
+        let addr = &addr[start..];
+
+        Some(qMemTags {
+            addr,
+            len,
+            tag_type,
+            buf,
+        })
+    }
+}
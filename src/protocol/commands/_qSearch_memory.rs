@@ -0,0 +1,31 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qSearchMemory<'a> {
+    pub addr: &'a [u8],
+    pub len: &'a [u8],
+    pub pattern: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for qSearchMemory<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qSearch:memory", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        let mut body = body.splitn_mut(3, |b| *b == b';');
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let len = decode_hex_buf(body.next()?).ok()?;
+        let pattern = decode_hex_buf(body.next()?).ok()?;
+
+        Some(qSearchMemory {
+            addr,
+            len,
+            pattern,
+        })
+    }
+}
+
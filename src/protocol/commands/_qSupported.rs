@@ -12,6 +12,12 @@ impl<'a> ParseCommand<'a> for qSupported<'a> {
             return None;
         }
 
+        // the feature list is separated from the packet name by a ':'
+        let body = match body.split_first() {
+            Some((b':', rest)) => rest,
+            _ => body,
+        };
+
         Some(qSupported {
             features: Features(body),
         })
@@ -22,6 +28,11 @@ impl<'a> ParseCommand<'a> for qSupported<'a> {
 pub struct Features<'a>(&'a [u8]);
 
 impl<'a> Features<'a> {
+    /// Iterate over the raw (unparsed) features, e.g: `b"swbreak+"`.
+    pub fn tokens(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.0.split(|b| *b == b';').filter(|s| !s.is_empty())
+    }
+
     pub fn into_iter(self) -> impl Iterator<Item = Option<Feature<'a>>> + 'a {
         self.0.split(|b| *b == b';').map(|s| match s.last() {
             None => None,
@@ -56,7 +67,7 @@ pub enum FeatureSupported {
 
 #[derive(Debug)]
 pub struct Feature<'a> {
-    name: Bstr<'a>,
-    val: Option<Bstr<'a>>,
-    status: FeatureSupported,
+    pub name: Bstr<'a>,
+    pub val: Option<Bstr<'a>>,
+    pub status: FeatureSupported,
 }
@@ -0,0 +1,15 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qTStatus;
+
+impl<'a> ParseCommand<'a> for qTStatus {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qTStatus", "from_packet");
+
+        if !buf.into_body().is_empty() {
+            return None;
+        }
+        Some(qTStatus)
+    }
+}
@@ -0,0 +1,25 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qThreadExtraInfo<'a> {
+    pub id: ThreadId,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qThreadExtraInfo<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qThreadExtraInfo", "from_packet");
+
+        // the thread-id is decoded up-front, which frees up the entire packet
+        // buffer to be re-used as a scratch buffer for the thread's description.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        let id = match &buf[body_range] {
+            [b',', body @ ..] => ThreadId::try_from(body).ok()?,
+            _ => return None,
+        };
+
+        Some(qThreadExtraInfo { id, buf })
+    }
+}
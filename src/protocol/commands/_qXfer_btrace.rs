@@ -0,0 +1,47 @@
+use super::prelude::*;
+
+use crate::target::ext::btrace::BtraceReadKind;
+
+#[derive(Debug)]
+pub struct qXferBtraceRead<'a> {
+    pub kind: BtraceReadKind,
+    pub offset: u64,
+    pub len: usize,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferBtraceRead<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:btrace:read", "from_packet");
+
+        // the annex, offset, and length are all decoded up-front, which frees
+        // up the entire packet buffer to be re-used as a scratch buffer for the
+        // trace.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        let (kind, offset, len) = match &buf[body_range] {
+            [b':', body @ ..] => {
+                let mut body = body.splitn(2, |b| *b == b':');
+                let kind = match body.next()? {
+                    b"all" => BtraceReadKind::All,
+                    b"new" => BtraceReadKind::New,
+                    b"delta" => BtraceReadKind::Delta,
+                    _ => return None,
+                };
+                let mut body = body.next()?.split(|b| *b == b',');
+                let offset = decode_hex(body.next()?).ok()?;
+                let len = decode_hex(body.next()?).ok()?;
+                (kind, offset, len)
+            }
+            _ => return None,
+        };
+
+        Some(qXferBtraceRead {
+            kind,
+            offset,
+            len,
+            buf,
+        })
+    }
+}
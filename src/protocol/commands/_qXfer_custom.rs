@@ -0,0 +1,67 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub enum XferOp<'a> {
+    Read {
+        offset: u64,
+        len: usize,
+
+        buf: &'a mut [u8],
+    },
+    Write {
+        offset: u64,
+        data: &'a [u8],
+    },
+}
+
+#[derive(Debug)]
+pub struct qXferCustom<'a> {
+    pub object: &'a [u8],
+    pub annex: &'a [u8],
+    pub op: XferOp<'a>,
+}
+
+impl<'a> ParseCommand<'a> for qXferCustom<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer", "from_packet");
+
+        // the object name and annex are borrowed from the packet body, so only the
+        // portion of the packet buffer _past_ the body can be re-used as a scratch
+        // buffer.
+        let (buf, body_range) = buf.into_raw_buf();
+        let (body, buf) = buf[body_range.start..].split_at_mut(body_range.len());
+
+        // `:OBJECT:OP:ANNEX:ARGS`, where a write's binary payload may itself contain
+        // ':' bytes
+        let mut body = match body {
+            [b':', body @ ..] => body.splitn_mut(4, |b| *b == b':'),
+            _ => return None,
+        };
+        let object = body.next()?;
+        let op = body.next()?;
+        let annex = body.next()?;
+        let args = body.next()?;
+
+        let op = match op as &[u8] {
+            b"read" => {
+                let mut args = args.split(|b| *b == b',');
+                let offset = decode_hex(args.next()?).ok()?;
+                let len = decode_hex(args.next()?).ok()?;
+                XferOp::Read { offset, len, buf }
+            }
+            b"write" => {
+                let mut args = args.splitn_mut(2, |b| *b == b':');
+                let offset = decode_hex(args.next()?).ok()?;
+                let data = decode_bin_buf(args.next()?)?;
+                XferOp::Write { offset, data }
+            }
+            _ => return None,
+        };
+
+        Some(qXferCustom {
+            object,
+            annex,
+            op,
+        })
+    }
+}
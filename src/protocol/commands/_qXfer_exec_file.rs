@@ -0,0 +1,46 @@
+use super::prelude::*;
+
+use crate::common::Pid;
+
+#[derive(Debug)]
+pub struct qXferExecFileRead<'a> {
+    pub pid: Option<Pid>,
+    pub offset: u64,
+    pub len: usize,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferExecFileRead<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:exec-file:read", "from_packet");
+
+        // the pid, offset, and length are decoded into integers, which frees up
+        // the entire packet buffer to be re-used as a scratch buffer for the
+        // path.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        let (pid, offset, len) = match &buf[body_range] {
+            [b':', body @ ..] => {
+                let mut body = body.splitn(2, |b| *b == b':');
+                // an empty annex refers to the current process
+                let pid = match body.next()? {
+                    [] => None,
+                    pid => Some(Pid::new(decode_hex(pid).ok()?)?),
+                };
+                let mut body = body.next()?.split(|b| *b == b',');
+                let offset = decode_hex(body.next()?).ok()?;
+                let len = decode_hex(body.next()?).ok()?;
+                (pid, offset, len)
+            }
+            _ => return None,
+        };
+
+        Some(qXferExecFileRead {
+            pid,
+            offset,
+            len,
+            buf,
+        })
+    }
+}
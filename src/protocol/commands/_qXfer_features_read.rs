@@ -1,22 +1,27 @@
 use super::prelude::*;
 
 #[derive(Debug)]
-pub struct qXferFeaturesRead {
-    pub offset: usize,
+pub struct qXferFeaturesRead<'a> {
+    pub annex: &'a [u8],
+    pub offset: u64,
     pub len: usize,
+
+    pub buf: &'a mut [u8],
 }
 
-impl<'a> ParseCommand<'a> for qXferFeaturesRead {
+impl<'a> ParseCommand<'a> for qXferFeaturesRead<'a> {
     fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
-        let body = buf.into_body();
-
-        if body.is_empty() {
-            return None;
-        }
+        // the annex is borrowed from the packet body, so only the portion of the
+        // packet buffer _past_ the body can be re-used as a scratch buffer.
+        let (buf, body_range) = buf.into_raw_buf();
+        let (body, buf) = buf[body_range.start..].split_at_mut(body_range.len());
 
-        let mut body = body.split(|b| *b == b':').skip(1);
+        let mut body = match body {
+            [b':', body @ ..] => body.split(|b| *b == b':'),
+            _ => return None,
+        };
         let annex = body.next()?;
-        if annex != b"target.xml" {
+        if annex.is_empty() {
             return None;
         }
 
@@ -24,6 +29,11 @@ impl<'a> ParseCommand<'a> for qXferFeaturesRead {
         let offset = decode_hex(body.next()?).ok()?;
         let len = decode_hex(body.next()?).ok()?;
 
-        Some(qXferFeaturesRead { offset, len })
+        Some(qXferFeaturesRead {
+            annex,
+            offset,
+            len,
+            buf,
+        })
     }
 }
@@ -0,0 +1,39 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qXferOsdataRead<'a> {
+    pub annex: &'a [u8],
+    pub offset: u64,
+    pub len: usize,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferOsdataRead<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:osdata:read", "from_packet");
+
+        // the annex is borrowed from the packet body, so only the portion of the
+        // packet buffer _past_ the body can be re-used as a scratch buffer.
+        let (buf, body_range) = buf.into_raw_buf();
+        let (body, buf) = buf[body_range.start..].split_at_mut(body_range.len());
+
+        let mut body = match body {
+            [b':', body @ ..] => body.split(|b| *b == b':'),
+            _ => return None,
+        };
+        // an empty annex requests the list of available types
+        let annex = body.next()?;
+
+        let mut body = body.next()?.split(|b| *b == b',');
+        let offset = decode_hex(body.next()?).ok()?;
+        let len = decode_hex(body.next()?).ok()?;
+
+        Some(qXferOsdataRead {
+            annex,
+            offset,
+            len,
+            buf,
+        })
+    }
+}
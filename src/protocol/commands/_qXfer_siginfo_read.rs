@@ -0,0 +1,32 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qXferSiginfoRead<'a> {
+    pub offset: u64,
+    pub len: usize,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferSiginfoRead<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:siginfo:read", "from_packet");
+
+        // the offset and length are decoded into integers, which frees up the
+        // entire packet buffer to be re-used as a scratch buffer for the data.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        // siginfo doesn't use an annex, hence the empty `::`
+        let (offset, len) = match &buf[body_range] {
+            [b':', b':', body @ ..] => {
+                let mut body = body.split(|b| *b == b',');
+                let offset = decode_hex(body.next()?).ok()?;
+                let len = decode_hex(body.next()?).ok()?;
+                (offset, len)
+            }
+            _ => return None,
+        };
+
+        Some(qXferSiginfoRead { offset, len, buf })
+    }
+}
@@ -0,0 +1,27 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qXferSiginfoWrite<'a> {
+    pub offset: u64,
+    pub data: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferSiginfoWrite<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:siginfo:write", "from_packet");
+
+        // siginfo doesn't use an annex, hence the empty `::`
+        let body = match buf.into_body() {
+            [b':', b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        // the binary payload may itself contain ':' bytes, so only split on the
+        // first one.
+        let mut body = body.splitn_mut(2, |b| *b == b':');
+        let offset = decode_hex(body.next()?).ok()?;
+        let data = decode_bin_buf(body.next()?)?;
+
+        Some(qXferSiginfoWrite { offset, data })
+    }
+}
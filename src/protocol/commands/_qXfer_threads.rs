@@ -0,0 +1,32 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct qXferThreadsRead<'a> {
+    pub offset: u64,
+    pub len: usize,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for qXferThreadsRead<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("qXfer:threads:read", "from_packet");
+
+        // the offset and length are decoded into integers, which frees up the
+        // entire packet buffer to be re-used as a scratch buffer for the XML.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        // the thread list doesn't use an annex, hence the empty `::`
+        let (offset, len) = match &buf[body_range] {
+            [b':', b':', body @ ..] => {
+                let mut body = body.split(|b| *b == b',');
+                let offset = decode_hex(body.next()?).ok()?;
+                let len = decode_hex(body.next()?).ok()?;
+                (offset, len)
+            }
+            _ => return None,
+        };
+
+        Some(qXferThreadsRead { offset, len, buf })
+    }
+}
@@ -0,0 +1,16 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct S {
+    pub sig: u8,
+}
+
+impl<'a> ParseCommand<'a> for S {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        let body = buf.into_body();
+        // TODO?: support the optional resume addr (i.e: `Ssig;addr`)
+        let sig = body.split(|b| *b == b';').next()?;
+        let sig = decode_hex(sig).ok()?;
+        Some(S { sig })
+    }
+}
@@ -0,0 +1,19 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct vFileClose {
+    pub fd: u32,
+}
+
+impl<'a> ParseCommand<'a> for vFileClose {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFile:close", "from_packet");
+
+        match buf.into_body() {
+            [b':', fd @ ..] => Some(vFileClose {
+                fd: decode_hex(fd).ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
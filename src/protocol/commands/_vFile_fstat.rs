@@ -0,0 +1,19 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct vFileFstat {
+    pub fd: u32,
+}
+
+impl<'a> ParseCommand<'a> for vFileFstat {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFile:fstat", "from_packet");
+
+        match buf.into_body() {
+            [b':', fd @ ..] => Some(vFileFstat {
+                fd: decode_hex(fd).ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct vFilePread<'a> {
+    pub fd: u32,
+    pub count: usize,
+    pub offset: u64,
+
+    pub buf: &'a mut [u8],
+}
+
+impl<'a> ParseCommand<'a> for vFilePread<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFile:pread", "from_packet");
+
+        // all of the packet's fields are decoded into integers, which frees up the
+        // entire packet buffer to be re-used as a scratch buffer for the read data.
+        let (buf, body_range) = buf.into_raw_buf();
+
+        let (fd, count, offset) = match &buf[body_range] {
+            [b':', body @ ..] => {
+                let mut body = body.split(|b| *b == b',');
+                let fd = decode_hex(body.next()?).ok()?;
+                let count = decode_hex(body.next()?).ok()?;
+                let offset = decode_hex(body.next()?).ok()?;
+                (fd, count, offset)
+            }
+            _ => return None,
+        };
+
+        Some(vFilePread {
+            fd,
+            count,
+            offset,
+            buf,
+        })
+    }
+}
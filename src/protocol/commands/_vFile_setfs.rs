@@ -0,0 +1,26 @@
+use super::prelude::*;
+
+use crate::target::ext::host_io::FsKind;
+
+#[derive(Debug)]
+pub struct vFileSetfs {
+    pub fs: FsKind,
+}
+
+impl<'a> ParseCommand<'a> for vFileSetfs {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFile:setfs", "from_packet");
+
+        let pid = match buf.into_body() {
+            [b':', pid @ ..] => decode_hex(pid).ok()?,
+            _ => return None,
+        };
+
+        let fs = match Pid::new(pid) {
+            None => FsKind::Stub,
+            Some(pid) => FsKind::Pid(pid),
+        };
+
+        Some(vFileSetfs { fs })
+    }
+}
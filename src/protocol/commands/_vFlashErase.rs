@@ -0,0 +1,24 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct vFlashErase<'a> {
+    pub addr: &'a [u8],
+    pub length: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for vFlashErase<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFlashErase", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        let mut body = body.split_mut(|b| *b == b',');
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let length = decode_hex_buf(body.next()?).ok()?;
+
+        Some(vFlashErase { addr, length })
+    }
+}
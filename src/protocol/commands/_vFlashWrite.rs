@@ -0,0 +1,26 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct vFlashWrite<'a> {
+    pub addr: &'a [u8],
+    pub val: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for vFlashWrite<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        crate::__dead_code_marker!("vFlashWrite", "from_packet");
+
+        let body = match buf.into_body() {
+            [b':', body @ ..] => body,
+            _ => return None,
+        };
+
+        // the binary payload may itself contain ':' bytes, so only split on the
+        // first one.
+        let mut body = body.splitn_mut(2, |b| *b == b':');
+        let addr = decode_hex_buf(body.next()?).ok()?;
+        let val = decode_bin_buf(body.next()?)?;
+
+        Some(vFlashWrite { addr, val })
+    }
+}
@@ -6,18 +6,20 @@ pub struct vRun<'a> {
     pub args: Args<'a>,
 }
 
+/// `None` if the packet didn't include any args. Otherwise, contains the
+/// `;`-separated list of hex-encoded args (which may include empty args).
 #[derive(Debug)]
-pub struct Args<'a>(&'a mut [u8]);
+pub struct Args<'a>(Option<&'a mut [u8]>);
 
 impl<'a> Args<'a> {
     pub fn into_iter(self) -> impl Iterator<Item = &'a [u8]> + 'a {
         self.0
-            .split_mut(|b| *b == b';')
+            .into_iter()
+            .flat_map(|args| args.split_mut(|b| *b == b';'))
             // the `from_packet` method guarantees that the args are valid hex ascii, so this should
             // method should never fail.
             .map(|raw| decode_hex_buf(raw).unwrap_or(&mut []))
             .map(|s| s as &[u8])
-            .filter(|s| !s.is_empty())
     }
 }
 
@@ -32,12 +34,14 @@ impl<'a> ParseCommand<'a> for vRun<'a> {
             [] => None,
             s => Some(s as &[u8]),
         };
-        let args = body.next().unwrap_or(&mut []); // args are optional
+        let args = body.next(); // args are optional
 
         // validate that args have valid hex encoding (with ';' delimiters).
         // this removes all the error handling from the lazy `Args` iterator.
-        if args.iter().any(|b| !(is_hex(*b) || *b == b';')) {
-            return None;
+        if let Some(args) = &args {
+            if args.iter().any(|b| !(is_hex(*b) || *b == b';')) {
+                return None;
+            }
         }
 
         Some(vRun {
@@ -103,6 +107,17 @@ mod tests {
         assert_eq!(args, &[b"test"]);
     }
 
+    #[test]
+    fn valid_vRun_empty_args() {
+        test_buf!(buf, b"vRun;74657374;;74657374;");
+
+        let pkt = vRun::from_packet(buf).unwrap();
+        let args = pkt.args.into_iter().collect::<Vec<_>>();
+
+        assert_eq!(pkt.filename, Some(&b"test"[..]));
+        assert_eq!(args, &[&b""[..], b"test", b""]);
+    }
+
     #[test]
     fn invalid_vRun_args() {
         test_buf!(buf, b"vRun;74657374;nothex");
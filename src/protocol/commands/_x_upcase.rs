@@ -0,0 +1,27 @@
+use super::prelude::*;
+
+#[derive(Debug)]
+pub struct X<'a> {
+    pub addr: &'a [u8],
+    pub len: usize,
+    pub val: &'a [u8],
+}
+
+impl<'a> ParseCommand<'a> for X<'a> {
+    fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
+        let body = buf.into_body();
+
+        // the binary payload may itself contain ',' and ':' bytes, so only
+        // split on the first occurrence of each.
+        let mut body = body.splitn_mut(2, |b| *b == b':');
+        let mut header = body.next()?.split_mut(|b| *b == b',');
+        let addr = decode_hex_buf(header.next()?).ok()?;
+        let len: usize = decode_hex(header.next()?).ok()?;
+        if header.next().is_some() {
+            return None;
+        }
+        let val = decode_bin_buf(body.next()?)?;
+
+        Some(X { addr, len, val })
+    }
+}
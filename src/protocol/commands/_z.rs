@@ -4,17 +4,33 @@ use super::prelude::*;
 pub struct z<'a> {
     pub type_: u8,
     pub addr: &'a [u8],
-    pub kind: u8,
+    pub kind: usize,
+    /// watchpoint address mask (if any)
+    pub mask: Option<&'a [u8]>,
 }
 
 impl<'a> ParseCommand<'a> for z<'a> {
     fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
         let body = buf.into_body();
+
+        let (body, mask) = match body.iter().position(|&b| b == b';') {
+            Some(idx) => {
+                let (body, mask) = body.split_at_mut(idx);
+                (body, Some(&*decode_hex_buf(&mut mask[1..]).ok()?))
+            }
+            None => (body, None),
+        };
+
         let mut body = body.split_mut(|&b| b == b',');
         let type_ = decode_hex(body.next()?).ok()?;
         let addr = decode_hex_buf(body.next()?).ok()?;
         let kind = decode_hex(body.next()?).ok()?;
 
-        Some(z { type_, addr, kind })
+        Some(z {
+            type_,
+            addr,
+            kind,
+            mask,
+        })
     }
 }
@@ -1,24 +1,143 @@
 use super::prelude::*;
 
+use crate::target::ext::agent::AgentExprs;
+
 #[derive(Debug)]
 pub struct Z<'a> {
     pub type_: u8,
     pub addr: &'a [u8],
     /// architecture dependent
-    pub kind: u8,
-    // TODO: Add support for breakpoint 'conds', 'persist', and 'cmds' feature
+    pub kind: usize,
+    /// target-side breakpoint conditions (if any)
+    pub conds: Option<AgentExprs<'a>>,
+    /// target-side breakpoint commands (if any)
+    pub cmds: Option<AgentExprs<'a>>,
+    /// whether the breakpoint commands should keep running after GDB
+    /// disconnects
+    pub persist: bool,
+    /// watchpoint address mask (if any)
+    pub mask: Option<&'a [u8]>,
 }
 
 impl<'a> ParseCommand<'a> for Z<'a> {
     fn from_packet(buf: PacketBuf<'a>) -> Option<Self> {
         let body = buf.into_body();
+
+        let (body, extra) = match body.iter().position(|&b| b == b';') {
+            Some(idx) => {
+                let (body, extra) = body.split_at_mut(idx);
+                (body, Some(&mut extra[1..]))
+            }
+            None => (body, None),
+        };
+
         let mut body = body.split_mut(|&b| b == b',');
         let type_ = decode_hex(body.next()?).ok()?;
         let addr = decode_hex_buf(body.next()?).ok()?;
         let kind = decode_hex(body.next()?).ok()?;
 
-        // TODO: properly parse 'conds', 'persist', and 'cmds' fields in 'Z' packets
+        // watchpoints (types 2, 3, and 4) may include an address mask, whereas
+        // breakpoints may include a list of conditions and / or commands
+        let mut opts = BreakpointOpts::default();
+        let mut mask = None;
+        match extra {
+            Some(extra) if matches!(type_, 2..=4) => mask = Some(&*decode_hex_buf(extra).ok()?),
+            Some(extra) => opts = parse_opts(extra)?,
+            None => {}
+        };
+
+        Some(Z {
+            type_,
+            addr,
+            kind,
+            conds: opts.conds,
+            cmds: opts.cmds,
+            persist: opts.persist,
+            mask,
+        })
+    }
+}
+
+#[derive(Default)]
+struct BreakpointOpts<'a> {
+    conds: Option<AgentExprs<'a>>,
+    cmds: Option<AgentExprs<'a>>,
+    persist: bool,
+}
+
+/// Parse a breakpoint's list of `X<len>,<bytecode>` condition expressions,
+/// followed by an optional `cmds:<persist>,` list of command expressions,
+/// decoding them in-place into the packed format expected by `AgentExprs`.
+///
+/// GDB concatenates each list's expressions back-to-back, though `;`
+/// separators between expressions are accepted as well.
+fn parse_opts(buf: &mut [u8]) -> Option<BreakpointOpts<'_>> {
+    // the packed representation (2 byte len + raw bytecode) is always shorter
+    // than the hex-encoded representation, so the read cursor never falls
+    // behind the write cursor.
+    let mut r = 0;
+    let mut w = 0;
+
+    let mut has_conds = false;
+    while r < buf.len() && !buf[r..].starts_with(b"cmds:") {
+        parse_expr(buf, &mut r, &mut w)?;
+        has_conds = true;
+    }
+    let conds_end = w;
+
+    let mut persist = None;
+    if r < buf.len() {
+        r += b"cmds:".len();
+        let comma = r + buf[r..].iter().position(|&b| b == b',')?;
+        persist = Some(decode_hex::<u8>(&buf[r..comma]).ok()? != 0);
+        r = comma + 1;
+
+        while r < buf.len() {
+            parse_expr(buf, &mut r, &mut w)?;
+        }
+    }
+
+    let (conds, cmd_exprs) = buf[..w].split_at(conds_end);
+    Some(BreakpointOpts {
+        conds: if has_conds {
+            Some(AgentExprs::new(conds))
+        } else {
+            None
+        },
+        cmds: persist.map(|_| AgentExprs::new(cmd_exprs)),
+        persist: persist.unwrap_or(false),
+    })
+}
+
+/// Decode a single `X<len>,<bytecode>` expression (along with any trailing
+/// `;` separator) starting at `buf[*r]`, and pack it into `buf[*w..]`.
+fn parse_expr(buf: &mut [u8], r: &mut usize, w: &mut usize) -> Option<()> {
+    if buf[*r] != b'X' {
+        return None;
+    }
+    *r += 1;
+
+    let comma = *r + buf[*r..].iter().position(|&b| b == b',')?;
+    let len: usize = decode_hex(&buf[*r..comma]).ok()?;
+    if len > u16::MAX as usize {
+        return None;
+    }
+    *r = comma + 1;
+
+    let hex_end = r.checked_add(len * 2)?;
+    if hex_end > buf.len() {
+        return None;
+    }
+    for i in 0..len {
+        let byte = decode_hex::<u8>(&buf[*r + i * 2..*r + i * 2 + 2]).ok()?;
+        buf[*w + 2 + i] = byte;
+    }
+    buf[*w..*w + 2].copy_from_slice(&(len as u16).to_be_bytes());
+    *w += 2 + len;
+    *r = hex_end;
 
-        Some(Z { type_, addr, kind })
+    if buf.get(*r) == Some(&b';') {
+        *r += 1;
     }
+    Some(())
 }
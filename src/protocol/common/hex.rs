@@ -87,6 +87,27 @@ pub fn decode_hex_buf(base_buf: &mut [u8]) -> Result<&mut [u8], DecodeHexBufErro
     Ok(&mut base_buf[..decoded_len + odd_adust])
 }
 
+/// Decode GDB escaped binary bytes into a byte slice _in place_.
+///
+/// Bytes preceded by the escape character `}` are XOR'd with `0x20`. Returns
+/// `None` if the buffer ends with a dangling escape character.
+pub fn decode_bin_buf(buf: &mut [u8]) -> Option<&mut [u8]> {
+    let mut i = 0;
+    let mut j = 0;
+    while i < buf.len() {
+        if buf[i] == b'}' {
+            i += 1;
+            buf[j] = *buf.get(i)? ^ 0x20;
+        } else {
+            buf[j] = buf[i];
+        }
+        i += 1;
+        j += 1;
+    }
+
+    Some(&mut buf[..j])
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum EncodeHexBufError {
@@ -187,4 +208,17 @@ mod tests {
         let res = decode_hex_buf(&mut payload).unwrap();
         assert_eq!(res, [0x1]);
     }
+
+    #[test]
+    fn decode_bin_buf_escaped() {
+        let mut payload = b"a}]b}\x03}\x04".to_vec();
+        let res = decode_bin_buf(&mut payload).unwrap();
+        assert_eq!(res, b"a}b#$");
+    }
+
+    #[test]
+    fn decode_bin_buf_dangling_escape() {
+        let mut payload = b"ab}".to_vec();
+        assert!(decode_bin_buf(&mut payload).is_none());
+    }
 }
@@ -1,8 +1,5 @@
 use core::fmt;
 
-#[cfg(feature = "alloc")]
-use alloc::vec::Vec;
-
 /// Helper struct to send console output to GDB.
 ///
 /// The recommended way to interact with `ConsoleOutput` is through the provided
@@ -13,12 +10,11 @@ use alloc::vec::Vec;
 /// the `write_raw()` method can be used to write raw data directly to the GDB
 /// console.
 ///
-/// When the `alloc` feature is disabled, all output buffering is disabled, and
-/// each call to `output!` will automatically flush data over the Connection.
-// TODO: support user-provided output buffers for no-`alloc` environments.
+/// Output is never buffered: each write is immediately sent to GDB (as one or
+/// more `O` packets, each of which fits within the stub's packet size). As
+/// such, arbitrarily large amounts of output can be written in a loop without
+/// having to hold it all in memory.
 pub struct ConsoleOutput<'a> {
-    #[cfg(feature = "alloc")]
-    buf: Vec<u8>,
     callback: &'a mut dyn FnMut(&[u8]),
 }
 
@@ -31,41 +27,29 @@ impl<'a> fmt::Write for ConsoleOutput<'a> {
 
 impl<'a> ConsoleOutput<'a> {
     pub(crate) fn new(callback: &'a mut dyn FnMut(&[u8])) -> ConsoleOutput<'a> {
+        ConsoleOutput { callback }
+    }
+
+    /// Reborrow the output as a shorter-lived `ConsoleOutput`, so it can be
+    /// handed to the target several times.
+    pub(crate) fn reborrow(&mut self) -> ConsoleOutput<'_> {
         ConsoleOutput {
-            #[cfg(feature = "alloc")]
-            buf: Vec::new(),
-            callback,
+            callback: &mut *self.callback,
         }
     }
 
     /// Write raw (non UTF-8) data to the GDB console.
     pub fn write_raw(&mut self, bytes: &[u8]) {
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "alloc")] {
-                self.buf.extend_from_slice(bytes);
-            } else {
-                (self.callback)(bytes);
-            }
+        if !bytes.is_empty() {
+            (self.callback)(bytes);
         }
     }
 
-    /// Flush the internal output buffer.
+    /// Flush any buffered output.
     ///
-    /// Only available when `alloc` is enabled.
-    #[cfg(feature = "alloc")]
-    pub fn flush(&mut self) {
-        if !self.buf.is_empty() {
-            (self.callback)(&self.buf);
-            self.buf.clear()
-        }
-    }
-}
-
-impl Drop for ConsoleOutput<'_> {
-    fn drop(&mut self) {
-        #[cfg(feature = "alloc")]
-        self.flush()
-    }
+    /// Since output is sent to GDB as soon as it's written, this is a no-op,
+    /// and is only retained for backwards compatibility.
+    pub fn flush(&mut self) {}
 }
 
 /// Send formatted data to the GDB client console.
@@ -74,8 +58,8 @@ impl Drop for ConsoleOutput<'_> {
 #[macro_export]
 macro_rules! output {
     ($console_output:expr, $($args:tt)*) => {{
-        use std::fmt::Write;
-        let _ = writeln!($console_output, $($args)*);
+        use core::fmt::Write;
+        let _ = write!($console_output, $($args)*);
     }};
 }
 
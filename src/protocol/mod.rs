@@ -5,10 +5,10 @@ mod response_writer;
 
 pub(crate) mod commands;
 
-pub(crate) use common::{IdKind, ThreadId};
+pub(crate) use common::{decode_hex, IdKind, ThreadId};
 pub(crate) use packet::Packet;
 pub(crate) use response_writer::{Error as ResponseWriterError, ResponseWriter};
 
 // These types end up a part of the public interface.
 pub use console_output::ConsoleOutput;
-pub use packet::PacketParseError;
+pub use packet::{PacketParseError, PacketSnippet};
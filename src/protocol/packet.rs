@@ -3,16 +3,106 @@ use crate::target::Target;
 
 /// Packet parse error.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum PacketParseError {
+    /// The packet's checksum didn't match its contents.
+    #[allow(missing_docs)]
     ChecksumMismatched { checksum: u8, calculated: u8 },
+    /// The packet was empty.
     EmptyBuf,
+    /// The packet didn't include a checksum.
     MissingChecksum,
+    /// The packet's checksum wasn't valid hex.
     MalformedChecksum,
-    MalformedCommand,
-    NotASCII,
+    /// The packet's body couldn't be parsed into a valid command (e.g: a
+    /// known command with missing / invalid arguments).
+    MalformedCommand(PacketSnippet),
+    /// The packet began with an unexpected byte.
     UnexpectedHeader(u8),
 }
 
+impl core::fmt::Display for PacketParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use self::PacketParseError::*;
+        match self {
+            ChecksumMismatched {
+                checksum,
+                calculated,
+            } => write!(
+                f,
+                "checksum mismatch (packet: {:02x}, calculated: {:02x})",
+                checksum, calculated
+            ),
+            EmptyBuf => write!(f, "empty packet"),
+            MissingChecksum => write!(f, "missing checksum"),
+            MalformedChecksum => write!(f, "malformed checksum"),
+            MalformedCommand(body) => write!(f, "malformed command: \"{}\"", body),
+            UnexpectedHeader(b) => write!(f, "unexpected packet header: {:#04x}", b),
+        }
+    }
+}
+
+/// The first few bytes of a packet which failed to parse.
+///
+/// Packets are parsed in-place within the packet buffer, so only a short
+/// (owned) snippet of the offending packet is retained for diagnostics.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PacketSnippet {
+    buf: [u8; PacketSnippet::MAX_LEN],
+    len: usize,
+    truncated: bool,
+}
+
+impl PacketSnippet {
+    /// The maximum number of bytes retained from the offending packet.
+    pub const MAX_LEN: usize = 32;
+
+    pub(crate) fn new(bytes: &[u8]) -> PacketSnippet {
+        let len = bytes.len().min(Self::MAX_LEN);
+        let mut buf = [0; Self::MAX_LEN];
+        buf[..len].copy_from_slice(&bytes[..len]);
+        PacketSnippet {
+            buf,
+            len,
+            truncated: bytes.len() > Self::MAX_LEN,
+        }
+    }
+
+    /// The retained bytes of the packet's body.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Whether the packet's body was longer than [`PacketSnippet::MAX_LEN`]
+    /// (and was therefore truncated).
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl core::fmt::Display for PacketSnippet {
+    /// Non-printable bytes are escaped, and truncated snippets end with `...`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        for &b in self.as_bytes() {
+            for c in core::ascii::escape_default(b) {
+                f.write_char(c as char)?;
+            }
+        }
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for PacketSnippet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "PacketSnippet(\"{}\")", self)
+    }
+}
+
 /// Top-Level GDB packet
 pub enum Packet<'a> {
     Ack,
@@ -28,13 +118,15 @@ pub struct PacketBuf<'a> {
 
 impl<'a> PacketBuf<'a> {
     /// Validate the contents of the raw packet buffer, checking for checksum
-    /// consistency, structural correctness, and ASCII validation.
+    /// consistency and structural correctness.
+    ///
+    /// _Note:_ the packet body is _not_ required to be ASCII, as certain
+    /// packets (e.g: `vFlashWrite`) carry escaped binary data.
+    ///
+    /// `pkt_buf` may be larger than the packet itself, in which case any bytes
+    /// past the packet's checksum are ignored (but can still be re-used as
+    /// scratch space via `into_raw_buf`).
     pub fn new(pkt_buf: &'a mut [u8]) -> Result<PacketBuf<'a>, PacketParseError> {
-        // validate the packet is valid ASCII
-        if !pkt_buf.is_ascii() {
-            return Err(PacketParseError::NotASCII);
-        }
-
         let end_of_body = pkt_buf
             .iter()
             .position(|b| *b == b'#')
@@ -56,9 +148,10 @@ impl<'a> PacketBuf<'a> {
         }
 
         if log_enabled!(log::Level::Trace) {
-            // SAFETY: body confirmed to be `is_ascii()`
-            let body = unsafe { core::str::from_utf8_unchecked(body) };
-            trace!("<-- ${}#{:02x?}", body, checksum);
+            match core::str::from_utf8(body) {
+                Ok(body) => trace!("<-- ${}#{:02x?}", body, checksum),
+                Err(_) => trace!("<-- ${:x?}#{:02x?}", body, checksum),
+            }
         }
 
         Ok(PacketBuf {
@@ -68,14 +161,9 @@ impl<'a> PacketBuf<'a> {
     }
 
     /// (used for tests) Create a packet buffer from a raw body buffer, skipping
-    /// the header/checksum trimming stage. ASCII validation is still performed.
+    /// the header/checksum trimming stage.
     #[cfg(test)]
     pub fn new_with_raw_body(body: &'a mut [u8]) -> Result<PacketBuf<'a>, PacketParseError> {
-        // validate the packet is valid ASCII
-        if !body.is_ascii() {
-            return Err(PacketParseError::NotASCII);
-        }
-
         let len = body.len();
         Ok(PacketBuf {
             buf: body,
@@ -100,10 +188,12 @@ impl<'a> PacketBuf<'a> {
         &mut self.buf[self.body_range]
     }
 
+    /// Return the current body as a `&str`.
+    ///
+    /// Bodies containing non UTF-8 data (i.e: binary packets) are replaced
+    /// with a placeholder string.
     pub fn into_body_str(self) -> &'a str {
-        // SAFETY: buffer confirmed to be `is_ascii()` in `new`, and no other PacketBuf
-        // member allow arbitrary modification of `self.buf`.
-        unsafe { core::str::from_utf8_unchecked(&self.buf[self.body_range]) }
+        core::str::from_utf8(&self.buf[self.body_range]).unwrap_or("<binary data>")
     }
 
     /// Return a mut reference to the _entire_ underlying packet buffer, and the
@@ -125,11 +215,14 @@ impl<'a> Packet<'a> {
         }
 
         match buf[0] {
-            b'$' => Ok(Packet::Command(
-                Command::from_packet(target, PacketBuf::new(buf)?)
-                    // TODO?: preserve command parse error context
-                    .map_err(|_| PacketParseError::MalformedCommand)?,
-            )),
+            b'$' => {
+                let buf = PacketBuf::new(buf)?;
+                // commands are decoded in-place, so the snippet must be taken up-front
+                let snippet = PacketSnippet::new(buf.as_body());
+                Ok(Packet::Command(Command::from_packet(target, buf).map_err(
+                    |_| PacketParseError::MalformedCommand(snippet),
+                )?))
+            }
             b'+' => Ok(Packet::Ack),
             b'-' => Ok(Packet::Nack),
             0x03 => Ok(Packet::Interrupt),
@@ -1,55 +1,164 @@
 #[cfg(feature = "alloc")]
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use num_traits::PrimInt;
 
 use crate::internal::BeBytes;
 use crate::protocol::{IdKind, ThreadId};
-use crate::Connection;
+use crate::{Connection, PacketDirection, PacketTraceFn};
 
 /// Newtype around a Connection error. Having a newtype allows implementing a
 /// `From<ResponseWriterError<C>> for crate::Error<T, C>`, which greatly
 /// simplifies some of the error handling in the main gdbstub.
 #[derive(Debug, Clone)]
-pub struct Error<C>(C);
+pub struct Error<C>(pub(crate) C);
 
 /// A wrapper around [`Connection`] that computes the single-byte checksum of
 /// incoming / outgoing data.
 pub struct ResponseWriter<'a, C: Connection + 'a> {
     inner: &'a mut C,
+    header: u8,
     started: bool,
     checksum: u8,
+    // number of raw bytes written to the connection
+    len: usize,
+    // run-length encoding state
+    rle_enabled: bool,
+    rle_char: u8,
+    rle_len: usize,
     // buffer outgoing message
     // TODO: add `write_all` method to Connection, and allow user to optionally pass outgoing
     // packet buffer? This could improve performance (instead of writing a single byte at a time)
     #[cfg(feature = "alloc")]
     msg: String,
+    // packet trace callback (if any), alongside a fixed-size buffer of the bytes
+    // most recently sent over the wire
+    trace: Option<PacketTrace>,
+    // a copy of the raw bytes sent over the wire (used to re-transmit the response),
+    // alongside the maximum number of bytes to keep a copy of
+    #[cfg(feature = "alloc")]
+    record: Option<&'a mut Vec<u8>>,
+    #[cfg(feature = "alloc")]
+    record_limit: usize,
+}
+
+/// Size of the buffer used to batch outgoing bytes before passing them to the
+/// packet trace callback.
+const TRACE_CHUNK_LEN: usize = 64;
+
+/// Accumulates outgoing bytes into fixed-size chunks for a packet trace
+/// callback, avoiding any allocations.
+struct PacketTrace {
+    callback: PacketTraceFn,
+    chunk: [u8; TRACE_CHUNK_LEN],
+    len: usize,
+}
+
+impl PacketTrace {
+    fn push(&mut self, byte: u8) {
+        if self.len == TRACE_CHUNK_LEN {
+            self.flush();
+        }
+        self.chunk[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn flush(&mut self) {
+        if self.len != 0 {
+            (self.callback)(PacketDirection::Outgoing, &self.chunk[..self.len]);
+            self.len = 0;
+        }
+    }
 }
 
 impl<'a, C: Connection + 'a> ResponseWriter<'a, C> {
-    /// Creates a new ResponseWriter
-    pub fn new(inner: &'a mut C) -> Self {
+    /// Creates a new ResponseWriter.
+    ///
+    /// If `rle_enabled` is set, the response is sent using run-length
+    /// encoding.
+    pub fn new(inner: &'a mut C, rle_enabled: bool) -> Self {
+        Self::new_with_header(inner, b'$', rle_enabled)
+    }
+
+    /// Creates a new ResponseWriter for an asynchronous notification (i.e: a
+    /// packet which starts with '%' instead of '$').
+    pub fn new_notification(inner: &'a mut C, rle_enabled: bool) -> Self {
+        Self::new_with_header(inner, b'%', rle_enabled)
+    }
+
+    fn new_with_header(inner: &'a mut C, header: u8, rle_enabled: bool) -> Self {
         Self {
             inner,
+            header,
             started: false,
             checksum: 0,
+            len: 0,
+            rle_enabled,
+            rle_char: 0,
+            rle_len: 0,
             #[cfg(feature = "alloc")]
             msg: String::new(),
+            trace: None,
+            #[cfg(feature = "alloc")]
+            record: None,
+            #[cfg(feature = "alloc")]
+            record_limit: 0,
         }
     }
 
-    /// Consumes self, writing out the final '#' and checksum
-    pub fn flush(mut self) -> Result<(), Error<C::Error>> {
+    /// Record a copy of the response's raw bytes (as sent over the wire) into
+    /// `record`, replacing its existing contents.
+    ///
+    /// `record` is left as-is if nothing is ever written to the response, and
+    /// is left empty if the response grows larger than `limit` bytes (e.g: a
+    /// large streamed memory read).
+    #[cfg(feature = "alloc")]
+    pub fn with_record(mut self, record: &'a mut Vec<u8>, limit: usize) -> Self {
+        self.record = Some(record);
+        self.record_limit = limit;
+        self
+    }
+
+    /// Report the response's raw bytes to the provided packet trace callback.
+    ///
+    /// Responses are reported in one or more consecutive chunks, with the
+    /// final chunk being reported once the response is flushed.
+    pub fn with_trace(mut self, trace: Option<PacketTraceFn>) -> Self {
+        self.trace = trace.map(|callback| PacketTrace {
+            callback,
+            chunk: [0; TRACE_CHUNK_LEN],
+            len: 0,
+        });
+        self
+    }
+
+    /// Consumes self, writing out the final '#' and checksum.
+    ///
+    /// Returns the total number of bytes sent over the wire.
+    pub fn flush(mut self) -> Result<usize, Error<C::Error>> {
+        self.flush_rle()?;
+
         // don't include '#' in checksum calculation
         let checksum = self.checksum;
 
         #[cfg(feature = "alloc")]
-        trace!("--> ${}#{:02x?}", self.msg, checksum);
+        trace!("--> {}{}#{:02x?}", self.header as char, self.msg, checksum);
 
-        self.write(b'#')?;
-        self.write_hex(checksum)?;
+        self.write_raw(b'#')?;
+        for digit in [(checksum & 0xf0) >> 4, checksum & 0x0f].iter() {
+            self.write_raw(hex_digit(*digit))?;
+        }
 
-        Ok(())
+        if let Some(trace) = &mut self.trace {
+            trace.flush();
+        }
+
+        Ok(self.len)
+    }
+
+    /// Returns `true` if any part of the response has been written.
+    pub fn is_started(&self) -> bool {
+        self.started
     }
 
     /// Get a mutable reference to the underlying connection.
@@ -62,15 +171,87 @@ impl<'a, C: Connection + 'a> ResponseWriter<'a, C> {
         #[cfg(feature = "alloc")]
         self.msg.push(byte as char);
 
+        if !self.rle_enabled {
+            return self.write_raw(byte);
+        }
+
+        if self.rle_len != 0 && self.rle_char == byte {
+            self.rle_len += 1;
+            return Ok(());
+        }
+
+        self.flush_rle()?;
+        self.rle_char = byte;
+        self.rle_len = 1;
+        Ok(())
+    }
+
+    /// Write a single byte directly to the connection, updating the checksum.
+    fn write_raw(&mut self, byte: u8) -> Result<(), Error<C::Error>> {
         if !self.started {
             self.started = true;
-            self.inner.write(b'$').map_err(Error)?;
+            self.len += 1;
+            self.inner.write(self.header).map_err(Error)?;
+            if let Some(trace) = &mut self.trace {
+                trace.push(self.header);
+            }
+            #[cfg(feature = "alloc")]
+            if let Some(record) = &mut self.record {
+                record.clear();
+                record.push(self.header);
+            }
+        }
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(byte);
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(record) = &mut self.record {
+            if record.len() < self.record_limit {
+                record.push(byte);
+            } else {
+                // too large to keep a copy of
+                record.clear();
+                self.record = None;
+            }
         }
 
+        self.len += 1;
         self.checksum = self.checksum.wrapping_add(byte);
         self.inner.write(byte).map_err(Error)
     }
 
+    /// Write out any pending run of repeated bytes.
+    ///
+    /// A run is encoded as the byte, followed by a `*`, followed by the number
+    /// of _additional_ repeats + 29 (as a printable ASCII character). Since
+    /// encoding a run takes 3 chars, only runs of 4+ bytes are encoded.
+    fn flush_rle(&mut self) -> Result<(), Error<C::Error>> {
+        let byte = self.rle_char;
+        let mut remaining = self.rle_len;
+        self.rle_len = 0;
+
+        while remaining != 0 {
+            self.write_raw(byte)?;
+            remaining -= 1;
+
+            if remaining >= 3 {
+                // the largest repeat count is 97 ('~'), and the repeat count
+                // must never produce '#' (6), '$' (7), or '}' (96)
+                let repeat = match remaining.min(97) {
+                    6 | 7 => 5,
+                    96 => 95,
+                    n => n,
+                };
+                self.write_raw(b'*')?;
+                self.write_raw(repeat as u8 + 29)?;
+                remaining -= repeat;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write an entire buffer over the connection.
     pub fn write_all(&mut self, data: &[u8]) -> Result<(), Error<C::Error>> {
         data.iter().try_for_each(|b| self.write(*b))
@@ -84,12 +265,7 @@ impl<'a, C: Connection + 'a> ResponseWriter<'a, C> {
     /// Write a single byte as a hex string (two ascii chars)
     fn write_hex(&mut self, byte: u8) -> Result<(), Error<C::Error>> {
         for digit in [(byte & 0xf0) >> 4, byte & 0x0f].iter() {
-            let c = match digit {
-                0..=9 => b'0' + digit,
-                10..=15 => b'a' + digit - 10,
-                _ => unreachable!(),
-            };
-            self.write(c)?;
+            self.write(hex_digit(*digit))?;
         }
         Ok(())
     }
@@ -146,3 +322,112 @@ impl<'a, C: Connection + 'a> ResponseWriter<'a, C> {
         Ok(())
     }
 }
+
+fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        0..=9 => b'0' + digit,
+        10..=15 => b'a' + digit - 10,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct VecConnection(Vec<u8>);
+
+    impl Connection for VecConnection {
+        type Error = ();
+
+        fn read(&mut self) -> Result<u8, ()> {
+            Err(())
+        }
+
+        fn write(&mut self, byte: u8) -> Result<(), ()> {
+            self.0.push(byte);
+            Ok(())
+        }
+
+        fn peek(&mut self) -> Result<Option<u8>, ()> {
+            Ok(None)
+        }
+
+        fn flush(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    fn encode(s: &str) -> String {
+        let mut conn = VecConnection(Vec::new());
+        let mut res = ResponseWriter::new(&mut conn, true);
+        res.write_str(s).unwrap();
+        res.flush().unwrap();
+
+        // strip the leading '$' and trailing checksum
+        let out = String::from_utf8(conn.0).unwrap();
+        out[1..out.len() - 3].into()
+    }
+
+    /// Expand a run-length encoded string, as GDB would.
+    fn decode(s: &str) -> String {
+        let mut out = Vec::new();
+        let mut bytes = s.bytes();
+        while let Some(b) = bytes.next() {
+            if b == b'*' {
+                let repeat = bytes.next().unwrap() - 29;
+                let last = *out.last().unwrap();
+                out.extend(core::iter::repeat_n(last, repeat as usize));
+            } else {
+                out.push(b);
+            }
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn gdb_docs_example() {
+        // from the GDB docs: "0* " is a run-length encoding of "0000"
+        assert_eq!(encode("0000"), "0* ");
+    }
+
+    #[test]
+    fn short_runs_are_not_encoded() {
+        assert_eq!(encode("abc000def"), "abc000def");
+    }
+
+    #[test]
+    fn avoids_special_repeat_chars() {
+        for len in 1..=300 {
+            let s = "0".repeat(len);
+            let encoded = encode(&s);
+            assert!(!encoded.contains(['#', '$', '}']), "{} -> {}", len, encoded);
+            assert_eq!(decode(&encoded), s);
+        }
+
+        // runs of 7 + 8 chars must be split, as '#' + '$' are reserved
+        assert_eq!(encode("0000000"), "0*\"0");
+        assert_eq!(encode("00000000"), "0*\"00");
+    }
+
+    #[test]
+    fn checksum_covers_encoded_data() {
+        let mut conn = VecConnection(Vec::new());
+        let mut res = ResponseWriter::new(&mut conn, true);
+        res.write_str("0000").unwrap();
+        res.flush().unwrap();
+
+        let checksum = b"0* ".iter().fold(0u8, |a, b| a.wrapping_add(*b));
+        let expected = format!("$0* #{:02x}", checksum);
+        assert_eq!(conn.0, expected.as_bytes());
+    }
+
+    #[test]
+    fn disabled() {
+        let mut conn = VecConnection(Vec::new());
+        let mut res = ResponseWriter::new(&mut conn, false);
+        res.write_str("0000").unwrap();
+        res.flush().unwrap();
+        assert_eq!(conn.0, b"$0000#c0");
+    }
+}
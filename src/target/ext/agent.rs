@@ -0,0 +1,439 @@
+//! Evaluate GDB [Agent Expressions](https://sourceware.org/gdb/current/onlinedocs/gdb/Agent-Expressions.html).
+//!
+//! Agent expressions are a simple stack-based bytecode, which GDB uses to
+//! offload work to the target (e.g: evaluating breakpoint conditions without
+//! having to stop and report back to GDB each time the breakpoint is hit).
+//!
+//! This module provides [`evaluate`], a small interpreter which supports the
+//! subset of the bytecode GDB emits for common expressions: constants,
+//! register reads, memory loads, integer arithmetic, comparisons, and
+//! branches. Opcodes related to tracing, trace state variables, floating
+//! point, and `printf` are not supported, and result in an
+//! [`AgentError::Unsupported`] error.
+//!
+//! Targets are free to ignore this interpreter entirely, and handle the raw
+//! bytecode themselves (e.g: by JIT-compiling it).
+
+/// An iterator over a list of agent expressions (as raw bytecode).
+///
+/// Returned by `gdbstub` when GDB attaches one or more agent expressions to
+/// a packet (e.g: breakpoint conditions).
+#[derive(Debug, Clone, Copy)]
+pub struct AgentExprs<'a> {
+    // a packed list of `[len: u16 (big endian)][bytecode; len]` entries
+    buf: &'a [u8],
+}
+
+impl<'a> AgentExprs<'a> {
+    /// `buf` must be a packed list of `[len: u16 (BE)][bytecode; len]`
+    /// entries.
+    pub(crate) fn new(buf: &'a [u8]) -> AgentExprs<'a> {
+        AgentExprs { buf }
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl<'a> Iterator for AgentExprs<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.buf.len() < 2 {
+            return None;
+        }
+
+        let len = u16::from_be_bytes([self.buf[0], self.buf[1]]) as usize;
+        let (bytecode, rest) = self.buf[2..].split_at(len);
+        self.buf = rest;
+        Some(bytecode)
+    }
+}
+
+/// Provides access to the target's state during agent expression evaluation.
+pub trait AgentContext {
+    /// Read the value of the register with the given GDB register number
+    /// (i.e: the same numbering used by the `p` / `P` packets).
+    ///
+    /// Return `None` if the register doesn't exist / cannot be read.
+    fn read_register(&mut self, regno: u16) -> Option<u64>;
+
+    /// Read `buf.len()` bytes of target memory, starting at `addr`.
+    ///
+    /// Return `false` if the memory could not be read.
+    fn read_memory(&mut self, addr: u64, buf: &mut [u8]) -> bool;
+
+    /// Whether multi-byte memory loads should be decoded as big-endian values.
+    ///
+    /// Defaults to `false` (i.e: little-endian).
+    fn big_endian(&self) -> bool {
+        false
+    }
+}
+
+/// An error encountered while evaluating an agent expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentError {
+    /// The bytecode contains an invalid opcode.
+    InvalidOpcode(u8),
+    /// The bytecode contains an opcode which isn't supported by the
+    /// interpreter.
+    Unsupported(u8),
+    /// The bytecode ended without an `end` opcode (or mid-instruction).
+    UnexpectedEnd,
+    /// A jump targeted an out-of-bounds offset.
+    InvalidJump(u16),
+    /// The expression pushed too many values onto the stack.
+    StackOverflow,
+    /// The expression tried to pop a value off an empty stack.
+    StackUnderflow,
+    /// The expression tried to divide by zero.
+    DivideByZero,
+    /// The expression tried to read a register which couldn't be read.
+    InvalidRegister(u16),
+    /// The expression tried to read memory which couldn't be read.
+    MemoryFault(u64),
+    /// The expression executed too many instructions (i.e: it likely contains
+    /// an infinite loop).
+    TooManyInstructions,
+}
+
+/// The maximum number of values on the interpreter's stack.
+pub const STACK_SIZE: usize = 64;
+
+/// The maximum number of instructions a single expression may execute.
+pub const MAX_INSTRUCTIONS: usize = 0x10000;
+
+struct Stack {
+    vals: [u64; STACK_SIZE],
+    len: usize,
+}
+
+impl Stack {
+    fn push(&mut self, val: u64) -> Result<(), AgentError> {
+        let slot = self
+            .vals
+            .get_mut(self.len)
+            .ok_or(AgentError::StackOverflow)?;
+        *slot = val;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<u64, AgentError> {
+        if self.len == 0 {
+            return Err(AgentError::StackUnderflow);
+        }
+        self.len -= 1;
+        Ok(self.vals[self.len])
+    }
+
+    /// Returns the value `n` entries from the top of the stack.
+    fn peek(&self, n: usize) -> Result<u64, AgentError> {
+        if n >= self.len {
+            return Err(AgentError::StackUnderflow);
+        }
+        Ok(self.vals[self.len - 1 - n])
+    }
+}
+
+fn sign_extend(val: u64, bits: u8) -> u64 {
+    if bits == 0 || bits >= 64 {
+        return val;
+    }
+    let shift = 64 - bits as u32;
+    (((val << shift) as i64) >> shift) as u64
+}
+
+fn zero_extend(val: u64, bits: u8) -> u64 {
+    if bits >= 64 {
+        return val;
+    }
+    val & ((1 << bits) - 1)
+}
+
+/// Evaluate the agent expression `bytecode`, returning the value at the top of
+/// the stack once the expression executes an `end` opcode.
+///
+/// When used as a breakpoint condition, the condition is considered to be
+/// `true` if the returned value is nonzero.
+pub fn evaluate(bytecode: &[u8], ctx: &mut dyn AgentContext) -> Result<u64, AgentError> {
+    let mut stack = Stack {
+        vals: [0; STACK_SIZE],
+        len: 0,
+    };
+    let mut pc = 0;
+
+    let operand = |pc: &mut usize, len: usize| -> Result<u64, AgentError> {
+        let bytes = bytecode
+            .get(*pc..*pc + len)
+            .ok_or(AgentError::UnexpectedEnd)?;
+        *pc += len;
+        Ok(bytes.iter().fold(0, |acc, b| (acc << 8) | *b as u64))
+    };
+
+    for _ in 0..MAX_INSTRUCTIONS {
+        let op = *bytecode.get(pc).ok_or(AgentError::UnexpectedEnd)?;
+        pc += 1;
+
+        match op {
+            // add, sub, mul
+            0x02 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a.wrapping_add(b))?
+            }
+            0x03 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a.wrapping_sub(b))?
+            }
+            0x04 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a.wrapping_mul(b))?
+            }
+            // div_signed, div_unsigned, rem_signed, rem_unsigned
+            0x05..=0x08 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                if b == 0 {
+                    return Err(AgentError::DivideByZero);
+                }
+                let val = match op {
+                    0x05 => (a as i64).wrapping_div(b as i64) as u64,
+                    0x06 => a / b,
+                    0x07 => (a as i64).wrapping_rem(b as i64) as u64,
+                    _ => a % b,
+                };
+                stack.push(val)?
+            }
+            // lsh, rsh_signed, rsh_unsigned
+            0x09 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(if b < 64 { a << b } else { 0 })?
+            }
+            0x0a => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(((a as i64) >> b.min(63)) as u64)?
+            }
+            0x0b => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(if b < 64 { a >> b } else { 0 })?
+            }
+            // log_not
+            0x0e => {
+                let a = stack.pop()?;
+                stack.push((a == 0) as u64)?
+            }
+            // bit_and, bit_or, bit_xor
+            0x0f => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a & b)?
+            }
+            0x10 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a | b)?
+            }
+            0x11 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(a ^ b)?
+            }
+            // bit_not
+            0x12 => {
+                let a = stack.pop()?;
+                stack.push(!a)?
+            }
+            // equal, less_signed, less_unsigned
+            0x13 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push((a == b) as u64)?
+            }
+            0x14 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(((a as i64) < (b as i64)) as u64)?
+            }
+            0x15 => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push((a < b) as u64)?
+            }
+            // ext n
+            0x16 => {
+                let bits = operand(&mut pc, 1)? as u8;
+                let a = stack.pop()?;
+                stack.push(sign_extend(a, bits))?
+            }
+            // ref8, ref16, ref32, ref64
+            0x17..=0x1a => {
+                let size = 1 << (op - 0x17);
+                let addr = stack.pop()?;
+                let mut buf = [0; 8];
+                let buf = &mut buf[..size];
+                if !ctx.read_memory(addr, buf) {
+                    return Err(AgentError::MemoryFault(addr));
+                }
+                let val = if ctx.big_endian() {
+                    buf.iter().fold(0, |acc, b| (acc << 8) | *b as u64)
+                } else {
+                    buf.iter().rev().fold(0, |acc, b| (acc << 8) | *b as u64)
+                };
+                stack.push(val)?
+            }
+            // if_goto, goto
+            0x20 | 0x21 => {
+                let target = operand(&mut pc, 2)? as u16;
+                let taken = op == 0x21 || stack.pop()? != 0;
+                if taken {
+                    if target as usize >= bytecode.len() {
+                        return Err(AgentError::InvalidJump(target));
+                    }
+                    pc = target as usize;
+                }
+            }
+            // const8, const16, const32, const64
+            0x22..=0x25 => {
+                let len = 1 << (op - 0x22);
+                let val = operand(&mut pc, len)?;
+                stack.push(val)?
+            }
+            // reg
+            0x26 => {
+                let regno = operand(&mut pc, 2)? as u16;
+                let val = ctx
+                    .read_register(regno)
+                    .ok_or(AgentError::InvalidRegister(regno))?;
+                stack.push(val)?
+            }
+            // end
+            0x27 => return stack.peek(0),
+            // dup, pop
+            0x28 => stack.push(stack.peek(0)?)?,
+            0x29 => {
+                stack.pop()?;
+            }
+            // zero_ext n
+            0x2a => {
+                let bits = operand(&mut pc, 1)? as u8;
+                let a = stack.pop()?;
+                stack.push(zero_extend(a, bits))?
+            }
+            // swap
+            0x2b => {
+                let (b, a) = (stack.pop()?, stack.pop()?);
+                stack.push(b)?;
+                stack.push(a)?
+            }
+            // pick n
+            0x32 => {
+                let n = operand(&mut pc, 1)?;
+                stack.push(stack.peek(n as usize)?)?
+            }
+            // rot
+            0x33 => {
+                let (c, b, a) = (stack.pop()?, stack.pop()?, stack.pop()?);
+                stack.push(c)?;
+                stack.push(a)?;
+                stack.push(b)?
+            }
+            // float, trace*, ref_float/double/long_double, l_to_d, d_to_l,
+            // getv, setv, tracev, tracenz, trace16, printf
+            0x01 | 0x0c | 0x0d | 0x1b..=0x1f | 0x2c..=0x30 | 0x34 => {
+                return Err(AgentError::Unsupported(op))
+            }
+            _ => return Err(AgentError::InvalidOpcode(op)),
+        }
+    }
+
+    Err(AgentError::TooManyInstructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Regs([u64; 4]);
+
+    impl AgentContext for Regs {
+        fn read_register(&mut self, regno: u16) -> Option<u64> {
+            self.0.get(regno as usize).copied()
+        }
+
+        fn read_memory(&mut self, addr: u64, buf: &mut [u8]) -> bool {
+            for (i, b) in buf.iter_mut().enumerate() {
+                *b = (addr as u8).wrapping_add(i as u8);
+            }
+            true
+        }
+    }
+
+    // reg 0; const8 5; equal; end
+    const REG0_EQ_5: &[u8] = &[0x26, 0x00, 0x00, 0x22, 0x05, 0x13, 0x27];
+
+    #[test]
+    fn reg_equals_const() {
+        assert_eq!(evaluate(REG0_EQ_5, &mut Regs([5, 0, 0, 0])), Ok(1));
+        assert_eq!(evaluate(REG0_EQ_5, &mut Regs([4, 0, 0, 0])), Ok(0));
+    }
+
+    #[test]
+    fn arithmetic_and_branches() {
+        // if (reg1 - 2 < 0) { 10 } else { 20 }, with signed comparison
+        let bytecode = &[
+            0x26, 0x00, 0x01, // reg 1
+            0x22, 0x02, // const8 2
+            0x03, // sub
+            0x22, 0x00, // const8 0
+            0x14, // less_signed
+            0x20, 0x00, 0x0f, // if_goto 15
+            0x22, 0x14, // const8 20
+            0x27, // end
+            0x22, 0x0a, // const8 10
+            0x27, // end
+        ];
+        assert_eq!(evaluate(bytecode, &mut Regs([0, 1, 0, 0])), Ok(10));
+        assert_eq!(evaluate(bytecode, &mut Regs([0, 3, 0, 0])), Ok(20));
+    }
+
+    #[test]
+    fn memory_loads() {
+        // ref16 at 0x10, little endian: 0x1110
+        let bytecode = &[0x22, 0x10, 0x18, 0x27];
+        assert_eq!(evaluate(bytecode, &mut Regs([0; 4])), Ok(0x1110));
+    }
+
+    #[test]
+    fn errors() {
+        let mut regs = Regs([0; 4]);
+        assert_eq!(
+            evaluate(&[0x13, 0x27], &mut regs),
+            Err(AgentError::StackUnderflow)
+        );
+        assert_eq!(
+            evaluate(&[0x22, 0x05], &mut regs),
+            Err(AgentError::UnexpectedEnd)
+        );
+        assert_eq!(
+            evaluate(&[0x26, 0x00, 0x09, 0x27], &mut regs),
+            Err(AgentError::InvalidRegister(9))
+        );
+        assert_eq!(
+            evaluate(&[0x22, 0x01, 0x22, 0x00, 0x05, 0x27], &mut regs),
+            Err(AgentError::DivideByZero)
+        );
+        assert_eq!(
+            evaluate(&[0x34], &mut regs),
+            Err(AgentError::Unsupported(0x34))
+        );
+        assert_eq!(
+            evaluate(&[0x21, 0x00, 0x00], &mut regs),
+            Err(AgentError::TooManyInstructions)
+        );
+    }
+
+    #[test]
+    fn expr_list() {
+        let packed = &[0x00, 0x02, 0xaa, 0xbb, 0x00, 0x01, 0xcc];
+        let mut exprs = AgentExprs::new(packed);
+        assert_eq!(exprs.next(), Some(&[0xaa, 0xbb][..]));
+        assert_eq!(exprs.next(), Some(&[0xcc][..]));
+        assert_eq!(exprs.next(), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Receive the set of operations GDB expects the target to permit.
+//!
+//! When debugging something which shouldn't be modified (e.g: a read-only
+//! crash dump), GDB uses the
+//! [`QAllow`](https://sourceware.org/gdb/current/onlinedocs/gdb/General-Query-Packets.html#index-QAllow-packet)
+//! packet to declare which operations it expects the target to allow.
+
+use core::ops::BitOr;
+
+use crate::target::{Target, TargetResult};
+
+/// A set of operations permitted by GDB, as sent via the `QAllow` packet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllowFlags(u8);
+
+impl AllowFlags {
+    /// Writing to registers.
+    pub const WRITE_REG: AllowFlags = AllowFlags(1 << 0);
+    /// Writing to memory.
+    pub const WRITE_MEM: AllowFlags = AllowFlags(1 << 1);
+    /// Inserting breakpoints.
+    pub const INSERT_BREAK: AllowFlags = AllowFlags(1 << 2);
+    /// Inserting tracepoints.
+    pub const INSERT_TRACE: AllowFlags = AllowFlags(1 << 3);
+    /// Inserting fast tracepoints.
+    pub const INSERT_FAST_TRACE: AllowFlags = AllowFlags(1 << 4);
+    /// Stopping the target.
+    pub const STOP: AllowFlags = AllowFlags(1 << 5);
+
+    /// Returns an empty set of flags (i.e: no operations are permitted).
+    pub const fn empty() -> AllowFlags {
+        AllowFlags(0)
+    }
+
+    /// Returns the raw value of the flags.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if all of the flags in `other` are set.
+    pub const fn contains(self, other: AllowFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Parse an operation name (as it appears in the `QAllow` packet), e.g:
+    /// `WriteReg`.
+    pub(crate) fn from_op_name(name: &[u8]) -> Option<AllowFlags> {
+        let flag = match name {
+            b"WriteReg" => AllowFlags::WRITE_REG,
+            b"WriteMem" => AllowFlags::WRITE_MEM,
+            b"InsertBreak" => AllowFlags::INSERT_BREAK,
+            b"InsertTrace" => AllowFlags::INSERT_TRACE,
+            b"InsertFastTrace" => AllowFlags::INSERT_FAST_TRACE,
+            b"Stop" => AllowFlags::STOP,
+            _ => return None,
+        };
+        Some(flag)
+    }
+}
+
+impl BitOr for AllowFlags {
+    type Output = AllowFlags;
+
+    fn bitor(self, rhs: AllowFlags) -> AllowFlags {
+        AllowFlags(self.0 | rhs.0)
+    }
+}
+
+/// Target Extension - Receive the set of operations GDB expects to be
+/// permitted.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait Allow: Target {
+    /// Record the set of operations GDB expects the target to permit.
+    ///
+    /// Operations which aren't included in `flags` should be treated as
+    /// disallowed. It's up to the target to decide how strictly to enforce
+    /// these permissions (if at all).
+    fn set_allowed(&mut self, flags: AllowFlags) -> TargetResult<(), Self>;
+}
+
+define_ext!(AllowOps, Allow);
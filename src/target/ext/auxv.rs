@@ -0,0 +1,23 @@
+//! Provide the target's ELF auxiliary vector.
+//!
+//! GDB uses the auxiliary vector to discover information about the running
+//! program (e.g: its entry point, or the system's page size).
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Provide the target's ELF auxiliary vector.
+///
+/// Corresponds to the `qXfer:auxv:read` command.
+pub trait Auxv: Target {
+    /// Read the target's raw auxiliary vector, starting at `offset`.
+    ///
+    /// The auxiliary vector is sent as-is (i.e: as a sequence of
+    /// `(a_type, a_val)` pairs, using the target's native word size and byte
+    /// order), and is _not_ encoded as XML.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    fn get_auxv(&self, offset: u64, length: usize, buf: &mut [u8]) -> TargetResult<usize, Self>;
+}
+
+define_ext!(AuxvOps, Auxv);
@@ -0,0 +1,45 @@
+//! Single-step whole instructions on targets which step at a finer
+//! granularity (e.g: a single CPU cycle).
+//!
+//! Cycle-accurate emulators often implement `ResumeAction::Step` as "execute
+//! a single cycle", which means a multi-cycle instruction may take several
+//! steps to retire. GDB's `stepi` expects every step to land on an instruction
+//! boundary, so targets which implement [`InstructionStep`] have each step
+//! requested by GDB synthesized by `gdbstub` as follows:
+//!
+//! 1. Resume the target using `ResumeAction::Step`.
+//! 2. If the target reports any stop reason other than `DoneStep` (e.g: a
+//!    breakpoint, or a signal), report it to GDB immediately.
+//! 3. Otherwise, ask the target whether it stopped on an instruction boundary
+//!    (via [`at_instruction_boundary`](InstructionStep::at_instruction_boundary)),
+//!    and keep stepping until it has.
+//!
+//! Any signal GDB requested be delivered alongside the step is only passed to
+//! the target's first step. If GDB interrupts the target part-way through an
+//! instruction, stepping stops immediately, and the interrupt is reported to
+//! GDB (i.e: as `ThreadStopReason::GdbInterrupt`).
+//!
+//! Interrupts which fire mid-instruction are up to the target to resolve: if
+//! taking the interrupt abandons the current instruction (e.g: by jumping to
+//! the interrupt vector), the target should report that it is on an
+//! instruction boundary once the vector has been entered.
+//!
+//! Targets which want to expose cycle-granularity stepping to the user (e.g:
+//! via a `monitor` command) can simply report `true` from
+//! `at_instruction_boundary` while cycle stepping is enabled.
+//!
+//! This extension is only used by single threaded targets.
+
+use crate::target::Target;
+
+/// Target Extension - Single-step whole instructions on targets which step at
+/// a finer granularity.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait InstructionStep: Target {
+    /// Whether the target is currently stopped on an instruction boundary
+    /// (i.e: the most recent step completed the in-flight instruction).
+    fn at_instruction_boundary(&mut self) -> Result<bool, Self::Error>;
+}
+
+define_ext!(InstructionStepOps, InstructionStep);
@@ -0,0 +1,88 @@
+//! Base operations required to debug any target (read/write memory/registers,
+//! step/resume, etc...)
+//!
+//! While not strictly required, it's recommended that single threaded targets
+//! implement the simplified `singlethread` API.
+
+use crate::common::Signal;
+
+pub mod instruction_step;
+pub mod multithread;
+pub mod non_stop;
+pub mod range_step;
+pub mod reverse_exec;
+pub mod single_step_via_breakpoints;
+pub mod singlethread;
+
+/// Base operations for single/multi threaded targets.
+pub enum BaseOps<'a, A, E> {
+    /// Single-threaded target
+    SingleThread(&'a mut dyn singlethread::SingleThreadOps<Arch = A, Error = E>),
+    /// Multi-threaded target
+    MultiThread(&'a mut dyn multithread::MultiThreadOps<Arch = A, Error = E>),
+}
+
+/// Describes how the target should be resumed.
+///
+/// If GDB requests that a signal be delivered to the target as it resumes
+/// (e.g: via the `signal SIGSEGV` command), the signal is included alongside
+/// the action. Otherwise, the signal is `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResumeAction {
+    /// Continue execution (until the next event occurs).
+    Continue(Option<Signal>),
+    /// Step forward a single instruction.
+    Step(Option<Signal>),
+}
+
+impl ResumeAction {
+    /// The signal to deliver to the target as it resumes (if any).
+    pub fn signal(self) -> Option<Signal> {
+        match self {
+            ResumeAction::Continue(sig) | ResumeAction::Step(sig) => sig,
+        }
+    }
+}
+
+/// Describes which [`ResumeAction`]s a target supports.
+///
+/// The supported actions are advertised to GDB in response to the `vCont?`
+/// packet, which GDB uses to decide how to implement commands such as `step`
+/// or `signal` (e.g: targets which can't single-step are stepped by GDB
+/// inserting temporary breakpoints instead). Continuing without a signal is
+/// always supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResumeCapabilities {
+    /// Single-step a single instruction (i.e: `ResumeAction::Step`).
+    pub step: bool,
+    /// Deliver a signal as the target resumes (i.e: `ResumeAction::Continue(Some(_))`,
+    /// and `ResumeAction::Step(Some(_))` if `step` is also supported).
+    ///
+    /// GDB refuses to use `vCont` unless resuming with a signal is advertised,
+    /// so it's always advertised. Instead, if this is `false`, GDB's requests
+    /// to resume with a signal are rejected with an error.
+    pub signals: bool,
+}
+
+impl ResumeCapabilities {
+    /// Every resume action is supported.
+    pub const ALL: ResumeCapabilities = ResumeCapabilities {
+        step: true,
+        signals: true,
+    };
+
+    /// Only continuing (without a signal) is supported.
+    pub const CONTINUE_ONLY: ResumeCapabilities = ResumeCapabilities {
+        step: false,
+        signals: false,
+    };
+
+    /// Check if `action` is supported.
+    pub fn supports(self, action: ResumeAction) -> bool {
+        let step_ok = match action {
+            ResumeAction::Continue(_) => true,
+            ResumeAction::Step(_) => self.step,
+        };
+        step_ok && (action.signal().is_none() || self.signals)
+    }
+}
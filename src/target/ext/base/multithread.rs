@@ -0,0 +1,545 @@
+//! Base debugging operations for multi threaded targets.
+
+use crate::arch::Arch;
+use crate::common::*;
+use crate::target::ext::breakpoints::WatchKind;
+use crate::target::ext::host_syscall::HostSyscallRequest;
+use crate::target::{Target, TargetResult};
+
+// Convenient re-exports
+pub use super::{ResumeAction, ResumeCapabilities};
+pub use crate::protocol::ConsoleOutput;
+
+/// Selects a thread corresponding to a ResumeAction.
+// NOTE: this is a subset of the internal `IdKind` type, albeit without an `Any` variant. Selecting
+// `Any` thread is something that's handled by `gdbstub` internally, and shouldn't be exposed to the
+// end user.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TidSelector {
+    /// Thread with a specific ID.
+    WithID(Tid),
+    /// All (other) threads belonging to the process with the specified ID.
+    ///
+    /// Only used when GDB's multiprocess extensions are enabled (see
+    /// [`ExtendedMode::supports_multiprocess`](crate::target::ext::extended_mode::ExtendedMode::supports_multiprocess)).
+    Process(Pid),
+    /// All (other) threads.
+    All,
+}
+
+/// Base debugging operations for multi threaded targets.
+///
+/// The `tid` passed to register and memory accessors is the thread most
+/// recently selected by GDB via `Hg` (or the thread which reported the last
+/// stop, if GDB hasn't selected one since).
+#[allow(clippy::type_complexity)]
+pub trait MultiThreadOps: Target {
+    /// Resume execution on the target.
+    ///
+    /// `actions` is an iterator over `(TidSelector, ResumeAction)` pairs which
+    /// specify how various threads should be resumed (i.e: single-step vs.
+    /// resume, and which signal to deliver, if any). It is _guaranteed_ to contain at least one action. It is not
+    /// guaranteed to be exhaustive over all live threads, and any threads
+    /// without a corresponding `TidSelector` should be left in the same state
+    /// (if possible).
+    ///
+    /// Selectors may overlap, in which case each thread is resumed according
+    /// to the _first_ action which selects it. e.g: `vCont;s:2;c` (i.e:
+    /// `[(WithID(2), Step), (All, Continue)]`) steps thread 2, while
+    /// continuing every other thread. [`Actions::resolve`] can be used to
+    /// work out which action applies to each thread.
+    ///
+    /// When the `alloc` feature is enabled, actions which select a specific
+    /// thread that is no longer alive (as reported by `is_thread_alive`) are
+    /// dropped before the target is resumed. If _every_ action selected a dead
+    /// thread, the target isn't resumed at all, and GDB is sent an error.
+    ///
+    /// The `check_gdb_interrupt` callback can be invoked to check if GDB sent
+    /// an Interrupt packet (i.e: the user pressed Ctrl-C). It's recommended to
+    /// invoke this callback every-so-often while the system is running (e.g:
+    /// every X cycles/milliseconds). Periodically checking for incoming
+    /// interrupt packets is _not_ required, but it is _recommended_.
+    ///
+    /// Once `check_gdb_interrupt` returns `true`, the interrupt has been
+    /// consumed, and the target should halt as soon as possible and return
+    /// [`ThreadStopReason::GdbInterrupt`] (or a more specific stop reason, e.g:
+    /// `DoneStep` if a single-step happened to complete in the meantime).
+    /// Targets may also choose to ignore the interrupt and keep running, in
+    /// which case subsequent calls return `false` (until GDB sends another
+    /// interrupt), and GDB is only notified once the target stops for some
+    /// other reason.
+    ///
+    /// # Implementation requirements
+    ///
+    /// These requirements cannot be satisfied by `gdbstub` internally, and must
+    /// be handled on a per-target basis.
+    ///
+    /// ### Adjusting PC after a breakpoint is hit
+    ///
+    /// The [GDB remote serial protocol documentation](https://sourceware.org/gdb/current/onlinedocs/gdb/Stop-Reply-Packets.html#swbreak-stop-reason)
+    /// notes the following:
+    ///
+    /// > On some architectures, such as x86, at the architecture level, when a
+    /// > breakpoint instruction executes the program counter points at the
+    /// > breakpoint address plus an offset. On such targets, the stub is
+    /// > responsible for adjusting the PC to point back at the breakpoint
+    /// > address.
+    ///
+    /// Omitting PC adjustment may result in unexpected execution flow and/or
+    /// breakpoints not appearing to work correctly.
+    ///
+    /// # Additional Considerations
+    ///
+    /// ### "Non-stop" mode
+    ///
+    /// By default, `gdbstub` uses GDB's
+    /// ["All-Stop" mode](https://sourceware.org/gdb/current/onlinedocs/gdb/All_002dStop-Mode.html),
+    /// whereby _all_ threads should be stopped when returning from `resume`
+    /// (not just the thread associated with the `ThreadStopReason`).
+    ///
+    /// Targets which support stopping/resuming individual threads can also
+    /// implement the [`NonStopMode`](super::non_stop::NonStopMode) extension.
+    ///
+    /// ### Bare-Metal Targets
+    ///
+    /// On bare-metal targets (such as microcontrollers or emulators), it's
+    /// common to treat individual _CPU cores_ as a separate "threads". e.g:
+    /// in a dual-core system, [CPU0, CPU1] might be mapped to [TID1, TID2]
+    /// (note that TIDs cannot be zero).
+    ///
+    /// In this case, the `Tid` argument of `read/write_addrs` becomes quite
+    /// relevant, as different cores may have different memory maps.
+    fn resume(
+        &mut self,
+        actions: Actions<'_>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+
+    /// (optional) Resume execution on the target, while being able to send
+    /// output to the GDB console (via `O` packets) as the target runs.
+    ///
+    /// See
+    /// [`SingleThreadOps::resume_with_console_output`](super::singlethread::SingleThreadOps::resume_with_console_output)
+    /// for more details.
+    fn resume_with_console_output(
+        &mut self,
+        actions: Actions<'_>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        console: ConsoleOutput<'_>,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error> {
+        let _ = console;
+        self.resume(actions, check_gdb_interrupt)
+    }
+
+    /// (optional) Detach from the target, leaving it running.
+    ///
+    /// Called when GDB ends the debugging session using a `D` packet (i.e:
+    /// the `detach` command). Unlike `kill`, detaching should _not_ halt the
+    /// target: once this method returns, `gdbstub` replies `OK`, and
+    /// `GdbStub::run` returns `DisconnectReason::Disconnect`, at which point
+    /// the target is expected to keep running without the debugger attached.
+    ///
+    /// GDB removes any breakpoints / watchpoints it inserted _before_ sending
+    /// `D`, so most targets don't need to do anything here. Targets which
+    /// hold extra debugger-only state (e.g: hardware debug registers, or a
+    /// "halted by debugger" flag) should release it.
+    ///
+    /// Returning a non-fatal error reports the failure to GDB, and keeps the
+    /// debugging session open.
+    ///
+    /// The default implementation does nothing.
+    fn detach(&mut self) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    /// (optional) Report which resume actions the target supports.
+    ///
+    /// `gdbstub` only advertises (and accepts) the reported actions, so GDB
+    /// will never ask the target to perform an unsupported action (e.g: a
+    /// target which returns [`ResumeCapabilities::CONTINUE_ONLY`] will never
+    /// be asked to single-step).
+    ///
+    /// By default, every action is supported.
+    fn resume_capabilities(&self) -> ResumeCapabilities {
+        ResumeCapabilities::ALL
+    }
+
+    /// Read the target's registers.
+    ///
+    /// If the registers could not be accessed, an appropriate non-fatal error
+    /// should be returned.
+    ///
+    /// `gdbstub` never caches register values: every `g` packet results in a
+    /// fresh call to this method (and every `p` packet in a fresh call to
+    /// [`read_register`](Self::read_register)), so registers modified
+    /// out-of-band (e.g: by a DMA engine) are always reported as-is.
+    ///
+    /// Note that GDB itself caches the registers it reads, and only discards
+    /// that cache once the target stops again (or when the user runs
+    /// `maint flush register-cache`).
+    ///
+    /// As such, there is deliberately no `invalidate_register_cache()`-style
+    /// hook. `gdbstub` has no register cache to invalidate (the expedited
+    /// registers included in stop replies are also read fresh on every stop),
+    /// and the remote protocol offers no way for a stub to tell GDB that its
+    /// own cache is stale.
+    fn read_registers(
+        &mut self,
+        regs: &mut <Self::Arch as Arch>::Registers,
+        tid: Tid,
+    ) -> TargetResult<(), Self>;
+
+    /// Write the target's registers.
+    ///
+    /// If the registers could not be accessed, an appropriate non-fatal error
+    /// should be returned.
+    fn write_registers(
+        &mut self,
+        regs: &<Self::Arch as Arch>::Registers,
+        tid: Tid,
+    ) -> TargetResult<(), Self>;
+
+    /// Read to a single register on the target.
+    ///
+    /// Implementations should write the value of the register using target's
+    /// native byte order in the buffer `dst`, returning the number of bytes
+    /// written.
+    ///
+    /// `dst` is sized according to the register size reported by the
+    /// architecture's `RegId` implementation. For fixed-size registers, the
+    /// entire buffer should be filled. Variable-sized registers (e.g: AArch64
+    /// SVE vector registers) report their _maximum_ size, and should only
+    /// write as many bytes as the register's current size.
+    ///
+    /// If the requested register could not be accessed, an appropriate
+    /// non-fatal error should be returned.
+    ///
+    /// _Note:_ This method includes a stubbed default implementation which
+    /// simply returns a zeroed-out `dst`. This is due to the fact that several
+    /// built-in `arch` implementations haven't been updated with proper
+    /// `RegId` implementations.
+    fn read_register(
+        &mut self,
+        reg_id: <Self::Arch as Arch>::RegId,
+        dst: &mut [u8],
+        tid: Tid,
+    ) -> TargetResult<usize, Self> {
+        let _ = (reg_id, tid);
+        Ok(dst.len())
+    }
+
+    /// Write from a single register on the target.
+    ///
+    /// The `val` buffer contains the new value of the register in the target's
+    /// native byte order. It is guaranteed to be the exact length as the target
+    /// register.
+    ///
+    /// If the requested register could not be accessed, an appropriate
+    /// non-fatal error should be returned.
+    ///
+    /// _Note:_ This method includes a stubbed default implementation which
+    /// simply returns `Ok(())`. This is due to the fact that several built-in
+    /// `arch` implementations haven't been updated with proper `RegId`
+    /// implementations.
+    fn write_register(
+        &mut self,
+        reg_id: <Self::Arch as Arch>::RegId,
+        val: &[u8],
+        tid: Tid,
+    ) -> TargetResult<(), Self> {
+        let _ = (reg_id, val, tid);
+        Ok(())
+    }
+
+    /// Read bytes from the specified address range.
+    ///
+    /// If the requested address range could not be accessed (e.g: due to
+    /// MMU protection, unhanded page fault, etc...), an appropriate non-fatal
+    /// error should be returned.
+    fn read_addrs(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        data: &mut [u8],
+        tid: Tid,
+    ) -> TargetResult<(), Self>;
+
+    /// Write bytes to the specified address range.
+    ///
+    /// If the requested address range could not be accessed (e.g: due to
+    /// MMU protection, unhanded page fault, etc...), an appropriate non-fatal
+    /// error should be returned.
+    fn write_addrs(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        data: &[u8],
+        tid: Tid,
+    ) -> TargetResult<(), Self>;
+
+    /// List all currently active threads.
+    ///
+    /// Threads should be listed in a consistent order: when there are too many
+    /// threads to report to GDB in a single packet, this method is called once
+    /// per packet, with `gdbstub` skipping over any threads it already
+    /// reported.
+    ///
+    /// See [the section above](#bare-metal-targets) on implementing
+    /// thread-related methods on bare-metal (threadless) targets.
+    fn list_active_threads(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Tid),
+    ) -> Result<(), Self::Error>;
+
+    /// Check if the specified thread is alive.
+    ///
+    /// Corresponds to GDB's `T<tid>` packet, which GDB sends before operating
+    /// on a thread it isn't sure still exists. Dead threads are reported to GDB
+    /// as an `E00` error.
+    ///
+    /// As a convenience, this method provides a default implementation which
+    /// uses `list_active_threads` to do a linear-search through all active
+    /// threads. On thread-heavy systems, it may be more efficient
+    /// to override this method with a more direct query.
+    fn is_thread_alive(&mut self, tid: Tid) -> Result<bool, Self::Error> {
+        let mut found = false;
+        self.list_active_threads(&mut |active_tid| {
+            if tid == active_tid {
+                found = true;
+            }
+        })?;
+        Ok(found)
+    }
+
+    /// (optional) List all currently active threads, alongside the ID of the
+    /// process each thread belongs to.
+    ///
+    /// The reported pids are only sent to GDB when GDB's multiprocess
+    /// extensions are enabled (see
+    /// [`ExtendedMode::supports_multiprocess`](crate::target::ext::extended_mode::ExtendedMode::supports_multiprocess)),
+    /// in which case thread ids must be unique _across_ all processes.
+    ///
+    /// The default implementation reports all threads returned by
+    /// `list_active_threads` as belonging to a single process (with Pid 1).
+    fn list_active_threads_with_pid(
+        &mut self,
+        thread_is_active: &mut dyn FnMut(Pid, Tid),
+    ) -> Result<(), Self::Error> {
+        self.list_active_threads(&mut |tid| thread_is_active(crate::FAKE_PID, tid))
+    }
+
+    /// (optional) Return the ID of the process that the thread `tid` belongs
+    /// to.
+    ///
+    /// As a convenience, this method provides a default implementation which
+    /// uses `list_active_threads_with_pid` to do a linear-search through all
+    /// active threads (returning Pid 1 if the thread couldn't be found).
+    fn thread_pid(&mut self, tid: Tid) -> Result<Pid, Self::Error> {
+        let mut pid = crate::FAKE_PID;
+        self.list_active_threads_with_pid(&mut |active_pid, active_tid| {
+            if tid == active_tid {
+                pid = active_pid;
+            }
+        })?;
+        Ok(pid)
+    }
+}
+
+/// Describes why a thread stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ThreadStopReason<U> {
+    /// Completed the single-step request.
+    ///
+    /// If the stepped instruction also triggered some other event (e.g: a
+    /// watchpoint, or a signal), report that event instead. `gdbstub` reports
+    /// the returned stop reason as-is, regardless of how the target was
+    /// resumed.
+    DoneStep,
+    /// `check_gdb_interrupt` returned `true`
+    GdbInterrupt,
+    /// Halted
+    Halted,
+    /// A process exited with the given exit code.
+    ///
+    /// Reported to GDB as a `W` stop reply, which ends the debugging session
+    /// (unless the target supports extended mode, in which case GDB may
+    /// `run` a new process).
+    Exited {
+        /// Which process exited (targets which don't support multiple
+        /// processes should use Pid 1)
+        pid: Pid,
+        /// The process's exit code
+        code: u8,
+    },
+    /// A process was terminated by the given signal.
+    ///
+    /// Reported to GDB as an `X` stop reply. See [`ThreadStopReason::Exited`]
+    /// for more details.
+    Terminated {
+        /// Which process was terminated
+        pid: Pid,
+        /// The signal which terminated the process
+        signal: u8,
+    },
+    /// A thread hit a software breakpoint (e.g. due to a trap instruction).
+    ///
+    /// NOTE: This does not necessarily have to be a breakpoint configured by
+    /// the client/user of the current GDB session.
+    ///
+    /// The `swbreak` stop reason is only reported if GDB negotiated it via
+    /// `qSupported`. Otherwise, GDB receives a plain `SIGTRAP`.
+    SwBreak(Tid),
+    /// A thread hit a hardware breakpoint.
+    ///
+    /// As with `SwBreak`, the `hwbreak` stop reason is only reported if GDB
+    /// negotiated it via `qSupported`.
+    HwBreak(Tid),
+    /// A thread hit a watchpoint.
+    ///
+    /// Reported to GDB as a `watch`, `rwatch`, or `awatch` stop reason (for
+    /// `WatchKind::Write`, `Read`, and `ReadWrite` respectively), along with
+    /// the address of the watched memory.
+    Watch {
+        /// Which thread hit the watchpoint
+        tid: Tid,
+        /// Kind of watchpoint that was hit
+        kind: WatchKind,
+        /// Address of watched memory
+        addr: U,
+    },
+    /// The program received a signal
+    ///
+    /// Signal numbers are reported to GDB as-is, unless the target implements
+    /// the [`SignalMapping`](crate::target::ext::signal_mapping::SignalMapping)
+    /// extension.
+    Signal(u8),
+    /// A specific thread stopped with the given signal (e.g: a thread was
+    /// stopped via a non-stop mode `vCont;t` request, in which case `signal`
+    /// should be `0`).
+    ///
+    /// When running in non-stop mode, this can be used to report that a
+    /// single thread stopped while all other threads continue to run.
+    SignalWithThread {
+        /// Which thread stopped
+        tid: Tid,
+        /// The signal the thread stopped with
+        signal: u8,
+    },
+    /// A thread forked, creating a new child process.
+    ///
+    /// Only reported to GDB if the target implements the
+    /// [`CatchFork`](crate::target::ext::catch_fork::CatchFork) extension and
+    /// GDB enabled fork events. Otherwise, this is reported as a plain
+    /// `SIGTRAP`.
+    Fork {
+        /// Which thread forked
+        tid: Tid,
+        /// The Pid of the new child process
+        new_pid: Pid,
+        /// The Tid of the child process's initial thread
+        new_tid: Tid,
+    },
+    /// A thread vforked, creating a new child process.
+    ///
+    /// See [`ThreadStopReason::Fork`] for more details.
+    Vfork {
+        /// Which thread vforked
+        tid: Tid,
+        /// The Pid of the new child process
+        new_pid: Pid,
+        /// The Tid of the child process's initial thread
+        new_tid: Tid,
+    },
+    /// A thread which previously vforked has resumed after the child process
+    /// exec'd or exited.
+    VforkDone(Tid),
+    /// A new thread was created, and is stopped until GDB resumes it.
+    ///
+    /// Reported to GDB as a `create` stop reason if GDB enabled thread events
+    /// via `QThreadEvents`. Otherwise, this is reported as a plain `SIGTRAP`
+    /// on the new thread.
+    ThreadCreated(Tid),
+    /// A thread exited.
+    ///
+    /// Only reported to GDB if it enabled thread events via `QThreadEvents`.
+    /// Otherwise, `gdbstub` resumes the target again (using the same actions),
+    /// unless the exiting thread was the process' last thread (i.e: it's no
+    /// longer reported by `list_active_threads`), in which case it's reported as
+    /// a plain `SIGTRAP`.
+    ///
+    /// _Note:_ resuming the target again requires the `alloc` feature. Without
+    /// it, the exit is always reported as a plain `SIGTRAP`.
+    ThreadExited {
+        /// Which thread exited
+        tid: Tid,
+        /// The thread's exit status
+        exit_status: u8,
+    },
+    /// A thread requested that GDB perform a system call on the host.
+    ///
+    /// See [`StopReason::HostSyscall`](super::singlethread::StopReason::HostSyscall)
+    /// for more details.
+    HostSyscall {
+        /// Which thread requested the syscall
+        tid: Tid,
+        /// The syscall to perform
+        request: HostSyscallRequest<U>,
+    },
+}
+
+/// An iterator of `(TidSelector, ResumeAction)` used to specify how threads
+/// should be resumed when running in multi threaded mode. It is _guaranteed_ to
+/// contain at least one action.
+///
+/// See the documentation for
+/// [`Target::resume`](trait.Target.html#tymethod.resume) for more details.
+pub struct Actions<'a> {
+    inner: &'a mut dyn Iterator<Item = (TidSelector, ResumeAction)>,
+}
+
+impl core::fmt::Debug for Actions<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Actions {{ .. }}")
+    }
+}
+
+impl Actions<'_> {
+    pub(crate) fn new(iter: &mut dyn Iterator<Item = (TidSelector, ResumeAction)>) -> Actions<'_> {
+        Actions { inner: iter }
+    }
+}
+
+impl Actions<'_> {
+    /// Work out how each thread in `threads` should be resumed, following
+    /// GDB's precedence rules (i.e: each thread is resumed according to the
+    /// _first_ action which selects it).
+    ///
+    /// `threads` should contain one `(tid, None)` entry per thread. Once this
+    /// method returns, each entry's action has been filled in, with threads
+    /// which weren't selected by _any_ action left as `None` (which should
+    /// remain stopped).
+    ///
+    /// _Note:_ `TidSelector::Process` selectors select every thread in
+    /// `threads`. Targets which report multiple processes to GDB (see
+    /// [`ExtendedMode::supports_multiprocess`](crate::target::ext::extended_mode::ExtendedMode::supports_multiprocess))
+    /// should match those selectors against each thread's process manually.
+    pub fn resolve(self, threads: &mut [(Tid, Option<ResumeAction>)]) {
+        for (selector, action) in self {
+            for (tid, slot) in threads.iter_mut().filter(|(_, slot)| slot.is_none()) {
+                let selected = match selector {
+                    TidSelector::WithID(id) => id == *tid,
+                    TidSelector::Process(_) | TidSelector::All => true,
+                };
+                if selected {
+                    *slot = Some(action);
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Actions<'_> {
+    type Item = (TidSelector, ResumeAction);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
@@ -0,0 +1,97 @@
+//! Support for GDB's [Non-Stop Mode](https://sourceware.org/gdb/current/onlinedocs/gdb/Non_002dStop-Mode.html),
+//! whereby individual threads can be stopped and resumed while other threads
+//! continue to run.
+//!
+//! In non-stop mode, resuming the target no longer blocks until the target
+//! stops. Instead, `gdbstub` immediately acknowledges the resume request, and
+//! periodically polls the target for stop events, which are then reported to
+//! GDB using `%Stop` asynchronous notifications.
+
+use crate::arch::Arch;
+use crate::target::ext::base::multithread::{
+    Actions, MultiThreadOps, ThreadStopReason, TidSelector,
+};
+use crate::target::Target;
+
+/// Target Extension - Support
+/// [Non-Stop Mode](https://sourceware.org/gdb/current/onlinedocs/gdb/Non_002dStop-Mode.html).
+///
+/// Non-stop mode is only available on multi threaded targets.
+///
+/// Once GDB enables non-stop mode (via the `QNonStop:1` packet), `gdbstub`
+/// will stop using [`MultiThreadOps::resume`], and will instead use the
+/// non-blocking methods provided by this trait.
+#[allow(clippy::type_complexity)]
+pub trait NonStopMode: MultiThreadOps {
+    /// Resume execution on the specified threads, _without_ waiting for the
+    /// target to stop.
+    ///
+    /// `actions` is an iterator over `(TidSelector, ResumeAction)` pairs, with
+    /// the same semantics as the `actions` passed to
+    /// [`MultiThreadOps::resume`]. Unlike in all-stop mode, threads without a
+    /// corresponding `TidSelector` must be left in their current state (i.e:
+    /// running threads should keep running, and stopped threads should remain
+    /// stopped).
+    ///
+    /// Any stop events which occur as a result of resuming the target should
+    /// be reported via [`poll_stop_event`](NonStopMode::poll_stop_event).
+    fn resume_non_stop(&mut self, actions: Actions<'_>) -> Result<(), Self::Error>;
+
+    /// Stop the specified thread(s), without affecting any other threads.
+    ///
+    /// Called in response to both `vCont;t` actions and `vCtrlC` packets (the
+    /// latter stopping whichever threads GDB last selected via `Hc`, or all
+    /// threads by default).
+    ///
+    /// This method should not block until the thread(s) have stopped. Once a
+    /// thread has stopped, a [`ThreadStopReason::SignalWithThread`] with
+    /// `signal: 0` should be reported via
+    /// [`poll_stop_event`](NonStopMode::poll_stop_event).
+    ///
+    /// Stopping a thread that's already stopped should not report a stop
+    /// event.
+    fn stop_threads(&mut self, tid: TidSelector) -> Result<(), Self::Error>;
+
+    /// Wait for the next stop event on any running thread.
+    ///
+    /// Implementations should:
+    ///
+    /// - Immediately return `Ok(Some(stop_reason))` if there is a stop event
+    ///   that has yet to be reported.
+    /// - Immediately return `Ok(None)` if there are no running threads.
+    /// - Otherwise, continue running the target until either a thread stops
+    ///   (returning `Ok(Some(stop_reason))`), or until the `incoming_data`
+    ///   callback returns `true` (returning `Ok(None)`).
+    ///
+    /// The `incoming_data` callback checks if GDB has sent a packet which must
+    /// be handled. Much like the `check_gdb_interrupt` callback passed to
+    /// [`MultiThreadOps::resume`], it's recommended to invoke this callback
+    /// every-so-often while the target is running.
+    ///
+    /// Each returned stop reason should describe a _single_ thread that
+    /// stopped. The remaining threads should continue to run.
+    fn poll_stop_event(
+        &mut self,
+        incoming_data: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<ThreadStopReason<<Self::Arch as Arch>::Usize>>, Self::Error>;
+
+    /// Report the stop reason of every thread that's currently stopped.
+    ///
+    /// Called in response to the `?` packet, which GDB sends when it
+    /// (re)connects to a target in non-stop mode. Each stopped thread should
+    /// be reported by calling `thread_is_stopped` with the stop reason that
+    /// was last reported for that thread (e.g: a
+    /// [`ThreadStopReason::SignalWithThread`] with `signal: 0` for threads
+    /// stopped via [`stop_threads`](NonStopMode::stop_threads)). Running
+    /// threads must not be reported.
+    ///
+    /// Threads should be reported in a consistent order, as `gdbstub` may
+    /// call this method several times while reporting the stopped threads to
+    /// GDB.
+    fn list_stopped_threads(
+        &mut self,
+        thread_is_stopped: &mut dyn FnMut(ThreadStopReason<<Self::Arch as Arch>::Usize>),
+    ) -> Result<(), Self::Error>;
+}
+
+define_ext!(NonStopModeOps, NonStopMode);
@@ -0,0 +1,45 @@
+//! Step a thread until its PC leaves an address range (i.e: range stepping).
+//!
+//! When stepping over a source line (e.g: via GDB's `step` / `next`
+//! commands), GDB normally single-steps the target one instruction at a time,
+//! checking after each step whether the PC has left the line's address range.
+//! Range stepping moves this loop into the target, which can be _much_ faster
+//! (especially over slow connections).
+//!
+//! Range stepping is only supported in all-stop mode.
+
+use crate::arch::Arch;
+use crate::common::Tid;
+use crate::target::ext::base::multithread::{Actions, ThreadStopReason};
+use crate::target::Target;
+
+/// Target Extension - Step a thread until its PC leaves an address range.
+///
+/// Corresponds to the `vCont;r` action.
+pub trait RangeStep: Target {
+    /// Step thread `tid` once, and then keep stepping it for as long as its
+    /// PC remains within `start..end` (i.e: `start` inclusive, `end`
+    /// exclusive).
+    ///
+    /// Once the PC leaves the range, the target should stop and report
+    /// `ThreadStopReason::DoneStep`. Just like any other resume, the target
+    /// should also stop early if some other event occurs (e.g: a breakpoint
+    /// is hit), and should periodically check `check_gdb_interrupt`.
+    ///
+    /// Any other threads should be resumed according to `other_actions`. This
+    /// iterator may be empty (e.g: when GDB's `scheduler-locking` is on), in
+    /// which case all other threads should remain stopped.
+    ///
+    /// Single threaded targets are always passed `tid` 1, and can ignore
+    /// `other_actions`.
+    fn resume_range_step(
+        &mut self,
+        tid: Tid,
+        start: <Self::Arch as Arch>::Usize,
+        end: <Self::Arch as Arch>::Usize,
+        other_actions: Actions<'_>,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+}
+
+define_ext!(RangeStepOps, RangeStep);
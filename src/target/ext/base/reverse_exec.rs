@@ -0,0 +1,43 @@
+//! Support for reverse debugging (i.e: executing the target backwards).
+//!
+//! Targets which record their execution (e.g: emulators, or record / replay
+//! systems) can implement this extension to support GDB's `reverse-continue`
+//! and `reverse-step` / `reverse-stepi` commands.
+//!
+//! Reverse execution reports the same stop reasons as forward execution (e.g:
+//! hitting a breakpoint or watchpoint while executing backwards). Once the
+//! target runs out of recorded history, it should stop and report
+//! `ThreadStopReason::DoneStep` (which GDB reports as "reached the beginning
+//! of the recorded history").
+
+use crate::arch::Arch;
+use crate::common::Tid;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::Target;
+
+/// Target Extension - Execute the target in reverse.
+///
+/// Corresponds to the `bc` and `bs` commands.
+pub trait ReverseExec: Target {
+    /// Execute the target backwards until the next event occurs (e.g: a
+    /// breakpoint is hit, or the start of the recorded history is reached).
+    ///
+    /// Just like forward execution, implementations should periodically check
+    /// `check_gdb_interrupt`, and stop with `ThreadStopReason::GdbInterrupt`
+    /// if it returns `true`.
+    fn reverse_cont(
+        &mut self,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+
+    /// Step thread `tid` backwards by a single instruction.
+    ///
+    /// Single threaded targets are always passed `tid` 1.
+    fn reverse_step(
+        &mut self,
+        tid: Tid,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+}
+
+define_ext!(ReverseExecOps, ReverseExec);
@@ -0,0 +1,65 @@
+//! Emulate single-stepping using temporary breakpoints.
+//!
+//! Many simple targets can't single-step in hardware, but can set
+//! breakpoints. Instead of implementing true single-stepping, these targets
+//! can implement [`SingleStepViaBreakpoints`], and `gdbstub` will synthesize
+//! each step requested by GDB as follows:
+//!
+//! 1. Ask the target for every address the current instruction may transfer
+//!    control to (via [`next_pcs`](SingleStepViaBreakpoints::next_pcs)).
+//! 2. Insert a temporary breakpoint at each of those addresses.
+//! 3. Continue the target (until it hits one of the temporary breakpoints).
+//! 4. Remove the temporary breakpoints, and report the step as complete.
+//!
+//! The hard part is computing the possible next PCs, which is left up to the
+//! target. For most instructions, there is a single next PC (the following
+//! instruction), while conditional branches typically have two (the branch
+//! target, and the following instruction). It's fine to over-approximate the
+//! set of next PCs (e.g: when the branch condition can't easily be
+//! evaluated), but under-approximating it will result in the target running
+//! past the step!
+//!
+//! This extension is only used by single threaded targets which don't report
+//! [`ResumeCapabilities::step`](super::ResumeCapabilities::step) support.
+
+use crate::arch::Arch;
+use crate::target::Target;
+
+/// The maximum number of addresses which may be reported by
+/// [`SingleStepViaBreakpoints::next_pcs`].
+pub const MAX_NEXT_PCS: usize = 4;
+
+/// Target Extension - Emulate single-stepping using temporary breakpoints.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait SingleStepViaBreakpoints: Target {
+    /// Predict the address(es) execution may continue at once the
+    /// instruction at the current PC has executed, reporting each one via
+    /// `next_pc`.
+    ///
+    /// Duplicate addresses are ignored. Reporting more than [`MAX_NEXT_PCS`]
+    /// unique addresses results in a fatal `GdbStubError::TargetMismatch`.
+    fn next_pcs(
+        &mut self,
+        next_pc: &mut dyn FnMut(<Self::Arch as Arch>::Usize),
+    ) -> Result<(), Self::Error>;
+
+    /// Insert a temporary breakpoint at `addr`.
+    ///
+    /// Temporary breakpoints should be tracked separately from any breakpoints
+    /// set by GDB, as `gdbstub` may insert a temporary breakpoint at an address
+    /// which already has a regular breakpoint.
+    fn add_step_breakpoint(&mut self, addr: <Self::Arch as Arch>::Usize)
+        -> Result<(), Self::Error>;
+
+    /// Remove the temporary breakpoint at `addr`.
+    ///
+    /// Every temporary breakpoint which was successfully inserted is removed,
+    /// even if inserting a later one (or continuing the target) fails.
+    fn remove_step_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+    ) -> Result<(), Self::Error>;
+}
+
+define_ext!(SingleStepViaBreakpointsOps, SingleStepViaBreakpoints);
@@ -0,0 +1,303 @@
+//! Base debugging operations for single threaded targets.
+
+use crate::arch::Arch;
+use crate::common::Pid;
+use crate::target::ext::breakpoints::WatchKind;
+use crate::target::ext::host_syscall::HostSyscallRequest;
+use crate::target::{Target, TargetResult};
+
+// Convenient re-exports
+pub use super::{ResumeAction, ResumeCapabilities};
+pub use crate::protocol::ConsoleOutput;
+
+/// Base debugging operations for single threaded targets.
+#[allow(clippy::type_complexity)]
+pub trait SingleThreadOps: Target {
+    /// Resume execution on the target.
+    ///
+    /// `action` specifies how the target should be resumed (i.e:
+    /// single-step vs. full continue), and which signal (if any) should be
+    /// delivered to the target as it resumes.
+    ///
+    /// The `check_gdb_interrupt` callback can be invoked to check if GDB sent
+    /// an Interrupt packet (i.e: the user pressed Ctrl-C). It's recommended to
+    /// invoke this callback every-so-often while the system is running (e.g:
+    /// every X cycles/milliseconds). Periodically checking for incoming
+    /// interrupt packets is _not_ required, but it is _recommended_.
+    ///
+    /// Once `check_gdb_interrupt` returns `true`, the interrupt has been
+    /// consumed, and the target should halt as soon as possible and return
+    /// [`StopReason::GdbInterrupt`] (or a more specific stop reason, e.g:
+    /// `DoneStep` if a single-step happened to complete in the meantime).
+    /// Targets may also choose to ignore the interrupt and keep running, in
+    /// which case subsequent calls return `false` (until GDB sends another
+    /// interrupt), and GDB is only notified once the target stops for some
+    /// other reason.
+    ///
+    /// # Implementation requirements
+    ///
+    /// These requirements cannot be satisfied by `gdbstub` internally, and must
+    /// be handled on a per-target basis.
+    ///
+    /// ### Adjusting PC after a breakpoint is hit
+    ///
+    /// The [GDB remote serial protocol documentation](https://sourceware.org/gdb/current/onlinedocs/gdb/Stop-Reply-Packets.html#swbreak-stop-reason)
+    /// notes the following:
+    ///
+    /// > On some architectures, such as x86, at the architecture level, when a
+    /// > breakpoint instruction executes the program counter points at the
+    /// > breakpoint address plus an offset. On such targets, the stub is
+    /// > responsible for adjusting the PC to point back at the breakpoint
+    /// > address.
+    ///
+    /// Omitting PC adjustment may result in unexpected execution flow and/or
+    /// breakpoints not appearing to work correctly.
+    fn resume(
+        &mut self,
+        action: ResumeAction,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<StopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+
+    /// (optional) Resume execution on the target, while being able to send
+    /// output to the GDB console (via `O` packets) as the target runs.
+    ///
+    /// `gdbstub` always resumes the target via this method, whose default
+    /// implementation simply discards `console` and calls
+    /// [`resume`](Self::resume). Targets which want to surface output while
+    /// running (e.g: log messages) should override this method.
+    ///
+    /// Output is sent to GDB as soon as it's written to `console`, and is
+    /// always sent _before_ the stop reply.
+    fn resume_with_console_output(
+        &mut self,
+        action: ResumeAction,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+        console: ConsoleOutput<'_>,
+    ) -> Result<StopReason<<Self::Arch as Arch>::Usize>, Self::Error> {
+        let _ = console;
+        self.resume(action, check_gdb_interrupt)
+    }
+
+    /// (optional) Detach from the target, leaving it running.
+    ///
+    /// Called when GDB ends the debugging session using a `D` packet (i.e:
+    /// the `detach` command). Unlike `kill`, detaching should _not_ halt the
+    /// target: once this method returns, `gdbstub` replies `OK`, and
+    /// `GdbStub::run` returns `DisconnectReason::Disconnect`, at which point
+    /// the target is expected to keep running without the debugger attached.
+    ///
+    /// GDB removes any breakpoints / watchpoints it inserted _before_ sending
+    /// `D`, so most targets don't need to do anything here. Targets which
+    /// hold extra debugger-only state (e.g: hardware debug registers, or a
+    /// "halted by debugger" flag) should release it.
+    ///
+    /// Returning a non-fatal error reports the failure to GDB, and keeps the
+    /// debugging session open.
+    ///
+    /// The default implementation does nothing.
+    fn detach(&mut self) -> TargetResult<(), Self> {
+        Ok(())
+    }
+
+    /// (optional) Report which resume actions the target supports.
+    ///
+    /// `gdbstub` only advertises (and accepts) the reported actions, so GDB
+    /// will never ask the target to perform an unsupported action (e.g: a
+    /// target which returns [`ResumeCapabilities::CONTINUE_ONLY`] will never
+    /// be asked to single-step).
+    ///
+    /// By default, every action is supported.
+    fn resume_capabilities(&self) -> ResumeCapabilities {
+        ResumeCapabilities::ALL
+    }
+
+    /// Read the target's registers.
+    ///
+    /// `gdbstub` never caches register values: every `g` packet results in a
+    /// fresh call to this method (and every `p` packet in a fresh call to
+    /// [`read_register`](Self::read_register)), so registers modified
+    /// out-of-band (e.g: by a DMA engine) are always reported as-is.
+    ///
+    /// Note that GDB itself caches the registers it reads, and only discards
+    /// that cache once the target stops again (or when the user runs
+    /// `maint flush register-cache`).
+    ///
+    /// As such, there is deliberately no `invalidate_register_cache()`-style
+    /// hook. `gdbstub` has no register cache to invalidate (the expedited
+    /// registers included in stop replies are also read fresh on every stop),
+    /// and the remote protocol offers no way for a stub to tell GDB that its
+    /// own cache is stale.
+    fn read_registers(
+        &mut self,
+        regs: &mut <Self::Arch as Arch>::Registers,
+    ) -> TargetResult<(), Self>;
+
+    /// Write the target's registers.
+    fn write_registers(&mut self, regs: &<Self::Arch as Arch>::Registers)
+        -> TargetResult<(), Self>;
+
+    /// Read to a single register on the target.
+    ///
+    /// Implementations should write the value of the register using target's
+    /// native byte order in the buffer `dst`, returning the number of bytes
+    /// written.
+    ///
+    /// `dst` is sized according to the register size reported by the
+    /// architecture's `RegId` implementation. For fixed-size registers, the
+    /// entire buffer should be filled. Variable-sized registers (e.g: AArch64
+    /// SVE vector registers) report their _maximum_ size, and should only
+    /// write as many bytes as the register's current size.
+    ///
+    /// If the requested register could not be accessed, an appropriate
+    /// non-fatal error should be returned.
+    ///
+    /// _Note:_ This method includes a stubbed default implementation which
+    /// simply returns a zeroed-out `dst`. This is due to the fact that several
+    /// built-in `arch` implementations haven't been updated with proper
+    /// `RegId` implementations.
+    fn read_register(
+        &mut self,
+        reg_id: <Self::Arch as Arch>::RegId,
+        dst: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let _ = reg_id;
+        Ok(dst.len())
+    }
+
+    /// Write from a single register on the target.
+    ///
+    /// The `val` buffer contains the new value of the register in the target's
+    /// native byte order. It is guaranteed to be the exact length as the target
+    /// register.
+    ///
+    /// If the requested register could not be accessed, an appropriate
+    /// non-fatal error should be returned.
+    ///
+    /// _Note:_ This method includes a stubbed default implementation which
+    /// simply returns `Ok(())`. This is due to the fact that several built-in
+    /// `arch` implementations haven't been updated with proper `RegId`
+    /// implementations.
+    fn write_register(
+        &mut self,
+        reg_id: <Self::Arch as Arch>::RegId,
+        val: &[u8],
+    ) -> TargetResult<(), Self> {
+        let _ = (reg_id, val);
+        Ok(())
+    }
+
+    /// Read bytes from the specified address range.
+    ///
+    /// If the requested address range could not be accessed (e.g: due to
+    /// MMU protection, unhanded page fault, etc...), an appropriate
+    /// non-fatal error should be returned.
+    fn read_addrs(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        data: &mut [u8],
+    ) -> TargetResult<(), Self>;
+
+    /// Write bytes to the specified address range.
+    ///
+    /// If the requested address range could not be accessed (e.g: due to
+    /// MMU protection, unhanded page fault, etc...), an appropriate
+    /// non-fatal error should be returned.
+    fn write_addrs(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        data: &[u8],
+    ) -> TargetResult<(), Self>;
+}
+
+/// Describes why the target stopped.
+// NOTE: This is a simplified version of `multithread::ThreadStopReason` that omits any references
+// to Tid or threads. Internally, it is converted into multithread::ThreadStopReason.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StopReason<U> {
+    /// Completed the single-step request.
+    ///
+    /// If the stepped instruction also triggered some other event (e.g: a
+    /// watchpoint, or a signal), report that event instead. `gdbstub` reports
+    /// the returned stop reason as-is, regardless of how the target was
+    /// resumed.
+    DoneStep,
+    /// `check_gdb_interrupt` returned `true`
+    GdbInterrupt,
+    /// Halted
+    Halted,
+    /// The program exited with the given exit code.
+    ///
+    /// Reported to GDB as a `W` stop reply, which ends the debugging session
+    /// (unless the target supports extended mode, in which case GDB may
+    /// `run` a new process).
+    Exited {
+        /// The program's exit code
+        code: u8,
+    },
+    /// The program was terminated by the given signal.
+    ///
+    /// Reported to GDB as an `X` stop reply. See [`StopReason::Exited`] for
+    /// more details.
+    Terminated {
+        /// The signal which terminated the program
+        signal: u8,
+    },
+    /// Hit a software breakpoint (e.g. due to a trap instruction).
+    ///
+    /// NOTE: This does not necessarily have to be a breakpoint configured by
+    /// the client/user of the current GDB session.
+    ///
+    /// The `swbreak` stop reason is only reported if GDB negotiated it via
+    /// `qSupported`. Otherwise, GDB receives a plain `SIGTRAP`.
+    SwBreak,
+    /// Hit a hardware breakpoint.
+    ///
+    /// As with `SwBreak`, the `hwbreak` stop reason is only reported if GDB
+    /// negotiated it via `qSupported`.
+    HwBreak,
+    /// Hit a watchpoint.
+    ///
+    /// Reported to GDB as a `watch`, `rwatch`, or `awatch` stop reason (for
+    /// `WatchKind::Write`, `Read`, and `ReadWrite` respectively), along with
+    /// the address of the watched memory.
+    Watch {
+        /// Kind of watchpoint that was hit
+        kind: WatchKind,
+        /// Address of watched memory
+        addr: U,
+    },
+    /// The program received a signal
+    ///
+    /// Signal numbers are reported to GDB as-is, unless the target implements
+    /// the [`SignalMapping`](crate::target::ext::signal_mapping::SignalMapping)
+    /// extension.
+    Signal(u8),
+    /// The program forked, creating a new child process with the given Pid.
+    ///
+    /// Only reported to GDB if the target implements the
+    /// [`CatchFork`](crate::target::ext::catch_fork::CatchFork) extension and
+    /// GDB enabled fork events. Otherwise, this is reported as a plain
+    /// `SIGTRAP`.
+    Fork {
+        /// The Pid of the new child process
+        new_pid: Pid,
+    },
+    /// The program vforked, creating a new child process with the given Pid.
+    ///
+    /// See [`StopReason::Fork`] for more details.
+    Vfork {
+        /// The Pid of the new child process
+        new_pid: Pid,
+    },
+    /// The program previously vforked, and has resumed after the child
+    /// process exec'd or exited.
+    VforkDone,
+    /// The program requested that GDB perform a system call on the host.
+    ///
+    /// Requires the target to implement the
+    /// [`HostSyscall`](crate::target::ext::host_syscall::HostSyscall)
+    /// extension, which is used to deliver the syscall's result and resume
+    /// the target once GDB is done.
+    HostSyscall(HostSyscallRequest<U>),
+}
@@ -0,0 +1,288 @@
+//! Add/Remove various kinds of breakpoints.
+
+use crate::arch::Arch;
+use crate::target::ext::agent::AgentExprs;
+use crate::target::{Target, TargetResult};
+
+/// The kind of watchpoint that should be set/removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Fire when the memory location is written to.
+    Write,
+    /// Fire when the memory location is read from.
+    Read,
+    /// Fire when the memory location is written to and/or read from.
+    ReadWrite,
+}
+
+/// Target Extension - Set/remove Software Breakpoints.
+///
+/// See [this stackoverflow discussion](https://stackoverflow.com/questions/8878716/what-is-the-difference-between-hardware-and-software-breakpoints)
+/// about the differences between hardware and software breakpoints.
+///
+/// _Recommendation:_ If you're implementing `Target` for an emulator that's
+/// using an _interpreted_ CPU (as opposed to a JIT), the simplest way to
+/// implement "software" breakpoints would be to check the `PC` value after each
+/// CPU cycle.
+///
+/// When the `alloc` feature is enabled, `gdbstub` refcounts breakpoints
+/// inserted several times at the same address (with the same `kind`): only
+/// the first insertion calls `add_sw_breakpoint`, and `remove_sw_breakpoint`
+/// is only called once every insertion has been removed. The same applies to
+/// [`HwBreakpoint`].
+pub trait SwBreakpoint: Target {
+    /// Add a new software breakpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    ///
+    /// `kind` is an architecture-specific description of the breakpoint
+    /// (e.g: the width of the trap instruction to insert). See
+    /// [`Arch::BreakpointKind`] for more details.
+    fn add_sw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Remove an existing software breakpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn remove_sw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Support for target-side evaluation of software breakpoint conditions.
+    fn sw_breakpoint_conditions(&mut self) -> Option<SwBreakpointConditionsOps<'_, Self>> {
+        None
+    }
+
+    /// Support for target-side software breakpoint commands.
+    fn sw_breakpoint_commands(&mut self) -> Option<SwBreakpointCommandsOps<'_, Self>> {
+        None
+    }
+}
+
+define_ext!(SwBreakpointOps, SwBreakpoint);
+
+/// Nested Target Extension - Evaluate software breakpoint conditions on the
+/// target.
+///
+/// When implemented, GDB will attach its breakpoint conditions (as
+/// [agent expressions](crate::target::ext::agent)) to each new software
+/// breakpoint. The target should only report a stop at the breakpoint if
+/// _any_ of the conditions evaluates to a nonzero value, and should otherwise
+/// silently continue execution.
+///
+/// See [`agent::evaluate`](crate::target::ext::agent::evaluate) for a simple
+/// interpreter which can be used to evaluate the conditions.
+///
+/// If only [`HwBreakpointConditions`] is implemented, conditional software
+/// breakpoints are added via [`SwBreakpoint::add_sw_breakpoint`], and GDB
+/// evaluates their conditions itself.
+pub trait SwBreakpointConditions: SwBreakpoint {
+    /// Add a new conditional software breakpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    ///
+    /// If the same address already has a breakpoint, its conditions should be
+    /// replaced with `conds`.
+    fn add_sw_breakpoint_with_conds(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+        conds: AgentExprs<'_>,
+    ) -> TargetResult<bool, Self>;
+}
+
+define_ext!(SwBreakpointConditionsOps, SwBreakpointConditions);
+
+/// Nested Target Extension - Store software breakpoint commands on the
+/// target.
+///
+/// When implemented, GDB will attach its breakpoint command lists (as
+/// [agent expressions](crate::target::ext::agent)) to each new software
+/// breakpoint, and expects the target to run them whenever the breakpoint is
+/// hit. Since GDB no longer needs to re-send the commands with every resume,
+/// this also keeps `Z` packets small.
+///
+/// Targets which can't run the commands may simply store (or discard) them.
+pub trait SwBreakpointCommands: SwBreakpoint {
+    /// Attach a list of commands to the software breakpoint at `addr`, which
+    /// has just been added.
+    /// Return `Ok(false)` if the operation could not be completed.
+    ///
+    /// If `persist` is set, the commands should keep running even after GDB
+    /// disconnects.
+    ///
+    /// If the same address already has commands, they should be replaced with
+    /// `cmds`.
+    fn set_sw_breakpoint_cmds(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+        cmds: AgentExprs<'_>,
+        persist: bool,
+    ) -> TargetResult<bool, Self>;
+}
+
+define_ext!(SwBreakpointCommandsOps, SwBreakpointCommands);
+
+/// Target Extension - Set/remove Hardware Breakpoints.
+///
+/// See [this stackoverflow discussion](https://stackoverflow.com/questions/8878716/what-is-the-difference-between-hardware-and-software-breakpoints)
+/// about the differences between hardware and software breakpoints.
+///
+/// _Recommendation:_ If you're implementing `Target` for an emulator that's
+/// using an _interpreted_ CPU (as opposed to a JIT), there shouldn't be any
+/// reason to implement this extension (as software breakpoints are likely to be
+/// just-as-fast).
+pub trait HwBreakpoint: Target {
+    /// Add a new hardware breakpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn add_hw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Remove an existing hardware breakpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn remove_hw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Support for target-side evaluation of hardware breakpoint conditions.
+    fn hw_breakpoint_conditions(&mut self) -> Option<HwBreakpointConditionsOps<'_, Self>> {
+        None
+    }
+
+    /// Support for target-side hardware breakpoint commands.
+    fn hw_breakpoint_commands(&mut self) -> Option<HwBreakpointCommandsOps<'_, Self>> {
+        None
+    }
+}
+
+define_ext!(HwBreakpointOps, HwBreakpoint);
+
+/// Nested Target Extension - Evaluate hardware breakpoint conditions on the
+/// target.
+///
+/// Intended for cores whose breakpoint comparators can match on more than
+/// just the address (e.g: ARM context-ID matching), allowing the condition
+/// to be checked by the hardware itself.
+///
+/// See [`SwBreakpointConditions`] for more details.
+pub trait HwBreakpointConditions: HwBreakpoint {
+    /// Add a new conditional hardware breakpoint.
+    ///
+    /// Return `Ok(false)` if the hardware can't evaluate `conds`, in which
+    /// case `gdbstub` adds a plain hardware breakpoint instead (via
+    /// [`HwBreakpoint::add_hw_breakpoint`]). GDB re-checks breakpoint
+    /// conditions itself whenever the target stops, so they still apply.
+    ///
+    /// The same fallback is used for targets which don't implement this
+    /// trait, but advertise conditional breakpoint support via
+    /// [`SwBreakpointConditions`].
+    ///
+    /// If the same address already has a breakpoint, its conditions should be
+    /// replaced with `conds`.
+    fn add_hw_breakpoint_with_conds(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+        conds: AgentExprs<'_>,
+    ) -> TargetResult<bool, Self>;
+}
+
+define_ext!(HwBreakpointConditionsOps, HwBreakpointConditions);
+
+/// Nested Target Extension - Store hardware breakpoint commands on the
+/// target.
+///
+/// See [`SwBreakpointCommands`] for more details.
+pub trait HwBreakpointCommands: HwBreakpoint {
+    /// Attach a list of commands to the hardware breakpoint at `addr`, which
+    /// has just been added.
+    /// Return `Ok(false)` if the operation could not be completed.
+    ///
+    /// If `persist` is set, the commands should keep running even after GDB
+    /// disconnects.
+    ///
+    /// If the same address already has commands, they should be replaced with
+    /// `cmds`.
+    fn set_hw_breakpoint_cmds(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: <Self::Arch as Arch>::BreakpointKind,
+        cmds: AgentExprs<'_>,
+        persist: bool,
+    ) -> TargetResult<bool, Self>;
+}
+
+define_ext!(HwBreakpointCommandsOps, HwBreakpointCommands);
+
+/// Target Extension - Set/remove Hardware Watchpoints.
+///
+/// See the [GDB documentation](https://sourceware.org/gdb/current/onlinedocs/gdb/Set-Watchpoints.html)
+/// regarding watchpoints for how they're supposed to work.
+///
+/// _NOTE:_ If this extension isn't implemented, GDB will default to using
+/// _software watchpoints_, which tend to be excruciatingly slow (as
+/// they are implemented by single-stepping the system, and reading the
+/// watched memory location after each step).
+pub trait HwWatchpoint: Target {
+    /// Add a new hardware watchpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn add_hw_watchpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Remove an existing hardware watchpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn remove_hw_watchpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Support for masked hardware watchpoints.
+    fn hw_watchpoint_masked(&mut self) -> Option<HwWatchpointMaskedOps<'_, Self>> {
+        None
+    }
+}
+
+define_ext!(HwWatchpointOps, HwWatchpoint);
+
+/// Nested Target Extension - Set/remove masked Hardware Watchpoints.
+///
+/// A masked watchpoint fires on any access to an address `a` for which
+/// `a & mask == addr & mask` (e.g: a mask of `!0xf` watches the entire 16 byte
+/// block containing `addr`).
+///
+/// GDB requests a masked watchpoint by appending a `;<mask>` suffix to the
+/// `Z2` / `Z3` / `Z4` packets. Watchpoints without a mask are still set/removed
+/// via [`HwWatchpoint`].
+pub trait HwWatchpointMasked: HwWatchpoint {
+    /// Add a new masked hardware watchpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn add_hw_watchpoint_masked(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        mask: <Self::Arch as Arch>::Usize,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self>;
+
+    /// Remove an existing masked hardware watchpoint.
+    /// Return `Ok(false)` if the operation could not be completed.
+    fn remove_hw_watchpoint_masked(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        mask: <Self::Arch as Arch>::Usize,
+        kind: WatchKind,
+    ) -> TargetResult<bool, Self>;
+}
+
+define_ext!(HwWatchpointMaskedOps, HwWatchpointMasked);
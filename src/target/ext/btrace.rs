@@ -0,0 +1,81 @@
+//! Record the target's branch trace (i.e: GDB's `record btrace` command).
+//!
+//! When recording using branch tracing, GDB enables the target's branch trace
+//! hardware (e.g: Intel's Branch Trace Store or Processor Trace) on the
+//! current thread, and then periodically reads back the recorded trace as an
+//! XML document.
+//!
+//! See the [`btrace.dtd`](https://sourceware.org/git/?p=binutils-gdb.git;a=blob;f=gdb/features/btrace.dtd)
+//! file in the GDB source tree for the format of the returned XML.
+
+use crate::common::Tid;
+use crate::target::{Target, TargetResult};
+
+/// A branch trace format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BtraceFormat {
+    /// Branch Trace Store (`bts`).
+    Bts,
+    /// Intel Processor Trace (`pt`).
+    Pt,
+}
+
+/// Which portion of the branch trace GDB is requesting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BtraceReadKind {
+    /// The complete branch trace (`all`).
+    All,
+    /// The complete branch trace, but only if it has changed since the last
+    /// read (`new`). Return an empty trace if nothing has changed.
+    New,
+    /// Only the branch trace recorded since the last read (`delta`). If this
+    /// isn't possible (e.g: the trace buffer overflowed), return an error,
+    /// and GDB will fall back to reading the complete trace.
+    Delta,
+}
+
+/// Target Extension - Record the target's branch trace.
+///
+/// Corresponds to the `Qbtrace`, `Qbtrace-conf`, and `qXfer:btrace:read`
+/// commands.
+pub trait Btrace: Target {
+    /// Whether the target supports recording branch traces in `format`.
+    ///
+    /// Only supported formats are advertised to GDB.
+    fn supports_format(&self, format: BtraceFormat) -> bool;
+
+    /// Start recording a branch trace of thread `tid`, using `format`.
+    ///
+    /// Single threaded targets are always passed `tid` 1.
+    fn enable(&mut self, tid: Tid, format: BtraceFormat) -> TargetResult<(), Self>;
+
+    /// Stop recording the branch trace of thread `tid`.
+    fn disable(&mut self, tid: Tid) -> TargetResult<(), Self>;
+
+    /// Set the size (in bytes) of the buffer used to record `format` branch
+    /// traces.
+    ///
+    /// The new size applies to any branch traces enabled afterwards.
+    fn set_buffer_size(&mut self, format: BtraceFormat, size: u64) -> TargetResult<(), Self>;
+
+    /// Read the XML-encoded branch trace of thread `tid`, starting at `offset`.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// Large traces are read using several consecutive calls with increasing
+    /// `offset`s. Since `kind` refers to the trace as of the _first_ of these
+    /// calls (i.e: the one with `offset` 0), targets should snapshot the trace
+    /// document when `offset` is 0, and serve subsequent reads from that
+    /// snapshot.
+    fn read_btrace(
+        &mut self,
+        tid: Tid,
+        kind: BtraceReadKind,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(BtraceOps, Btrace);
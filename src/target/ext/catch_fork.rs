@@ -0,0 +1,26 @@
+//! Report fork / vfork events to GDB.
+//!
+//! Reporting fork events allows GDB to follow child processes (e.g: via
+//! `set follow-fork-mode child`), and enables the `catch fork` / `catch vfork`
+//! commands.
+//!
+//! Fork events are reported using the `Fork` / `Vfork` / `VforkDone` variants
+//! of [`StopReason`](crate::target::ext::base::singlethread::StopReason) and
+//! [`ThreadStopReason`](crate::target::ext::base::multithread::ThreadStopReason).
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Report fork / vfork events to GDB.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait CatchFork: Target {
+    /// Enable / disable reporting fork and vfork events.
+    ///
+    /// This is called when GDB connects, based on which events GDB declared
+    /// support for. Fork events should only be reported if they've been
+    /// enabled: `gdbstub` will report any disabled fork events as a plain
+    /// `SIGTRAP`.
+    fn set_fork_events(&mut self, fork: bool, vfork: bool) -> TargetResult<(), Self>;
+}
+
+define_ext!(CatchForkOps, CatchFork);
@@ -0,0 +1,36 @@
+//! Decide how to handle interrupts (i.e: GDB's Ctrl-C) which arrive while the
+//! target is stopped.
+//!
+//! Interrupts which arrive while the target is running are already surfaced
+//! to the target, via the `check_gdb_interrupt` callback passed to `resume`.
+//! The target can decide to stop (by returning `GdbInterrupt`, or any other
+//! stop reason), or to ignore the interrupt and continue running.
+//!
+//! By default, an interrupt which arrives while the target is stopped (e.g:
+//! due to a race between the target stopping and the user pressing Ctrl-C)
+//! is immediately answered with a `S05` stop reply. Implementing
+//! [`CtrlCInterrupt`] lets the target pick the stop reason which is reported
+//! instead, or ignore the interrupt altogether.
+
+use crate::arch::Arch;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::Target;
+
+/// Target Extension - Handle interrupts which arrive while the target is
+/// stopped.
+pub trait CtrlCInterrupt: Target {
+    /// Called when GDB sends an interrupt (i.e: a `0x03` byte) while the
+    /// target is stopped.
+    ///
+    /// Return `Some(stop_reason)` to report `stop_reason` to GDB, or `None` to
+    /// ignore the interrupt (in which case no reply is sent).
+    ///
+    /// Single threaded targets should report tid 1 in any stop reasons which
+    /// include a thread id.
+    #[allow(clippy::type_complexity)]
+    fn on_ctrl_c(
+        &mut self,
+    ) -> Result<Option<ThreadStopReason<<Self::Arch as Arch>::Usize>>, Self::Error>;
+}
+
+define_ext!(CtrlCInterruptOps, CtrlCInterrupt);
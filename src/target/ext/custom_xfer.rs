@@ -0,0 +1,78 @@
+//! Transfer target-defined objects using GDB's generic `qXfer` mechanism.
+//!
+//! `gdbstub` includes dedicated extensions for the `qXfer` objects it knows
+//! about (e.g: `auxv`, `memory-map`, `osdata`), but GDB (and other RSP
+//! clients) support many more: e.g: `spu`, `fdpic`, `traceframe-info`, or
+//! vendor-specific objects used by custom tooling.
+//!
+//! This extension lets a target register any number of additional objects
+//! (by name, i.e: the string after `qXfer:`), each of which may be readable
+//! and/or writable. `gdbstub` advertises each registered object via
+//! `qSupported` (e.g: `qXfer:spu:read+`), and routes matching `qXfer`
+//! requests to the target.
+//!
+//! Objects handled by one of `gdbstub`'s built-in extensions (e.g: `auxv`
+//! when the target implements [`Auxv`](super::auxv::Auxv)) take precedence
+//! over any custom object with the same name.
+
+use crate::target::{Target, TargetError, TargetResult};
+
+/// A custom `qXfer` object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CustomXferObject {
+    /// The object's name (e.g: `spu` for `qXfer:spu:read`).
+    pub name: &'static str,
+    /// Whether the object can be read (`qXfer:NAME:read`).
+    pub read: bool,
+    /// Whether the object can be written (`qXfer:NAME:write`).
+    pub write: bool,
+}
+
+/// Target Extension - Transfer target-defined `qXfer` objects.
+///
+/// Corresponds to the `qXfer:NAME:read` and `qXfer:NAME:write` commands.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait CustomXfer: Target {
+    /// The custom objects supported by the target.
+    fn custom_xfer_objects(&self) -> &[CustomXferObject];
+
+    /// Read the custom object `object`, using the (possibly empty) `annex`.
+    ///
+    /// Only called for objects registered as readable.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    fn read_object(
+        &mut self,
+        object: &str,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let _ = (object, annex, offset, length, buf);
+        Err(TargetError::NonFatal)
+    }
+
+    /// Write `data` into the custom object `object` (using the (possibly
+    /// empty) `annex`), starting `offset` bytes into the object.
+    ///
+    /// Only called for objects registered as writable.
+    ///
+    /// Returns the number of bytes written, which may be less than
+    /// `data.len()` (in which case GDB will send the rest in a subsequent
+    /// request).
+    fn write_object(
+        &mut self,
+        object: &str,
+        annex: &[u8],
+        offset: u64,
+        data: &[u8],
+    ) -> TargetResult<usize, Self> {
+        let _ = (object, annex, offset, data);
+        Err(TargetError::NonFatal)
+    }
+}
+
+define_ext!(CustomXferOps, CustomXfer);
@@ -0,0 +1,25 @@
+//! Override the register byte order specified by `Target::Arch`.
+//!
+//! By default, `gdbstub` de/serializes the target's registers using the byte
+//! order returned by [`Arch::endianness`](crate::arch::Arch::endianness).
+//! Bi-endian targets whose byte order is selected at runtime (e.g: an ARM core
+//! with a configurable endianness control bit) can implement this extension
+//! to report their _current_ byte order instead, without needing a separate
+//! `Arch` type for each byte order.
+
+use crate::arch::Endian;
+use crate::target::Target;
+
+/// Target Extension - Override the register byte order specified by
+/// `Target::Arch`.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait EndiannessOverride: Target {
+    /// Return the target's current byte order.
+    ///
+    /// This is queried whenever the target's registers are de/serialized (i.e:
+    /// on every `g` / `G` packet).
+    fn endianness(&self) -> Endian;
+}
+
+define_ext!(EndiannessOverrideOps, EndiannessOverride);
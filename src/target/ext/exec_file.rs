@@ -0,0 +1,36 @@
+//! Provide the absolute path of the executable running in a given process.
+//!
+//! When connected via `target extended-remote`, GDB uses this information to
+//! automatically load the program's symbols (without having to manually run
+//! `file` beforehand).
+
+use crate::common::Pid;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Provide the path of a process's executable.
+///
+/// Corresponds to the `qXfer:exec-file:read` command.
+pub trait ExecFile: Target {
+    /// Read the absolute path of the executable corresponding to process
+    /// `pid`, starting at `offset`.
+    ///
+    /// `pid` is `None` when GDB hasn't negotiated the multiprocess extensions,
+    /// in which case the path of the current process's executable should be
+    /// returned.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// If `pid` doesn't correspond to a known process (or the path of its
+    /// executable is unknown), return an error (e.g: `TargetError::Errno(2)`)
+    /// instead of an empty / placeholder path.
+    fn get_exec_file(
+        &self,
+        pid: Option<Pid>,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(ExecFileOps, ExecFile);
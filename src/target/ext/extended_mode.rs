@@ -0,0 +1,363 @@
+//! Enables [Extended Mode](https://sourceware.org/gdb/current/onlinedocs/gdb/Connecting.html)
+//! functionality when connecting using `target extended-remote`, such as
+//! spawning new processes and/or attaching to existing processes.
+//!
+//! # Disclaimer
+//!
+//! While this API has been end-to-end tested and confirmed working with a "toy"
+//! target implementation (see the included `armv4t` example), it has _not_ been
+//! "battle-tested" with a fully-featured extended-mode capable target.
+//!
+//! If you end up using this API to implement an extended-mode capable target,
+//! _please_ file an issue on the repo detailing any bugs / usability issues you
+//! may encountered while implementing this API! If everything happens to Just
+//! Work as expected, nonetheless file an issue so that this disclaimer can be
+//! removed in future releases!
+
+use crate::arch::Arch;
+use crate::common::*;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::{Target, TargetResult};
+
+/// Returned from `ExtendedMode::kill` and `ExtendedMode::detach`
+///
+/// Retuning `ShouldTerminate::Yes` will cause the `GdbStub` to immediately
+/// shut down and return a `DisconnectReason::Kill` (or
+/// `DisconnectReason::Disconnect`, when detaching). Returning
+/// `ShouldTerminate::No` will keep the `GdbStub` running and listening for
+/// further run/attach requests.
+pub enum ShouldTerminate {
+    /// Terminate GdbStub
+    Yes,
+    /// Don't Terminate GdbStub
+    No,
+}
+
+impl From<ShouldTerminate> for bool {
+    fn from(st: ShouldTerminate) -> bool {
+        match st {
+            ShouldTerminate::Yes => true,
+            ShouldTerminate::No => false,
+        }
+    }
+}
+
+/// Describes how the target attached to a process.
+pub enum AttachKind {
+    /// It attached to an existing process.
+    Attach,
+    /// It spawned a new process.
+    Run,
+}
+
+impl AttachKind {
+    pub(crate) fn was_attached(self) -> bool {
+        match self {
+            AttachKind::Attach => true,
+            AttachKind::Run => false,
+        }
+    }
+}
+
+/// Target Extension - Support
+/// [Extended Mode](https://sourceware.org/gdb/current/onlinedocs/gdb/Connecting.html) functionality.
+///
+/// # Extended Mode for Single/Multi Threaded Targets
+///
+/// While extended-mode is primarily intended to be implemented by targets which
+/// support debugging multiple processes, there's no reason why a basic
+/// single/multi-threaded target can't implement these extensions as well.
+///
+/// For example, instead of "spawning" a process, the `run` command could be
+/// used to reset the execution state instead (e.g: resetting an emulator).
+pub trait ExtendedMode: Target {
+    /// Spawn and attach to the program `filename`, passing it the provided
+    /// `args` on its command line.
+    ///
+    /// The program is created in the stopped state.
+    ///
+    /// GDB sends an empty filename to request that the last program be re-run.
+    /// When the `alloc` feature is enabled, `gdbstub` handles this case by
+    /// passing the filename from the most recent successful `run` call. If no
+    /// filename is provided (e.g: on the first `run`, or when `alloc` is
+    /// disabled), the stub may use a default program (e.g. the last program
+    /// run), or a non fatal error should be returned.
+    ///
+    /// `args` yields each argument (hex-decoded), and may include empty
+    /// arguments (e.g: `run prog "" foo`).
+    ///
+    /// `filename` and `args` are not guaranteed to be valid UTF-8, and are
+    /// passed as raw byte arrays. If the filenames/arguments could not be
+    /// converted into an appropriate representation, a non fatal error should
+    /// be returned.
+    ///
+    /// _Note:_ This method's implementation should handle any additional
+    /// configuration options set via the various `ConfigureXXX` extensions to
+    /// `ExtendedMode`. e.g: if the [`ConfigureEnv`](trait.ConfigureEnv.html)
+    /// extension is implemented and enabled, this method should set the spawned
+    /// processes' environment variables accordingly.
+    fn run(&mut self, filename: Option<&[u8]>, args: Args) -> TargetResult<Pid, Self>;
+
+    /// Attach to a new process with the specified PID, returning the reason
+    /// the process stopped (which is reported to GDB as the attach's initial
+    /// stop reply).
+    ///
+    /// In all-stop mode, all threads in the attached process are stopped; in
+    /// non-stop mode, it may be attached without being stopped (if that is
+    /// supported by the target). In non-stop mode, `gdbstub` acknowledges the
+    /// attach with "OK", and reports the returned stop reason via a `%Stop`
+    /// notification.
+    ///
+    /// Processes which were stopped by the attach itself (as opposed to ones
+    /// which happened to be sitting at a breakpoint) should typically report
+    /// a `ThreadStopReason::SignalWithThread` with `signal: 0`.
+    ///
+    /// If `pid` doesn't exist, a non fatal error should be returned. When the
+    /// `alloc` feature is enabled, `gdbstub` rejects attempts to attach to an
+    /// already-attached (or spawned) process with an `EPERM` error before
+    /// calling this method. Otherwise, that case must be handled by the
+    /// target.
+    ///
+    /// Once attached, `gdbstub` switches its current thread over to the
+    /// attached process's first thread (as reported by
+    /// [`MultiThreadOps::list_active_threads_with_pid`](crate::target::ext::base::multithread::MultiThreadOps::list_active_threads_with_pid)).
+    fn attach(
+        &mut self,
+        pid: Pid,
+    ) -> TargetResult<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self>;
+
+    /// Query if specified PID was spawned by the target (via `run`), or if the
+    /// target attached to an existing process (via `attach`).
+    ///
+    /// GDB uses this to decide whether a process should be killed (if it was
+    /// spawned) or detached from (if it was attached to) when the debugging
+    /// session ends.
+    ///
+    /// If the `alloc` feature is enabled, `gdbstub` automatically tracks any
+    /// processes created / attached to via `run` / `attach`, and only calls
+    /// this method for other processes (e.g: a process which the target
+    /// spawned before GDB connected).
+    ///
+    /// Defaults to reporting that the process was attached to.
+    fn query_if_attached(&mut self, pid: Pid) -> TargetResult<AttachKind, Self> {
+        let _ = pid;
+        Ok(AttachKind::Attach)
+    }
+
+    /// Called when the GDB client sends a Kill request.
+    ///
+    /// GDB may or may not specify a specific PID to kill (i.e: `vKill;PID` vs.
+    /// a bare `k`). When no PID is specified, the target is free to decide what
+    /// to do (e.g: kill the last-used pid, terminate the connection, etc...).
+    /// Single-process targets can safely ignore `pid`.
+    ///
+    /// `gdbstub` replies `OK` to `vKill` requests, while `k` requests (as per
+    /// the protocol) don't receive any reply.
+    ///
+    /// If `ShouldTerminate::Yes` is returned, `GdbStub` will immediately stop
+    /// and return a `DisconnectReason::Kill`. Otherwise, the connection will
+    /// remain open, and `GdbStub` will continue listening for run/attach
+    /// requests.
+    fn kill(&mut self, pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self>;
+
+    /// (optional) Detach from the process with the specified PID, leaving any
+    /// other processes attached.
+    ///
+    /// Called when the GDB client sends a `D;PID` request (i.e: `detach PID`
+    /// in multiprocess mode). A plain `D` request (without a PID) still
+    /// detaches from everything and ends the debugging session.
+    ///
+    /// If `pid` doesn't correspond to an attached process, a non fatal error
+    /// should be returned.
+    ///
+    /// If `ShouldTerminate::Yes` is returned (e.g: after detaching from the
+    /// last remaining process), `GdbStub` will immediately stop and return a
+    /// `DisconnectReason::Disconnect` (after calling the base ops' `detach`
+    /// method). Otherwise, the connection will remain open.
+    ///
+    /// The default implementation returns `ShouldTerminate::Yes`, i.e: it
+    /// treats `D;PID` the same as a plain `D`.
+    fn detach(&mut self, pid: Pid) -> TargetResult<ShouldTerminate, Self> {
+        let _ = pid;
+        Ok(ShouldTerminate::Yes)
+    }
+
+    /// Restart the program being debugged, resetting it to its initial
+    /// (entry) state.
+    ///
+    /// The GDB docs don't do a good job describing what a "restart" operation
+    /// entails. For reference, the official `gdbserver` seems to kill all
+    /// inferior processes, and then re-run whatever program was provided on the
+    /// command line (if one was provided).
+    ///
+    /// Unlike `run`, no stop reply is sent to GDB after restarting (i.e: the
+    /// `R` packet has no response at all). GDB queries the restarted
+    /// program's state / resumes it using separate packets.
+    ///
+    /// _Author's Note:_ Based on my current (as of Sept 2020) understanding of
+    /// the GDB client;s source code, it seems that the "R" packet is _never_
+    /// sent so-long as the target implements the "vRun" packet (which
+    /// corresponds to this trait's `run` method). As such, while `gdbstub`
+    /// exposes this functionality, and "requires" an implementation, unless
+    /// you're running a fairly old version of GDB, it should be fine to
+    /// simply stub it out -- e.g: using the `unimplemented!()` macro /
+    /// returning a fatal error.
+    fn restart(&mut self) -> Result<(), Self::Error>;
+
+    /// (optional) Report whether the target can debug multiple processes
+    /// at once.
+    ///
+    /// Returning `true` enables GDB's multiprocess extensions, whereby thread
+    /// ids are reported using the `p<pid>.<tid>` syntax (sourcing each
+    /// thread's pid from [`MultiThreadOps::list_active_threads_with_pid`] /
+    /// [`MultiThreadOps::thread_pid`]), and resume actions may target all the
+    /// threads of a specific process (via [`TidSelector::Process`]).
+    ///
+    /// The default implementation returns `false`, in which case thread ids
+    /// are reported using the legacy (pid-less) syntax.
+    ///
+    /// [`MultiThreadOps::list_active_threads_with_pid`]: crate::target::ext::base::multithread::MultiThreadOps::list_active_threads_with_pid
+    /// [`MultiThreadOps::thread_pid`]: crate::target::ext::base::multithread::MultiThreadOps::thread_pid
+    /// [`TidSelector::Process`]: crate::target::ext::base::multithread::TidSelector::Process
+    fn supports_multiprocess(&self) -> bool {
+        false
+    }
+
+    /// (optional) Invoked when GDB client switches to extended mode.
+    ///
+    /// The default implementation is a no-op.
+    ///
+    /// Target implementations can override this implementation if they need to
+    /// perform any operations once extended mode is activated.
+    fn on_start(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Enable/Disable ASLR for spawned processes.
+    fn configure_aslr(&mut self) -> Option<ConfigureASLROps<'_, Self>> {
+        None
+    }
+
+    /// Set/Remove/Reset Environment variables for spawned processes.
+    fn configure_env(&mut self) -> Option<ConfigureEnvOps<'_, Self>> {
+        None
+    }
+
+    /// Configure if spawned processes should be spawned using a shell.
+    fn configure_startup_shell(&mut self) -> Option<ConfigureStartupShellOps<'_, Self>> {
+        None
+    }
+
+    /// Configure the working directory for spawned processes.
+    fn configure_working_dir(&mut self) -> Option<ConfigureWorkingDirOps<'_, Self>> {
+        None
+    }
+}
+
+define_ext!(ExtendedModeOps, ExtendedMode);
+
+/// Iterator of `args` passed to a spawned process (used in
+/// `ExtendedMode::run`)
+pub struct Args<'a, 'args> {
+    inner: &'a mut dyn Iterator<Item = &'args [u8]>,
+}
+
+impl core::fmt::Debug for Args<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Args {{ .. }}")
+    }
+}
+
+impl<'a, 'b> Args<'a, 'b> {
+    pub(crate) fn new(inner: &'a mut dyn Iterator<Item = &'b [u8]>) -> Args<'a, 'b> {
+        Args { inner }
+    }
+}
+
+impl<'args> Iterator for Args<'_, 'args> {
+    type Item = &'args [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Enable/Disable ASLR for spawned processes (for a more consistent debugging
+/// experience).
+///
+/// Corresponds to GDB's [`set disable-randomization`](https://sourceware.org/gdb/onlinedocs/gdb/Starting.html) command.
+///
+/// Since GDB's setting defaults to `on`, GDB typically disables ASLR before
+/// every `run`. The setting should be stored by the target, and applied to any
+/// processes it subsequently spawns (i.e: via [`ExtendedMode::run`]).
+pub trait ConfigureASLR: ExtendedMode {
+    /// Enable/Disable ASLR for spawned processes.
+    ///
+    /// `enabled` is `false` when GDB sends `QDisableRandomization:1`, and
+    /// `true` when GDB sends `QDisableRandomization:0`.
+    fn cfg_aslr(&mut self, enabled: bool) -> TargetResult<(), Self>;
+}
+
+define_ext!(ConfigureASLROps, ConfigureASLR);
+
+/// Set/Remove/Reset the Environment variables for spawned processes.
+///
+/// Corresponds to GDB's [`set environment`](https://sourceware.org/gdb/onlinedocs/gdb/Environment.html#set-environment) cmd.
+///
+/// When starting a program, GDB first sends a reset request, followed by the
+/// full set of variables which were set / unset via GDB's `set environment` /
+/// `unset environment` commands. Any changes should be applied to _subsequent_
+/// [`ExtendedMode::run`] calls.
+///
+/// _Note:_ Environment variables are not guaranteed to be UTF-8, and are passed
+/// as raw byte arrays. If the provided keys/values could not be converted into
+/// an appropriate representation, a non fatal error should be returned.
+pub trait ConfigureEnv: ExtendedMode {
+    /// Set an environment variable.
+    ///
+    /// `val` is `None` if the variable was set to an empty value.
+    fn set_env(&mut self, key: &[u8], val: Option<&[u8]>) -> TargetResult<(), Self>;
+
+    /// Remove an environment variable.
+    fn remove_env(&mut self, key: &[u8]) -> TargetResult<(), Self>;
+
+    /// Reset all environment variables to their initial state (i.e: undo all
+    /// previous `set/remove_env` calls).
+    fn reset_env(&mut self) -> TargetResult<(), Self>;
+}
+
+define_ext!(ConfigureEnvOps, ConfigureEnv);
+
+/// Configure if spawned processes should be spawned using a shell.
+///
+/// Corresponds to GDB's [`set startup-with-shell`](https://sourceware.org/gdb/onlinedocs/gdb/Starting.html) command.
+pub trait ConfigureStartupShell: ExtendedMode {
+    /// Configure if spawned processes should be spawned using a shell.
+    ///
+    /// On UNIX-like targets, it is possible to start the inferior using a shell
+    /// program. This is the default behavior on both `GDB` and `gdbserver`.
+    ///
+    /// The setting should be stored, and applied to _subsequent_
+    /// [`ExtendedMode::run`] calls (e.g: by spawning the program via `$SHELL
+    /// -c`, so that its arguments are subject to globbing / expansion).
+    fn cfg_startup_with_shell(&mut self, enabled: bool) -> TargetResult<(), Self>;
+}
+
+define_ext!(ConfigureStartupShellOps, ConfigureStartupShell);
+
+/// Configure the working directory for spawned processes.
+///
+/// Corresponds to GDB's [`set cwd` and `cd`](https://sourceware.org/gdb/onlinedocs/gdb/Working-Directory.html) commands.
+pub trait ConfigureWorkingDir: ExtendedMode {
+    /// Set the working directory for spawned processes.
+    ///
+    /// If no directory is provided, the stub should reset the value to it's
+    /// original value.
+    ///
+    /// The path is not guaranteed to be valid UTF-8, and is passed as a raw
+    /// byte array. If the path could not be converted into an appropriate
+    /// representation, a non fatal error should be returned.
+    fn cfg_working_dir(&mut self, dir: Option<&[u8]>) -> TargetResult<(), Self>;
+}
+
+define_ext!(ConfigureWorkingDirOps, ConfigureWorkingDir);
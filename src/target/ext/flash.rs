@@ -0,0 +1,56 @@
+//! Provide flash programming operations for the target.
+//!
+//! GDB will only use the flash programming packets (`vFlashErase`,
+//! `vFlashWrite`, and `vFlashDone`) when loading a program into a memory
+//! region which the target's memory map marks as `flash`. As such, this
+//! extension should be implemented alongside the
+//! [`MemoryMap`](super::memory_map::MemoryMap) extension, which must describe
+//! the target's flash regions (including their `blocksize`).
+//!
+//! A typical `load` sequence consists of one or more `erase` calls (each
+//! aligned to the flash region's block size), followed by one or more `write`
+//! calls, and finally a single call to `done`.
+//!
+//! See GDB's [flash programming](https://sourceware.org/gdb/current/onlinedocs/gdb/Packets.html#vFlashErase)
+//! packet documentation for more details.
+
+use crate::arch::Arch;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Support flash programming.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait Flash: Target {
+    /// Erase `length` bytes of flash memory, starting at `start_addr`.
+    ///
+    /// GDB guarantees that the region will be aligned to the flash block size
+    /// reported by the target's memory map.
+    fn erase(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        length: <Self::Arch as Arch>::Usize,
+    ) -> TargetResult<(), Self>;
+
+    /// Write `data` to flash memory, starting at `start_addr`.
+    ///
+    /// GDB may issue multiple writes before calling [`done`](Flash::done),
+    /// and writes are always issued in order of increasing address. Targets
+    /// are free to buffer these writes, and defer actually programming the
+    /// flash until `done` is called.
+    fn write(
+        &mut self,
+        start_addr: <Self::Arch as Arch>::Usize,
+        data: &[u8],
+    ) -> TargetResult<(), Self>;
+
+    /// Indicate that flash programming is complete.
+    ///
+    /// Any buffered writes must be flushed to flash before returning.
+    ///
+    /// _Note:_ GDB does not guarantee that flash memory is in a consistent
+    /// state until this method returns, and the contents of any erased-but-
+    /// not-yet-written regions are unspecified until then.
+    fn done(&mut self) -> TargetResult<(), Self>;
+}
+
+define_ext!(FlashOps, Flash);
@@ -0,0 +1,207 @@
+//! Provide Host I/O operations for the target.
+//!
+//! Host I/O allows GDB to access files on the target's filesystem (e.g: when
+//! running `remote get`, or when GDB needs to read the program's executable
+//! from the target), using the `vFile` family of packets.
+//!
+//! See GDB's [Host I/O Packets](https://sourceware.org/gdb/current/onlinedocs/gdb/Host-I_002fO-Packets.html)
+//! documentation for more details.
+//!
+//! _Note:_ The various flags and values passed to / returned from these
+//! methods use the "portable" encodings described in GDB's
+//! [File-I/O protocol](https://sourceware.org/gdb/current/onlinedocs/gdb/Protocol_002dspecific-Representation-of-Datatypes.html),
+//! which don't necessarily match the values used by the host's libc.
+
+use crate::common::*;
+use crate::target::Target;
+
+/// An errno value, as defined by GDB's
+/// [File-I/O protocol](https://sourceware.org/gdb/current/onlinedocs/gdb/Errno-Values.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum HostIoErrno {
+    /// Operation not permitted
+    EPERM = 1,
+    /// No such file or directory
+    ENOENT = 2,
+    /// Interrupted system call
+    EINTR = 4,
+    /// Bad file number
+    EBADF = 9,
+    /// Permission denied
+    EACCES = 13,
+    /// Bad address
+    EFAULT = 14,
+    /// Device or resource busy
+    EBUSY = 16,
+    /// File exists
+    EEXIST = 17,
+    /// No such device
+    ENODEV = 19,
+    /// Not a directory
+    ENOTDIR = 20,
+    /// Is a directory
+    EISDIR = 21,
+    /// Invalid argument
+    EINVAL = 22,
+    /// File table overflow
+    ENFILE = 23,
+    /// Too many open files
+    EMFILE = 24,
+    /// File too large
+    EFBIG = 27,
+    /// No space left on device
+    ENOSPC = 28,
+    /// Illegal seek
+    ESPIPE = 29,
+    /// Read-only file system
+    EROFS = 30,
+    /// File name too long
+    ENAMETOOLONG = 91,
+    /// Unknown error
+    EUNKNOWN = 9999,
+}
+
+/// The error type for Host I/O operations.
+pub enum HostIoError<E> {
+    /// An operation-specific non-fatal error code, which is reported back to
+    /// GDB as the result of the operation.
+    Errno(HostIoErrno),
+    /// A target-specific fatal error.
+    ///
+    /// **WARNING:** Returning this error will immediately halt the target's
+    /// execution and return a `GdbStubError::TargetError` from `GdbStub::run`!
+    Fatal(E),
+}
+
+/// Converts a `std::io::Error` into a `HostIoError::Errno`.
+#[cfg(feature = "std")]
+impl<E> From<std::io::Error> for HostIoError<E> {
+    fn from(e: std::io::Error) -> HostIoError<E> {
+        use std::io::ErrorKind::*;
+        let errno = match e.kind() {
+            PermissionDenied => HostIoErrno::EPERM,
+            NotFound => HostIoErrno::ENOENT,
+            Interrupted => HostIoErrno::EINTR,
+            AlreadyExists => HostIoErrno::EEXIST,
+            InvalidInput => HostIoErrno::EINVAL,
+            _ => HostIoErrno::EUNKNOWN,
+        };
+        HostIoError::Errno(errno)
+    }
+}
+
+/// A specialized `Result` type for Host I/O operations.
+pub type HostIoResult<T, Tgt> = Result<T, HostIoError<<Tgt as Target>::Error>>;
+
+/// Information about a file, as returned by [`HostIo::fstat`].
+///
+/// This struct corresponds to the `struct stat` defined by GDB's
+/// [File-I/O protocol](https://sourceware.org/gdb/current/onlinedocs/gdb/struct-stat.html),
+/// and is sent to GDB as a 64-byte big-endian blob.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HostIoStat {
+    /// The device
+    pub st_dev: u32,
+    /// The inode
+    pub st_ino: u32,
+    /// Protection bits (i.e: file type + permissions)
+    pub st_mode: u32,
+    /// Number of hard links
+    pub st_nlink: u32,
+    /// User ID of owner
+    pub st_uid: u32,
+    /// Group ID of owner
+    pub st_gid: u32,
+    /// Device type (if inode device)
+    pub st_rdev: u32,
+    /// Total size, in bytes
+    pub st_size: u64,
+    /// Blocksize for filesystem I/O
+    pub st_blksize: u64,
+    /// Number of blocks allocated
+    pub st_blocks: u64,
+    /// Time of last access (seconds since the epoch)
+    pub st_atime: u32,
+    /// Time of last modification (seconds since the epoch)
+    pub st_mtime: u32,
+    /// Time of last change (seconds since the epoch)
+    pub st_ctime: u32,
+}
+
+impl HostIoStat {
+    /// Serialize the stat struct into the 64-byte big-endian layout expected
+    /// by GDB.
+    pub(crate) fn to_be_bytes(self) -> [u8; 64] {
+        let mut buf = [0; 64];
+        let mut i = 0;
+        let mut put = |bytes: &[u8]| {
+            buf[i..i + bytes.len()].copy_from_slice(bytes);
+            i += bytes.len();
+        };
+
+        put(&self.st_dev.to_be_bytes());
+        put(&self.st_ino.to_be_bytes());
+        put(&self.st_mode.to_be_bytes());
+        put(&self.st_nlink.to_be_bytes());
+        put(&self.st_uid.to_be_bytes());
+        put(&self.st_gid.to_be_bytes());
+        put(&self.st_rdev.to_be_bytes());
+        put(&self.st_size.to_be_bytes());
+        put(&self.st_blksize.to_be_bytes());
+        put(&self.st_blocks.to_be_bytes());
+        put(&self.st_atime.to_be_bytes());
+        put(&self.st_mtime.to_be_bytes());
+        put(&self.st_ctime.to_be_bytes());
+
+        buf
+    }
+}
+
+/// Selects the filesystem that subsequent Host I/O operations which accept a
+/// filename should operate on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsKind {
+    /// The remote stub's filesystem.
+    Stub,
+    /// The filesystem as seen by process `Pid`.
+    Pid(Pid),
+}
+
+/// Target Extension - Perform I/O operations on the target's filesystem.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait HostIo: Target {
+    /// Open a file at `filename`, returning a file descriptor.
+    ///
+    /// `flags` and `mode` use the [File-I/O protocol](https://sourceware.org/gdb/current/onlinedocs/gdb/Open-Flags.html)
+    /// encodings.
+    fn open(&mut self, filename: &[u8], flags: u32, mode: u32) -> HostIoResult<u32, Self>;
+
+    /// Close the open file corresponding to `fd`.
+    fn close(&mut self, fd: u32) -> HostIoResult<(), Self>;
+
+    /// Read up to `buf.len()` bytes from the open file corresponding to `fd`,
+    /// starting at `offset`, returning the number of bytes read.
+    ///
+    /// `buf` is a scratch buffer provided by `gdbstub`, whose length is bounded
+    /// by both the number of bytes requested by GDB and the size of the packet
+    /// buffer. As such, implementations only ever need to read `buf.len()`
+    /// bytes at a time, and never need to buffer the file's contents.
+    ///
+    /// Returning less bytes than requested is not an error, and returning `0`
+    /// indicates that the end of the file has been reached.
+    fn pread(&mut self, fd: u32, offset: u64, buf: &mut [u8]) -> HostIoResult<usize, Self>;
+
+    /// Get information about the open file corresponding to `fd`.
+    fn fstat(&mut self, fd: u32) -> HostIoResult<HostIoStat, Self>;
+
+    /// Select the filesystem on which subsequent operations which accept a
+    /// filename (e.g: `open`) will operate.
+    ///
+    /// By default, filenames should be resolved using the remote stub's
+    /// filesystem.
+    fn setfs(&mut self, fs: FsKind) -> HostIoResult<(), Self>;
+}
+
+define_ext!(HostIoOps, HostIo);
@@ -0,0 +1,177 @@
+//! Perform system calls on the host, using GDB's
+//! [File-I/O](https://sourceware.org/gdb/current/onlinedocs/gdb/File_002dI_002fO-Remote-Protocol-Extension.html)
+//! protocol extension.
+//!
+//! File-I/O allows freestanding programs (e.g: ones running on bare-metal
+//! hardware, or under an emulator) to access the host's filesystem and console,
+//! by asking GDB to perform system calls on their behalf.
+//!
+//! Unlike most other protocol extensions, File-I/O requests are initiated by
+//! the _target_:
+//!
+//! 1. While the target is running, it stops and reports a
+//!    [`StopReason::HostSyscall`](crate::target::ext::base::singlethread::StopReason::HostSyscall)
+//!    (or the equivalent `ThreadStopReason`) describing the syscall.
+//! 2. GDB performs the syscall on the host, reading / writing any buffers
+//!    referenced by the syscall's arguments directly from / to the target's
+//!    memory.
+//! 3. GDB sends back the syscall's result, which is passed to
+//!    [`HostSyscall::host_syscall_reply`].
+//! 4. Unless the user interrupted the syscall (via Ctrl-C), `gdbstub` then
+//!    resumes the target via [`HostSyscall::resume_after_host_syscall`].
+//!
+//! _Note:_ The various flags and values passed to / returned from host
+//! syscalls use the "portable" encodings described in GDB's
+//! [File-I/O protocol](https://sourceware.org/gdb/current/onlinedocs/gdb/Protocol_002dspecific-Representation-of-Datatypes.html),
+//! which don't necessarily match the values used by the host's libc.
+
+use crate::arch::Arch;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::Target;
+
+/// A system call to be performed by GDB on the host.
+///
+/// Buffers (e.g: `path`) are passed as pointers into the target's memory, and
+/// the lengths of strings _include_ their trailing NUL byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostSyscallRequest<U> {
+    /// `open(path, flags, mode)`
+    Open {
+        /// Pointer to the file's path
+        path: U,
+        /// Length of the path (including the trailing NUL byte)
+        path_len: usize,
+        /// Open flags (e.g: `O_RDONLY`)
+        flags: u32,
+        /// Mode of the file, if it's created
+        mode: u32,
+    },
+    /// `close(fd)`
+    Close {
+        /// File descriptor
+        fd: u32,
+    },
+    /// `read(fd, buf, count)`
+    Read {
+        /// File descriptor
+        fd: u32,
+        /// Buffer to read data into
+        buf: U,
+        /// Maximum number of bytes to read
+        count: usize,
+    },
+    /// `write(fd, buf, count)`
+    Write {
+        /// File descriptor
+        fd: u32,
+        /// Buffer containing the data to write
+        buf: U,
+        /// Number of bytes to write
+        count: usize,
+    },
+    /// `lseek(fd, offset, flag)`
+    Lseek {
+        /// File descriptor
+        fd: u32,
+        /// Offset to seek to (relative to `flag`)
+        offset: i64,
+        /// `SEEK_SET`, `SEEK_CUR`, or `SEEK_END`
+        flag: u32,
+    },
+    /// `rename(old_path, new_path)`
+    Rename {
+        /// Pointer to the file's current path
+        old_path: U,
+        /// Length of the current path (including the trailing NUL byte)
+        old_path_len: usize,
+        /// Pointer to the file's new path
+        new_path: U,
+        /// Length of the new path (including the trailing NUL byte)
+        new_path_len: usize,
+    },
+    /// `unlink(path)`
+    Unlink {
+        /// Pointer to the file's path
+        path: U,
+        /// Length of the path (including the trailing NUL byte)
+        path_len: usize,
+    },
+    /// `stat(path, buf)`
+    Stat {
+        /// Pointer to the file's path
+        path: U,
+        /// Length of the path (including the trailing NUL byte)
+        path_len: usize,
+        /// Buffer to write the `struct stat` into
+        buf: U,
+    },
+    /// `fstat(fd, buf)`
+    Fstat {
+        /// File descriptor
+        fd: u32,
+        /// Buffer to write the `struct stat` into
+        buf: U,
+    },
+    /// `gettimeofday(tv, tz)`
+    Gettimeofday {
+        /// Buffer to write the `struct timeval` into
+        tv: U,
+        /// Timezone (unused, and should be `0`)
+        tz: U,
+    },
+    /// `isatty(fd)`
+    Isatty {
+        /// File descriptor
+        fd: u32,
+    },
+    /// `system(cmd)`
+    System {
+        /// Pointer to the command string
+        cmd: U,
+        /// Length of the command (including the trailing NUL byte)
+        cmd_len: usize,
+    },
+}
+
+/// GDB's reply to a [`HostSyscallRequest`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HostSyscallReply {
+    /// The syscall's return value (e.g: `-1` on error).
+    pub retval: i64,
+    /// The syscall's errno value, if it failed.
+    ///
+    /// See [`HostIoErrno`](crate::target::ext::host_io::HostIoErrno) for the
+    /// meaning of each value.
+    pub errno: Option<u32>,
+    /// The user pressed Ctrl-C while the syscall was running.
+    ///
+    /// If set, `gdbstub` reports the target as having stopped with `SIGINT`
+    /// (instead of resuming it).
+    pub ctrl_c: bool,
+}
+
+/// Target Extension - Perform system calls on the host.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait HostSyscall: Target {
+    /// Handle GDB's reply to the most recently requested host syscall (e.g:
+    /// by storing `reply.retval` in the target's return value register).
+    fn host_syscall_reply(&mut self, reply: HostSyscallReply) -> Result<(), Self::Error>;
+
+    /// Resume the target after a host syscall completed.
+    ///
+    /// The target should resume execution in the same way as it was running
+    /// prior to requesting the syscall (i.e: using the same `ResumeAction`s),
+    /// and return the next stop reason, exactly like
+    /// [`MultiThreadOps::resume`](crate::target::ext::base::multithread::MultiThreadOps::resume).
+    ///
+    /// Single threaded targets can convert their
+    /// [`StopReason`](crate::target::ext::base::singlethread::StopReason)
+    /// via `.into()`.
+    fn resume_after_host_syscall(
+        &mut self,
+        check_gdb_interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+}
+
+define_ext!(HostSyscallOps, HostSyscall);
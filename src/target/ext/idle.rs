@@ -0,0 +1,34 @@
+//! Run housekeeping tasks while waiting for GDB to send a packet.
+//!
+//! By default, `GdbStub` blocks indefinitely while waiting for the next
+//! packet. Implementing [`Idle`] makes `GdbStub` wait for packets using
+//! [`Connection::read_timeout`](crate::Connection::read_timeout) instead,
+//! calling [`Idle::on_idle`] each time the timeout elapses without a packet
+//! arriving.
+//!
+//! Timeouts are only checked _between_ packets: once GDB starts sending a
+//! packet, the rest of it is read without a timeout.
+//!
+//! Timing out never results in a response being re-sent. In ack mode, the
+//! stub only re-transmits a response once GDB explicitly rejects it (with a
+//! `-`), and in no-ack mode, responses are never re-transmitted. As such, a
+//! long-running `on_idle` simply delays the handling of the next packet.
+//!
+//! _Note:_ `Connection`s which don't implement `read_timeout` never time out,
+//! in which case `on_idle` is never called. Similarly, `AsyncGdbStub` never
+//! calls `on_idle`, as async runtimes already provide their own timers.
+
+use core::time::Duration;
+
+use crate::target::Target;
+
+/// Target Extension - Run housekeeping tasks while waiting for GDB.
+pub trait Idle: Target {
+    /// How long to wait for a packet before calling `on_idle`.
+    fn idle_timeout(&self) -> Duration;
+
+    /// Called each time `idle_timeout` elapses without GDB sending a packet.
+    fn on_idle(&mut self) -> Result<(), Self::Error>;
+}
+
+define_ext!(IdleOps, Idle);
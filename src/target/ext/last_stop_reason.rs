@@ -0,0 +1,37 @@
+//! Report why the target is currently stopped.
+//!
+//! GDB sends a `?` packet right after connecting to find out why (and on
+//! which thread) the target is stopped. This is particularly important when
+//! GDB _reconnects_ to an already-stopped target, as the new `GdbStub`
+//! instance has no way of knowing which stop event was last reported.
+
+use crate::arch::Arch;
+use crate::target::ext::base::multithread::ThreadStopReason;
+use crate::target::Target;
+
+/// Target Extension - Report why the target is currently stopped.
+///
+/// Corresponds to the `?` command.
+pub trait LastStopReason: Target {
+    /// Return the reason the target last stopped.
+    ///
+    /// Single threaded targets should report thread-specific stop reasons
+    /// (e.g: `SwBreak`) using `tid` 1.
+    ///
+    /// `ThreadStopReason::DoneStep`, `GdbInterrupt` and `Signal` are reported
+    /// on the currently selected thread.
+    ///
+    /// Events which GDB was already notified of are never re-issued. `Exited`
+    /// and `Terminated` are reported as a plain `SIGTRAP` on the currently
+    /// selected thread, while `Fork`, `Vfork`, `VforkDone`, `ThreadCreated`
+    /// and `HostSyscall` are reported as a plain `SIGTRAP` on the thread they
+    /// occurred on.
+    ///
+    /// Without this extension, `gdbstub` reports a `SIGTRAP` on the currently
+    /// selected thread.
+    fn get_last_stop_reason(
+        &mut self,
+    ) -> Result<ThreadStopReason<<Self::Arch as Arch>::Usize>, Self::Error>;
+}
+
+define_ext!(LastStopReasonOps, LastStopReason);
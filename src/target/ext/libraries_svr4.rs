@@ -0,0 +1,45 @@
+//! Report the list of shared libraries loaded by the target, using the SVR4
+//! (i.e: System-V / ELF) library list format.
+//!
+//! This allows GDB to automatically load symbols for any shared libraries
+//! loaded by the target (without having to manually run `add-symbol-file`).
+//!
+//! See GDB's [Library List Format for SVR4 Targets](https://sourceware.org/gdb/current/onlinedocs/gdb/Library-List-Format-for-SVR4-Targets.html)
+//! documentation for details on the XML format.
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Report the list of loaded shared libraries (in the SVR4
+/// format).
+///
+/// Corresponds to the `qXfer:libraries-svr4:read` command.
+pub trait LibrariesSvr4: Target {
+    /// Read the target's SVR4 library list XML, starting at `offset`.
+    ///
+    /// The XML document lists each loaded module's `name`, along with the
+    /// address of its `link_map` (`lm`), its load bias (`l_addr`), and the
+    /// address of its dynamic section (`l_ld`). e.g:
+    ///
+    /// ```xml
+    /// <library-list-svr4 version="1.0" main-lm="0x7ffff7ffe190">
+    ///   <library name="/lib/libc.so.6" lm="0x7ffff7fc3000"
+    ///            l_addr="0x7ffff7dd5000" l_ld="0x7ffff7fb6b40"/>
+    /// </library-list-svr4>
+    /// ```
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// _Note:_ GDB re-reads the list (starting at offset `0`) every time the
+    /// target reports a shared library event, so the XML should reflect the
+    /// set of libraries loaded at the time of the initial (`offset == 0`)
+    /// read.
+    fn get_libraries_svr4(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(LibrariesSvr4Ops, LibrariesSvr4);
@@ -0,0 +1,40 @@
+//! Provide a target memory map.
+//!
+//! The memory map describes which regions of the target's address space are
+//! RAM, ROM, or flash, which GDB uses to (among other things) determine
+//! whether software breakpoints can be used in a particular region.
+//!
+//! See GDB's [Memory Map Format](https://sourceware.org/gdb/current/onlinedocs/gdb/Memory-Map-Format.html)
+//! documentation for details on the XML format.
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Provide a target memory map.
+///
+/// Corresponds to the `qXfer:memory-map:read` command.
+pub trait MemoryMap: Target {
+    /// Read the target's memory map XML, starting at `offset`.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// For targets with a static memory map, this can be implemented as:
+    ///
+    /// ```rust,ignore
+    /// const MEMORY_MAP: &str = r#"<?xml version="1.0"?> ... "#;
+    ///
+    /// let xml = MEMORY_MAP.as_bytes();
+    /// let data = xml.get(offset as usize..).unwrap_or(&[]);
+    /// let len = data.len().min(length);
+    /// buf[..len].copy_from_slice(&data[..len]);
+    /// Ok(len)
+    /// ```
+    fn memory_map_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(MemoryMapOps, MemoryMap);
@@ -0,0 +1,27 @@
+//! Search the target's memory for a byte pattern.
+//!
+//! Implementing this extension allows GDB's `find` command to run directly on
+//! the target, instead of having GDB read back (potentially large) regions of
+//! memory and perform the search itself.
+
+use crate::arch::Arch;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Search the target's memory for a byte pattern.
+///
+/// Corresponds to the `qSearch:memory` command.
+pub trait MemorySearch: Target {
+    /// Search `length` bytes of memory starting at `start` for the first
+    /// occurrence of `pattern`.
+    ///
+    /// Return `Ok(Some(addr))` with the address of the first match, or
+    /// `Ok(None)` if the pattern couldn't be found.
+    fn search(
+        &mut self,
+        start: <Self::Arch as Arch>::Usize,
+        length: <Self::Arch as Arch>::Usize,
+        pattern: &[u8],
+    ) -> TargetResult<Option<<Self::Arch as Arch>::Usize>, Self>;
+}
+
+define_ext!(MemorySearchOps, MemorySearch);
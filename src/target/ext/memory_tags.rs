@@ -0,0 +1,52 @@
+//! Access memory tags (e.g: ARM's Memory Tagging Extension).
+//!
+//! GDB uses this extension to implement its `memory-tag` family of commands,
+//! via the `qMemTags` and `QMemTags` packets.
+//!
+//! Memory tags are associated with fixed-size "granules" of memory, rather
+//! than with individual bytes. On AArch64 MTE, allocation tags (tag type `1`)
+//! have a granule size of 16 bytes, and are packed one tag per byte. As such,
+//! a request covering `len` bytes starting at `addr` spans every granule
+//! which overlaps the range `addr..addr + len`, with one tag byte per
+//! granule.
+//!
+//! See GDB's [memory tagging](https://sourceware.org/gdb/current/onlinedocs/gdb/General-Query-Packets.html#qMemTags)
+//! packet documentation for more details.
+
+use crate::arch::Arch;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Access memory tags.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait MemoryTags: Target {
+    /// Read the tags of type `tag_type` covering the `len` bytes starting at
+    /// `addr` into `tags`, returning the number of tag bytes written.
+    ///
+    /// `tags` is a scratch buffer provided by `gdbstub` (whose length is
+    /// bounded by the size of the packet buffer). If the requested range spans
+    /// more granules than can fit in `tags`, a non-fatal error should be
+    /// returned.
+    fn read_tags(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        len: <Self::Arch as Arch>::Usize,
+        tag_type: u32,
+        tags: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+
+    /// Write the tags of type `tag_type` covering the `len` bytes starting at
+    /// `addr`.
+    ///
+    /// If `tags` contains fewer tags than the number of granules in the range,
+    /// the tags should be repeated until the entire range has been tagged.
+    fn write_tags(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        len: <Self::Arch as Arch>::Usize,
+        tag_type: u32,
+        tags: &[u8],
+    ) -> TargetResult<(), Self>;
+}
+
+define_ext!(MemoryTagsOps, MemoryTags);
@@ -0,0 +1,155 @@
+//! Create custom target-specific debugging commands accessible via GDB's
+//! `monitor` command!
+
+use crate::target::Target;
+
+pub use crate::protocol::ConsoleOutput;
+pub use crate::{output, outputln};
+
+/// Target Extension - Handle custom GDB `monitor` commands.
+pub trait MonitorCmd: Target {
+    /// Handle custom commands sent using the `monitor` command.
+    ///
+    /// The GDB remote serial protocol includes a built-in mechanism to send
+    /// arbitrary commands to the remote stub: the `monitor` command. For
+    /// example, running `monitor dbg` from the GDB client will invoke
+    /// `handle_monitor_cmd` with `cmd = b"dbg"`.
+    ///
+    /// Commands are _not_ guaranteed to be valid UTF-8, hence the use of
+    /// `&[u8]` as opposed to `&str`.
+    ///
+    /// Intermediate console output can be written back to the GDB client using
+    /// the provided `ConsoleOutput` object + the
+    /// [`gdbstub::output!`](macro.output.html) macro. Binary (non UTF-8)
+    /// output can be written using [`ConsoleOutput::write_raw`].
+    ///
+    /// _Note:_ The maximum length of incoming commands is limited by the size
+    /// of the packet buffer provided to the [`GdbStub`](struct.GdbStub.html).
+    /// Specifically, commands can only be up to `(buf.len() - 10) / 2` bytes.
+    ///
+    /// Targets with several commands may want to forward `cmd` to
+    /// [`dispatch_monitor_cmd`], which takes care of prefix matching and
+    /// `monitor help` output.
+    fn handle_monitor_cmd(&mut self, cmd: &[u8], out: ConsoleOutput<'_>)
+        -> Result<(), Self::Error>;
+}
+
+define_ext!(MonitorCmdOps, MonitorCmd);
+
+/// A named `monitor` subcommand, for use with [`dispatch_monitor_cmd`].
+#[allow(clippy::type_complexity)]
+pub struct MonitorSubcommand<T: Target> {
+    /// The name used to invoke the subcommand (e.g: `reset`).
+    pub name: &'static str,
+    /// A one-line description of the subcommand, shown by `monitor help`.
+    pub help: &'static str,
+    /// Invoked with any arguments following the subcommand's name (with
+    /// leading whitespace removed).
+    pub handler: fn(&mut T, args: &[u8], out: ConsoleOutput<'_>) -> Result<(), T::Error>,
+}
+
+/// Dispatch a `monitor` command to one of several named `subcommands`.
+///
+/// The first whitespace-separated word of `cmd` selects the subcommand to
+/// run. Subcommands can be invoked using any unambiguous prefix of their name,
+/// with exact matches always taking priority (e.g: given `reset` and
+/// `reset-all`, `res` is ambiguous, `reset` runs `reset`, and `reset-` runs
+/// `reset-all`).
+///
+/// The `help` subcommand is provided automatically: `monitor help` (or an
+/// empty `monitor` command) lists every subcommand along with its help text,
+/// while `monitor help <name>` describes a single subcommand. Unknown and
+/// ambiguous commands are reported back to the GDB console.
+///
+/// This is a convenience helper meant to be called from
+/// [`MonitorCmd::handle_monitor_cmd`], and is entirely optional.
+pub fn dispatch_monitor_cmd<T: Target>(
+    target: &mut T,
+    subcommands: &[MonitorSubcommand<T>],
+    cmd: &[u8],
+    mut out: ConsoleOutput<'_>,
+) -> Result<(), T::Error> {
+    let (name, args) = split_word(cmd);
+
+    if name == b"help" || name.is_empty() {
+        let (name, _) = split_word(args);
+        if name.is_empty() {
+            let width = subcommands
+                .iter()
+                .map(|c| c.name.len())
+                .fold("help".len(), usize::max);
+            outputln!(out, "List of monitor commands:");
+            outputln!(out);
+            outputln!(
+                out,
+                "{:width$} -- Print this help message.",
+                "help",
+                width = width
+            );
+            for c in subcommands {
+                outputln!(out, "{:width$} -- {}", c.name, c.help, width = width);
+            }
+        } else if let Some(subcommand) = lookup(subcommands, name, &mut out) {
+            outputln!(out, "{} -- {}", subcommand.name, subcommand.help);
+        }
+        return Ok(());
+    }
+
+    match lookup(subcommands, name, &mut out) {
+        Some(subcommand) => (subcommand.handler)(target, args, out),
+        None => Ok(()),
+    }
+}
+
+/// Split `s` into its first whitespace-separated word, and the remainder of
+/// the string (with leading whitespace removed).
+fn split_word(s: &[u8]) -> (&[u8], &[u8]) {
+    let s = trim_start(s);
+    match s.iter().position(u8::is_ascii_whitespace) {
+        Some(i) => (&s[..i], trim_start(&s[i..])),
+        None => (s, &[]),
+    }
+}
+
+fn trim_start(s: &[u8]) -> &[u8] {
+    let start = s
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(s.len());
+    &s[start..]
+}
+
+/// Find the subcommand matching `name`, reporting unknown / ambiguous names
+/// to the GDB console.
+fn lookup<'a, T: Target>(
+    subcommands: &'a [MonitorSubcommand<T>],
+    name: &[u8],
+    out: &mut ConsoleOutput<'_>,
+) -> Option<&'a MonitorSubcommand<T>> {
+    if let Some(subcommand) = subcommands.iter().find(|c| c.name.as_bytes() == name) {
+        return Some(subcommand);
+    }
+
+    let mut matches = subcommands
+        .iter()
+        .filter(|c| c.name.as_bytes().starts_with(name));
+    match (matches.next(), matches.next()) {
+        (Some(subcommand), None) => Some(subcommand),
+        (None, _) => {
+            output!(out, "Undefined monitor command: \"");
+            out.write_raw(name);
+            outputln!(out, "\".  Try \"monitor help\".");
+            None
+        }
+        (Some(first), Some(second)) => {
+            output!(out, "Ambiguous monitor command \"");
+            out.write_raw(name);
+            output!(out, "\": {}, {}", first.name, second.name);
+            for subcommand in matches {
+                output!(out, ", {}", subcommand.name);
+            }
+            outputln!(out, ".");
+            None
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Provide information about the target's operating system (i.e: GDB's
+//! `info os` command).
+//!
+//! Each kind of OS data (e.g: `processes`, `threads`) is sent to GDB as an XML
+//! table, with one `<item>` per row, and one `<column>` per field:
+//!
+//! ```xml
+//! <osdata type="processes">
+//!   <item>
+//!     <column name="pid">1</column>
+//!     <column name="user">root</column>
+//!     <column name="command">init</column>
+//!   </item>
+//! </osdata>
+//! ```
+//!
+//! The list of available types is requested using an empty annex, and is
+//! itself an `<osdata type="types">` table, with `Type`, `Description`, and
+//! `Title` columns for each type.
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Provide information about the target's operating
+/// system.
+///
+/// Corresponds to the `qXfer:osdata:read` command.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait OsData: Target {
+    /// Read the XML-encoded OS data table of type `annex` (e.g: `processes`).
+    ///
+    /// An empty `annex` requests the table of available types.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// Unknown annexes should be reported by returning a non-fatal error
+    /// (e.g: `Err(TargetError::NonFatal)`).
+    fn read_osdata(
+        &self,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(OsDataOps, OsData);
@@ -0,0 +1,53 @@
+//! Customize the features advertised in response to GDB's `qSupported`
+//! packet.
+//!
+//! `gdbstub` automatically advertises features based on which protocol
+//! extensions the target implements. Implementing [`QSupported`] allows the
+//! target to react to the features offered by GDB, suppress any of the
+//! features `gdbstub` would advertise, and advertise additional features.
+//!
+//! A feature can be overridden by suppressing it, and then advertising a
+//! replacement (e.g: suppressing `PacketSize`, and advertising
+//! `PacketSize=8000`).
+//!
+//! Suppressing a feature only removes it from the `qSupported` response, and
+//! doesn't otherwise change how `gdbstub` behaves. The exceptions are
+//! `multiprocess`, `fork-events`, and `vfork-events`, which are disabled for
+//! the rest of the session when suppressed.
+//!
+//! _Note:_ `gdbstub` doesn't validate any of these changes! e.g: advertising
+//! a `PacketSize` larger than the stub's packet buffer, or advertising a
+//! feature which `gdbstub` doesn't support, will likely break the session.
+//!
+//! See the [GDB docs](https://sourceware.org/gdb/current/onlinedocs/gdb/General-Query-Packets.html#qSupported)
+//! for the list of features.
+
+use crate::target::Target;
+
+/// Target Extension - Customize the features advertised via `qSupported`.
+pub trait QSupported: Target {
+    /// Called with each feature offered by GDB (e.g: `b"swbreak+"`,
+    /// `b"xmlRegisters=i386"`), before the response is sent.
+    fn on_gdb_feature(&mut self, feature: &[u8]) -> Result<(), Self::Error> {
+        let _ = feature;
+        Ok(())
+    }
+
+    /// Return `false` to suppress the feature `name` (e.g: `b"swbreak"`,
+    /// `b"PacketSize"`), which `gdbstub` would otherwise advertise.
+    fn advertise_feature(&mut self, name: &[u8]) -> bool {
+        let _ = name;
+        true
+    }
+
+    /// Advertise additional features, by calling `add_feature` with each
+    /// feature (e.g: `b"PacketSize=8000"`, or `b"foo+"`).
+    ///
+    /// Features are appended after any features advertised by `gdbstub`.
+    fn extra_features(&mut self, add_feature: &mut dyn FnMut(&[u8])) -> Result<(), Self::Error> {
+        let _ = add_feature;
+        Ok(())
+    }
+}
+
+define_ext!(QSupportedOps, QSupported);
@@ -0,0 +1,29 @@
+//! Mark the target as read-only (e.g: a core dump, or a memory snapshot).
+//!
+//! Static targets can be inspected, but can't be modified or resumed. Instead
+//! of having every write / resume method return an error by hand, a target
+//! can implement [`ReadOnly`], and `gdbstub` will reply to the following
+//! packets with `E01` without ever invoking the target:
+//!
+//! - Register / memory writes (`G`, `P`, `M`, `QMemTags`,
+//!   `qXfer:siginfo:write`)
+//! - Inserting breakpoints / watchpoints (`Z`)
+//! - Resuming execution (`c`, `C`, `s`, `S`, `vCont;...`, `bc`, `bs`)
+//! - Flash programming (`vFlashErase`, `vFlashWrite`, `vFlashDone`)
+//!
+//! Reading registers / memory (`g`, `p`, `m`), querying the stop reason, and
+//! removing breakpoints (`z`) continue to work as usual.
+//!
+//! Targets which still need to implement the write methods of
+//! [`BaseOps`](crate::target::ext::base::BaseOps) (e.g: `write_registers`) can
+//! simply return an error from them, as they won't be called.
+
+use crate::target::Target;
+
+/// Target Extension - Mark the target as read-only.
+///
+/// This extension has no methods: returning `Some(self)` from
+/// `Target::read_only` is enough to reject all write / resume operations.
+pub trait ReadOnly: Target {}
+
+define_ext!(ReadOnlyOps, ReadOnly);
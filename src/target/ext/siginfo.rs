@@ -0,0 +1,40 @@
+//! Access the `siginfo` of the signal which stopped the target.
+//!
+//! When a thread stops on a signal, GDB can display / modify its `siginfo_t`
+//! via the `$_siginfo` convenience variable.
+
+use crate::common::Tid;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Read / write the `siginfo` of a stopped thread.
+///
+/// Corresponds to the `qXfer:siginfo:read` and `qXfer:siginfo:write`
+/// commands.
+pub trait Siginfo: Target {
+    /// Read the raw `siginfo` of the last signal received by thread `tid`,
+    /// starting at `offset`.
+    ///
+    /// The `siginfo` is sent as-is (i.e: as the target's native `siginfo_t`
+    /// structure), and is _not_ encoded as XML.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// Single threaded targets are always passed `tid` 1.
+    fn read_siginfo(
+        &self,
+        tid: Tid,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+
+    /// Overwrite the raw `siginfo` of thread `tid` (starting at `offset`)
+    /// with `data`, returning the number of bytes written.
+    ///
+    /// The modified `siginfo` should be delivered alongside the signal once
+    /// the thread is resumed.
+    fn write_siginfo(&mut self, tid: Tid, offset: u64, data: &[u8]) -> TargetResult<usize, Self>;
+}
+
+define_ext!(SiginfoOps, Siginfo);
@@ -0,0 +1,37 @@
+//! Translate between the target's native signal numbers and GDB's signal
+//! numbering.
+//!
+//! GDB uses its own, target-independent signal numbers in the remote
+//! protocol (see [`Signal`]), which only partially overlap with the numbering
+//! used by any given OS (e.g: `SIGUSR1` is `10` on Linux, but `30` in GDB).
+//!
+//! Without this extension, the signal numbers reported in stop reasons (e.g:
+//! `StopReason::Signal`) are assumed to already use GDB's numbering.
+
+use crate::common::Signal;
+use crate::target::Target;
+
+/// Target Extension - Translate between the target's native signal numbers
+/// and GDB's signal numbering.
+pub trait SignalMapping: Target {
+    /// Convert a native signal number into a GDB [`Signal`].
+    ///
+    /// `gdbstub` calls this method on the `signal` of every `Signal` /
+    /// `SignalWithThread` stop reason before reporting it to GDB. Native
+    /// signals without a GDB equivalent should be reported as
+    /// `Signal::SIGUNKNOWN`.
+    fn native_to_gdb(&self, native: u8) -> Signal;
+
+    /// Convert a GDB [`Signal`] into a native signal number, returning `None`
+    /// if the target has no equivalent signal.
+    ///
+    /// Resume actions always carry GDB's `Signal`, and should be converted
+    /// using this method before being delivered to the program.
+    ///
+    /// This method must be the inverse of `native_to_gdb`, as `gdbstub` uses
+    /// it to convert any stop reasons it synthesizes itself (e.g: the
+    /// `SIGTRAP` reported when a process is spawned) into native signals.
+    fn gdb_to_native(&self, signal: Signal) -> Option<u8>;
+}
+
+define_ext!(SignalMappingOps, SignalMapping);
@@ -0,0 +1,72 @@
+//! Filter which signals stop the target, and which are delivered to the
+//! program.
+//!
+//! GDB's `handle` command (e.g: `handle SIGUSR1 nostop noprint pass`) is
+//! implemented using the `QPassSignals` and `QProgramSignals` packets, which
+//! each send the complete set of signals the setting applies to:
+//!
+//! - `QPassSignals`: signals which should be passed straight to the program,
+//!   _without_ stopping the target and reporting them to GDB.
+//! - `QProgramSignals`: signals which may be delivered to the program when
+//!   GDB resumes it (e.g: via `continue` after a signal stop). Any other
+//!   signals should be discarded.
+//!
+//! Each new packet replaces the set sent by the previous packet of the same
+//! kind, and an empty set clears it.
+
+use crate::common::Signal;
+use crate::target::{Target, TargetResult};
+
+/// An iterator over a set of signals, as sent by GDB.
+#[derive(Debug, Clone)]
+pub struct SignalList<'a> {
+    inner: core::slice::Split<'a, u8, fn(&u8) -> bool>,
+}
+
+impl<'a> SignalList<'a> {
+    /// `list` must be a pre-validated, `;`-separated list of hex-encoded
+    /// signal numbers.
+    pub(crate) fn new(list: &'a [u8]) -> SignalList<'a> {
+        fn is_sep(b: &u8) -> bool {
+            *b == b';'
+        }
+
+        SignalList {
+            inner: list.split(is_sep as fn(&u8) -> bool),
+        }
+    }
+}
+
+impl Iterator for SignalList<'_> {
+    type Item = Signal;
+
+    fn next(&mut self) -> Option<Signal> {
+        loop {
+            let sig = self.inner.next()?;
+            // skip over empty entries (e.g: a trailing ';', or an empty list)
+            if sig.is_empty() {
+                continue;
+            }
+            let sig = crate::protocol::decode_hex(sig).ok()?;
+            // skip over signals which aren't known to `gdbstub`
+            if let Some(sig) = Signal::from_raw(sig) {
+                return Some(sig);
+            }
+        }
+    }
+}
+
+/// Target Extension - Filter which signals stop the target, and which are
+/// delivered to the program.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait Signals: Target {
+    /// Set the signals which should be passed to the program without stopping
+    /// the target.
+    fn pass_signals(&mut self, signals: SignalList<'_>) -> TargetResult<(), Self>;
+
+    /// Set the signals which may be delivered to the program.
+    fn program_signals(&mut self, signals: SignalList<'_>) -> TargetResult<(), Self>;
+}
+
+define_ext!(SignalsOps, Signals);
@@ -0,0 +1,47 @@
+//! Override the target description XML specified by `Target::Arch`.
+//!
+//! By default, `gdbstub` serves the static target description returned by
+//! [`Arch::target_description_xml`](crate::arch::Arch::target_description_xml).
+//! Targets whose register set is only known at runtime (e.g: configurable
+//! soft-cores) can implement this extension to generate the target
+//! description on-the-fly instead.
+//!
+//! This extension also makes it possible to split the target description
+//! across multiple files using `<xi:include href="..."/>` directives, as GDB
+//! requests each included file using the same `qXfer:features:read` packet
+//! (with the included file's name as the annex).
+//!
+//! See GDB's [Target Descriptions](https://sourceware.org/gdb/current/onlinedocs/gdb/Target-Descriptions.html)
+//! documentation for details on the XML format.
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Override the target description XML specified by
+/// `Target::Arch`.
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait TargetDescriptionXmlOverride: Target {
+    /// Read a target description XML file.
+    ///
+    /// `annex` is the name of the requested file. GDB always starts by
+    /// requesting `target.xml`, followed by any files referenced via
+    /// `<xi:include href="..."/>` directives.
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// Unknown annexes should be reported by returning a non-fatal error
+    /// (e.g: `Err(TargetError::NonFatal)`).
+    fn target_description_xml(
+        &self,
+        annex: &[u8],
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(
+    TargetDescriptionXmlOverrideOps,
+    TargetDescriptionXmlOverride
+);
@@ -0,0 +1,24 @@
+//! Provide extra information about a thread (e.g: its name or state).
+//!
+//! This information is displayed alongside each thread in the output of GDB's
+//! `info threads` command.
+
+use crate::common::Tid;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Provide extra information about a thread.
+///
+/// Corresponds to the `qThreadExtraInfo` command.
+pub trait ThreadExtraInfo: Target {
+    /// Write a short, human-readable description of the thread `tid` (e.g:
+    /// `"worker-3 (blocked on mutex)"`) into `buf`, returning the number of
+    /// bytes written.
+    ///
+    /// The description may be arbitrary UTF-8. If the description doesn't fit
+    /// in `buf`, simply copy as many bytes as will fit: `gdbstub` will drop
+    /// any partial UTF-8 character left at the end of a truncated
+    /// description.
+    fn thread_info(&self, tid: Tid, buf: &mut [u8]) -> TargetResult<usize, Self>;
+}
+
+define_ext!(ThreadExtraInfoOps, ThreadExtraInfo);
@@ -0,0 +1,46 @@
+//! Report the list of threads (along with additional per-thread metadata,
+//! such as their name and core affinity) as an XML document.
+//!
+//! Unlike the bare thread IDs returned by
+//! [`MultiThreadOps::list_active_threads`](super::base::multithread::MultiThreadOps::list_active_threads),
+//! the XML thread list lets GDB display each thread's name and the core it
+//! last ran on in its `info threads` output.
+//!
+//! See GDB's [Thread List Format](https://sourceware.org/gdb/current/onlinedocs/gdb/Thread-List-Format.html)
+//! documentation for details on the XML format.
+
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Report the list of threads as an XML document.
+///
+/// Corresponds to the `qXfer:threads:read` command.
+pub trait ThreadListXml: Target {
+    /// Read the target's thread list XML, starting at `offset`.
+    ///
+    /// Each thread is described by a `<thread>` element, whose `id` uses the
+    /// same `p<pid>.<tid>` / `<tid>` syntax (in hex) as the rest of the
+    /// protocol. The `core` and `name` attributes are optional, and the
+    /// element's body may contain a free-form description of the thread. e.g:
+    ///
+    /// ```xml
+    /// <threads>
+    ///   <thread id="1" core="0" name="main">idle</thread>
+    ///   <thread id="2" core="3" name="worker"/>
+    /// </threads>
+    /// ```
+    ///
+    /// See [Reading Objects in Chunks](crate::target::ext#note-reading-objects-in-chunks-qxfer)
+    /// for how `offset`, `length` and `buf` are used.
+    ///
+    /// _Note:_ GDB reads the document in multiple chunks, so the XML should
+    /// reflect the set of threads that existed at the time of the initial
+    /// (`offset == 0`) read.
+    fn get_thread_list_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self>;
+}
+
+define_ext!(ThreadListXmlOps, ThreadListXml);
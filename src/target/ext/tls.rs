@@ -0,0 +1,23 @@
+//! Resolve the addresses of thread-local storage (TLS) variables.
+//!
+//! GDB uses this extension to print `__thread` / `thread_local` variables,
+//! via the `qGetTLSAddr` packet.
+
+use crate::arch::Arch;
+use crate::common::Tid;
+use crate::target::{Target, TargetResult};
+
+/// Target Extension - Resolve the addresses of thread-local storage variables.
+pub trait Tls: Target {
+    /// Return the address of the thread-local variable located `offset` bytes
+    /// into the TLS block of the load module at `load_module` (typically the
+    /// address of the module's `link_map`), for the thread `tid`.
+    fn get_tls_addr(
+        &mut self,
+        tid: Tid,
+        offset: <Self::Arch as Arch>::Usize,
+        load_module: <Self::Arch as Arch>::Usize,
+    ) -> TargetResult<<Self::Arch as Arch>::Usize, Self>;
+}
+
+define_ext!(TlsOps, Tls);
@@ -0,0 +1,82 @@
+//! Define, start, and stop [Tracepoints](https://sourceware.org/gdb/current/onlinedocs/gdb/Tracepoints.html).
+//!
+//! Tracepoints allow the target to collect data at specific points in the
+//! program _without_ stopping it, which can then be inspected after the fact.
+//!
+//! _Note:_ At the moment, this extension only covers the tracepoint
+//! definition + start/stop lifecycle, alongside basic status reporting.
+//! Tracepoint actions (e.g: `collect` / `while-stepping`), selecting trace
+//! frames (`QTFrame`), and reading the trace buffer (`qTBuffer`) are not yet
+//! supported.
+
+use crate::arch::Arch;
+use crate::target::{Target, TargetResult};
+
+/// A new tracepoint, as defined by GDB via the `QTDP` packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewTracepoint<U> {
+    /// The tracepoint's number (as assigned by GDB).
+    pub number: usize,
+    /// The address the tracepoint is set at.
+    pub addr: U,
+    /// Whether the tracepoint is enabled.
+    pub enabled: bool,
+    /// The number of single-steps to collect data for once the tracepoint is
+    /// hit (i.e: `while-stepping`).
+    pub step_count: usize,
+    /// Stop the trace experiment once the tracepoint has been hit this many
+    /// times (`0` means "no limit").
+    pub pass_count: usize,
+}
+
+/// The current state of the trace experiment, as reported via `qTStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceStatus {
+    /// Whether or not a trace experiment is currently running.
+    pub running: bool,
+    /// The number of trace frames currently in the trace buffer (if known).
+    pub frames: Option<usize>,
+    /// The total number of trace frames created during the experiment (if
+    /// known).
+    pub created: Option<usize>,
+}
+
+/// Target Extension - Support
+/// [Tracepoints](https://sourceware.org/gdb/current/onlinedocs/gdb/Tracepoints.html).
+///
+/// See the [module level documentation](index.html) for more details.
+pub trait Tracepoints: Target {
+    /// Clear any existing tracepoints and trace state variables, in
+    /// preparation for a new trace experiment.
+    fn tracepoints_init(&mut self) -> TargetResult<(), Self>;
+
+    /// Define a new tracepoint.
+    fn define_tracepoint(
+        &mut self,
+        tp: NewTracepoint<<Self::Arch as Arch>::Usize>,
+    ) -> TargetResult<(), Self>;
+
+    /// Define a new trace state variable, with the given `initial_value`.
+    ///
+    /// `name` is the variable's name (without the leading `$`), and
+    /// `builtin` indicates if the variable is one of GDB's built-in trace
+    /// state variables.
+    fn define_trace_state_variable(
+        &mut self,
+        number: usize,
+        initial_value: i64,
+        builtin: bool,
+        name: &[u8],
+    ) -> TargetResult<(), Self>;
+
+    /// Start the trace experiment.
+    fn start_tracing(&mut self) -> TargetResult<(), Self>;
+
+    /// Stop the trace experiment.
+    fn stop_tracing(&mut self) -> TargetResult<(), Self>;
+
+    /// Report the current status of the trace experiment.
+    fn tracepoint_status(&mut self) -> TargetResult<TraceStatus, Self>;
+}
+
+define_ext!(TracepointsOps, Tracepoints);
@@ -0,0 +1,621 @@
+//! Everything related to the [`Target`] trait + associated extension traits.
+//!
+//! The [`Target`] trait describes how to control and modify a system's
+//! execution state during a GDB debugging session, and serves as the
+//! primary bridge between `gdbstub`'s generic protocol implementation and a
+//! target's project/platform-specific code.
+//!
+//! **`Target` is the most important trait in `gdbstub`, and must be implemented
+//! by all consumers of the library!**
+//!
+//! # Implementing `Target`
+//!
+//! `gdbstub` uses a technique called "Inlineable Dyn Extension Traits" (IDETs)
+//! to expose an ergonomic and extensible interface to the GDB protocol. It's
+//! not a very common pattern, and can seem a little "weird" at first glance,
+//! but it's actually very straightforward to use!
+//!
+//! Please refer to the [documentation in the `ext` module](ext) for more
+//! information on IDETs, and how they're used to implement `Target` and it's
+//! various extension traits.
+//!
+//! **TL;DR:** Whenever you see a method that has `Option<FooOps>` in the return
+//! type, that method should return `Some(self)` if the extension is
+//! implemented, or `None` if it's unimplemented / disabled.
+//!
+//! ## Associated Types
+//!
+//! - The [`Target::Arch`](trait.Target.html#associatedtype.Arch) associated
+//!   type encodes information about the target's architecture, such as it's
+//!   pointer size, register layout, etc... `gdbstub` comes with several
+//!   built-in architecture definitions, which can be found under the
+//!   [`arch`](../arch/index.html) module.
+//!
+//! - The [`Target::Error`](trait.Target.html#associatedtype.Error) associated
+//!   type allows implementors to plumb-through their own project-specific fatal
+//!   error type into the `Target` trait. This is a big-boost to library
+//!   ergonomics, as it enables consumers of `gdbstub` to preserve
+//!   target-specific context while using `gdbstub`, without having to do any
+//!   "error-stashing".
+//!
+//! For example: consider an emulated target where certain devices might return
+//! a `MyEmuError::ContractViolation` error whenever they're accessed
+//! "improperly" (e.g: setting registers in the wrong order). By setting `type
+//! Error = MyEmuError`, the method signature of the `Target`'s `resume` method
+//! becomes `fn resume(&mut self, ...) -> Result<_, MyEmuError>`, which makes it
+//! possible to preserve the target-specific error while using `gdbstub`!
+//!
+//! ## Required Methods
+//!
+//! The [`Target::base_ops`](trait.Target.html#tymethod.base_ops) method
+//! describes the base debugging operations that must be implemented by any
+//! target. These are things such as starting/stopping execution,
+//! reading/writing memory, etc..
+//!
+//! All other methods are entirely optional! Check out the
+//! [`target_ext`](../target_ext/index.html) module for a full list of currently
+//! supported protocol extensions.
+//!
+//! ## Example: A Bare-Minimum Single Threaded `Target`
+//!
+//! ```rust,ignore
+//! use gdbstub::target::Target;
+//! use gdbstub::target::ext::base::singlethread::SingleThreadOps;
+//!
+//! impl SingleThreadOps for MyTarget {
+//!     // ... omitted for brevity
+//! }
+//!
+//! impl Target for MyTarget {
+//!     fn base_ops(&mut self) -> base::BaseOps<'_, Self::Arch, Self::Error> {
+//!         base::BaseOps::SingleThread(self)
+//!     }
+//! }
+//! ```
+
+use crate::arch::Arch;
+
+pub mod ext;
+
+/// The error type for various methods on `Target` and it's assorted associated
+/// extension traits.
+///
+/// # Error Handling over the GDB Remote Serial Protocol
+///
+/// The GDB Remote Serial Protocol has less-than-stellar support for error
+/// handling, typically taking the form of a single-byte
+/// [`errno`-style error codes](https://www-numi.fnal.gov/offline_software/srt_public_context/WebDocs/Errors/unix_system_errors.html).
+/// Moreover, often times the GDB client will simply _ignore_ the specific error
+/// code returned by the stub, and print a generic failure message instead.
+///
+/// As such, while it's certainly better to use appropriate error codes when
+/// possible (e.g: returning a `EFAULT` (14) when reading from invalid memory),
+/// it's often fine to simply return the more general `TargetError::NonFatal`
+/// instead, and avoid the headache of picking a "descriptive" error code. Under
+/// the good, `TargetError::NonFatal` is sent to the GDB client as a generic
+/// `EREMOTEIO` (121) error.
+///
+/// # `From` and `Into` implementations
+///
+/// - `From<()>` -> `TargetError::NonFatal`
+/// - `From<io::Error>` -> `TargetError::Io(io::Error)` (requires `std` feature)
+///
+/// When using a custom target-specific fatal error type, users are encouraged
+/// to write the following impl to simplify error handling in `Target` methods:
+///
+/// ```rust,ignore
+/// type MyTargetFatalError = ...; // Target-specific Fatal Error
+/// impl From<MyTargetFatalError> for TargetError<MyTargetFatalError> {
+///     fn from(e: MyTargetFatalError) -> Self {
+///         TargetError::Fatal(e)
+///     }
+/// }
+/// ```
+///
+/// Unfortunately, a blanket impl such as `impl<T: Target> From<T::Error> for
+/// TargetError<T::Error>` isn't possible, as it could result in impl conflicts.
+/// For example, if a Target decided to use `()` as it's fatal error type, then
+/// there would be conflict with the existing `From<()>` impl.
+#[non_exhaustive]
+pub enum TargetError<E> {
+    /// A non-specific, non-fatal error has occurred.
+    NonFatal,
+    /// I/O Error.
+    ///
+    /// At the moment, this is just shorthand for
+    /// `TargetError::NonFatal(e.raw_os_err().unwrap_or(121))`. Error code `121`
+    /// corresponds to `EREMOTEIO`.
+    ///
+    /// In the future, `gdbstub` may add support for the "QEnableErrorStrings"
+    /// LLDB protocol extension, which would allow sending additional error
+    /// context (in the form of an ASCII string) when an I/O error occurs. If
+    /// this is something you're interested in, consider opening a PR!
+    ///
+    /// Only available when the `std` feature is enabled.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// An operation-specific non-fatal error code.
+    Errno(u8),
+    /// A target-specific fatal error.
+    ///
+    /// **WARNING:** Returning this error will immediately halt the target's
+    /// execution and return a `GdbStubError::TargetError` from `GdbStub::run`!
+    /// Note that the debugging session will will _not_ be terminated, and can
+    /// be resumed by calling `GdbStub::run` after resolving the error and/or
+    /// setting up a post-mortem debugging environment.
+    Fatal(E),
+}
+
+/// Converts a `()` into a `TargetError::NonFatal`.
+impl<E> From<()> for TargetError<E> {
+    fn from(_: ()) -> TargetError<E> {
+        TargetError::NonFatal
+    }
+}
+
+/// Converts a `std::io::Error` into a `TargetError::Io`.
+#[cfg(feature = "std")]
+impl<E> From<std::io::Error> for TargetError<E> {
+    fn from(e: std::io::Error) -> TargetError<E> {
+        TargetError::Io(e)
+    }
+}
+
+/// A specialized `Result` type for `Target` operations.
+///
+/// _Note:_ While it's typically parameterized as `TargetResult<T, Self>`, the
+/// error value is in-fact `TargetError<Self::Error>` (not `Self`).
+pub type TargetResult<T, Tgt> = Result<T, TargetError<<Tgt as Target>::Error>>;
+
+/// Describes the architecture and capabilities of a target which can be
+/// debugged by [`GdbStub`](../struct.GdbStub.html).
+///
+/// The [`Target`](trait.Target.html) trait describes how to control and modify
+/// a system's execution state during a GDB debugging session, and serves as the
+/// primary bridge between `gdbstub`'s generic protocol implementation and a
+/// target's project/platform-specific code.
+///
+/// **`Target` is the most important trait in `gdbstub`, and must be implemented
+/// by anyone who uses the library!**
+///
+/// Please refer to the the documentation in the [`target` module](index.html)
+/// for more information on how to implement and work with `Target` and it's
+/// various extension traits.
+pub trait Target {
+    /// The target's architecture.
+    type Arch: Arch;
+
+    /// A target-specific **fatal** error.
+    type Error;
+
+    /// Base operations such as reading/writing from memory/registers,
+    /// stopping/resuming the target, etc....
+    ///
+    /// For example, on a single-threaded target:
+    ///
+    /// ```rust,ignore
+    /// use gdbstub::target::Target;
+    /// use gdbstub::target::base::singlethread::SingleThreadOps;
+    ///
+    /// impl SingleThreadOps for MyTarget {
+    ///     // ...
+    /// }
+    ///
+    /// impl Target for MyTarget {
+    ///     fn base_ops(&mut self) -> base::BaseOps<'_, Self::Arch, Self::Error> {
+    ///         base::BaseOps::SingleThread(self)
+    ///     }
+    /// }
+    /// ```
+    fn base_ops(&mut self) -> ext::base::BaseOps<'_, Self::Arch, Self::Error>;
+
+    /// Set/Remote software breakpoints.
+    fn sw_breakpoint(&mut self) -> Option<ext::breakpoints::SwBreakpointOps<'_, Self>> {
+        None
+    }
+
+    /// Set/Remote hardware breakpoints.
+    fn hw_breakpoint(&mut self) -> Option<ext::breakpoints::HwBreakpointOps<'_, Self>> {
+        None
+    }
+
+    /// Set/Remote hardware watchpoints.
+    fn hw_watchpoint(&mut self) -> Option<ext::breakpoints::HwWatchpointOps<'_, Self>> {
+        None
+    }
+
+    /// Handle custom GDB `monitor` commands.
+    fn monitor_cmd(&mut self) -> Option<ext::monitor_cmd::MonitorCmdOps<'_, Self>> {
+        None
+    }
+
+    /// Support for Extended Mode operations.
+    fn extended_mode(&mut self) -> Option<ext::extended_mode::ExtendedModeOps<'_, Self>> {
+        None
+    }
+
+    /// Handle requests to get the target's current section (or segment)
+    /// offsets.
+    fn section_offsets(&mut self) -> Option<ext::section_offsets::SectionOffsetsOps<'_, Self>> {
+        None
+    }
+
+    /// Support for Non-Stop Mode (multi threaded targets only).
+    fn non_stop_mode(&mut self) -> Option<ext::base::non_stop::NonStopModeOps<'_, Self>> {
+        None
+    }
+
+    /// Support for range stepping (i.e: stepping until the PC leaves an
+    /// address range).
+    fn range_step(&mut self) -> Option<ext::base::range_step::RangeStepOps<'_, Self>> {
+        None
+    }
+
+    /// Support for reverse execution (e.g: `reverse-continue` / `reverse-step`).
+    fn reverse_exec(&mut self) -> Option<ext::base::reverse_exec::ReverseExecOps<'_, Self>> {
+        None
+    }
+
+    /// Emulate single-stepping using temporary breakpoints.
+    fn single_step_via_breakpoints(
+        &mut self,
+    ) -> Option<ext::base::single_step_via_breakpoints::SingleStepViaBreakpointsOps<'_, Self>> {
+        None
+    }
+
+    /// Single-step whole instructions on targets which step at a finer
+    /// granularity (e.g: a single CPU cycle).
+    fn instruction_step(
+        &mut self,
+    ) -> Option<ext::base::instruction_step::InstructionStepOps<'_, Self>> {
+        None
+    }
+
+    /// Define, start, and stop tracepoints.
+    fn tracepoints(&mut self) -> Option<ext::tracepoints::TracepointsOps<'_, Self>> {
+        None
+    }
+
+    /// Support for Host I/O operations (i.e: accessing the target's
+    /// filesystem).
+    fn host_io(&mut self) -> Option<ext::host_io::HostIoOps<'_, Self>> {
+        None
+    }
+
+    /// Provide the target's ELF auxiliary vector.
+    fn auxv(&mut self) -> Option<ext::auxv::AuxvOps<'_, Self>> {
+        None
+    }
+
+    /// Report why the target is currently stopped.
+    fn last_stop_reason(&mut self) -> Option<ext::last_stop_reason::LastStopReasonOps<'_, Self>> {
+        None
+    }
+
+    /// Access the `siginfo` of the signal which stopped a thread.
+    fn siginfo(&mut self) -> Option<ext::siginfo::SiginfoOps<'_, Self>> {
+        None
+    }
+
+    /// Provide the path of a process's executable.
+    fn exec_file(&mut self) -> Option<ext::exec_file::ExecFileOps<'_, Self>> {
+        None
+    }
+
+    /// Report the list of loaded shared libraries (in the SVR4 format).
+    fn libraries_svr4(&mut self) -> Option<ext::libraries_svr4::LibrariesSvr4Ops<'_, Self>> {
+        None
+    }
+
+    /// Report the list of threads (along with their names and core affinity)
+    /// as an XML document.
+    fn thread_list_xml(&mut self) -> Option<ext::thread_list_xml::ThreadListXmlOps<'_, Self>> {
+        None
+    }
+
+    /// Provide a target memory map.
+    fn memory_map(&mut self) -> Option<ext::memory_map::MemoryMapOps<'_, Self>> {
+        None
+    }
+
+    /// Support flash programming.
+    fn flash(&mut self) -> Option<ext::flash::FlashOps<'_, Self>> {
+        None
+    }
+
+    /// Search the target's memory for a byte pattern.
+    fn memory_search(&mut self) -> Option<ext::memory_search::MemorySearchOps<'_, Self>> {
+        None
+    }
+
+    /// Access memory tags (e.g: ARM's Memory Tagging Extension).
+    fn memory_tags(&mut self) -> Option<ext::memory_tags::MemoryTagsOps<'_, Self>> {
+        None
+    }
+
+    /// Customize the features advertised in response to `qSupported`.
+    fn qsupported(&mut self) -> Option<ext::qsupported::QSupportedOps<'_, Self>> {
+        None
+    }
+
+    /// Run housekeeping tasks while waiting for GDB to send a packet.
+    fn idle(&mut self) -> Option<ext::idle::IdleOps<'_, Self>> {
+        None
+    }
+
+    /// Receive the set of operations GDB expects to be permitted.
+    fn allow(&mut self) -> Option<ext::allow::AllowOps<'_, Self>> {
+        None
+    }
+
+    /// Mark the target as read-only (e.g: a core dump), rejecting any
+    /// operations which would modify or resume it.
+    fn read_only(&mut self) -> Option<ext::read_only::ReadOnlyOps<'_, Self>> {
+        None
+    }
+
+    /// Report fork / vfork events.
+    fn catch_fork(&mut self) -> Option<ext::catch_fork::CatchForkOps<'_, Self>> {
+        None
+    }
+
+    /// Decide how to handle interrupts which arrive while the target is
+    /// stopped.
+    fn ctrl_c_interrupt(&mut self) -> Option<ext::ctrl_c_interrupt::CtrlCInterruptOps<'_, Self>> {
+        None
+    }
+
+    /// Provide extra information about a thread (e.g: its name or state).
+    fn thread_extra_info(
+        &mut self,
+    ) -> Option<ext::thread_extra_info::ThreadExtraInfoOps<'_, Self>> {
+        None
+    }
+
+    /// Translate between the target's native signal numbers and GDB's signal
+    /// numbering.
+    fn signal_mapping(&mut self) -> Option<ext::signal_mapping::SignalMappingOps<'_, Self>> {
+        None
+    }
+
+    /// Filter which signals stop the target, and which are delivered to the
+    /// program.
+    fn signals(&mut self) -> Option<ext::signals::SignalsOps<'_, Self>> {
+        None
+    }
+
+    /// Resolve the addresses of thread-local storage variables.
+    fn tls(&mut self) -> Option<ext::tls::TlsOps<'_, Self>> {
+        None
+    }
+
+    /// Provide information about the target's operating system (i.e: GDB's
+    /// `info os`).
+    fn osdata(&mut self) -> Option<ext::osdata::OsDataOps<'_, Self>> {
+        None
+    }
+
+    /// Record the target's branch trace (i.e: GDB's `record btrace`).
+    fn btrace(&mut self) -> Option<ext::btrace::BtraceOps<'_, Self>> {
+        None
+    }
+
+    /// Transfer target-defined objects using GDB's generic `qXfer` mechanism.
+    fn custom_xfer(&mut self) -> Option<ext::custom_xfer::CustomXferOps<'_, Self>> {
+        None
+    }
+
+    /// Support for performing system calls on the host (i.e: GDB's File-I/O
+    /// protocol extension).
+    fn host_syscall(&mut self) -> Option<ext::host_syscall::HostSyscallOps<'_, Self>> {
+        None
+    }
+
+    /// Override the register byte order specified by `Target::Arch`.
+    fn endianness_override(
+        &mut self,
+    ) -> Option<ext::endianness_override::EndiannessOverrideOps<'_, Self>> {
+        None
+    }
+
+    /// Override the target description XML specified by `Target::Arch`.
+    fn target_description_xml_override(
+        &mut self,
+    ) -> Option<ext::target_description_xml_override::TargetDescriptionXmlOverrideOps<'_, Self>>
+    {
+        None
+    }
+}
+
+macro_rules! impl_dyn_target {
+    ($type:ty) => {
+        #[allow(clippy::type_complexity)]
+        impl<A, E> Target for $type
+        where
+            A: Arch,
+        {
+            type Arch = A;
+            type Error = E;
+
+            fn base_ops(&mut self) -> ext::base::BaseOps<'_, Self::Arch, Self::Error> {
+                (**self).base_ops()
+            }
+
+            fn sw_breakpoint(&mut self) -> Option<ext::breakpoints::SwBreakpointOps<'_, Self>> {
+                (**self).sw_breakpoint()
+            }
+
+            fn hw_breakpoint(&mut self) -> Option<ext::breakpoints::HwBreakpointOps<'_, Self>> {
+                (**self).hw_breakpoint()
+            }
+
+            fn hw_watchpoint(&mut self) -> Option<ext::breakpoints::HwWatchpointOps<'_, Self>> {
+                (**self).hw_watchpoint()
+            }
+
+            fn monitor_cmd(&mut self) -> Option<ext::monitor_cmd::MonitorCmdOps<'_, Self>> {
+                (**self).monitor_cmd()
+            }
+
+            fn extended_mode(&mut self) -> Option<ext::extended_mode::ExtendedModeOps<'_, Self>> {
+                (**self).extended_mode()
+            }
+
+            fn section_offsets(&mut self) -> Option<ext::section_offsets::SectionOffsetsOps<'_, Self>> {
+                (**self).section_offsets()
+            }
+
+            fn non_stop_mode(&mut self) -> Option<ext::base::non_stop::NonStopModeOps<'_, Self>> {
+                (**self).non_stop_mode()
+            }
+
+            fn range_step(&mut self) -> Option<ext::base::range_step::RangeStepOps<'_, Self>> {
+                (**self).range_step()
+            }
+
+            fn reverse_exec(&mut self) -> Option<ext::base::reverse_exec::ReverseExecOps<'_, Self>> {
+                (**self).reverse_exec()
+            }
+
+            fn single_step_via_breakpoints(
+                &mut self,
+            ) -> Option<ext::base::single_step_via_breakpoints::SingleStepViaBreakpointsOps<'_, Self>>
+            {
+                (**self).single_step_via_breakpoints()
+            }
+
+            fn instruction_step(
+                &mut self,
+            ) -> Option<ext::base::instruction_step::InstructionStepOps<'_, Self>> {
+                (**self).instruction_step()
+            }
+
+            fn tracepoints(&mut self) -> Option<ext::tracepoints::TracepointsOps<'_, Self>> {
+                (**self).tracepoints()
+            }
+
+            fn host_io(&mut self) -> Option<ext::host_io::HostIoOps<'_, Self>> {
+                (**self).host_io()
+            }
+
+            fn auxv(&mut self) -> Option<ext::auxv::AuxvOps<'_, Self>> {
+                (**self).auxv()
+            }
+
+            fn last_stop_reason(
+                &mut self,
+            ) -> Option<ext::last_stop_reason::LastStopReasonOps<'_, Self>> {
+                (**self).last_stop_reason()
+            }
+
+            fn siginfo(&mut self) -> Option<ext::siginfo::SiginfoOps<'_, Self>> {
+                (**self).siginfo()
+            }
+
+            fn exec_file(&mut self) -> Option<ext::exec_file::ExecFileOps<'_, Self>> {
+                (**self).exec_file()
+            }
+
+            fn libraries_svr4(&mut self) -> Option<ext::libraries_svr4::LibrariesSvr4Ops<'_, Self>> {
+                (**self).libraries_svr4()
+            }
+
+            fn thread_list_xml(&mut self) -> Option<ext::thread_list_xml::ThreadListXmlOps<'_, Self>> {
+                (**self).thread_list_xml()
+            }
+
+            fn memory_map(&mut self) -> Option<ext::memory_map::MemoryMapOps<'_, Self>> {
+                (**self).memory_map()
+            }
+
+            fn flash(&mut self) -> Option<ext::flash::FlashOps<'_, Self>> {
+                (**self).flash()
+            }
+
+            fn memory_search(&mut self) -> Option<ext::memory_search::MemorySearchOps<'_, Self>> {
+                (**self).memory_search()
+            }
+
+            fn memory_tags(&mut self) -> Option<ext::memory_tags::MemoryTagsOps<'_, Self>> {
+                (**self).memory_tags()
+            }
+
+            fn qsupported(&mut self) -> Option<ext::qsupported::QSupportedOps<'_, Self>> {
+                (**self).qsupported()
+            }
+
+            fn idle(&mut self) -> Option<ext::idle::IdleOps<'_, Self>> {
+                (**self).idle()
+            }
+
+            fn allow(&mut self) -> Option<ext::allow::AllowOps<'_, Self>> {
+                (**self).allow()
+            }
+
+            fn read_only(&mut self) -> Option<ext::read_only::ReadOnlyOps<'_, Self>> {
+                (**self).read_only()
+            }
+
+            fn catch_fork(&mut self) -> Option<ext::catch_fork::CatchForkOps<'_, Self>> {
+                (**self).catch_fork()
+            }
+
+            fn ctrl_c_interrupt(
+                &mut self,
+            ) -> Option<ext::ctrl_c_interrupt::CtrlCInterruptOps<'_, Self>> {
+                (**self).ctrl_c_interrupt()
+            }
+
+            fn thread_extra_info(
+                &mut self,
+            ) -> Option<ext::thread_extra_info::ThreadExtraInfoOps<'_, Self>> {
+                (**self).thread_extra_info()
+            }
+
+            fn signal_mapping(&mut self) -> Option<ext::signal_mapping::SignalMappingOps<'_, Self>> {
+                (**self).signal_mapping()
+            }
+
+            fn signals(&mut self) -> Option<ext::signals::SignalsOps<'_, Self>> {
+                (**self).signals()
+            }
+
+            fn tls(&mut self) -> Option<ext::tls::TlsOps<'_, Self>> {
+                (**self).tls()
+            }
+
+            fn osdata(&mut self) -> Option<ext::osdata::OsDataOps<'_, Self>> {
+                (**self).osdata()
+            }
+
+            fn btrace(&mut self) -> Option<ext::btrace::BtraceOps<'_, Self>> {
+                (**self).btrace()
+            }
+
+            fn custom_xfer(&mut self) -> Option<ext::custom_xfer::CustomXferOps<'_, Self>> {
+                (**self).custom_xfer()
+            }
+
+            fn host_syscall(&mut self) -> Option<ext::host_syscall::HostSyscallOps<'_, Self>> {
+                (**self).host_syscall()
+            }
+
+            fn endianness_override(
+                &mut self,
+            ) -> Option<ext::endianness_override::EndiannessOverrideOps<'_, Self>> {
+                (**self).endianness_override()
+            }
+
+            fn target_description_xml_override(
+                &mut self,
+            ) -> Option<ext::target_description_xml_override::TargetDescriptionXmlOverrideOps<'_, Self>>
+            {
+                (**self).target_description_xml_override()
+            }
+        }
+    };
+}
+
+impl_dyn_target!(&mut dyn Target<Arch = A, Error = E>);
+#[cfg(feature = "alloc")]
+impl_dyn_target!(alloc::boxed::Box<dyn Target<Arch = A, Error = E>>);
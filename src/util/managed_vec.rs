@@ -17,30 +17,23 @@ impl<'a, 'b, T> ManagedVec<'a, 'b, T> {
         ManagedVec { buf, len: 0 }
     }
 
+    pub fn as_slice(&self) -> &[T] {
+        &self.buf[..self.len]
+    }
+
     pub fn clear(&mut self) {
-        match &mut self.buf {
-            ManagedSlice::Borrowed(_) => self.len = 0,
-            #[cfg(feature = "alloc")]
-            ManagedSlice::Owned(buf) => buf.clear(),
-        }
+        self.len = 0;
     }
 
     pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
-        match &mut self.buf {
-            ManagedSlice::Borrowed(buf) => {
-                if self.len < buf.len() {
-                    buf[self.len] = value;
-                    self.len += 1;
-                    Ok(())
-                } else {
-                    Err(CapacityError(value))
-                }
-            }
-            #[cfg(feature = "alloc")]
-            ManagedSlice::Owned(buf) => {
-                buf.push(value);
-                Ok(())
-            }
+        // Owned buffers are pre-allocated to their full size, and are treated
+        // exactly the same as Borrowed buffers.
+        if self.len < self.buf.len() {
+            self.buf[self.len] = value;
+            self.len += 1;
+            Ok(())
+        } else {
+            Err(CapacityError(value))
         }
     }
 }